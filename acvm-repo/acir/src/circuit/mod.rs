@@ -2,19 +2,32 @@ pub mod black_box_functions;
 pub mod brillig;
 pub mod directives;
 pub mod opcodes;
+mod parser;
+mod printer;
+mod serialization;
+mod validation;
 
 use crate::native_types::Witness;
 pub use opcodes::Opcode;
+pub use parser::AcirParseError;
+pub use serialization::{SerializationError, SerializationFormat, CURRENT_FORMAT_VERSION};
 use thiserror::Error;
+pub use validation::{opcode_witnesses, ValidationError};
 
-use std::{io::prelude::*, num::ParseIntError, str::FromStr};
+use std::{num::ParseIntError, str::FromStr};
 
 use base64::Engine;
-use flate2::Compression;
 use serde::{de::Error as DeserializationError, Deserialize, Deserializer, Serialize, Serializer};
 
 use std::collections::BTreeSet;
 
+/// A program represented by multiple ACIR circuits. The first circuit is the entry point of the
+/// program, and circuits may call each other through [`Opcode::Call`].
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct Program {
+    pub functions: Vec<Circuit>,
+}
+
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct Circuit {
     // current_witness_index is the highest witness index in the circuit. The next witness to be added to this circuit
@@ -126,29 +139,35 @@ impl Circuit {
         PublicInputs(public_inputs)
     }
 
-    fn write<W: std::io::Write>(&self, writer: W) -> std::io::Result<()> {
-        let buf = bincode::serialize(self).unwrap();
-        let mut encoder = flate2::write::GzEncoder::new(writer, Compression::default());
-        encoder.write_all(&buf)?;
-        encoder.finish()?;
-        Ok(())
+    fn write<W: std::io::Write>(
+        &self,
+        format: SerializationFormat,
+        writer: W,
+    ) -> std::io::Result<()> {
+        serialization::write(self, format, writer)
     }
 
-    fn read<R: std::io::Read>(reader: R) -> std::io::Result<Self> {
-        let mut gz_decoder = flate2::read::GzDecoder::new(reader);
-        let mut buf_d = Vec::new();
-        gz_decoder.read_to_end(&mut buf_d)?;
-        bincode::deserialize(&buf_d)
-            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))
+    fn read<R: std::io::Read>(reader: R) -> Result<Self, SerializationError> {
+        serialization::read(reader)
     }
 
     pub fn serialize_circuit(circuit: &Circuit) -> Vec<u8> {
+        Circuit::serialize_circuit_with_format(circuit, SerializationFormat::default())
+    }
+
+    /// Serializes the circuit with the given encoding, under the current format version.
+    pub fn serialize_circuit_with_format(
+        circuit: &Circuit,
+        format: SerializationFormat,
+    ) -> Vec<u8> {
         let mut circuit_bytes: Vec<u8> = Vec::new();
-        circuit.write(&mut circuit_bytes).expect("expected circuit to be serializable");
+        circuit.write(format, &mut circuit_bytes).expect("expected circuit to be serializable");
         circuit_bytes
     }
 
-    pub fn deserialize_circuit(serialized_circuit: &[u8]) -> std::io::Result<Self> {
+    /// Deserializes a circuit written by any supported format version and encoding,
+    /// migrating it from an earlier version of ACIR if necessary.
+    pub fn deserialize_circuit(serialized_circuit: &[u8]) -> Result<Self, SerializationError> {
         Circuit::read(serialized_circuit)
     }
 
@@ -180,29 +199,34 @@ impl std::fmt::Display for Circuit {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "current witness index : {}", self.current_witness_index)?;
 
-        let write_public_inputs = |f: &mut std::fmt::Formatter<'_>,
-                                   public_inputs: &PublicInputs|
+        let write_witness_indices = |f: &mut std::fmt::Formatter<'_>,
+                                     witnesses: &BTreeSet<Witness>|
          -> Result<(), std::fmt::Error> {
             write!(f, "[")?;
-            let public_input_indices = public_inputs.indices();
-            for (index, public_input) in public_input_indices.iter().enumerate() {
-                write!(f, "{public_input}")?;
-                if index != public_input_indices.len() - 1 {
+            for (index, witness) in witnesses.iter().enumerate() {
+                write!(f, "{}", witness.witness_index())?;
+                if index != witnesses.len() - 1 {
                     write!(f, ", ")?;
                 }
             }
             writeln!(f, "]")
         };
 
+        write!(f, "private parameters indices : ")?;
+        write_witness_indices(f, &self.private_parameters)?;
+
         write!(f, "public parameters indices : ")?;
-        write_public_inputs(f, &self.public_parameters)?;
+        write_witness_indices(f, &self.public_parameters.0)?;
 
         write!(f, "return value indices : ")?;
-        write_public_inputs(f, &self.return_values)?;
+        write_witness_indices(f, &self.return_values.0)?;
 
         for opcode in &self.opcodes {
             writeln!(f, "{opcode}")?;
         }
+        for (location, message) in &self.assert_messages {
+            writeln!(f, "ASSERT {location} : {message:?}")?;
+        }
         Ok(())
     }
 }
@@ -213,6 +237,84 @@ impl std::fmt::Debug for Circuit {
     }
 }
 
+impl Program {
+    /// Returns the circuit which is executed first when running the program.
+    pub fn main(&self) -> &Circuit {
+        &self.functions[0]
+    }
+
+    fn write<W: std::io::Write>(
+        &self,
+        format: SerializationFormat,
+        writer: W,
+    ) -> std::io::Result<()> {
+        serialization::write(self, format, writer)
+    }
+
+    fn read<R: std::io::Read>(reader: R) -> Result<Self, SerializationError> {
+        serialization::read(reader)
+    }
+
+    pub fn serialize_program(program: &Program) -> Vec<u8> {
+        Program::serialize_program_with_format(program, SerializationFormat::default())
+    }
+
+    /// Serializes the program with the given encoding, under the current format version.
+    pub fn serialize_program_with_format(
+        program: &Program,
+        format: SerializationFormat,
+    ) -> Vec<u8> {
+        let mut program_bytes: Vec<u8> = Vec::new();
+        program.write(format, &mut program_bytes).expect("expected program to be serializable");
+        program_bytes
+    }
+
+    /// Deserializes a program written by any supported format version and encoding,
+    /// migrating it from an earlier version of ACIR if necessary.
+    pub fn deserialize_program(serialized_program: &[u8]) -> Result<Self, SerializationError> {
+        Program::read(serialized_program)
+    }
+
+    // Serialize and base64 encode program
+    pub fn serialize_program_base64<S>(program: &Program, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let program_bytes = Program::serialize_program(program);
+        let encoded_b64 = base64::engine::general_purpose::STANDARD.encode(program_bytes);
+        s.serialize_str(&encoded_b64)
+    }
+
+    // Deserialize and base64 decode program
+    pub fn deserialize_program_base64<'de, D>(deserializer: D) -> Result<Program, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytecode_b64: String = serde::Deserialize::deserialize(deserializer)?;
+        let program_bytes = base64::engine::general_purpose::STANDARD
+            .decode(bytecode_b64)
+            .map_err(D::Error::custom)?;
+        let program = Self::deserialize_program(&program_bytes).map_err(D::Error::custom)?;
+        Ok(program)
+    }
+}
+
+impl std::fmt::Display for Program {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, function) in self.functions.iter().enumerate() {
+            writeln!(f, "func {index}")?;
+            writeln!(f, "{function}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for Program {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct PublicInputs(pub BTreeSet<Witness>);
 
@@ -233,10 +335,12 @@ mod tests {
 
     use super::{
         opcodes::{BlackBoxFuncCall, FunctionInput},
-        Circuit, Compression, Opcode, PublicInputs,
+        Circuit, Opcode, Program, PublicInputs, SerializationError, SerializationFormat,
+        CURRENT_FORMAT_VERSION,
     };
     use crate::native_types::Witness;
     use acir_field::FieldElement;
+    use flate2::{Compression, GzBuilder};
 
     fn and_opcode() -> Opcode {
         Opcode::BlackBoxFuncCall(BlackBoxFuncCall::AND {
@@ -272,6 +376,36 @@ mod tests {
         assert_eq!(circ, got_circ);
     }
 
+    #[test]
+    fn program_serialization_roundtrip() {
+        let callee = Circuit {
+            current_witness_index: 3,
+            opcodes: vec![and_opcode()],
+            private_parameters: BTreeSet::from_iter(vec![Witness(1), Witness(2)]),
+            public_parameters: PublicInputs::default(),
+            return_values: PublicInputs(BTreeSet::from_iter(vec![Witness(3)])),
+            assert_messages: Default::default(),
+        };
+        let main = Circuit {
+            current_witness_index: 3,
+            opcodes: vec![Opcode::Call {
+                id: 1,
+                inputs: vec![Witness(1), Witness(2)],
+                outputs: vec![Witness(3)],
+                predicate: None,
+            }],
+            private_parameters: BTreeSet::from_iter(vec![Witness(1), Witness(2)]),
+            public_parameters: PublicInputs::default(),
+            return_values: PublicInputs(BTreeSet::from_iter(vec![Witness(3)])),
+            assert_messages: Default::default(),
+        };
+        let program = Program { functions: vec![main, callee] };
+
+        let bytes = Program::serialize_program(&program);
+        let got_program = Program::deserialize_program(&bytes).unwrap();
+        assert_eq!(program, got_program);
+    }
+
     #[test]
     fn test_serialize() {
         let circuit = Circuit {
@@ -313,4 +447,80 @@ mod tests {
         let deserialization_result = Circuit::deserialize_circuit(&zipped_bad_circuit);
         assert!(deserialization_result.is_err());
     }
+
+    #[test]
+    fn serialization_roundtrip_json() {
+        let circuit = Circuit {
+            current_witness_index: 5,
+            opcodes: vec![and_opcode(), range_opcode()],
+            private_parameters: BTreeSet::from_iter(vec![Witness(1), Witness(2)]),
+            public_parameters: PublicInputs(BTreeSet::from_iter(vec![Witness(3)])),
+            return_values: PublicInputs(BTreeSet::from_iter(vec![Witness(3)])),
+            assert_messages: Default::default(),
+        };
+
+        let bytes = Circuit::serialize_circuit_with_format(&circuit, SerializationFormat::Json);
+        let got_circuit = Circuit::deserialize_circuit(&bytes).unwrap();
+        assert_eq!(circuit, got_circuit);
+    }
+
+    #[test]
+    fn reads_unversioned_circuit() {
+        use std::io::Write;
+
+        let circuit = Circuit { opcodes: vec![and_opcode()], ..Circuit::default() };
+
+        // Circuits serialized before the format version was introduced have no extra header field.
+        let mut legacy_bytes = Vec::new();
+        let mut encoder = flate2::write::GzEncoder::new(&mut legacy_bytes, Compression::default());
+        encoder.write_all(&bincode::serialize(&circuit).unwrap()).unwrap();
+        encoder.finish().unwrap();
+
+        let got_circuit = Circuit::deserialize_circuit(&legacy_bytes).unwrap();
+        assert_eq!(circuit, got_circuit);
+    }
+
+    #[test]
+    fn reads_unversioned_program() {
+        use std::io::Write;
+
+        let program = Program {
+            functions: vec![Circuit {
+                opcodes: vec![and_opcode(), range_opcode()],
+                ..Circuit::default()
+            }],
+        };
+
+        let mut legacy_bytes = Vec::new();
+        let mut encoder = flate2::write::GzEncoder::new(&mut legacy_bytes, Compression::default());
+        encoder.write_all(&bincode::serialize(&program).unwrap()).unwrap();
+        encoder.finish().unwrap();
+
+        let got_program = Program::deserialize_program(&legacy_bytes).unwrap();
+        assert_eq!(program, got_program);
+    }
+
+    #[test]
+    fn rejects_newer_format_version() {
+        use std::io::Write;
+
+        let circuit = Circuit { opcodes: vec![and_opcode()], ..Circuit::default() };
+
+        let mut header = b"AC".to_vec();
+        header.extend_from_slice(&5u16.to_le_bytes());
+        header.extend_from_slice(&(CURRENT_FORMAT_VERSION + 1).to_le_bytes());
+        header.push(0);
+
+        let mut bytes = Vec::new();
+        let mut encoder = GzBuilder::new().extra(header).write(&mut bytes, Compression::default());
+        encoder.write_all(&bincode::serialize(&circuit).unwrap()).unwrap();
+        encoder.finish().unwrap();
+
+        let deserialization_result = Circuit::deserialize_circuit(&bytes);
+        assert!(matches!(
+            deserialization_result,
+            Err(SerializationError::UnsupportedVersion { found, latest })
+                if found == CURRENT_FORMAT_VERSION + 1 && latest == CURRENT_FORMAT_VERSION
+        ));
+    }
 }
@@ -1,6 +1,7 @@
 use super::{
-    brillig::Brillig,
+    brillig::{Brillig, BrilligInputs, BrilligOutputs},
     directives::{Directive, QuotientDirective},
+    printer::{self, DisplayBrilligOpcode, DisplayExpression, DisplayList, DisplayPredicate},
 };
 use crate::native_types::{Expression, Witness};
 use serde::{Deserialize, Serialize};
@@ -31,6 +32,17 @@ pub enum Opcode {
         block_id: BlockId,
         init: Vec<Witness>,
     },
+    /// Calls to another ACIR function in the same [program][super::Program]
+    Call {
+        /// Index of the called function in [`Program::functions`][super::Program::functions]
+        id: u32,
+        /// Witnesses passed as the arguments of the called function, in order
+        inputs: Vec<Witness>,
+        /// Witnesses assigned to the return values of the called function, in order
+        outputs: Vec<Witness>,
+        /// Predicate of the call - indicates if it should be skipped
+        predicate: Option<Expression>,
+    },
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -59,6 +71,7 @@ impl Opcode {
             Opcode::Brillig(_) => "brillig",
             Opcode::MemoryOp { .. } => "mem",
             Opcode::MemoryInit { .. } => "init memory block",
+            Opcode::Call { .. } => "call",
         }
     }
 
@@ -88,85 +101,84 @@ impl Opcode {
 impl std::fmt::Display for Opcode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Opcode::Arithmetic(expr) => {
-                write!(f, "EXPR [ ")?;
-                for i in &expr.mul_terms {
-                    write!(f, "({}, _{}, _{}) ", i.0, i.1.witness_index(), i.2.witness_index())?;
-                }
-                for i in &expr.linear_combinations {
-                    write!(f, "({}, _{}) ", i.0, i.1.witness_index())?;
-                }
-                write!(f, "{}", expr.q_c)?;
-
-                write!(f, " ]")
-            }
+            Opcode::Arithmetic(expr) => write!(f, "EXPR {}", DisplayExpression(expr)),
             Opcode::Directive(Directive::Quotient(QuotientDirective { a, b, q, r, predicate })) => {
-                write!(f, "DIR::QUOTIENT ")?;
-                if let Some(pred) = predicate {
-                    writeln!(f, "PREDICATE = {pred}")?;
-                }
-
                 write!(
                     f,
-                    "(out : _{},  (_{}, {}), _{})",
-                    a,
+                    "DIR::QUOTIENT {}a: {}, b: {}, q: _{}, r: _{}",
+                    DisplayPredicate(predicate),
+                    DisplayExpression(a),
+                    DisplayExpression(b),
                     q.witness_index(),
-                    b,
                     r.witness_index()
                 )
             }
             Opcode::BlackBoxFuncCall(g) => write!(f, "{g}"),
-            Opcode::Directive(Directive::ToLeRadix { a, b, radix: _ }) => {
-                write!(f, "DIR::TORADIX ")?;
+            Opcode::Directive(Directive::ToLeRadix { a, b, radix }) => {
                 write!(
                     f,
-                    // TODO (Note): this assumes that the decomposed bits have contiguous witness indices
-                    // This should be the case, however, we can also have a function which checks this
-                    "(_{}, [_{}..._{}] )",
-                    a,
-                    b.first().unwrap().witness_index(),
-                    b.last().unwrap().witness_index(),
+                    "DIR::TORADIX a: {}, b: {}, radix: {radix}",
+                    DisplayExpression(a),
+                    printer::witnesses(b)
                 )
             }
-            Opcode::Directive(Directive::PermutationSort { inputs: a, tuple, bits, sort_by }) => {
-                write!(f, "DIR::PERMUTATIONSORT ")?;
+            Opcode::Directive(Directive::PermutationSort { inputs, tuple, bits, sort_by }) => {
                 write!(
                     f,
-                    "(permutation size: {} {}-tuples, sort_by: {:#?}, bits: [_{}..._{}]))",
-                    a.len(),
-                    tuple,
-                    sort_by,
-                    // (Note): the bits do not have contiguous index but there are too many for display
-                    bits.first().unwrap().witness_index(),
-                    bits.last().unwrap().witness_index(),
+                    "DIR::PERMUTATIONSORT inputs: {}, tuple: {tuple}, bits: {}, sort_by: {sort_by:?}",
+                    DisplayList(&inputs[..], |tuple| printer::expressions(tuple).to_string()),
+                    printer::witnesses(bits)
                 )
             }
-
             Opcode::Brillig(brillig) => {
-                write!(f, "BRILLIG: ")?;
-                writeln!(f, "inputs: {:?}", brillig.inputs)?;
-                writeln!(f, "outputs: {:?}", brillig.outputs)?;
-                writeln!(f, "{:?}", brillig.bytecode)
+                let inputs = DisplayList(&brillig.inputs[..], |input| match input {
+                    BrilligInputs::Single(expr) => format!("Single({})", DisplayExpression(expr)),
+                    BrilligInputs::Array(exprs) => {
+                        format!("Array({})", printer::expressions(exprs))
+                    }
+                });
+                let outputs = DisplayList(&brillig.outputs[..], |output| match output {
+                    BrilligOutputs::Simple(witness) => format!("Simple(_{})", witness.0),
+                    BrilligOutputs::Array(witnesses) => {
+                        format!("Array({})", printer::witnesses(witnesses))
+                    }
+                });
+                writeln!(
+                    f,
+                    "BRILLIG {}inputs: {inputs}, outputs: {outputs}, bytecode: [",
+                    DisplayPredicate(&brillig.predicate)
+                )?;
+                for (index, opcode) in brillig.bytecode.iter().enumerate() {
+                    writeln!(f, "  {index}: {}", DisplayBrilligOpcode(opcode))?;
+                }
+                write!(f, "]")
             }
             Opcode::MemoryOp { block_id, op, predicate } => {
-                write!(f, "MEM ")?;
-                if let Some(pred) = predicate {
-                    writeln!(f, "PREDICATE = {pred}")?;
-                }
+                write!(f, "MEM {}", DisplayPredicate(predicate))?;
 
                 let is_read = op.operation.is_zero();
                 let is_write = op.operation == Expression::one();
+                let (index, value) = (DisplayExpression(&op.index), DisplayExpression(&op.value));
                 if is_read {
-                    write!(f, "(id: {}, read at: {}, value: {}) ", block_id.0, op.index, op.value)
+                    write!(f, "(id: {}, read at: {index}, value: {value})", block_id.0)
                 } else if is_write {
-                    write!(f, "(id: {}, write {} at: {}) ", block_id.0, op.value, op.index)
+                    write!(f, "(id: {}, write {value} at: {index})", block_id.0)
                 } else {
-                    write!(f, "(id: {}, op {} at: {}) ", block_id.0, op.operation, op.index)
+                    let operation = DisplayExpression(&op.operation);
+                    write!(f, "(id: {}, op {operation} at: {index}, value: {value})", block_id.0)
                 }
             }
             Opcode::MemoryInit { block_id, init } => {
-                write!(f, "INIT ")?;
-                write!(f, "(id: {}, len: {}) ", block_id.0, init.len())
+                write!(f, "INIT (id: {}, init: {})", block_id.0, printer::witnesses(init))
+            }
+            Opcode::Call { id, inputs, outputs, predicate } => {
+                write!(
+                    f,
+                    "CALL {}func {id}: inputs: {}, outputs: {}",
+                    DisplayPredicate(predicate),
+                    printer::witnesses(inputs),
+                    printer::witnesses(outputs)
+                )
             }
         }
     }
@@ -1,3 +1,4 @@
+use crate::circuit::printer;
 use crate::native_types::Witness;
 use crate::BlackBoxFunc;
 use serde::{Deserialize, Serialize};
@@ -257,92 +258,134 @@ impl BlackBoxFuncCall {
     }
 }
 
-const ABBREVIATION_LIMIT: usize = 5;
-
-fn get_inputs_string(inputs: &[FunctionInput]) -> String {
-    // Once a vectors length gets above this limit,
-    // instead of listing all of their elements, we use ellipses
-    // to abbreviate them
-    let should_abbreviate_inputs = inputs.len() <= ABBREVIATION_LIMIT;
-
-    if should_abbreviate_inputs {
-        let mut result = String::new();
-        for (index, inp) in inputs.iter().enumerate() {
-            result += &format!("(_{}, num_bits: {})", inp.witness.witness_index(), inp.num_bits);
-            // Add a comma, unless it is the last entry
-            if index != inputs.len() - 1 {
-                result += ", ";
-            }
-        }
-        result
-    } else {
-        let first = inputs.first().unwrap();
-        let last = inputs.last().unwrap();
-
-        let mut result = String::new();
-
-        result += &format!(
-            "(_{}, num_bits: {})...(_{}, num_bits: {})",
-            first.witness.witness_index(),
-            first.num_bits,
-            last.witness.witness_index(),
-            last.num_bits,
-        );
-
-        result
-    }
-}
-
-fn get_outputs_string(outputs: &[Witness]) -> String {
-    let should_abbreviate_outputs = outputs.len() <= ABBREVIATION_LIMIT;
-
-    if should_abbreviate_outputs {
-        let mut result = String::new();
-        for (index, output) in outputs.iter().enumerate() {
-            result += &format!("_{}", output.witness_index());
-            // Add a comma, unless it is the last entry
-            if index != outputs.len() - 1 {
-                result += ", ";
-            }
-        }
-        result
-    } else {
-        let first = outputs.first().unwrap();
-        let last = outputs.last().unwrap();
-
-        let mut result = String::new();
-        result += &format!("(_{},...,_{})", first.witness_index(), last.witness_index());
-        result
-    }
-}
-
 impl std::fmt::Display for BlackBoxFuncCall {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Keccak256VariableLength shares its name with Keccak256 and is recognized by its extra field.
         let uppercase_name = self.name().to_uppercase();
         write!(f, "BLACKBOX::{uppercase_name} ")?;
-        // INPUTS
-        write!(f, "[")?;
-
-        let inputs_str = get_inputs_string(&self.get_inputs_vec());
-
-        write!(f, "{inputs_str}")?;
-        write!(f, "] ")?;
-
-        // OUTPUTS
-        write!(f, "[ ")?;
-
-        let outputs_str = get_outputs_string(&self.get_outputs_vec());
-
-        write!(f, "{outputs_str}")?;
-
-        write!(f, "]")?;
 
-        // SPECIFIC PARAMETERS
         match self {
-            BlackBoxFuncCall::PedersenCommitment { domain_separator, .. } => {
-                write!(f, " domain_separator: {domain_separator}")
+            BlackBoxFuncCall::AND { lhs, rhs, output }
+            | BlackBoxFuncCall::XOR { lhs, rhs, output } => write!(
+                f,
+                "lhs: {}, rhs: {}, output: _{}",
+                printer::function_input(lhs),
+                printer::function_input(rhs),
+                output.0
+            ),
+            BlackBoxFuncCall::RANGE { input } => {
+                write!(f, "input: {}", printer::function_input(input))
+            }
+            BlackBoxFuncCall::SHA256 { inputs, outputs }
+            | BlackBoxFuncCall::Blake2s { inputs, outputs }
+            | BlackBoxFuncCall::Keccak256 { inputs, outputs } => write!(
+                f,
+                "inputs: {}, outputs: {}",
+                printer::function_inputs(inputs),
+                printer::witnesses(outputs)
+            ),
+            BlackBoxFuncCall::SchnorrVerify {
+                public_key_x,
+                public_key_y,
+                signature,
+                message,
+                output,
+            } => write!(
+                f,
+                "public_key_x: {}, public_key_y: {}, signature: {}, message: {}, output: _{}",
+                printer::function_input(public_key_x),
+                printer::function_input(public_key_y),
+                printer::function_inputs(signature),
+                printer::function_inputs(message),
+                output.0
+            ),
+            BlackBoxFuncCall::PedersenCommitment { inputs, domain_separator, outputs } => write!(
+                f,
+                "inputs: {}, domain_separator: {domain_separator}, outputs: (_{}, _{})",
+                printer::function_inputs(inputs),
+                outputs.0 .0,
+                outputs.1 .0
+            ),
+            BlackBoxFuncCall::PedersenHash { inputs, domain_separator, output } => write!(
+                f,
+                "inputs: {}, domain_separator: {domain_separator}, output: _{}",
+                printer::function_inputs(inputs),
+                output.0
+            ),
+            BlackBoxFuncCall::HashToField128Security { inputs, output } => write!(
+                f,
+                "inputs: {}, output: _{}",
+                printer::function_inputs(inputs),
+                output.0
+            ),
+            BlackBoxFuncCall::EcdsaSecp256k1 {
+                public_key_x,
+                public_key_y,
+                signature,
+                hashed_message,
+                output,
+            }
+            | BlackBoxFuncCall::EcdsaSecp256r1 {
+                public_key_x,
+                public_key_y,
+                signature,
+                hashed_message,
+                output,
+            } => write!(
+                f,
+                "public_key_x: {}, public_key_y: {}, signature: {}, hashed_message: {}, output: _{}",
+                printer::function_inputs(public_key_x),
+                printer::function_inputs(public_key_y),
+                printer::function_inputs(signature),
+                printer::function_inputs(hashed_message),
+                output.0
+            ),
+            BlackBoxFuncCall::FixedBaseScalarMul { low, high, outputs } => write!(
+                f,
+                "low: {}, high: {}, outputs: (_{}, _{})",
+                printer::function_input(low),
+                printer::function_input(high),
+                outputs.0 .0,
+                outputs.1 .0
+            ),
+            BlackBoxFuncCall::Keccak256VariableLength { inputs, var_message_size, outputs } => {
+                write!(
+                    f,
+                    "inputs: {}, var_message_size: {}, outputs: {}",
+                    printer::function_inputs(inputs),
+                    printer::function_input(var_message_size),
+                    printer::witnesses(outputs)
+                )
+            }
+            BlackBoxFuncCall::RecursiveAggregation {
+                verification_key,
+                proof,
+                public_inputs,
+                key_hash,
+                input_aggregation_object,
+                output_aggregation_object,
+            } => {
+                write!(
+                    f,
+                    "verification_key: {}, proof: {}, public_inputs: {}, key_hash: {}, ",
+                    printer::function_inputs(verification_key),
+                    printer::function_inputs(proof),
+                    printer::function_inputs(public_inputs),
+                    printer::function_input(key_hash)
+                )?;
+                if let Some(input_aggregation_object) = input_aggregation_object {
+                    write!(
+                        f,
+                        "input_aggregation_object: {}, ",
+                        printer::function_inputs(input_aggregation_object)
+                    )?;
+                }
+                write!(
+                    f,
+                    "output_aggregation_object: {}",
+                    printer::witnesses(output_aggregation_object)
+                )
             }
-            _ => write!(f, ""),
         }
     }
 }
@@ -0,0 +1,953 @@
+//! This module parses the textual ACIR format printed by the `Display` impls of [`Circuit`]
+//! and [`Program`], i.e. the output of `--print-acir`, back into those structures.
+//!
+//! This allows hand-written circuits to drive ACVM tests and lets circuits be compared as text.
+//! Printing a parsed circuit gives back the same text, modulo whitespace.
+//!
+//! A program lists its circuits in order, each preceded by its index:
+//!
+//! ```text
+//! func 0
+//! current witness index : 3
+//! private parameters indices : [1, 2]
+//! public parameters indices : []
+//! return value indices : [3]
+//! CALL func 1: inputs: [_1, _2], outputs: [_3]
+//! func 1
+//! ...
+//! ```
+//!
+//! A circuit is the header above followed by its opcodes and then its assert messages,
+//! e.g. `ASSERT 2 : "x must be 5"` or `ASSERT 0.3 : "..."` for a Brillig opcode.
+//!
+//! Witnesses are written `_5`. Field elements are decimal or `0x`-prefixed hexadecimal integers,
+//! optionally negated. An expression is written `[ (q_m, _l, _r)... (q_l, _w)... q_c ]`, listing
+//! its multiplication terms, then its linear terms, then its constant. Lists use square brackets
+//! and are comma separated. Each opcode starts with a keyword:
+//!
+//! ```text
+//! EXPR [ (1, _1, _2) (-1, _3) 0 ]
+//! BLACKBOX::AND lhs: (_1, num_bits: 8), rhs: (_2, num_bits: 8), output: _3
+//! DIR::QUOTIENT PREDICATE = [ 1 ] a: [ (1, _1) 0 ], b: [ 4 ], q: _2, r: _3
+//! DIR::TORADIX a: [ (1, _1) 0 ], b: [_2, _3], radix: 2
+//! DIR::PERMUTATIONSORT inputs: [[[ (1, _1) 0 ]], [[ (1, _2) 0 ]]], tuple: 1, bits: [_3], sort_by: [0]
+//! MEM (id: 0, read at: [ (1, _1) 0 ], value: [ (1, _2) 0 ])
+//! MEM PREDICATE = [ (1, _4) 0 ] (id: 0, write [ (1, _3) 0 ] at: [ 1 ])
+//! INIT (id: 0, init: [_1, _2, _3])
+//! CALL PREDICATE = [ (1, _4) 0 ] func 1: inputs: [_1, _2], outputs: [_3]
+//! BRILLIG inputs: [Single([ (1, _1) 0 ]), Array([[ (1, _2) 0 ]])], outputs: [Simple(_3)], bytecode: [
+//!   0: r1 = const 1
+//!   1: r2 = field add r0, r1
+//!   2: stop
+//! ]
+//! ```
+//!
+//! The fields of each black box function are named after those of [`BlackBoxFuncCall`], with a
+//! function input written as `(_1, num_bits: 8)`. Brillig opcodes are numbered and written as:
+//!
+//! ```text
+//! r2 = field add r0, r1     (add, sub, mul, div, eq)
+//! r2 = u32 lt r0, r1        (add, sub, mul, sdiv, udiv, eq, lt, lte, and, or, xor, shl, shr)
+//! r0 = const 5
+//! r0 = mov r1
+//! r0 = load r1
+//! store r0, r1
+//! jmp 3
+//! jmp_if r0, 3
+//! jmp_if_not r0, 3
+//! call 3
+//! return
+//! trap
+//! stop
+//! [r0, array(r1, 4), vector(r2, r3)] = foreign_call "name" [r4]
+//! black_box sha256 message: vector(r0, r1), output: array(r2, 32)
+//! ```
+use std::{collections::BTreeSet, str::FromStr};
+
+use acir_field::FieldElement;
+use brillig::{
+    BinaryFieldOp, BinaryIntOp, BlackBoxOp, HeapArray, HeapVector, Opcode as BrilligOpcode,
+    RegisterIndex, RegisterOrMemory, Value,
+};
+use thiserror::Error;
+
+use super::{
+    brillig::{Brillig, BrilligInputs, BrilligOutputs},
+    directives::{Directive, QuotientDirective},
+    opcodes::{BlackBoxFuncCall, BlockId, FunctionInput, MemOp},
+    printer::DisplayField,
+    Circuit, Opcode, OpcodeLocation, Program, PublicInputs,
+};
+use crate::{
+    native_types::{Expression, Witness},
+    BlackBoxFunc,
+};
+
+use lexer::{Lexer, SpannedToken, Token};
+
+mod lexer;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AcirParseError {
+    #[error("Unexpected character '{character}' on line {line}")]
+    UnexpectedCharacter { character: char, line: usize },
+    #[error("Invalid integer {value} on line {line}")]
+    InvalidInteger { value: String, line: usize },
+    #[error("Unterminated string starting on line {line}")]
+    UnterminatedString { line: usize },
+    #[error("Invalid escape sequence in string on line {line}")]
+    InvalidEscape { line: usize },
+    #[error("Expected {expected} but found {found} on line {line}")]
+    UnexpectedToken { expected: String, found: String, line: usize },
+    #[error("Unknown black box function {name} on line {line}")]
+    UnknownBlackBoxFunction { name: String, line: usize },
+}
+
+impl FromStr for Circuit {
+    type Err = AcirParseError;
+
+    fn from_str(source: &str) -> Result<Circuit, AcirParseError> {
+        let tokens = Lexer::new(source).lex()?;
+        let mut parser = AcirParser::new(tokens);
+        let circuit = parser.parse_circuit()?;
+        parser.eat(Token::Eof)?;
+        Ok(circuit)
+    }
+}
+
+impl FromStr for Program {
+    type Err = AcirParseError;
+
+    fn from_str(source: &str) -> Result<Program, AcirParseError> {
+        let tokens = Lexer::new(source).lex()?;
+        AcirParser::new(tokens).parse_program()
+    }
+}
+
+struct AcirParser {
+    tokens: Vec<SpannedToken>,
+    position: usize,
+}
+
+impl AcirParser {
+    fn new(tokens: Vec<SpannedToken>) -> Self {
+        Self { tokens, position: 0 }
+    }
+
+    fn parse_program(mut self) -> Result<Program, AcirParseError> {
+        let mut functions = Vec::new();
+        while self.peek() != &Token::Eof {
+            self.eat_keyword("func")?;
+            let index = functions.len();
+            if self.parse_usize()? != index {
+                return self.expected_previous(&format!("function index {index}"));
+            }
+            functions.push(self.parse_circuit()?);
+        }
+        Ok(Program { functions })
+    }
+
+    fn parse_circuit(&mut self) -> Result<Circuit, AcirParseError> {
+        self.eat_keywords(&["current", "witness", "index"])?;
+        self.eat(Token::Colon)?;
+        let current_witness_index = self.parse_u32()?;
+
+        self.eat_keywords(&["private", "parameters", "indices"])?;
+        let private_parameters = self.parse_witness_indices()?;
+        self.eat_keywords(&["public", "parameters", "indices"])?;
+        let public_parameters = PublicInputs(self.parse_witness_indices()?);
+        self.eat_keywords(&["return", "value", "indices"])?;
+        let return_values = PublicInputs(self.parse_witness_indices()?);
+
+        let mut opcodes = Vec::new();
+        let mut assert_messages = Vec::new();
+        loop {
+            let keyword = match self.peek() {
+                Token::Ident(keyword) => keyword.clone(),
+                Token::Eof => break,
+                _ => return self.expected("an opcode"),
+            };
+            let opcode = match keyword.as_str() {
+                "EXPR" => {
+                    self.advance();
+                    Opcode::Arithmetic(self.parse_expression()?)
+                }
+                "BLACKBOX" => Opcode::BlackBoxFuncCall(self.parse_black_box_func_call()?),
+                "DIR" => Opcode::Directive(self.parse_directive()?),
+                "BRILLIG" => Opcode::Brillig(self.parse_brillig()?),
+                "MEM" => self.parse_memory_op()?,
+                "INIT" => self.parse_memory_init()?,
+                "CALL" => self.parse_call()?,
+                "ASSERT" => {
+                    assert_messages.push(self.parse_assert_message()?);
+                    continue;
+                }
+                "func" => break,
+                _ => return self.expected("an opcode"),
+            };
+            opcodes.push(opcode);
+        }
+
+        Ok(Circuit {
+            current_witness_index,
+            opcodes,
+            private_parameters,
+            public_parameters,
+            return_values,
+            assert_messages,
+        })
+    }
+
+    /// Parses the `: [1, 2, 3]` list of witness indices in the circuit header
+    fn parse_witness_indices(&mut self) -> Result<BTreeSet<Witness>, AcirParseError> {
+        self.eat(Token::Colon)?;
+        let indices = self.parse_list(|parser| parser.parse_u32().map(Witness))?;
+        Ok(indices.into_iter().collect())
+    }
+
+    fn parse_assert_message(&mut self) -> Result<(OpcodeLocation, String), AcirParseError> {
+        self.eat_keyword("ASSERT")?;
+        let acir_index = self.parse_usize()?;
+        let location = if self.eat_if(Token::Dot) {
+            OpcodeLocation::Brillig { acir_index, brillig_index: self.parse_usize()? }
+        } else {
+            OpcodeLocation::Acir(acir_index)
+        };
+        self.eat(Token::Colon)?;
+        match self.peek().clone() {
+            Token::Str(message) => {
+                self.advance();
+                Ok((location, message))
+            }
+            _ => self.expected("an assert message"),
+        }
+    }
+
+    fn parse_expression(&mut self) -> Result<Expression, AcirParseError> {
+        self.eat(Token::LeftBracket)?;
+        let mut expression = Expression::default();
+        while self.eat_if(Token::LeftParen) {
+            let coefficient = self.parse_field()?;
+            self.eat(Token::Comma)?;
+            let witness = self.parse_witness()?;
+            if self.eat_if(Token::Comma) {
+                let rhs = self.parse_witness()?;
+                expression.mul_terms.push((coefficient, witness, rhs));
+            } else {
+                expression.linear_combinations.push((coefficient, witness));
+            }
+            self.eat(Token::RightParen)?;
+        }
+        expression.q_c = self.parse_field()?;
+        self.eat(Token::RightBracket)?;
+        Ok(expression)
+    }
+
+    /// Parses the optional `PREDICATE = [ ... ]` prefix of an opcode
+    fn parse_predicate(&mut self) -> Result<Option<Expression>, AcirParseError> {
+        if self.is_keyword("PREDICATE") {
+            self.advance();
+            self.eat(Token::Assign)?;
+            Ok(Some(self.parse_expression()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parses the `KEYWORD::NAME` prefix of black box function calls and directives,
+    /// returning the name.
+    fn parse_qualified_name(&mut self, keyword: &str) -> Result<String, AcirParseError> {
+        self.eat_keyword(keyword)?;
+        self.eat(Token::Colon)?;
+        self.eat(Token::Colon)?;
+        self.parse_ident("a name")
+    }
+
+    fn parse_black_box_func_call(&mut self) -> Result<BlackBoxFuncCall, AcirParseError> {
+        let name = self.parse_qualified_name("BLACKBOX")?;
+        let Some(func) = BlackBoxFunc::lookup(&name.to_lowercase()) else {
+            return Err(AcirParseError::UnknownBlackBoxFunction { name, line: self.line() });
+        };
+
+        let call = match func {
+            BlackBoxFunc::AND | BlackBoxFunc::XOR => {
+                let lhs = self.parse_field_named("lhs", Self::parse_function_input)?;
+                let rhs = self.parse_next_field("rhs", Self::parse_function_input)?;
+                let output = self.parse_next_field("output", Self::parse_witness)?;
+                if func == BlackBoxFunc::AND {
+                    BlackBoxFuncCall::AND { lhs, rhs, output }
+                } else {
+                    BlackBoxFuncCall::XOR { lhs, rhs, output }
+                }
+            }
+            BlackBoxFunc::RANGE => {
+                let input = self.parse_field_named("input", Self::parse_function_input)?;
+                BlackBoxFuncCall::RANGE { input }
+            }
+            BlackBoxFunc::SHA256 | BlackBoxFunc::Blake2s | BlackBoxFunc::Keccak256 => {
+                let inputs = self.parse_field_named("inputs", Self::parse_function_inputs)?;
+                self.eat(Token::Comma)?;
+                if func == BlackBoxFunc::Keccak256 && self.is_keyword("var_message_size") {
+                    let var_message_size =
+                        self.parse_field_named("var_message_size", Self::parse_function_input)?;
+                    let outputs = self.parse_next_field("outputs", Self::parse_witnesses)?;
+                    return Ok(BlackBoxFuncCall::Keccak256VariableLength {
+                        inputs,
+                        var_message_size,
+                        outputs,
+                    });
+                }
+                let outputs = self.parse_field_named("outputs", Self::parse_witnesses)?;
+                match func {
+                    BlackBoxFunc::SHA256 => BlackBoxFuncCall::SHA256 { inputs, outputs },
+                    BlackBoxFunc::Blake2s => BlackBoxFuncCall::Blake2s { inputs, outputs },
+                    _ => BlackBoxFuncCall::Keccak256 { inputs, outputs },
+                }
+            }
+            BlackBoxFunc::SchnorrVerify => BlackBoxFuncCall::SchnorrVerify {
+                public_key_x: self.parse_field_named("public_key_x", Self::parse_function_input)?,
+                public_key_y: self.parse_next_field("public_key_y", Self::parse_function_input)?,
+                signature: self.parse_next_field("signature", Self::parse_function_inputs)?,
+                message: self.parse_next_field("message", Self::parse_function_inputs)?,
+                output: self.parse_next_field("output", Self::parse_witness)?,
+            },
+            BlackBoxFunc::PedersenCommitment => BlackBoxFuncCall::PedersenCommitment {
+                inputs: self.parse_field_named("inputs", Self::parse_function_inputs)?,
+                domain_separator: self.parse_next_field("domain_separator", Self::parse_u32)?,
+                outputs: self.parse_next_field("outputs", Self::parse_witness_pair)?,
+            },
+            BlackBoxFunc::PedersenHash => BlackBoxFuncCall::PedersenHash {
+                inputs: self.parse_field_named("inputs", Self::parse_function_inputs)?,
+                domain_separator: self.parse_next_field("domain_separator", Self::parse_u32)?,
+                output: self.parse_next_field("output", Self::parse_witness)?,
+            },
+            BlackBoxFunc::HashToField128Security => BlackBoxFuncCall::HashToField128Security {
+                inputs: self.parse_field_named("inputs", Self::parse_function_inputs)?,
+                output: self.parse_next_field("output", Self::parse_witness)?,
+            },
+            BlackBoxFunc::EcdsaSecp256k1 | BlackBoxFunc::EcdsaSecp256r1 => {
+                let public_key_x =
+                    self.parse_field_named("public_key_x", Self::parse_function_inputs)?;
+                let public_key_y =
+                    self.parse_next_field("public_key_y", Self::parse_function_inputs)?;
+                let signature = self.parse_next_field("signature", Self::parse_function_inputs)?;
+                let hashed_message =
+                    self.parse_next_field("hashed_message", Self::parse_function_inputs)?;
+                let output = self.parse_next_field("output", Self::parse_witness)?;
+                if func == BlackBoxFunc::EcdsaSecp256k1 {
+                    BlackBoxFuncCall::EcdsaSecp256k1 {
+                        public_key_x,
+                        public_key_y,
+                        signature,
+                        hashed_message,
+                        output,
+                    }
+                } else {
+                    BlackBoxFuncCall::EcdsaSecp256r1 {
+                        public_key_x,
+                        public_key_y,
+                        signature,
+                        hashed_message,
+                        output,
+                    }
+                }
+            }
+            BlackBoxFunc::FixedBaseScalarMul => BlackBoxFuncCall::FixedBaseScalarMul {
+                low: self.parse_field_named("low", Self::parse_function_input)?,
+                high: self.parse_next_field("high", Self::parse_function_input)?,
+                outputs: self.parse_next_field("outputs", Self::parse_witness_pair)?,
+            },
+            BlackBoxFunc::RecursiveAggregation => {
+                let verification_key =
+                    self.parse_field_named("verification_key", Self::parse_function_inputs)?;
+                let proof = self.parse_next_field("proof", Self::parse_function_inputs)?;
+                let public_inputs =
+                    self.parse_next_field("public_inputs", Self::parse_function_inputs)?;
+                let key_hash = self.parse_next_field("key_hash", Self::parse_function_input)?;
+                self.eat(Token::Comma)?;
+                let input_aggregation_object = if self.is_keyword("input_aggregation_object") {
+                    let object = self.parse_field_named(
+                        "input_aggregation_object",
+                        Self::parse_function_inputs,
+                    )?;
+                    self.eat(Token::Comma)?;
+                    Some(object)
+                } else {
+                    None
+                };
+                let output_aggregation_object =
+                    self.parse_field_named("output_aggregation_object", Self::parse_witnesses)?;
+                BlackBoxFuncCall::RecursiveAggregation {
+                    verification_key,
+                    proof,
+                    public_inputs,
+                    key_hash,
+                    input_aggregation_object,
+                    output_aggregation_object,
+                }
+            }
+        };
+        Ok(call)
+    }
+
+    fn parse_directive(&mut self) -> Result<Directive, AcirParseError> {
+        let name = self.parse_qualified_name("DIR")?;
+        let directive = match name.as_str() {
+            "QUOTIENT" => {
+                let predicate = self.parse_predicate()?;
+                Directive::Quotient(QuotientDirective {
+                    a: self.parse_field_named("a", Self::parse_expression)?,
+                    b: self.parse_next_field("b", Self::parse_expression)?,
+                    q: self.parse_next_field("q", Self::parse_witness)?,
+                    r: self.parse_next_field("r", Self::parse_witness)?,
+                    predicate,
+                })
+            }
+            "TORADIX" => Directive::ToLeRadix {
+                a: self.parse_field_named("a", Self::parse_expression)?,
+                b: self.parse_next_field("b", Self::parse_witnesses)?,
+                radix: self.parse_next_field("radix", Self::parse_u32)?,
+            },
+            "PERMUTATIONSORT" => Directive::PermutationSort {
+                inputs: self.parse_field_named("inputs", |parser| {
+                    parser.parse_list(|parser| parser.parse_list(Self::parse_expression))
+                })?,
+                tuple: self.parse_next_field("tuple", Self::parse_u32)?,
+                bits: self.parse_next_field("bits", Self::parse_witnesses)?,
+                sort_by: self
+                    .parse_next_field("sort_by", |parser| parser.parse_list(Self::parse_u32))?,
+            },
+            _ => {
+                return self.expected_previous("a directive (QUOTIENT, TORADIX or PERMUTATIONSORT)")
+            }
+        };
+        Ok(directive)
+    }
+
+    fn parse_brillig(&mut self) -> Result<Brillig, AcirParseError> {
+        self.eat_keyword("BRILLIG")?;
+        let predicate = self.parse_predicate()?;
+        let inputs = self.parse_field_named("inputs", |parser| {
+            parser.parse_list(|parser| {
+                let input = if parser.eat_variant("Single")? {
+                    BrilligInputs::Single(parser.parse_expression()?)
+                } else {
+                    BrilligInputs::Array(parser.parse_list(Self::parse_expression)?)
+                };
+                parser.eat(Token::RightParen)?;
+                Ok(input)
+            })
+        })?;
+        let outputs = self.parse_next_field("outputs", |parser| {
+            parser.parse_list(|parser| {
+                let output = if parser.eat_variant("Simple")? {
+                    BrilligOutputs::Simple(parser.parse_witness()?)
+                } else {
+                    BrilligOutputs::Array(parser.parse_witnesses()?)
+                };
+                parser.eat(Token::RightParen)?;
+                Ok(output)
+            })
+        })?;
+        self.eat(Token::Comma)?;
+        self.eat_keyword("bytecode")?;
+        self.eat(Token::Colon)?;
+        self.eat(Token::LeftBracket)?;
+        let mut bytecode = Vec::new();
+        while !self.eat_if(Token::RightBracket) {
+            let index = bytecode.len();
+            if self.parse_usize()? != index {
+                return self.expected_previous(&format!("Brillig opcode index {index}"));
+            }
+            self.eat(Token::Colon)?;
+            bytecode.push(self.parse_brillig_opcode()?);
+        }
+        Ok(Brillig { inputs, outputs, bytecode, predicate })
+    }
+
+    /// Parses the `Single(` or `Simple(` prefix of a Brillig input or output, returning true if
+    /// it is `expected` rather than `Array`.
+    fn eat_variant(&mut self, expected: &str) -> Result<bool, AcirParseError> {
+        let is_expected = self.is_keyword(expected);
+        if is_expected {
+            self.advance();
+        } else {
+            self.eat_keyword("Array")?;
+        }
+        self.eat(Token::LeftParen)?;
+        Ok(is_expected)
+    }
+
+    fn parse_brillig_opcode(&mut self) -> Result<BrilligOpcode, AcirParseError> {
+        if self.peek() == &Token::LeftBracket {
+            let destinations = self.parse_list(Self::parse_register_or_memory)?;
+            self.eat(Token::Assign)?;
+            self.eat_keyword("foreign_call")?;
+            let function = match self.peek().clone() {
+                Token::Str(function) => {
+                    self.advance();
+                    function
+                }
+                _ => return self.expected("a foreign function name"),
+            };
+            let inputs = self.parse_list(Self::parse_register_or_memory)?;
+            return Ok(BrilligOpcode::ForeignCall { function, destinations, inputs });
+        }
+
+        let name = self.parse_ident("a Brillig opcode")?;
+        if let Some(destination) = parse_register(&name) {
+            self.eat(Token::Assign)?;
+            return self.parse_brillig_assignment(destination);
+        }
+
+        let opcode = match name.as_str() {
+            "jmp_if_not" => BrilligOpcode::JumpIfNot {
+                condition: self.parse_register()?,
+                location: self.parse_next(Self::parse_usize)?,
+            },
+            "jmp_if" => BrilligOpcode::JumpIf {
+                condition: self.parse_register()?,
+                location: self.parse_next(Self::parse_usize)?,
+            },
+            "jmp" => BrilligOpcode::Jump { location: self.parse_usize()? },
+            "call" => BrilligOpcode::Call { location: self.parse_usize()? },
+            "store" => BrilligOpcode::Store {
+                destination_pointer: self.parse_register()?,
+                source: self.parse_next(Self::parse_register)?,
+            },
+            "return" => BrilligOpcode::Return,
+            "trap" => BrilligOpcode::Trap,
+            "stop" => BrilligOpcode::Stop,
+            "black_box" => BrilligOpcode::BlackBox(self.parse_black_box_op()?),
+            _ => return self.expected_previous("a Brillig opcode"),
+        };
+        Ok(opcode)
+    }
+
+    /// Parses the right hand side of a Brillig opcode of the form `r0 = ...`
+    fn parse_brillig_assignment(
+        &mut self,
+        destination: RegisterIndex,
+    ) -> Result<BrilligOpcode, AcirParseError> {
+        let name = self.parse_ident("a Brillig opcode")?;
+        let opcode = match name.as_str() {
+            "const" => {
+                BrilligOpcode::Const { destination, value: Value::from(self.parse_field()?) }
+            }
+            "mov" => BrilligOpcode::Mov { destination, source: self.parse_register()? },
+            "load" => BrilligOpcode::Load { destination, source_pointer: self.parse_register()? },
+            "field" => {
+                let op = match self.parse_ident("a binary field operation")?.as_str() {
+                    "add" => BinaryFieldOp::Add,
+                    "sub" => BinaryFieldOp::Sub,
+                    "mul" => BinaryFieldOp::Mul,
+                    "div" => BinaryFieldOp::Div,
+                    "eq" => BinaryFieldOp::Equals,
+                    _ => return self.expected_previous("a binary field operation"),
+                };
+                let lhs = self.parse_register()?;
+                let rhs = self.parse_next(Self::parse_register)?;
+                BrilligOpcode::BinaryFieldOp { destination, op, lhs, rhs }
+            }
+            _ => {
+                let Some(bit_size) = name.strip_prefix('u').and_then(|bits| bits.parse::<u32>().ok())
+                else {
+                    return self.expected_previous("a Brillig opcode");
+                };
+                let op = match self.parse_ident("a binary integer operation")?.as_str() {
+                    "add" => BinaryIntOp::Add,
+                    "sub" => BinaryIntOp::Sub,
+                    "mul" => BinaryIntOp::Mul,
+                    "sdiv" => BinaryIntOp::SignedDiv,
+                    "udiv" => BinaryIntOp::UnsignedDiv,
+                    "eq" => BinaryIntOp::Equals,
+                    "lt" => BinaryIntOp::LessThan,
+                    "lte" => BinaryIntOp::LessThanEquals,
+                    "and" => BinaryIntOp::And,
+                    "or" => BinaryIntOp::Or,
+                    "xor" => BinaryIntOp::Xor,
+                    "shl" => BinaryIntOp::Shl,
+                    "shr" => BinaryIntOp::Shr,
+                    _ => return self.expected_previous("a binary integer operation"),
+                };
+                let lhs = self.parse_register()?;
+                let rhs = self.parse_next(Self::parse_register)?;
+                BrilligOpcode::BinaryIntOp { destination, op, bit_size, lhs, rhs }
+            }
+        };
+        Ok(opcode)
+    }
+
+    fn parse_black_box_op(&mut self) -> Result<BlackBoxOp, AcirParseError> {
+        let name = self.parse_ident("a black box function")?;
+        let Some(func) = BlackBoxFunc::lookup(&name) else {
+            return Err(AcirParseError::UnknownBlackBoxFunction { name, line: self.line() });
+        };
+
+        let op = match func {
+            BlackBoxFunc::SHA256 | BlackBoxFunc::Blake2s | BlackBoxFunc::Keccak256 => {
+                let message = self.parse_field_named("message", Self::parse_heap_vector)?;
+                let output = self.parse_next_field("output", Self::parse_heap_array)?;
+                match func {
+                    BlackBoxFunc::SHA256 => BlackBoxOp::Sha256 { message, output },
+                    BlackBoxFunc::Blake2s => BlackBoxOp::Blake2s { message, output },
+                    _ => BlackBoxOp::Keccak256 { message, output },
+                }
+            }
+            BlackBoxFunc::HashToField128Security => BlackBoxOp::HashToField128Security {
+                message: self.parse_field_named("message", Self::parse_heap_vector)?,
+                output: self.parse_next_field("output", Self::parse_register)?,
+            },
+            BlackBoxFunc::EcdsaSecp256k1 | BlackBoxFunc::EcdsaSecp256r1 => {
+                let hashed_msg = self.parse_field_named("hashed_msg", Self::parse_heap_vector)?;
+                let public_key_x = self.parse_next_field("public_key_x", Self::parse_heap_array)?;
+                let public_key_y = self.parse_next_field("public_key_y", Self::parse_heap_array)?;
+                let signature = self.parse_next_field("signature", Self::parse_heap_array)?;
+                let result = self.parse_next_field("result", Self::parse_register)?;
+                if func == BlackBoxFunc::EcdsaSecp256k1 {
+                    BlackBoxOp::EcdsaSecp256k1 {
+                        hashed_msg,
+                        public_key_x,
+                        public_key_y,
+                        signature,
+                        result,
+                    }
+                } else {
+                    BlackBoxOp::EcdsaSecp256r1 {
+                        hashed_msg,
+                        public_key_x,
+                        public_key_y,
+                        signature,
+                        result,
+                    }
+                }
+            }
+            BlackBoxFunc::SchnorrVerify => BlackBoxOp::SchnorrVerify {
+                public_key_x: self.parse_field_named("public_key_x", Self::parse_register)?,
+                public_key_y: self.parse_next_field("public_key_y", Self::parse_register)?,
+                message: self.parse_next_field("message", Self::parse_heap_vector)?,
+                signature: self.parse_next_field("signature", Self::parse_heap_vector)?,
+                result: self.parse_next_field("result", Self::parse_register)?,
+            },
+            BlackBoxFunc::PedersenCommitment => BlackBoxOp::PedersenCommitment {
+                inputs: self.parse_field_named("inputs", Self::parse_heap_vector)?,
+                domain_separator: self
+                    .parse_next_field("domain_separator", Self::parse_register)?,
+                output: self.parse_next_field("output", Self::parse_heap_array)?,
+            },
+            BlackBoxFunc::PedersenHash => BlackBoxOp::PedersenHash {
+                inputs: self.parse_field_named("inputs", Self::parse_heap_vector)?,
+                domain_separator: self
+                    .parse_next_field("domain_separator", Self::parse_register)?,
+                output: self.parse_next_field("output", Self::parse_register)?,
+            },
+            BlackBoxFunc::FixedBaseScalarMul => BlackBoxOp::FixedBaseScalarMul {
+                low: self.parse_field_named("low", Self::parse_register)?,
+                high: self.parse_next_field("high", Self::parse_register)?,
+                result: self.parse_next_field("result", Self::parse_heap_array)?,
+            },
+            BlackBoxFunc::AND
+            | BlackBoxFunc::XOR
+            | BlackBoxFunc::RANGE
+            | BlackBoxFunc::RecursiveAggregation => {
+                return Err(AcirParseError::UnknownBlackBoxFunction { name, line: self.line() })
+            }
+        };
+        Ok(op)
+    }
+
+    fn parse_register_or_memory(&mut self) -> Result<RegisterOrMemory, AcirParseError> {
+        if self.is_keyword("array") {
+            Ok(RegisterOrMemory::HeapArray(self.parse_heap_array()?))
+        } else if self.is_keyword("vector") {
+            Ok(RegisterOrMemory::HeapVector(self.parse_heap_vector()?))
+        } else {
+            Ok(RegisterOrMemory::RegisterIndex(self.parse_register()?))
+        }
+    }
+
+    /// Parses a heap array such as `array(r0, 4)`
+    fn parse_heap_array(&mut self) -> Result<HeapArray, AcirParseError> {
+        self.eat_keyword("array")?;
+        self.eat(Token::LeftParen)?;
+        let pointer = self.parse_register()?;
+        let size = self.parse_next(Self::parse_usize)?;
+        self.eat(Token::RightParen)?;
+        Ok(HeapArray { pointer, size })
+    }
+
+    /// Parses a heap vector such as `vector(r0, r1)`
+    fn parse_heap_vector(&mut self) -> Result<HeapVector, AcirParseError> {
+        self.eat_keyword("vector")?;
+        self.eat(Token::LeftParen)?;
+        let pointer = self.parse_register()?;
+        let size = self.parse_next(Self::parse_register)?;
+        self.eat(Token::RightParen)?;
+        Ok(HeapVector { pointer, size })
+    }
+
+    fn parse_register(&mut self) -> Result<RegisterIndex, AcirParseError> {
+        if let Token::Ident(name) = self.peek() {
+            if let Some(register) = parse_register(name) {
+                self.advance();
+                return Ok(register);
+            }
+        }
+        self.expected("a register")
+    }
+
+    fn parse_memory_op(&mut self) -> Result<Opcode, AcirParseError> {
+        self.eat_keyword("MEM")?;
+        let predicate = self.parse_predicate()?;
+        self.eat(Token::LeftParen)?;
+        let block_id = BlockId(self.parse_field_named("id", Self::parse_u32)?);
+        self.eat(Token::Comma)?;
+
+        let op = match self.parse_ident("`read`, `write` or `op`")?.as_str() {
+            "read" => {
+                let index = self.parse_field_named("at", Self::parse_expression)?;
+                let value = self.parse_next_field("value", Self::parse_expression)?;
+                MemOp { operation: Expression::zero(), index, value }
+            }
+            "write" => {
+                let value = self.parse_expression()?;
+                let index = self.parse_field_named("at", Self::parse_expression)?;
+                MemOp { operation: Expression::one(), index, value }
+            }
+            "op" => {
+                let operation = self.parse_expression()?;
+                let index = self.parse_field_named("at", Self::parse_expression)?;
+                let value = self.parse_next_field("value", Self::parse_expression)?;
+                MemOp { operation, index, value }
+            }
+            _ => return self.expected_previous("`read`, `write` or `op`"),
+        };
+        self.eat(Token::RightParen)?;
+        Ok(Opcode::MemoryOp { block_id, op, predicate })
+    }
+
+    fn parse_memory_init(&mut self) -> Result<Opcode, AcirParseError> {
+        self.eat_keyword("INIT")?;
+        self.eat(Token::LeftParen)?;
+        let block_id = BlockId(self.parse_field_named("id", Self::parse_u32)?);
+        let init = self.parse_next_field("init", Self::parse_witnesses)?;
+        self.eat(Token::RightParen)?;
+        Ok(Opcode::MemoryInit { block_id, init })
+    }
+
+    fn parse_call(&mut self) -> Result<Opcode, AcirParseError> {
+        self.eat_keyword("CALL")?;
+        let predicate = self.parse_predicate()?;
+        self.eat_keyword("func")?;
+        let id = self.parse_u32()?;
+        self.eat(Token::Colon)?;
+        let inputs = self.parse_field_named("inputs", Self::parse_witnesses)?;
+        let outputs = self.parse_next_field("outputs", Self::parse_witnesses)?;
+        Ok(Opcode::Call { id, inputs, outputs, predicate })
+    }
+
+    /// Parses a function input such as `(_1, num_bits: 8)`
+    fn parse_function_input(&mut self) -> Result<FunctionInput, AcirParseError> {
+        self.eat(Token::LeftParen)?;
+        let witness = self.parse_witness()?;
+        let num_bits = self.parse_next_field("num_bits", Self::parse_u32)?;
+        self.eat(Token::RightParen)?;
+        Ok(FunctionInput { witness, num_bits })
+    }
+
+    fn parse_function_inputs(&mut self) -> Result<Vec<FunctionInput>, AcirParseError> {
+        self.parse_list(Self::parse_function_input)
+    }
+
+    fn parse_witness(&mut self) -> Result<Witness, AcirParseError> {
+        match self.peek() {
+            Token::Witness(index) => {
+                let witness = Witness(*index);
+                self.advance();
+                Ok(witness)
+            }
+            _ => self.expected("a witness"),
+        }
+    }
+
+    fn parse_witnesses(&mut self) -> Result<Vec<Witness>, AcirParseError> {
+        self.parse_list(Self::parse_witness)
+    }
+
+    /// Parses a pair of witnesses such as `(_1, _2)`
+    fn parse_witness_pair(&mut self) -> Result<(Witness, Witness), AcirParseError> {
+        self.eat(Token::LeftParen)?;
+        let first = self.parse_witness()?;
+        let second = self.parse_next(Self::parse_witness)?;
+        self.eat(Token::RightParen)?;
+        Ok((first, second))
+    }
+
+    fn parse_field(&mut self) -> Result<FieldElement, AcirParseError> {
+        match self.peek() {
+            Token::Int(value) => {
+                let value = *value;
+                self.advance();
+                Ok(value)
+            }
+            _ => self.expected("an integer"),
+        }
+    }
+
+    fn parse_u32(&mut self) -> Result<u32, AcirParseError> {
+        let line = self.line();
+        let value = self.parse_field()?;
+        value.try_to_u64().and_then(|value| u32::try_from(value).ok()).ok_or_else(|| {
+            AcirParseError::InvalidInteger { value: DisplayField(value).to_string(), line }
+        })
+    }
+
+    fn parse_usize(&mut self) -> Result<usize, AcirParseError> {
+        let line = self.line();
+        let value = self.parse_field()?;
+        value.try_to_u64().and_then(|value| usize::try_from(value).ok()).ok_or_else(|| {
+            AcirParseError::InvalidInteger { value: DisplayField(value).to_string(), line }
+        })
+    }
+
+    /// Parses a comma separated list surrounded by square brackets
+    fn parse_list<T>(
+        &mut self,
+        mut parse_item: impl FnMut(&mut Self) -> Result<T, AcirParseError>,
+    ) -> Result<Vec<T>, AcirParseError> {
+        self.eat(Token::LeftBracket)?;
+        let mut items = Vec::new();
+        if self.eat_if(Token::RightBracket) {
+            return Ok(items);
+        }
+        loop {
+            items.push(parse_item(self)?);
+            if !self.eat_if(Token::Comma) {
+                break;
+            }
+        }
+        self.eat(Token::RightBracket)?;
+        Ok(items)
+    }
+
+    /// Parses a named field such as `output: _3`
+    fn parse_field_named<T>(
+        &mut self,
+        name: &str,
+        parse_value: impl FnOnce(&mut Self) -> Result<T, AcirParseError>,
+    ) -> Result<T, AcirParseError> {
+        self.eat_keyword(name)?;
+        self.eat(Token::Colon)?;
+        parse_value(self)
+    }
+
+    /// Parses a named field preceded by a comma
+    fn parse_next_field<T>(
+        &mut self,
+        name: &str,
+        parse_value: impl FnOnce(&mut Self) -> Result<T, AcirParseError>,
+    ) -> Result<T, AcirParseError> {
+        self.eat(Token::Comma)?;
+        self.parse_field_named(name, parse_value)
+    }
+
+    /// Parses a value preceded by a comma
+    fn parse_next<T>(
+        &mut self,
+        parse_value: impl FnOnce(&mut Self) -> Result<T, AcirParseError>,
+    ) -> Result<T, AcirParseError> {
+        self.eat(Token::Comma)?;
+        parse_value(self)
+    }
+
+    fn parse_ident(&mut self, expected: &str) -> Result<String, AcirParseError> {
+        match self.peek() {
+            Token::Ident(name) => {
+                let name = name.clone();
+                self.advance();
+                Ok(name)
+            }
+            _ => self.expected(expected),
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Token::Ident(name) if name == keyword)
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> Result<(), AcirParseError> {
+        if self.is_keyword(keyword) {
+            self.advance();
+            Ok(())
+        } else {
+            self.expected(&format!("`{keyword}`"))
+        }
+    }
+
+    fn eat_keywords(&mut self, keywords: &[&str]) -> Result<(), AcirParseError> {
+        keywords.iter().try_for_each(|keyword| self.eat_keyword(keyword))
+    }
+
+    fn eat(&mut self, token: Token) -> Result<(), AcirParseError> {
+        if self.eat_if(token.clone()) {
+            Ok(())
+        } else {
+            self.expected(&format!("`{token}`"))
+        }
+    }
+
+    /// Advances past the next token if it is the given token, returning true if so
+    fn eat_if(&mut self, token: Token) -> bool {
+        if self.peek() == &token {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expected<T>(&self, expected: &str) -> Result<T, AcirParseError> {
+        self.unexpected_token(expected, self.position)
+    }
+
+    /// Reports that the token which was just parsed was not the `expected` one
+    fn expected_previous<T>(&self, expected: &str) -> Result<T, AcirParseError> {
+        self.unexpected_token(expected, self.position.saturating_sub(1))
+    }
+
+    fn unexpected_token<T>(&self, expected: &str, position: usize) -> Result<T, AcirParseError> {
+        let (found, line) = &self.tokens[position];
+        Err(AcirParseError::UnexpectedToken {
+            expected: expected.to_owned(),
+            found: found.to_string(),
+            line: *line,
+        })
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position].0
+    }
+
+    fn line(&self) -> usize {
+        self.tokens[self.position].1
+    }
+
+    fn advance(&mut self) {
+        // Never advance past the trailing Eof token
+        if self.position + 1 < self.tokens.len() {
+            self.position += 1;
+        }
+    }
+}
+
+/// Parses a register such as `r3`
+fn parse_register(name: &str) -> Option<RegisterIndex> {
+    let index = name.strip_prefix('r')?;
+    if index.is_empty() || !index.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    index.parse().ok().map(RegisterIndex)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,199 @@
+use acir_field::FieldElement;
+
+use super::AcirParseError;
+
+/// The tokens of the textual ACIR format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Token {
+    /// Keywords, opcode names, field names and Brillig registers (`r0`)
+    Ident(String),
+    /// A witness index such as `_5`
+    Witness(u32),
+    /// A decimal or hexadecimal integer, optionally negated
+    Int(FieldElement),
+    /// The unescaped contents of a double quoted string
+    Str(String),
+    LeftParen,
+    RightParen,
+    LeftBracket,
+    RightBracket,
+    Comma,
+    Colon,
+    Dot,
+    Assign,
+    Eof,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Ident(name) => write!(f, "{name}"),
+            Token::Witness(index) => write!(f, "_{index}"),
+            Token::Int(value) => write!(f, "{value}"),
+            Token::Str(string) => write!(f, "{string:?}"),
+            Token::LeftParen => write!(f, "("),
+            Token::RightParen => write!(f, ")"),
+            Token::LeftBracket => write!(f, "["),
+            Token::RightBracket => write!(f, "]"),
+            Token::Comma => write!(f, ","),
+            Token::Colon => write!(f, ":"),
+            Token::Dot => write!(f, "."),
+            Token::Assign => write!(f, "="),
+            Token::Eof => write!(f, "end of input"),
+        }
+    }
+}
+
+/// A token along with the line it was found on
+pub(super) type SpannedToken = (Token, usize);
+
+pub(super) struct Lexer<'src> {
+    source: &'src str,
+    position: usize,
+    line: usize,
+}
+
+impl<'src> Lexer<'src> {
+    pub(super) fn new(source: &'src str) -> Self {
+        Self { source, position: 0, line: 1 }
+    }
+
+    /// Splits the source into tokens. The returned tokens always end with `Token::Eof`.
+    pub(super) fn lex(mut self) -> Result<Vec<SpannedToken>, AcirParseError> {
+        let mut tokens = Vec::new();
+        loop {
+            let token = self.next_token()?;
+            let done = token == Token::Eof;
+            tokens.push((token, self.line));
+            if done {
+                return Ok(tokens);
+            }
+        }
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.source[self.position..].chars().next()
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let char = self.peek_char()?;
+        self.position += char.len_utf8();
+        if char == '\n' {
+            self.line += 1;
+        }
+        Some(char)
+    }
+
+    /// Consumes characters for as long as `predicate` holds, returning the consumed slice
+    fn eat_while(&mut self, predicate: impl Fn(char) -> bool) -> &'src str {
+        let start = self.position;
+        while self.peek_char().map_or(false, &predicate) {
+            self.next_char();
+        }
+        &self.source[start..self.position]
+    }
+
+    fn next_token(&mut self) -> Result<Token, AcirParseError> {
+        self.eat_while(char::is_whitespace);
+
+        let Some(char) = self.next_char() else {
+            return Ok(Token::Eof);
+        };
+
+        let token = match char {
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '[' => Token::LeftBracket,
+            ']' => Token::RightBracket,
+            ',' => Token::Comma,
+            ':' => Token::Colon,
+            '.' => Token::Dot,
+            '=' => Token::Assign,
+            '-' if self.peek_char().map_or(false, |char| char.is_ascii_digit()) => {
+                let first_digit = self.next_char().unwrap();
+                Token::Int(-self.lex_integer(first_digit)?)
+            }
+            '_' if self.peek_char().map_or(false, |char| char.is_ascii_digit()) => {
+                let index = self.eat_while(|char| char.is_ascii_digit());
+                let index = index.parse().map_err(|_| AcirParseError::InvalidInteger {
+                    value: format!("_{index}"),
+                    line: self.line,
+                })?;
+                Token::Witness(index)
+            }
+            '"' => Token::Str(self.lex_string()?),
+            char if char.is_ascii_digit() => Token::Int(self.lex_integer(char)?),
+            char if char.is_ascii_alphabetic() => {
+                let start = self.position - char.len_utf8();
+                self.eat_while(|char| char.is_ascii_alphanumeric() || char == '_');
+                Token::Ident(self.source[start..self.position].to_owned())
+            }
+            character => {
+                return Err(AcirParseError::UnexpectedCharacter { character, line: self.line })
+            }
+        };
+        Ok(token)
+    }
+
+    /// Lexes a decimal integer, or a hexadecimal one if it starts with `0x`
+    fn lex_integer(&mut self, first_digit: char) -> Result<FieldElement, AcirParseError> {
+        let start = self.position - first_digit.len_utf8();
+        let value = if first_digit == '0' && self.peek_char() == Some('x') {
+            self.next_char();
+            self.eat_while(|char| char.is_ascii_hexdigit());
+            let digits = &self.source[start + 2..self.position];
+            // `FieldElement::from_hex` expects whole bytes
+            let padded =
+                if digits.len() % 2 == 1 { format!("0{digits}") } else { digits.to_owned() };
+            FieldElement::from_hex(&padded).filter(|_| !digits.is_empty())
+        } else {
+            self.eat_while(|char| char.is_ascii_digit());
+            let digits = &self.source[start..self.position];
+            match digits.parse::<u128>() {
+                Ok(value) => Some(FieldElement::from(value)),
+                Err(_) => FieldElement::try_from_str(digits),
+            }
+        };
+        value.ok_or_else(|| AcirParseError::InvalidInteger {
+            value: self.source[start..self.position].to_owned(),
+            line: self.line,
+        })
+    }
+
+    /// Lexes a string after its opening quote, unescaping it as written by `{:?}`
+    fn lex_string(&mut self) -> Result<String, AcirParseError> {
+        let line = self.line;
+        let mut string = String::new();
+        loop {
+            let char = match self.next_char() {
+                Some('"') => return Ok(string),
+                Some('\\') => self.lex_escape()?,
+                Some(char) => char,
+                None => return Err(AcirParseError::UnterminatedString { line }),
+            };
+            string.push(char);
+        }
+    }
+
+    fn lex_escape(&mut self) -> Result<char, AcirParseError> {
+        let line = self.line;
+        let char = match self.next_char() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('0') => '\0',
+            Some(char @ ('\\' | '"' | '\'')) => char,
+            Some('u') if self.peek_char() == Some('{') => {
+                self.next_char();
+                let digits = self.eat_while(|char| char.is_ascii_hexdigit());
+                let char = u32::from_str_radix(digits, 16).ok().and_then(char::from_u32);
+                match (char, self.next_char()) {
+                    (Some(char), Some('}')) => char,
+                    _ => return Err(AcirParseError::InvalidEscape { line }),
+                }
+            }
+            _ => return Err(AcirParseError::InvalidEscape { line }),
+        };
+        Ok(char)
+    }
+}
@@ -0,0 +1,423 @@
+use std::collections::BTreeSet;
+
+use acir_field::FieldElement;
+use brillig::{
+    BinaryFieldOp, BinaryIntOp, BlackBoxOp, HeapArray, HeapVector, Opcode as BrilligOpcode,
+    RegisterIndex, RegisterOrMemory, Value,
+};
+
+use super::AcirParseError;
+use crate::{
+    circuit::{
+        brillig::{Brillig, BrilligInputs, BrilligOutputs},
+        directives::{Directive, QuotientDirective},
+        opcodes::{BlackBoxFuncCall, BlockId, FunctionInput, MemOp},
+        Circuit, Opcode, OpcodeLocation, Program, PublicInputs,
+    },
+    native_types::{Expression, Witness},
+};
+
+/// Parses the given circuit and checks that printing it back out gives the same text,
+/// ignoring indentation.
+fn assert_circuit_roundtrip(src: &str) {
+    let circuit: Circuit = src.parse().unwrap();
+    let printed = circuit.to_string();
+    let expected: Vec<_> = src.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+    let actual: Vec<_> = printed.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+    assert_eq!(actual, expected, "Printed circuit:\n{printed}");
+}
+
+/// Prints the given circuit and checks that parsing the text gives back the same circuit
+fn assert_print_parse_roundtrip(circuit: Circuit) {
+    let printed = circuit.to_string();
+    let parsed: Circuit =
+        printed.parse().unwrap_or_else(|error| panic!("{error} in circuit:\n{printed}"));
+    assert_eq!(parsed, circuit);
+}
+
+fn input(witness: u32, num_bits: u32) -> FunctionInput {
+    FunctionInput { witness: Witness(witness), num_bits }
+}
+
+fn inputs(witnesses: std::ops::Range<u32>) -> Vec<FunctionInput> {
+    witnesses.map(|witness| input(witness, 8)).collect()
+}
+
+fn witnesses(witnesses: std::ops::Range<u32>) -> Vec<Witness> {
+    witnesses.map(Witness).collect()
+}
+
+fn circuit(opcodes: Vec<Opcode>) -> Circuit {
+    Circuit {
+        current_witness_index: 100,
+        opcodes,
+        private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+        public_parameters: PublicInputs(BTreeSet::from([Witness(3)])),
+        return_values: PublicInputs(BTreeSet::from([Witness(4), Witness(5)])),
+        assert_messages: Vec::new(),
+    }
+}
+
+#[test]
+fn roundtrip_header_and_arithmetic() {
+    let src = "
+        current witness index : 4
+        private parameters indices : [1, 2]
+        public parameters indices : []
+        return value indices : [4]
+        EXPR [ (1, _1, _2) (-1, _3) 0 ]
+        EXPR [ (2, _3) (-1, _4) 5 ]
+        EXPR [ -7 ]
+        ASSERT 1 : \"x must be \\\"5\\\"\"
+        ASSERT 0.3 : \"brillig failure\"
+    ";
+    assert_circuit_roundtrip(src);
+
+    let circuit: Circuit = src.parse().unwrap();
+    assert_eq!(
+        circuit.assert_messages,
+        vec![
+            (OpcodeLocation::Acir(1), "x must be \"5\"".to_owned()),
+            (OpcodeLocation::Brillig { acir_index: 0, brillig_index: 3 }, "brillig failure".into())
+        ]
+    );
+    assert_eq!(
+        circuit.opcodes[2],
+        Opcode::Arithmetic(Expression::from_field(-FieldElement::from(7u128)))
+    );
+}
+
+#[test]
+fn roundtrip_memory_and_calls() {
+    let src = "
+        current witness index : 5
+        private parameters indices : [1, 2, 3]
+        public parameters indices : [1]
+        return value indices : []
+        INIT (id: 0, init: [_1, _2, _3])
+        MEM (id: 0, read at: [ (1, _1) 0 ], value: [ (1, _4) 0 ])
+        MEM PREDICATE = [ (1, _2) 0 ] (id: 0, write [ (1, _3) 0 ] at: [ 1 ])
+        MEM (id: 0, op [ (1, _2) 0 ] at: [ 2 ], value: [ (1, _4) 0 ])
+        CALL func 1: inputs: [_1, _2], outputs: [_5]
+        CALL PREDICATE = [ (1, _3) 0 ] func 2: inputs: [], outputs: [_4]
+    ";
+    assert_circuit_roundtrip(src);
+}
+
+#[test]
+fn roundtrip_brillig() {
+    let src = "
+        current witness index : 3
+        private parameters indices : [1]
+        public parameters indices : []
+        return value indices : [3]
+        BRILLIG PREDICATE = [ 1 ] inputs: [Single([ (1, _1) 0 ]), Array([[ 1 ], [ (2, _1) 0 ]])], outputs: [Simple(_2), Array([_3])], bytecode: [
+          0: r1 = const 1
+          1: r2 = field add r0, r1
+          2: r3 = u32 lt r0, r2
+          3: jmp_if_not r3, 5
+          4: [r4, array(r5, 2)] = foreign_call \"oracle\" [r0, vector(r6, r7)]
+          5: black_box sha256 message: vector(r0, r1), output: array(r2, 32)
+          6: stop
+        ]
+    ";
+    assert_circuit_roundtrip(src);
+}
+
+#[test]
+fn roundtrip_program() {
+    let src = "
+        func 0
+        current witness index : 3
+        private parameters indices : [1, 2]
+        public parameters indices : []
+        return value indices : [3]
+        CALL func 1: inputs: [_1, _2], outputs: [_3]
+
+        func 1
+        current witness index : 3
+        private parameters indices : [1, 2]
+        public parameters indices : []
+        return value indices : [3]
+        EXPR [ (1, _1) (1, _2) (-1, _3) 0 ]
+    ";
+    let program: Program = src.parse().unwrap();
+    assert_eq!(program.functions.len(), 2);
+    assert_eq!(program.to_string().parse::<Program>().unwrap(), program);
+}
+
+#[test]
+fn print_parse_roundtrip_of_every_opcode() {
+    let expression = Expression {
+        mul_terms: vec![(FieldElement::from(3u128), Witness(1), Witness(2))],
+        linear_combinations: vec![(-FieldElement::one(), Witness(3))],
+        // Too large to be printed in decimal
+        q_c: FieldElement::from(u128::MAX) * FieldElement::from(u128::MAX),
+    };
+    let opcodes = vec![
+        Opcode::Arithmetic(expression.clone()),
+        Opcode::Directive(Directive::Quotient(QuotientDirective {
+            a: expression.clone(),
+            b: Expression::from_field(FieldElement::from(1u128 << 100)),
+            q: Witness(4),
+            r: Witness(5),
+            predicate: Some(Expression::one()),
+        })),
+        Opcode::Directive(Directive::ToLeRadix {
+            a: expression.clone(),
+            b: witnesses(6..10),
+            radix: 2,
+        }),
+        Opcode::Directive(Directive::PermutationSort {
+            inputs: vec![
+                vec![Witness(1).into(), Witness(2).into()],
+                vec![Witness(3).into(), expression.clone()],
+            ],
+            tuple: 2,
+            bits: witnesses(10..13),
+            sort_by: vec![1, 0],
+        }),
+        Opcode::MemoryInit { block_id: BlockId(1), init: witnesses(1..4) },
+        Opcode::MemoryOp {
+            block_id: BlockId(1),
+            op: MemOp::read_at_mem_index(expression.clone(), Witness(4)),
+            predicate: None,
+        },
+        Opcode::MemoryOp {
+            block_id: BlockId(1),
+            op: MemOp::write_to_mem_index(Expression::one(), expression.clone()),
+            predicate: Some(Witness(5).into()),
+        },
+        Opcode::Call { id: 1, inputs: witnesses(1..3), outputs: witnesses(3..4), predicate: None },
+    ];
+    assert_print_parse_roundtrip(circuit(opcodes));
+}
+
+#[test]
+fn print_parse_roundtrip_of_every_black_box_function() {
+    let calls = vec![
+        BlackBoxFuncCall::AND { lhs: input(1, 8), rhs: input(2, 8), output: Witness(3) },
+        BlackBoxFuncCall::XOR { lhs: input(1, 8), rhs: input(2, 8), output: Witness(3) },
+        BlackBoxFuncCall::RANGE { input: input(1, 32) },
+        BlackBoxFuncCall::SHA256 { inputs: inputs(1..4), outputs: witnesses(4..36) },
+        BlackBoxFuncCall::Blake2s { inputs: inputs(1..4), outputs: witnesses(4..36) },
+        BlackBoxFuncCall::SchnorrVerify {
+            public_key_x: input(1, 254),
+            public_key_y: input(2, 254),
+            signature: inputs(3..67),
+            message: inputs(67..70),
+            output: Witness(70),
+        },
+        BlackBoxFuncCall::PedersenCommitment {
+            inputs: inputs(1..3),
+            domain_separator: 5,
+            outputs: (Witness(3), Witness(4)),
+        },
+        BlackBoxFuncCall::PedersenHash {
+            inputs: inputs(1..3),
+            domain_separator: 0,
+            output: Witness(3),
+        },
+        BlackBoxFuncCall::HashToField128Security { inputs: inputs(1..3), output: Witness(3) },
+        BlackBoxFuncCall::EcdsaSecp256k1 {
+            public_key_x: inputs(1..33),
+            public_key_y: inputs(33..65),
+            signature: inputs(65..129),
+            hashed_message: inputs(129..161),
+            output: Witness(161),
+        },
+        BlackBoxFuncCall::EcdsaSecp256r1 {
+            public_key_x: inputs(1..33),
+            public_key_y: inputs(33..65),
+            signature: inputs(65..129),
+            hashed_message: inputs(129..161),
+            output: Witness(161),
+        },
+        BlackBoxFuncCall::FixedBaseScalarMul {
+            low: input(1, 128),
+            high: input(2, 128),
+            outputs: (Witness(3), Witness(4)),
+        },
+        BlackBoxFuncCall::Keccak256 { inputs: inputs(1..4), outputs: witnesses(4..36) },
+        BlackBoxFuncCall::Keccak256VariableLength {
+            inputs: inputs(1..4),
+            var_message_size: input(4, 32),
+            outputs: witnesses(5..37),
+        },
+        BlackBoxFuncCall::RecursiveAggregation {
+            verification_key: inputs(1..3),
+            proof: inputs(3..5),
+            public_inputs: inputs(5..6),
+            key_hash: input(6, 254),
+            input_aggregation_object: None,
+            output_aggregation_object: witnesses(7..9),
+        },
+        BlackBoxFuncCall::RecursiveAggregation {
+            verification_key: inputs(1..3),
+            proof: inputs(3..5),
+            public_inputs: vec![],
+            key_hash: input(6, 254),
+            input_aggregation_object: Some(inputs(9..11)),
+            output_aggregation_object: witnesses(11..13),
+        },
+    ];
+    assert_print_parse_roundtrip(circuit(
+        calls.into_iter().map(Opcode::BlackBoxFuncCall).collect(),
+    ));
+}
+
+#[test]
+fn print_parse_roundtrip_of_every_brillig_opcode() {
+    let r = RegisterIndex;
+    let vector = HeapVector { pointer: r(0), size: r(1) };
+    let array = HeapArray { pointer: r(2), size: 32 };
+    let mut bytecode = vec![
+        BrilligOpcode::BinaryFieldOp {
+            destination: r(2),
+            op: BinaryFieldOp::Div,
+            lhs: r(0),
+            rhs: r(1),
+        },
+        BrilligOpcode::JumpIfNot { condition: r(0), location: 3 },
+        BrilligOpcode::JumpIf { condition: r(0), location: 3 },
+        BrilligOpcode::Jump { location: 0 },
+        BrilligOpcode::Call { location: 7 },
+        BrilligOpcode::Const { destination: r(0), value: Value::from(-FieldElement::from(2u128)) },
+        BrilligOpcode::Return,
+        BrilligOpcode::ForeignCall {
+            function: "print\n\u{1}".to_owned(),
+            destinations: vec![],
+            inputs: vec![
+                RegisterOrMemory::RegisterIndex(r(0)),
+                RegisterOrMemory::HeapArray(array),
+                RegisterOrMemory::HeapVector(vector),
+            ],
+        },
+        BrilligOpcode::Mov { destination: r(0), source: r(1) },
+        BrilligOpcode::Load { destination: r(0), source_pointer: r(1) },
+        BrilligOpcode::Store { destination_pointer: r(0), source: r(1) },
+        BrilligOpcode::BlackBox(BlackBoxOp::Sha256 { message: vector, output: array }),
+        BrilligOpcode::BlackBox(BlackBoxOp::Blake2s { message: vector, output: array }),
+        BrilligOpcode::BlackBox(BlackBoxOp::Keccak256 { message: vector, output: array }),
+        BrilligOpcode::BlackBox(BlackBoxOp::HashToField128Security {
+            message: vector,
+            output: r(3),
+        }),
+        BrilligOpcode::BlackBox(BlackBoxOp::EcdsaSecp256k1 {
+            hashed_msg: vector,
+            public_key_x: array,
+            public_key_y: array,
+            signature: array,
+            result: r(3),
+        }),
+        BrilligOpcode::BlackBox(BlackBoxOp::EcdsaSecp256r1 {
+            hashed_msg: vector,
+            public_key_x: array,
+            public_key_y: array,
+            signature: array,
+            result: r(3),
+        }),
+        BrilligOpcode::BlackBox(BlackBoxOp::SchnorrVerify {
+            public_key_x: r(3),
+            public_key_y: r(4),
+            message: vector,
+            signature: vector,
+            result: r(5),
+        }),
+        BrilligOpcode::BlackBox(BlackBoxOp::PedersenCommitment {
+            inputs: vector,
+            domain_separator: r(3),
+            output: array,
+        }),
+        BrilligOpcode::BlackBox(BlackBoxOp::PedersenHash {
+            inputs: vector,
+            domain_separator: r(3),
+            output: r(4),
+        }),
+        BrilligOpcode::BlackBox(BlackBoxOp::FixedBaseScalarMul {
+            low: r(0),
+            high: r(1),
+            result: array,
+        }),
+        BrilligOpcode::Trap,
+        BrilligOpcode::Stop,
+    ];
+    for op in [
+        BinaryIntOp::Add,
+        BinaryIntOp::Sub,
+        BinaryIntOp::Mul,
+        BinaryIntOp::SignedDiv,
+        BinaryIntOp::UnsignedDiv,
+        BinaryIntOp::Equals,
+        BinaryIntOp::LessThan,
+        BinaryIntOp::LessThanEquals,
+        BinaryIntOp::And,
+        BinaryIntOp::Or,
+        BinaryIntOp::Xor,
+        BinaryIntOp::Shl,
+        BinaryIntOp::Shr,
+    ] {
+        bytecode.push(BrilligOpcode::BinaryIntOp {
+            destination: r(2),
+            op,
+            bit_size: 64,
+            lhs: r(0),
+            rhs: r(1),
+        });
+    }
+
+    let brillig = Opcode::Brillig(Brillig {
+        inputs: vec![BrilligInputs::Single(Witness(1).into())],
+        outputs: vec![BrilligOutputs::Array(witnesses(2..4))],
+        bytecode,
+        predicate: None,
+    });
+    assert_print_parse_roundtrip(circuit(vec![brillig]));
+}
+
+#[test]
+fn reports_errors_with_line_numbers() {
+    let header = "current witness index : 1
+        private parameters indices : []
+        public parameters indices : []
+        return value indices : []
+    ";
+
+    let error = format!("{header}EXPR [ (1, _1 ]").parse::<Circuit>().unwrap_err();
+    assert_eq!(
+        error,
+        AcirParseError::UnexpectedToken { expected: "`)`".into(), found: "]".into(), line: 5 }
+    );
+
+    let error = format!("{header}BLACKBOX::MD5 inputs: []").parse::<Circuit>().unwrap_err();
+    assert_eq!(error, AcirParseError::UnknownBlackBoxFunction { name: "MD5".into(), line: 5 });
+
+    let error = format!("{header}\nASSERT 0 : \"unterminated").parse::<Circuit>().unwrap_err();
+    assert_eq!(error, AcirParseError::UnterminatedString { line: 6 });
+
+    let error = format!("{header}INIT (id: 4294967296, init: [])").parse::<Circuit>().unwrap_err();
+    assert_eq!(error, AcirParseError::InvalidInteger { value: "4294967296".into(), line: 5 });
+}
+
+#[test]
+fn rejects_misnumbered_brillig_opcodes() {
+    let src = "
+        current witness index : 1
+        private parameters indices : []
+        public parameters indices : []
+        return value indices : []
+        BRILLIG inputs: [], outputs: [], bytecode: [
+          0: stop
+          2: stop
+        ]
+    ";
+    let error = src.parse::<Circuit>().unwrap_err();
+    assert_eq!(
+        error,
+        AcirParseError::UnexpectedToken {
+            expected: "Brillig opcode index 1".into(),
+            found: "2".into(),
+            line: 8
+        }
+    );
+}
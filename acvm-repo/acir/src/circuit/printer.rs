@@ -0,0 +1,279 @@
+//! Helpers for printing the components of the textual ACIR format which are shared between opcodes.
+//!
+//! The format itself is described in the [`parser`][super::parser] module, which reads it back.
+use std::fmt::{Display, Formatter, Result};
+
+use acir_field::FieldElement;
+use brillig::{
+    BinaryFieldOp, BinaryIntOp, BlackBoxOp, HeapArray, HeapVector, Opcode as BrilligOpcode,
+    RegisterIndex, RegisterOrMemory,
+};
+
+use super::opcodes::FunctionInput;
+use crate::native_types::{Expression, Witness};
+
+/// Prints a field element as a decimal integer if it or its negation fits in a `u128`,
+/// or as a hexadecimal integer otherwise.
+///
+/// Unlike [`FieldElement`]'s own `Display` impl, this never abbreviates powers of two.
+pub(crate) struct DisplayField(pub(crate) FieldElement);
+
+impl Display for DisplayField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if self.0.fits_in_u128() {
+            write!(f, "{}", self.0.to_u128())
+        } else if (-self.0).fits_in_u128() {
+            write!(f, "-{}", (-self.0).to_u128())
+        } else {
+            write!(f, "0x{}", self.0.to_hex())
+        }
+    }
+}
+
+/// Prints an expression as `[ (q_m, _l, _r)... (q_l, _w)... q_c ]`
+pub(crate) struct DisplayExpression<'a>(pub(crate) &'a Expression);
+
+impl Display for DisplayExpression<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "[ ")?;
+        for (coefficient, lhs, rhs) in &self.0.mul_terms {
+            write!(f, "({}, _{}, _{}) ", DisplayField(*coefficient), lhs.0, rhs.0)?;
+        }
+        for (coefficient, witness) in &self.0.linear_combinations {
+            write!(f, "({}, _{}) ", DisplayField(*coefficient), witness.0)?;
+        }
+        write!(f, "{} ]", DisplayField(self.0.q_c))
+    }
+}
+
+/// Prints the `PREDICATE = [ ... ] ` prefix of opcodes which may be skipped
+pub(crate) struct DisplayPredicate<'a>(pub(crate) &'a Option<Expression>);
+
+impl Display for DisplayPredicate<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.0 {
+            Some(predicate) => write!(f, "PREDICATE = {} ", DisplayExpression(predicate)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Prints a comma separated list surrounded by square brackets
+pub(crate) struct DisplayList<'a, T>(pub(crate) &'a [T], pub(crate) fn(&T) -> String);
+
+impl<T> Display for DisplayList<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "[")?;
+        for (index, item) in self.0.iter().enumerate() {
+            if index != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", (self.1)(item))?;
+        }
+        write!(f, "]")
+    }
+}
+
+pub(crate) fn witnesses(witnesses: &[Witness]) -> DisplayList<'_, Witness> {
+    DisplayList(witnesses, |witness| format!("_{}", witness.0))
+}
+
+pub(crate) fn expressions(expressions: &[Expression]) -> DisplayList<'_, Expression> {
+    DisplayList(expressions, |expression| DisplayExpression(expression).to_string())
+}
+
+pub(crate) fn function_input(input: &FunctionInput) -> String {
+    format!("(_{}, num_bits: {})", input.witness.0, input.num_bits)
+}
+
+pub(crate) fn function_inputs(inputs: &[FunctionInput]) -> DisplayList<'_, FunctionInput> {
+    DisplayList(inputs, function_input)
+}
+
+/// Prints a Brillig opcode in an assembly-like syntax, e.g. `r2 = u32 add r0, r1`
+pub(crate) struct DisplayBrilligOpcode<'a>(pub(crate) &'a BrilligOpcode);
+
+impl Display for DisplayBrilligOpcode<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.0 {
+            BrilligOpcode::BinaryFieldOp { destination, op, lhs, rhs } => {
+                let op = binary_field_op_name(*op);
+                write!(f, "r{} = field {op} r{}, r{}", destination.0, lhs.0, rhs.0)
+            }
+            BrilligOpcode::BinaryIntOp { destination, op, bit_size, lhs, rhs } => {
+                let op = binary_int_op_name(*op);
+                write!(f, "r{} = u{bit_size} {op} r{}, r{}", destination.0, lhs.0, rhs.0)
+            }
+            BrilligOpcode::JumpIfNot { condition, location } => {
+                write!(f, "jmp_if_not r{}, {location}", condition.0)
+            }
+            BrilligOpcode::JumpIf { condition, location } => {
+                write!(f, "jmp_if r{}, {location}", condition.0)
+            }
+            BrilligOpcode::Jump { location } => write!(f, "jmp {location}"),
+            BrilligOpcode::Call { location } => write!(f, "call {location}"),
+            BrilligOpcode::Const { destination, value } => {
+                write!(f, "r{} = const {}", destination.0, DisplayField(value.to_field()))
+            }
+            BrilligOpcode::Return => write!(f, "return"),
+            BrilligOpcode::ForeignCall { function, destinations, inputs } => {
+                write!(
+                    f,
+                    "{} = foreign_call {function:?} {}",
+                    DisplayList(&destinations[..], register_or_memory),
+                    DisplayList(&inputs[..], register_or_memory),
+                )
+            }
+            BrilligOpcode::Mov { destination, source } => {
+                write!(f, "r{} = mov r{}", destination.0, source.0)
+            }
+            BrilligOpcode::Load { destination, source_pointer } => {
+                write!(f, "r{} = load r{}", destination.0, source_pointer.0)
+            }
+            BrilligOpcode::Store { destination_pointer, source } => {
+                write!(f, "store r{}, r{}", destination_pointer.0, source.0)
+            }
+            BrilligOpcode::BlackBox(op) => write_black_box_op(f, op),
+            BrilligOpcode::Trap => write!(f, "trap"),
+            BrilligOpcode::Stop => write!(f, "stop"),
+        }
+    }
+}
+
+fn binary_field_op_name(op: BinaryFieldOp) -> &'static str {
+    match op {
+        BinaryFieldOp::Add => "add",
+        BinaryFieldOp::Sub => "sub",
+        BinaryFieldOp::Mul => "mul",
+        BinaryFieldOp::Div => "div",
+        BinaryFieldOp::Equals => "eq",
+    }
+}
+
+fn binary_int_op_name(op: BinaryIntOp) -> &'static str {
+    match op {
+        BinaryIntOp::Add => "add",
+        BinaryIntOp::Sub => "sub",
+        BinaryIntOp::Mul => "mul",
+        BinaryIntOp::SignedDiv => "sdiv",
+        BinaryIntOp::UnsignedDiv => "udiv",
+        BinaryIntOp::Equals => "eq",
+        BinaryIntOp::LessThan => "lt",
+        BinaryIntOp::LessThanEquals => "lte",
+        BinaryIntOp::And => "and",
+        BinaryIntOp::Or => "or",
+        BinaryIntOp::Xor => "xor",
+        BinaryIntOp::Shl => "shl",
+        BinaryIntOp::Shr => "shr",
+    }
+}
+
+fn register_or_memory(value: &RegisterOrMemory) -> String {
+    match value {
+        RegisterOrMemory::RegisterIndex(register) => register_index(register),
+        RegisterOrMemory::HeapArray(array) => heap_array(array),
+        RegisterOrMemory::HeapVector(vector) => heap_vector(vector),
+    }
+}
+
+fn register_index(register: &RegisterIndex) -> String {
+    format!("r{}", register.0)
+}
+
+fn heap_array(array: &HeapArray) -> String {
+    format!("array(r{}, {})", array.pointer.0, array.size)
+}
+
+fn heap_vector(vector: &HeapVector) -> String {
+    format!("vector(r{}, r{})", vector.pointer.0, vector.size.0)
+}
+
+fn write_black_box_op(f: &mut Formatter<'_>, op: &BlackBoxOp) -> Result {
+    match op {
+        BlackBoxOp::Sha256 { message, output } => write!(
+            f,
+            "black_box sha256 message: {}, output: {}",
+            heap_vector(message),
+            heap_array(output)
+        ),
+        BlackBoxOp::Blake2s { message, output } => write!(
+            f,
+            "black_box blake2s message: {}, output: {}",
+            heap_vector(message),
+            heap_array(output)
+        ),
+        BlackBoxOp::Keccak256 { message, output } => write!(
+            f,
+            "black_box keccak256 message: {}, output: {}",
+            heap_vector(message),
+            heap_array(output)
+        ),
+        BlackBoxOp::HashToField128Security { message, output } => write!(
+            f,
+            "black_box hash_to_field_128_security message: {}, output: {}",
+            heap_vector(message),
+            register_index(output)
+        ),
+        BlackBoxOp::EcdsaSecp256k1 {
+            hashed_msg,
+            public_key_x,
+            public_key_y,
+            signature,
+            result,
+        }
+        | BlackBoxOp::EcdsaSecp256r1 {
+            hashed_msg,
+            public_key_x,
+            public_key_y,
+            signature,
+            result,
+        } => {
+            let name = if matches!(op, BlackBoxOp::EcdsaSecp256k1 { .. }) {
+                "ecdsa_secp256k1"
+            } else {
+                "ecdsa_secp256r1"
+            };
+            write!(
+                f,
+                "black_box {name} hashed_msg: {}, public_key_x: {}, public_key_y: {}, signature: {}, result: {}",
+                heap_vector(hashed_msg),
+                heap_array(public_key_x),
+                heap_array(public_key_y),
+                heap_array(signature),
+                register_index(result)
+            )
+        }
+        BlackBoxOp::SchnorrVerify { public_key_x, public_key_y, message, signature, result } => {
+            write!(
+                f,
+                "black_box schnorr_verify public_key_x: {}, public_key_y: {}, message: {}, signature: {}, result: {}",
+                register_index(public_key_x),
+                register_index(public_key_y),
+                heap_vector(message),
+                heap_vector(signature),
+                register_index(result)
+            )
+        }
+        BlackBoxOp::PedersenCommitment { inputs, domain_separator, output } => write!(
+            f,
+            "black_box pedersen inputs: {}, domain_separator: {}, output: {}",
+            heap_vector(inputs),
+            register_index(domain_separator),
+            heap_array(output)
+        ),
+        BlackBoxOp::PedersenHash { inputs, domain_separator, output } => write!(
+            f,
+            "black_box pedersen_hash inputs: {}, domain_separator: {}, output: {}",
+            heap_vector(inputs),
+            register_index(domain_separator),
+            register_index(output)
+        ),
+        BlackBoxOp::FixedBaseScalarMul { low, high, result } => write!(
+            f,
+            "black_box fixed_base_scalar_mul low: {}, high: {}, result: {}",
+            register_index(low),
+            register_index(high),
+            heap_array(result)
+        ),
+    }
+}
@@ -0,0 +1,153 @@
+//! Versioned serialization of [circuits][super::Circuit] and [programs][super::Program].
+//!
+//! Serialized ACIR is a gzip stream whose payload is the bincode or JSON encoding of the circuit.
+//! The version of the format and the encoding of the payload are stored in an extra field of the
+//! gzip header. Gzip decoders skip this field, so backends can keep decompressing the bincode
+//! payload directly. ACIR serialized before this header was introduced has no such field and is
+//! read as format version 0.
+use std::io::{Read, Write};
+
+use flate2::{read::GzDecoder, Compression, GzBuilder};
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
+
+/// The version of the serialization format written by this version of ACIR.
+///
+/// This must be bumped whenever a change to the ACIR types changes how they are encoded, along with
+/// a migration from the previous version in [`migrate`].
+pub const CURRENT_FORMAT_VERSION: u32 = 1;
+
+/// The id of the subfield of the gzip header's extra field which holds the format version and encoding
+const HEADER_SUBFIELD_ID: [u8; 2] = *b"AC";
+
+/// The encoding of the payload of serialized ACIR
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SerializationFormat {
+    /// The compact encoding expected by backends
+    #[default]
+    Bincode,
+    Json,
+}
+
+impl SerializationFormat {
+    fn id(self) -> u8 {
+        match self {
+            SerializationFormat::Bincode => 0,
+            SerializationFormat::Json => 1,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(SerializationFormat::Bincode),
+            1 => Some(SerializationFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SerializationError {
+    #[error("ACIR was serialized with format version {found} but only versions up to {latest} are supported. Please upgrade to a newer version of ACIR")]
+    UnsupportedVersion { found: u32, latest: u32 },
+    #[error("ACIR was serialized with an unknown encoding ({0})")]
+    UnknownEncoding(u8),
+    #[error("The ACIR format header is malformed")]
+    MalformedHeader,
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Failed to deserialize ACIR: {0}")]
+    Bincode(#[from] bincode::Error),
+    #[error("Failed to deserialize ACIR: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+pub(super) fn write<T: Serialize, W: Write>(
+    value: &T,
+    format: SerializationFormat,
+    writer: W,
+) -> std::io::Result<()> {
+    let buf = match format {
+        SerializationFormat::Bincode => bincode::serialize(value).unwrap(),
+        SerializationFormat::Json => serde_json::to_vec(value).unwrap(),
+    };
+
+    let mut header = HEADER_SUBFIELD_ID.to_vec();
+    header.extend_from_slice(&5u16.to_le_bytes());
+    header.extend_from_slice(&CURRENT_FORMAT_VERSION.to_le_bytes());
+    header.push(format.id());
+
+    let mut encoder = GzBuilder::new().extra(header).write(writer, Compression::default());
+    encoder.write_all(&buf)?;
+    encoder.finish()?;
+    Ok(())
+}
+
+pub(super) fn read<T: DeserializeOwned, R: Read>(reader: R) -> Result<T, SerializationError> {
+    let mut gz_decoder = GzDecoder::new(reader);
+    let mut buf_d = Vec::new();
+    gz_decoder.read_to_end(&mut buf_d)?;
+
+    let (version, format) = match gz_decoder.header().and_then(|header| header.extra()) {
+        Some(extra) => read_header(extra)?,
+        None => (0, SerializationFormat::Bincode),
+    };
+    migrate(version, format, &buf_d)
+}
+
+/// Finds the format version and encoding in the extra field of a gzip header
+fn read_header(extra: &[u8]) -> Result<(u32, SerializationFormat), SerializationError> {
+    let mut subfields = extra;
+    while subfields.len() >= 4 {
+        let id = [subfields[0], subfields[1]];
+        let length = u16::from_le_bytes([subfields[2], subfields[3]]) as usize;
+        let data = subfields.get(4..4 + length).ok_or(SerializationError::MalformedHeader)?;
+        subfields = &subfields[4 + length..];
+        if id != HEADER_SUBFIELD_ID {
+            continue;
+        }
+
+        let &[v0, v1, v2, v3, encoding] = data else {
+            return Err(SerializationError::MalformedHeader);
+        };
+        let version = u32::from_le_bytes([v0, v1, v2, v3]);
+        let format = SerializationFormat::from_id(encoding)
+            .ok_or(SerializationError::UnknownEncoding(encoding))?;
+        return Ok((version, format));
+    }
+    Ok((0, SerializationFormat::Bincode))
+}
+
+/// Decodes a payload written with the given format version, upgrading it to the current ACIR types.
+///
+/// Payloads written by a newer version of ACIR are rejected, as their types are unknown to this version.
+///
+/// When a change to the ACIR types breaks how they are encoded, keep a copy of the affected types
+/// as they were in a module for the previous version, and add a case here which decodes a payload
+/// into those types and converts them into the current ones.
+fn migrate<T: DeserializeOwned>(
+    version: u32,
+    format: SerializationFormat,
+    payload: &[u8],
+) -> Result<T, SerializationError> {
+    match version {
+        // Version 0 is the unversioned format written before the version header was introduced.
+        // It encodes the same types as version 1, so upgrading it is the identity.
+        0 => migrate(1, format, payload),
+        CURRENT_FORMAT_VERSION => decode(format, payload),
+        _ => Err(SerializationError::UnsupportedVersion {
+            found: version,
+            latest: CURRENT_FORMAT_VERSION,
+        }),
+    }
+}
+
+fn decode<T: DeserializeOwned>(
+    format: SerializationFormat,
+    payload: &[u8],
+) -> Result<T, SerializationError> {
+    match format {
+        SerializationFormat::Bincode => Ok(bincode::deserialize(payload)?),
+        SerializationFormat::Json => Ok(serde_json::from_slice(payload)?),
+    }
+}
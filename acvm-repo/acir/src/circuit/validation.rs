@@ -0,0 +1,421 @@
+//! Checks for the structural invariants of a [`Circuit`] which backends rely on.
+//!
+//! Circuits which break these invariants are not necessarily unsatisfiable, but backends tend to
+//! fail on them in ways which are hard to trace back to the offending opcode.
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use brillig::Opcode as BrilligOpcode;
+use thiserror::Error;
+
+use super::{
+    brillig::{Brillig, BrilligInputs, BrilligOutputs},
+    directives::{Directive, QuotientDirective},
+    opcodes::{BlackBoxFuncCall, BlockId, MemOp},
+    Circuit, Opcode, OpcodeLocation,
+};
+use crate::{
+    native_types::{Expression, Witness},
+    BlackBoxFunc,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum ValidationError {
+    #[error("Opcode {location} uses witness _{} which is above the current witness index {current_witness_index}", witness.0)]
+    WitnessOutOfBounds { location: OpcodeLocation, witness: Witness, current_witness_index: u32 },
+    #[error("Public input or return value _{} is above the current witness index {current_witness_index}{}", witness.0, used_at(location))]
+    PublicInputOutOfBounds {
+        /// The first opcode which uses the witness, if any
+        location: Option<OpcodeLocation>,
+        witness: Witness,
+        current_witness_index: u32,
+    },
+    #[error("Opcode {location} accesses memory block {} before it is initialized", block_id.0)]
+    UninitializedMemoryBlock { location: OpcodeLocation, block_id: BlockId },
+    #[error("Opcode {location} calls {func} with {found} witnesses for `{argument}` but it expects {expected}")]
+    BlackBoxArgumentCount {
+        location: OpcodeLocation,
+        func: BlackBoxFunc,
+        argument: &'static str,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Circuit {
+    /// Checks that the circuit is well formed, returning the first violation found:
+    ///
+    /// - No witness is above [`current_witness_index`][Circuit::current_witness_index].
+    /// - Memory blocks are initialized before they are accessed.
+    /// - Black box functions are called with the number of witnesses they expect.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let opcode_witnesses: Vec<_> = self.opcodes.iter().map(opcode_witnesses).collect();
+
+        for witness in self.public_parameters.0.iter().chain(&self.return_values.0) {
+            if witness.0 > self.current_witness_index {
+                let location = opcode_witnesses
+                    .iter()
+                    .position(|witnesses| witnesses.contains(witness))
+                    .map(OpcodeLocation::Acir);
+                return Err(ValidationError::PublicInputOutOfBounds {
+                    location,
+                    witness: *witness,
+                    current_witness_index: self.current_witness_index,
+                });
+            }
+        }
+
+        let mut initialized_blocks = HashSet::new();
+        for (index, (opcode, witnesses)) in self.opcodes.iter().zip(&opcode_witnesses).enumerate() {
+            let location = OpcodeLocation::Acir(index);
+
+            if let Some(witness) = witnesses.iter().find(|w| w.0 > self.current_witness_index) {
+                return Err(ValidationError::WitnessOutOfBounds {
+                    location,
+                    witness: *witness,
+                    current_witness_index: self.current_witness_index,
+                });
+            }
+
+            match opcode {
+                Opcode::MemoryInit { block_id, .. } => {
+                    initialized_blocks.insert(*block_id);
+                }
+                Opcode::MemoryOp { block_id, .. } => {
+                    if !initialized_blocks.contains(block_id) {
+                        return Err(ValidationError::UninitializedMemoryBlock {
+                            location,
+                            block_id: *block_id,
+                        });
+                    }
+                }
+                Opcode::BlackBoxFuncCall(call) => {
+                    for (argument, expected, found) in fixed_argument_counts(call) {
+                        if expected != found {
+                            return Err(ValidationError::BlackBoxArgumentCount {
+                                location,
+                                func: call.get_black_box_func(),
+                                argument,
+                                expected,
+                                found,
+                            });
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the location of each Brillig opcode none of whose outputs are used elsewhere in
+    /// the circuit. Brillig opcodes which make foreign calls are skipped as they may be executed
+    /// only for their side effects.
+    ///
+    /// Such opcodes do not make the circuit invalid, but are usually left behind by a missed
+    /// optimization and only slow down witness generation.
+    pub fn find_unused_brillig_outputs(&self) -> Vec<OpcodeLocation> {
+        let opcode_witnesses: Vec<_> = self.opcodes.iter().map(opcode_witnesses).collect();
+
+        // The number of opcodes which use each witness
+        let mut witness_uses: HashMap<Witness, usize> = HashMap::new();
+        for witnesses in &opcode_witnesses {
+            for witness in witnesses {
+                *witness_uses.entry(*witness).or_default() += 1;
+            }
+        }
+        let is_used = |witness: &Witness| {
+            witness_uses[witness] > 1
+                || self.public_parameters.contains(witness.0 as usize)
+                || self.return_values.contains(witness.0 as usize)
+        };
+
+        let mut unused = Vec::new();
+        for (index, opcode) in self.opcodes.iter().enumerate() {
+            let Opcode::Brillig(brillig) = opcode else {
+                continue;
+            };
+            let has_side_effects = brillig
+                .bytecode
+                .iter()
+                .any(|opcode| matches!(opcode, BrilligOpcode::ForeignCall { .. }));
+            let outputs = brillig_output_witnesses(brillig);
+            if !has_side_effects && !outputs.is_empty() && !outputs.iter().any(is_used) {
+                unused.push(OpcodeLocation::Acir(index));
+            }
+        }
+        unused
+    }
+}
+
+fn used_at(location: &Option<OpcodeLocation>) -> String {
+    match location {
+        Some(location) => format!(" (used by opcode {location})"),
+        None => String::new(),
+    }
+}
+
+/// Returns the name, expected length and actual length of each argument of a black box function
+/// call which must have a fixed number of witnesses.
+fn fixed_argument_counts(call: &BlackBoxFuncCall) -> Vec<(&'static str, usize, usize)> {
+    match call {
+        BlackBoxFuncCall::SHA256 { outputs, .. }
+        | BlackBoxFuncCall::Blake2s { outputs, .. }
+        | BlackBoxFuncCall::Keccak256 { outputs, .. }
+        | BlackBoxFuncCall::Keccak256VariableLength { outputs, .. } => {
+            vec![("outputs", 32, outputs.len())]
+        }
+        BlackBoxFuncCall::SchnorrVerify { signature, .. } => {
+            vec![("signature", 64, signature.len())]
+        }
+        BlackBoxFuncCall::EcdsaSecp256k1 { public_key_x, public_key_y, signature, .. }
+        | BlackBoxFuncCall::EcdsaSecp256r1 { public_key_x, public_key_y, signature, .. } => vec![
+            ("public_key_x", 32, public_key_x.len()),
+            ("public_key_y", 32, public_key_y.len()),
+            ("signature", 64, signature.len()),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+fn brillig_output_witnesses(brillig: &Brillig) -> Vec<Witness> {
+    let mut witnesses = Vec::new();
+    for output in &brillig.outputs {
+        match output {
+            BrilligOutputs::Simple(witness) => witnesses.push(*witness),
+            BrilligOutputs::Array(array) => witnesses.extend(array),
+        }
+    }
+    witnesses
+}
+
+/// Returns every witness which an opcode reads or writes
+pub fn opcode_witnesses(opcode: &Opcode) -> BTreeSet<Witness> {
+    let mut witnesses = BTreeSet::new();
+    match opcode {
+        Opcode::Arithmetic(expression) => add_expression(&mut witnesses, expression),
+        Opcode::BlackBoxFuncCall(call) => {
+            witnesses.extend(call.get_inputs_vec().iter().map(|input| input.witness));
+            witnesses.extend(call.get_outputs_vec());
+        }
+        Opcode::Directive(Directive::Quotient(QuotientDirective { a, b, q, r, predicate })) => {
+            add_expression(&mut witnesses, a);
+            add_expression(&mut witnesses, b);
+            witnesses.extend([*q, *r]);
+            add_predicate(&mut witnesses, predicate);
+        }
+        Opcode::Directive(Directive::ToLeRadix { a, b, .. }) => {
+            add_expression(&mut witnesses, a);
+            witnesses.extend(b);
+        }
+        Opcode::Directive(Directive::PermutationSort { inputs, bits, .. }) => {
+            inputs.iter().flatten().for_each(|input| add_expression(&mut witnesses, input));
+            witnesses.extend(bits);
+        }
+        Opcode::Brillig(brillig) => {
+            for input in &brillig.inputs {
+                match input {
+                    BrilligInputs::Single(expression) => add_expression(&mut witnesses, expression),
+                    BrilligInputs::Array(array) => {
+                        array.iter().for_each(|input| add_expression(&mut witnesses, input));
+                    }
+                }
+            }
+            witnesses.extend(brillig_output_witnesses(brillig));
+            add_predicate(&mut witnesses, &brillig.predicate);
+        }
+        Opcode::MemoryOp { op: MemOp { operation, index, value }, predicate, .. } => {
+            add_expression(&mut witnesses, operation);
+            add_expression(&mut witnesses, index);
+            add_expression(&mut witnesses, value);
+            add_predicate(&mut witnesses, predicate);
+        }
+        Opcode::MemoryInit { init, .. } => witnesses.extend(init),
+        Opcode::Call { inputs, outputs, predicate, .. } => {
+            witnesses.extend(inputs);
+            witnesses.extend(outputs);
+            add_predicate(&mut witnesses, predicate);
+        }
+    }
+    witnesses
+}
+
+fn add_expression(witnesses: &mut BTreeSet<Witness>, expression: &Expression) {
+    for (_, lhs, rhs) in &expression.mul_terms {
+        witnesses.extend([*lhs, *rhs]);
+    }
+    witnesses.extend(expression.linear_combinations.iter().map(|(_, witness)| *witness));
+}
+
+fn add_predicate(witnesses: &mut BTreeSet<Witness>, predicate: &Option<Expression>) {
+    if let Some(predicate) = predicate {
+        add_expression(witnesses, predicate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use brillig::{Opcode as BrilligOpcode, RegisterIndex, Value};
+
+    use super::ValidationError;
+    use crate::{
+        circuit::{
+            brillig::{Brillig, BrilligInputs, BrilligOutputs},
+            opcodes::{BlackBoxFuncCall, BlockId, FunctionInput, MemOp},
+            Circuit, Opcode, OpcodeLocation, PublicInputs,
+        },
+        native_types::{Expression, Witness},
+        BlackBoxFunc,
+    };
+
+    fn brillig_opcode(output: Witness) -> Opcode {
+        Opcode::Brillig(Brillig {
+            inputs: vec![BrilligInputs::Single(Witness(1).into())],
+            outputs: vec![BrilligOutputs::Simple(output)],
+            bytecode: vec![BrilligOpcode::Const {
+                destination: RegisterIndex(0),
+                value: Value::from(1_usize),
+            }],
+            predicate: None,
+        })
+    }
+
+    #[test]
+    fn accepts_valid_circuit() {
+        let circuit = Circuit {
+            current_witness_index: 3,
+            opcodes: vec![
+                brillig_opcode(Witness(2)),
+                Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(1), Witness(2)] },
+                Opcode::MemoryOp {
+                    block_id: BlockId(0),
+                    op: MemOp::read_at_mem_index(Expression::zero(), Witness(3)),
+                    predicate: None,
+                },
+            ],
+            return_values: PublicInputs([Witness(3)].into()),
+            ..Circuit::default()
+        };
+        assert_eq!(circuit.validate(), Ok(()));
+        assert!(circuit.find_unused_brillig_outputs().is_empty());
+    }
+
+    #[test]
+    fn rejects_witness_above_current_witness_index() {
+        let circuit = Circuit {
+            current_witness_index: 1,
+            opcodes: vec![Opcode::Arithmetic(&Expression::from(Witness(1)) - Witness(2))],
+            ..Circuit::default()
+        };
+        assert_eq!(
+            circuit.validate(),
+            Err(ValidationError::WitnessOutOfBounds {
+                location: OpcodeLocation::Acir(0),
+                witness: Witness(2),
+                current_witness_index: 1,
+            })
+        );
+
+        let circuit = Circuit {
+            current_witness_index: 1,
+            return_values: PublicInputs([Witness(2)].into()),
+            ..Circuit::default()
+        };
+        assert_eq!(
+            circuit.validate(),
+            Err(ValidationError::PublicInputOutOfBounds {
+                location: None,
+                witness: Witness(2),
+                current_witness_index: 1,
+            })
+        );
+
+        let circuit = Circuit {
+            current_witness_index: 1,
+            opcodes: vec![
+                Opcode::Arithmetic(Expression::from(Witness(1))),
+                Opcode::Arithmetic(&Expression::from(Witness(1)) - Witness(2)),
+            ],
+            public_parameters: PublicInputs([Witness(2)].into()),
+            ..Circuit::default()
+        };
+        assert_eq!(
+            circuit.validate(),
+            Err(ValidationError::PublicInputOutOfBounds {
+                location: Some(OpcodeLocation::Acir(1)),
+                witness: Witness(2),
+                current_witness_index: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_uninitialized_memory_block() {
+        let circuit = Circuit {
+            current_witness_index: 2,
+            opcodes: vec![
+                Opcode::MemoryOp {
+                    block_id: BlockId(1),
+                    op: MemOp::read_at_mem_index(Expression::zero(), Witness(2)),
+                    predicate: None,
+                },
+                Opcode::MemoryInit { block_id: BlockId(1), init: vec![Witness(1)] },
+            ],
+            ..Circuit::default()
+        };
+        assert_eq!(
+            circuit.validate(),
+            Err(ValidationError::UninitializedMemoryBlock {
+                location: OpcodeLocation::Acir(0),
+                block_id: BlockId(1),
+            })
+        );
+    }
+
+    #[test]
+    fn finds_unused_brillig_outputs() {
+        let circuit = Circuit {
+            current_witness_index: 2,
+            opcodes: vec![brillig_opcode(Witness(2))],
+            ..Circuit::default()
+        };
+        // Unused outputs do not make the circuit invalid
+        assert_eq!(circuit.validate(), Ok(()));
+        assert_eq!(circuit.find_unused_brillig_outputs(), vec![OpcodeLocation::Acir(0)]);
+
+        // Brillig opcodes which make foreign calls may be executed for their side effects alone.
+        let Opcode::Brillig(mut brillig) = brillig_opcode(Witness(2)) else { unreachable!() };
+        brillig.bytecode.push(BrilligOpcode::ForeignCall {
+            function: "print".to_owned(),
+            destinations: Vec::new(),
+            inputs: Vec::new(),
+        });
+        let circuit = Circuit {
+            current_witness_index: 2,
+            opcodes: vec![Opcode::Brillig(brillig)],
+            ..Circuit::default()
+        };
+        assert!(circuit.find_unused_brillig_outputs().is_empty());
+    }
+
+    #[test]
+    fn rejects_wrong_black_box_argument_count() {
+        let circuit = Circuit {
+            current_witness_index: 33,
+            opcodes: vec![Opcode::BlackBoxFuncCall(BlackBoxFuncCall::SHA256 {
+                inputs: vec![FunctionInput { witness: Witness(1), num_bits: 8 }],
+                outputs: (2..33).map(Witness).collect(),
+            })],
+            ..Circuit::default()
+        };
+        assert_eq!(
+            circuit.validate(),
+            Err(ValidationError::BlackBoxArgumentCount {
+                location: OpcodeLocation::Acir(0),
+                func: BlackBoxFunc::SHA256,
+                argument: "outputs",
+                expected: 32,
+                found: 31,
+            })
+        );
+    }
+}
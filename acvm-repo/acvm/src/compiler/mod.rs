@@ -1,5 +1,5 @@
 use acir::{
-    circuit::{opcodes::UnsupportedMemoryOpcode, Circuit, Opcode, OpcodeLocation},
+    circuit::{opcodes::UnsupportedMemoryOpcode, Circuit, Opcode, OpcodeLocation, ValidationError},
     BlackBoxFunc,
 };
 use thiserror::Error;
@@ -11,7 +11,7 @@ mod optimizers;
 mod transformers;
 
 pub use optimizers::optimize;
-use optimizers::optimize_internal;
+use optimizers::{optimize_internal, LinearSubstitutionOptimizer};
 pub use transformers::transform;
 use transformers::transform_internal;
 
@@ -21,6 +21,15 @@ pub enum CompileError {
     UnsupportedBlackBox(BlackBoxFunc),
     #[error("The opcode {0} is not supported by the backend and acvm does not have a fallback implementation")]
     UnsupportedMemoryOpcode(UnsupportedMemoryOpcode),
+    #[error("The compiled circuit is malformed: {0}")]
+    InvalidCircuit(#[from] ValidationError),
+}
+
+/// Issues found in a compiled circuit which do not prevent it from being executed
+#[derive(PartialEq, Eq, Debug, Error)]
+pub enum CompileWarning {
+    #[error("The outputs of the Brillig opcode at {0} are never used")]
+    UnusedBrilligOutputs(OpcodeLocation),
 }
 
 /// This module moves and decomposes acir opcodes. The transformation map allows consumers of this module to map
@@ -69,17 +78,124 @@ fn transform_assert_messages(
 }
 
 /// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] specific optimizations to a [`Circuit`].
+///
+/// Along with the optimized circuit, returns any issues found in it which should be reported to the user.
 pub fn compile(
     acir: Circuit,
     np_language: Language,
     is_opcode_supported: impl Fn(&Opcode) -> bool,
-) -> Result<(Circuit, AcirTransformationMap), CompileError> {
+) -> Result<(Circuit, AcirTransformationMap, Vec<CompileWarning>), CompileError> {
     let (acir, AcirTransformationMap { acir_opcode_positions }) = optimize_internal(acir);
 
+    // Linear substitution depends on the width of the target language so it is not part of `optimize`
+    let linear_optimizer = LinearSubstitutionOptimizer::new(acir, np_language);
+    let (acir, acir_opcode_positions) =
+        linear_optimizer.substitute_linear_witnesses(acir_opcode_positions);
+
     let (mut acir, transformation_map) =
         transform_internal(acir, np_language, is_opcode_supported, acir_opcode_positions)?;
 
     acir.assert_messages = transform_assert_messages(acir.assert_messages, &transformation_map);
 
-    Ok((acir, transformation_map))
+    // Catch malformed circuits here rather than letting them reach the backend.
+    #[cfg(debug_assertions)]
+    acir.validate()?;
+
+    let warnings = acir
+        .find_unused_brillig_outputs()
+        .into_iter()
+        .map(CompileWarning::UnusedBrilligOutputs)
+        .collect();
+
+    Ok((acir, transformation_map, warnings))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir::{
+        brillig::{Opcode as BrilligOpcode, RegisterIndex, Value},
+        circuit::{
+            brillig::{Brillig, BrilligInputs, BrilligOutputs},
+            opcodes::{BlackBoxFuncCall, FunctionInput},
+            Circuit, Opcode, OpcodeLocation, PublicInputs,
+        },
+        native_types::{Expression, Witness},
+        FieldElement,
+    };
+
+    use super::{compile, CompileWarning};
+    use crate::Language;
+
+    fn linear_opcode(terms: &[(FieldElement, u32)]) -> Opcode {
+        Opcode::Arithmetic(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: terms
+                .iter()
+                .map(|&(coefficient, witness)| (coefficient, Witness(witness)))
+                .collect(),
+            q_c: FieldElement::zero(),
+        })
+    }
+
+    #[test]
+    fn moves_assert_messages_of_removed_opcodes() {
+        let one = FieldElement::one();
+        let two = FieldElement::from(2_u128);
+        let circuit = Circuit {
+            current_witness_index: 4,
+            opcodes: vec![
+                // w3 = w1 + 2 * w2, which is substituted into the next opcode and removed
+                linear_opcode(&[(one, 3), (-one, 1), (-two, 2)]),
+                // w4 = w3 + w1
+                linear_opcode(&[(one, 4), (-one, 3), (-one, 1)]),
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+                    input: FunctionInput { witness: Witness(4), num_bits: 8 },
+                }),
+            ],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            return_values: PublicInputs(BTreeSet::from([Witness(4)])),
+            assert_messages: vec![
+                (OpcodeLocation::Acir(0), "removed".to_string()),
+                (OpcodeLocation::Acir(1), "substituted".to_string()),
+                (OpcodeLocation::Acir(2), "range".to_string()),
+            ],
+            ..Circuit::default()
+        };
+
+        let (circuit, _, _) = compile(circuit, Language::PLONKCSat { width: 3 }, |_| true).unwrap();
+
+        assert_eq!(circuit.opcodes.len(), 2);
+        assert_eq!(
+            circuit.assert_messages,
+            vec![
+                (OpcodeLocation::Acir(0), "substituted".to_string()),
+                (OpcodeLocation::Acir(1), "range".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn warns_about_unused_brillig_outputs() {
+        let brillig = Opcode::Brillig(Brillig {
+            inputs: vec![BrilligInputs::Single(Witness(1).into())],
+            outputs: vec![BrilligOutputs::Simple(Witness(2))],
+            bytecode: vec![BrilligOpcode::Const {
+                destination: RegisterIndex(0),
+                value: Value::from(1_usize),
+            }],
+            predicate: None,
+        });
+        let circuit = Circuit {
+            current_witness_index: 2,
+            opcodes: vec![brillig],
+            private_parameters: BTreeSet::from([Witness(1)]),
+            ..Circuit::default()
+        };
+
+        let (_, _, warnings) =
+            compile(circuit, Language::PLONKCSat { width: 3 }, |_| true).unwrap();
+        assert_eq!(warnings, vec![CompileWarning::UnusedBrilligOutputs(OpcodeLocation::Acir(0))]);
+    }
 }
@@ -0,0 +1,291 @@
+use acir::{
+    circuit::{opcode_witnesses, Circuit, Opcode},
+    native_types::{Expression, Witness},
+    FieldElement,
+};
+use std::collections::{HashMap, HashSet};
+
+use crate::Language;
+
+/// `LinearSubstitutionOptimizer` will replace witnesses which are defined by a linear [`Opcode::Arithmetic`]
+/// with their definition wherever they are used, removing the defining opcode.
+///
+/// # Example
+///
+/// The opcodes
+///
+/// ```text
+/// w3 - w1 - 2 * w2 = 0
+/// w4 - w3 - w1 = 0
+/// ```
+/// where `w3` is not used anywhere else can be replaced by the single opcode
+///
+/// ```text
+/// w4 - 2 * w1 - 2 * w2 = 0
+/// ```
+///
+/// A witness is only substituted if:
+/// - it is not a parameter, public input or return value of the circuit,
+/// - it is only used by [`Opcode::Arithmetic`]s, as the inputs of other opcodes must be kept as witnesses,
+/// - it is only used linearly, so that substituting it does not add multiplication terms,
+/// - every expression it is substituted into still fits within the width of the target [`Language`].
+pub(crate) struct LinearSubstitutionOptimizer {
+    circuit: Circuit,
+    np_language: Language,
+}
+
+impl LinearSubstitutionOptimizer {
+    pub(crate) fn new(circuit: Circuit, np_language: Language) -> Self {
+        Self { circuit, np_language }
+    }
+
+    /// Collects the witnesses which must be kept in the circuit, as they are visible outside of it
+    /// or used by opcodes which cannot take arbitrary expressions.
+    fn collect_protected_witnesses(circuit: &Circuit) -> HashSet<Witness> {
+        let mut protected_witnesses: HashSet<Witness> =
+            circuit.circuit_arguments().into_iter().collect();
+        protected_witnesses.extend(&circuit.return_values.0);
+        for opcode in &circuit.opcodes {
+            if !matches!(opcode, Opcode::Arithmetic(_)) {
+                protected_witnesses.extend(opcode_witnesses(opcode));
+            }
+        }
+        protected_witnesses
+    }
+
+    /// Returns a `Circuit` where linearly defined witnesses have been substituted into the
+    /// [`Opcode::Arithmetic`]s which use them and their definitions dropped.
+    pub(crate) fn substitute_linear_witnesses(
+        self,
+        order_list: Vec<usize>,
+    ) -> (Circuit, Vec<usize>) {
+        let np_language = self.np_language;
+        let circuit_arguments = self.circuit.circuit_arguments();
+        let protected_witnesses = Self::collect_protected_witnesses(&self.circuit);
+
+        // The index of the first opcode which uses each witness, which is the opcode which solves it
+        // unless it is a circuit argument.
+        let mut first_uses: HashMap<Witness, usize> = HashMap::new();
+        // The indices of the arithmetic opcodes which use each witness
+        let mut arithmetic_uses: HashMap<Witness, Vec<usize>> = HashMap::new();
+        for (index, opcode) in self.circuit.opcodes.iter().enumerate() {
+            for witness in opcode_witnesses(opcode) {
+                first_uses.entry(witness).or_insert(index);
+                if matches!(opcode, Opcode::Arithmetic(_)) {
+                    arithmetic_uses.entry(witness).or_default().push(index);
+                }
+            }
+        }
+
+        let mut opcodes: Vec<Option<Opcode>> = self.circuit.opcodes.into_iter().map(Some).collect();
+        for index in 0..opcodes.len() {
+            let Some(Opcode::Arithmetic(definition)) = &opcodes[index] else { continue };
+            if !definition.mul_terms.is_empty() {
+                continue;
+            }
+
+            // The witnesses which are solved by this opcode
+            let solved_witnesses: Vec<_> = definition
+                .linear_combinations
+                .iter()
+                .filter(|(_, witness)| {
+                    first_uses[witness] == index && !circuit_arguments.contains(witness)
+                })
+                .collect();
+            let [&(coefficient, witness)] = solved_witnesses[..] else { continue };
+            // Witnesses which are not used elsewhere are left alone, as removing their definition
+            // would drop the only use of the other witnesses in it.
+            if protected_witnesses.contains(&witness) || arithmetic_uses[&witness].len() == 1 {
+                continue;
+            }
+
+            let mut definition = definition.clone();
+            definition.sort();
+            // `definition` is `coefficient * witness + rest = 0` so substituting `witness` in an expression
+            // in which it has coefficient `k` means adding `-k / coefficient * definition` to it.
+            let mut substitutions = Vec::new();
+            for &use_index in &arithmetic_uses[&witness] {
+                if use_index == index {
+                    continue;
+                }
+                let Some(Opcode::Arithmetic(expression)) = &opcodes[use_index] else {
+                    unreachable!("only definitions before this opcode have been removed")
+                };
+                let Some(substituted) =
+                    Self::substitute(np_language, expression, witness, coefficient, &definition)
+                else {
+                    break;
+                };
+                substitutions.push((use_index, substituted));
+            }
+            if substitutions.len() + 1 != arithmetic_uses[&witness].len() {
+                continue;
+            }
+
+            for (use_index, substituted) in substitutions {
+                opcodes[use_index] = Some(Opcode::Arithmetic(substituted));
+            }
+            opcodes[index] = None;
+        }
+
+        let mut new_order_list = Vec::with_capacity(order_list.len());
+        let mut optimized_opcodes = Vec::with_capacity(opcodes.len());
+        for (idx, opcode) in opcodes.into_iter().enumerate() {
+            if let Some(opcode) = opcode {
+                new_order_list.push(order_list[idx]);
+                optimized_opcodes.push(opcode);
+            }
+        }
+
+        (Circuit { opcodes: optimized_opcodes, ..self.circuit }, new_order_list)
+    }
+
+    /// Substitutes `witness` in `expression` using the linear `definition` in which it has the given coefficient.
+    ///
+    /// Returns `None` if `witness` is part of a multiplication term or if the resulting expression does not
+    /// fit within the width of the target language.
+    fn substitute(
+        np_language: Language,
+        expression: &Expression,
+        witness: Witness,
+        coefficient: FieldElement,
+        definition: &Expression,
+    ) -> Option<Expression> {
+        if expression.mul_terms.iter().any(|(_, lhs, rhs)| *lhs == witness || *rhs == witness) {
+            return None;
+        }
+        let (use_coefficient, _) =
+            expression.linear_combinations.iter().find(|(_, term)| *term == witness)?;
+
+        let mut expression = expression.clone();
+        expression.sort();
+        let substituted = expression.add_mul(-(*use_coefficient / coefficient), definition);
+
+        let fits = match np_language {
+            Language::R1CS => true,
+            Language::PLONKCSat { width } => substituted.fits_in_one_identity(width),
+        };
+        fits.then_some(substituted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir::{
+        circuit::{
+            opcodes::{BlackBoxFuncCall, FunctionInput},
+            Circuit, Opcode, PublicInputs,
+        },
+        native_types::{Expression, Witness},
+        FieldElement,
+    };
+
+    use super::LinearSubstitutionOptimizer;
+    use crate::Language;
+
+    fn linear_opcode(terms: &[(i128, u32)]) -> Opcode {
+        let linear_combinations = terms
+            .iter()
+            .map(|&(coefficient, witness)| {
+                let magnitude = FieldElement::from(coefficient.unsigned_abs());
+                let coefficient = if coefficient < 0 { -magnitude } else { magnitude };
+                (coefficient, Witness(witness))
+            })
+            .collect();
+        Opcode::Arithmetic(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations,
+            q_c: FieldElement::zero(),
+        })
+    }
+
+    fn test_circuit(opcodes: Vec<Opcode>) -> Circuit {
+        Circuit {
+            current_witness_index: 5,
+            opcodes,
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            return_values: PublicInputs(BTreeSet::from([Witness(4)])),
+            ..Circuit::default()
+        }
+    }
+
+    #[test]
+    fn substitutes_linear_definition() {
+        let circuit = test_circuit(vec![
+            // w3 = w1 + 2 * w2
+            linear_opcode(&[(1, 3), (-1, 1), (-2, 2)]),
+            // w4 = w3 + w1
+            linear_opcode(&[(1, 4), (-1, 3), (-1, 1)]),
+        ]);
+
+        let optimizer = LinearSubstitutionOptimizer::new(circuit, Language::PLONKCSat { width: 3 });
+        let (optimized_circuit, new_order_list) = optimizer.substitute_linear_witnesses(vec![0, 1]);
+
+        // w4 = 2 * w1 + 2 * w2
+        assert_eq!(optimized_circuit.opcodes, vec![linear_opcode(&[(-2, 1), (-2, 2), (1, 4)])]);
+        assert_eq!(new_order_list, vec![1]);
+    }
+
+    #[test]
+    fn respects_language_width() {
+        let circuit = test_circuit(vec![
+            // w3 = w1 + 2 * w2
+            linear_opcode(&[(1, 3), (-1, 1), (-2, 2)]),
+            // w4 = w3 + w5
+            linear_opcode(&[(1, 4), (-1, 3), (-1, 5)]),
+        ]);
+        let circuit = Circuit {
+            private_parameters: BTreeSet::from([Witness(1), Witness(2), Witness(5)]),
+            ..circuit
+        };
+
+        // Substituting `w3` would need 4 terms, which does not fit within a width of 3.
+        let optimizer =
+            LinearSubstitutionOptimizer::new(circuit.clone(), Language::PLONKCSat { width: 3 });
+        let (optimized_circuit, new_order_list) = optimizer.substitute_linear_witnesses(vec![0, 1]);
+        assert_eq!(optimized_circuit.opcodes, circuit.opcodes);
+        assert_eq!(new_order_list, vec![0, 1]);
+
+        // R1CS does not limit the number of linear terms.
+        let optimizer = LinearSubstitutionOptimizer::new(circuit, Language::R1CS);
+        let (optimized_circuit, new_order_list) = optimizer.substitute_linear_witnesses(vec![0, 1]);
+        assert_eq!(optimized_circuit.opcodes.len(), 1);
+        assert_eq!(new_order_list, vec![1]);
+    }
+
+    #[test]
+    fn keeps_witnesses_used_by_other_opcodes() {
+        let range_opcode = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+            input: FunctionInput { witness: Witness(3), num_bits: 8 },
+        });
+        let circuit = test_circuit(vec![
+            // w3 = w1 + 2 * w2
+            linear_opcode(&[(1, 3), (-1, 1), (-2, 2)]),
+            range_opcode,
+            // w4 = w3 + w1
+            linear_opcode(&[(1, 4), (-1, 3), (-1, 1)]),
+        ]);
+
+        let optimizer =
+            LinearSubstitutionOptimizer::new(circuit.clone(), Language::PLONKCSat { width: 3 });
+        let (optimized_circuit, new_order_list) =
+            optimizer.substitute_linear_witnesses(vec![0, 1, 2]);
+        assert_eq!(optimized_circuit.opcodes, circuit.opcodes);
+        assert_eq!(new_order_list, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn keeps_return_values() {
+        let circuit = test_circuit(vec![
+            // w4 = w1 + 2 * w2
+            linear_opcode(&[(1, 4), (-1, 1), (-2, 2)]),
+        ]);
+
+        let optimizer =
+            LinearSubstitutionOptimizer::new(circuit.clone(), Language::PLONKCSat { width: 3 });
+        let (optimized_circuit, _) = optimizer.substitute_linear_witnesses(vec![0]);
+        assert_eq!(optimized_circuit.opcodes, circuit.opcodes);
+    }
+}
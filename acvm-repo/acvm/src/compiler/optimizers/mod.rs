@@ -1,10 +1,12 @@
 use acir::circuit::{Circuit, Opcode};
 
 mod general;
+mod linear_substitution;
 mod redundant_range;
 mod unused_memory;
 
 pub(crate) use general::GeneralOptimizer;
+pub(crate) use linear_substitution::LinearSubstitutionOptimizer;
 pub(crate) use redundant_range::RangeOptimizer;
 
 use self::unused_memory::UnusedMemoryOptimizer;
@@ -259,7 +259,7 @@ impl CSatTransformer {
     /// The sets of previously generated witness and their (normalized) expression is cached in the intermediate_variables map
     /// If there is no cache hit, we generate a new witness (and add the expression to the cache)
     /// else, we return the cached witness along with the scaling factor so it is equal to the provided expression
-    fn get_or_create_intermediate_vars(
+    pub(super) fn get_or_create_intermediate_vars(
         intermediate_variables: &mut IndexMap<Expression, (FieldElement, Witness)>,
         expr: Expression,
         num_witness: &mut u32,
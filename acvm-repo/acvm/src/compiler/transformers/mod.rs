@@ -49,23 +49,14 @@ pub(super) fn transform_internal(
         FallbackTransformer::transform(acir, is_opcode_supported, acir_opcode_positions)?;
 
     let mut transformer = match &np_language {
-        crate::Language::R1CS => {
-            let transformation_map = AcirTransformationMap { acir_opcode_positions };
-            let transformer = R1CSTransformer::new(acir);
-            return Ok((transformer.transform(), transformation_map));
-        }
+        crate::Language::R1CS => ExpressionTransformer::R1CS(R1CSTransformer::new()),
         crate::Language::PLONKCSat { width } => {
-            let mut csat = CSatTransformer::new(*width);
-            for value in acir.circuit_arguments() {
-                csat.mark_solvable(value);
-            }
-            csat
+            ExpressionTransformer::CSat(CSatTransformer::new(*width))
         }
     };
-
-    // TODO: the code below is only for CSAT transformer
-    // TODO it may be possible to refactor it in a way that we do not need to return early from the r1cs
-    // TODO or at the very least, we could put all of it inside of CSatOptimizer pass
+    for value in acir.circuit_arguments() {
+        transformer.mark_solvable(value);
+    }
 
     let mut new_acir_opcode_positions: Vec<usize> = Vec::with_capacity(acir_opcode_positions.len());
     // Optimize the arithmetic gates by reducing them into the correct width and
@@ -202,6 +193,13 @@ pub(super) fn transform_internal(
                 new_acir_opcode_positions.push(acir_opcode_positions[index]);
                 transformed_opcodes.push(opcode);
             }
+            Opcode::Call { ref outputs, .. } => {
+                for witness in outputs {
+                    transformer.mark_solvable(*witness);
+                }
+                new_acir_opcode_positions.push(acir_opcode_positions[index]);
+                transformed_opcodes.push(opcode);
+            }
         }
     }
 
@@ -219,3 +217,34 @@ pub(super) fn transform_internal(
 
     Ok((acir, transformation_map))
 }
+
+/// The transformer which reduces arithmetic opcodes to the form expected by the target [`Language`][crate::Language].
+enum ExpressionTransformer {
+    R1CS(R1CSTransformer),
+    CSat(CSatTransformer),
+}
+
+impl ExpressionTransformer {
+    fn mark_solvable(&mut self, witness: Witness) {
+        match self {
+            ExpressionTransformer::R1CS(transformer) => transformer.mark_solvable(witness),
+            ExpressionTransformer::CSat(transformer) => transformer.mark_solvable(witness),
+        }
+    }
+
+    fn transform(
+        &mut self,
+        opcode: Expression,
+        intermediate_variables: &mut IndexMap<Expression, (FieldElement, Witness)>,
+        num_witness: &mut u32,
+    ) -> Expression {
+        match self {
+            ExpressionTransformer::R1CS(transformer) => {
+                transformer.transform(opcode, intermediate_variables, num_witness)
+            }
+            ExpressionTransformer::CSat(transformer) => {
+                transformer.transform(opcode, intermediate_variables, num_witness)
+            }
+        }
+    }
+}
@@ -1,16 +1,298 @@
-use acir::circuit::Circuit;
+use std::collections::HashSet;
 
-/// Currently a "noop" transformer.
+use acir::{
+    native_types::{Expression, Witness},
+    FieldElement,
+};
+use indexmap::IndexMap;
+
+use super::CSatTransformer;
+
+/// A transformer which processes any [`Expression`]s to break them up such that they
+/// contain at most one multiplication term.
+///
+/// This transformer is only used when targetting the [`R1CS`][crate::Language::R1CS] language.
+///
+/// This is done by moving the extra multiplication terms into intermediate variables, each of which
+/// is constrained by its own opcode.
 pub(crate) struct R1CSTransformer {
-    acir: Circuit,
+    /// Track the witness that can be solved
+    solvable_witness: HashSet<Witness>,
 }
 
 impl R1CSTransformer {
-    pub(crate) fn new(acir: Circuit) -> Self {
-        Self { acir }
+    pub(crate) fn new() -> Self {
+        Self { solvable_witness: HashSet::new() }
+    }
+
+    /// Adds the witness to set of solvable witness
+    pub(crate) fn mark_solvable(&mut self, witness: Witness) {
+        self.solvable_witness.insert(witness);
+    }
+
+    fn is_solvable(&self, witness: &Witness) -> bool {
+        self.solvable_witness.contains(witness)
+    }
+
+    /// Check if the equation 'expression=0' can be solved, and if yes, add the solved witness to set of solvable witness
+    fn try_solve(&mut self, opcode: &Expression) {
+        let mut unresolved = Vec::new();
+        for (_, lhs, rhs) in &opcode.mul_terms {
+            match (self.is_solvable(lhs), self.is_solvable(rhs)) {
+                (false, false) => return,
+                (false, true) => unresolved.push(*lhs),
+                (true, false) => unresolved.push(*rhs),
+                (true, true) => (),
+            }
+        }
+        for (_, witness) in &opcode.linear_combinations {
+            if !self.is_solvable(witness) {
+                unresolved.push(*witness);
+            }
+        }
+        unresolved.sort();
+        unresolved.dedup();
+        if unresolved.len() == 1 {
+            self.mark_solvable(unresolved[0]);
+        }
+    }
+
+    pub(crate) fn transform(
+        &mut self,
+        opcode: Expression,
+        intermediate_variables: &mut IndexMap<Expression, (FieldElement, Witness)>,
+        num_witness: &mut u32,
+    ) -> Expression {
+        let mut opcode = if opcode.mul_terms.len() > 1 {
+            self.extract_mul_terms(opcode, intermediate_variables, num_witness)
+        } else {
+            opcode
+        };
+        opcode.sort();
+        self.try_solve(&opcode);
+        opcode
+    }
+
+    // This moves all but one of the multiplication terms of an expression into intermediate variables:
+    // qM1 * wL1 * wR1 + qM2 * wL2 * wR2 + qO * wO + qC
+    // becomes
+    // t = wL2 * wR2
+    // qM1 * wL1 * wR1 + qM2 * t + qO * wO + qC
+    //
+    // The intermediate opcodes are placed before the opcode which uses them, so a multiplication term can only be
+    // moved out if both of its witnesses are solvable at that point. The remaining term is chosen such that the
+    // expression is still solvable for its one unknown witness, if it has one:
+    // - If no multiplication term contains an unknown witness, the first one is kept.
+    // - If a single multiplication term contains an unknown witness, that term is kept.
+    // - If several multiplication terms contain the same unknown witness x, each multiplied by a solvable witness,
+    //   they are factored into a single term x * t, where t = qM1 * wR1 + qM2 * wR2 + ... is an intermediate variable.
+    // Otherwise the expression has several unknowns, so it cannot be solved in any case, and we keep the first term
+    // which contains an unknown witness.
+    fn extract_mul_terms(
+        &mut self,
+        opcode: Expression,
+        intermediate_variables: &mut IndexMap<Expression, (FieldElement, Witness)>,
+        num_witness: &mut u32,
+    ) -> Expression {
+        let Expression { mul_terms, linear_combinations, q_c } = opcode;
+        let (solvable_terms, mut unsolvable_terms): (Vec<_>, Vec<_>) = mul_terms
+            .into_iter()
+            .partition(|(_, lhs, rhs)| self.is_solvable(lhs) && self.is_solvable(rhs));
+
+        let mut extracted_terms = Vec::new();
+        let kept_term = if unsolvable_terms.is_empty() {
+            let mut solvable_terms = solvable_terms.into_iter();
+            let kept_term = solvable_terms.next();
+            extracted_terms.extend(solvable_terms);
+            kept_term
+        } else {
+            extracted_terms.extend(solvable_terms);
+            if unsolvable_terms.len() == 1 {
+                unsolvable_terms.pop()
+            } else if let Some(term) =
+                self.factor_unknown(&unsolvable_terms, intermediate_variables, num_witness)
+            {
+                Some(term)
+            } else {
+                let kept_term = unsolvable_terms.remove(0);
+                extracted_terms.extend(unsolvable_terms);
+                Some(kept_term)
+            }
+        };
+
+        let mut new_opcode =
+            Expression { mul_terms: kept_term.into_iter().collect(), linear_combinations, q_c };
+        new_opcode.sort();
+        for (coefficient, lhs, rhs) in extracted_terms {
+            let (lhs, rhs) = if lhs <= rhs { (lhs, rhs) } else { (rhs, lhs) };
+            let product = Expression {
+                mul_terms: vec![(coefficient, lhs, rhs)],
+                linear_combinations: Vec::new(),
+                q_c: FieldElement::zero(),
+            };
+            let (scale, intermediate) = CSatTransformer::get_or_create_intermediate_vars(
+                intermediate_variables,
+                product,
+                num_witness,
+            );
+            if self.is_solvable(&lhs) && self.is_solvable(&rhs) {
+                self.mark_solvable(intermediate);
+            }
+            new_opcode = new_opcode.add_mul(scale, &intermediate.into());
+        }
+        new_opcode
+    }
+
+    /// Factors multiplication terms which all multiply the same unknown witness by a solvable witness
+    /// into a single multiplication term, returning `None` if the terms cannot be factored.
+    fn factor_unknown(
+        &mut self,
+        terms: &[(FieldElement, Witness, Witness)],
+        intermediate_variables: &mut IndexMap<Expression, (FieldElement, Witness)>,
+        num_witness: &mut u32,
+    ) -> Option<(FieldElement, Witness, Witness)> {
+        // Split each term into its unknown witness and its solvable one
+        let split_term = |(coefficient, lhs, rhs): &(FieldElement, Witness, Witness)| match (
+            self.is_solvable(lhs),
+            self.is_solvable(rhs),
+        ) {
+            (false, true) => Some((*lhs, (*coefficient, *rhs))),
+            (true, false) => Some((*rhs, (*coefficient, *lhs))),
+            _ => None,
+        };
+        let (unknown, _) = split_term(&terms[0])?;
+        let mut factor = Expression::default();
+        for term in terms {
+            let (term_unknown, (coefficient, witness)) = split_term(term)?;
+            if term_unknown != unknown {
+                return None;
+            }
+            factor = factor.add_mul(coefficient, &witness.into());
+        }
+        if factor.is_const() {
+            return None;
+        }
+
+        let (scale, intermediate) = CSatTransformer::get_or_create_intermediate_vars(
+            intermediate_variables,
+            factor,
+            num_witness,
+        );
+        self.mark_solvable(intermediate);
+        Some((scale, unknown, intermediate))
+    }
+}
+
+#[test]
+fn extracts_solvable_mul_terms() {
+    let a = Witness(0);
+    let b = Witness(1);
+    let c = Witness(2);
+    let d = Witness(3);
+    let e = Witness(4);
+
+    // a * b + c * d - e = 0
+    let opcode = Expression {
+        mul_terms: vec![(FieldElement::one(), a, b), (FieldElement::one(), c, d)],
+        linear_combinations: vec![(-FieldElement::one(), e)],
+        q_c: FieldElement::zero(),
+    };
+
+    let mut intermediate_variables: IndexMap<Expression, (FieldElement, Witness)> = IndexMap::new();
+    let mut num_witness = 5;
+
+    let mut transformer = R1CSTransformer::new();
+    for witness in [a, b, c, d] {
+        transformer.mark_solvable(witness);
     }
-    // TODO: We could possibly make sure that all polynomials are at most degree-2
-    pub(crate) fn transform(self) -> Circuit {
-        self.acir
+    let got_opcode = transformer.transform(opcode, &mut intermediate_variables, &mut num_witness);
+
+    // a * b + f - e = 0
+    // c * d - f = 0
+    let f = Witness(5);
+    let expected_opcode = Expression {
+        mul_terms: vec![(FieldElement::one(), a, b)],
+        linear_combinations: vec![(-FieldElement::one(), e), (FieldElement::one(), f)],
+        q_c: FieldElement::zero(),
+    };
+    assert_eq!(got_opcode, expected_opcode);
+
+    let expected_intermediate = Expression {
+        mul_terms: vec![(FieldElement::one(), c, d)],
+        linear_combinations: Vec::new(),
+        q_c: FieldElement::zero(),
+    };
+    assert_eq!(intermediate_variables.len(), 1);
+    assert_eq!(intermediate_variables[&expected_intermediate], (FieldElement::one(), f));
+    // The output of the transformed opcode has been solved
+    assert!(transformer.is_solvable(&e));
+}
+
+#[test]
+fn factors_unknown_witness() {
+    let a = Witness(0);
+    let b = Witness(1);
+    let x = Witness(2);
+    let y = Witness(3);
+
+    // 2 * a * x + 3 * b * x + y = 0
+    let opcode = Expression {
+        mul_terms: vec![(FieldElement::from(2_u128), a, x), (FieldElement::from(3_u128), x, b)],
+        linear_combinations: vec![(FieldElement::one(), y)],
+        q_c: FieldElement::zero(),
+    };
+
+    let mut intermediate_variables: IndexMap<Expression, (FieldElement, Witness)> = IndexMap::new();
+    let mut num_witness = 4;
+
+    let mut transformer = R1CSTransformer::new();
+    for witness in [a, b, y] {
+        transformer.mark_solvable(witness);
+    }
+    let got_opcode = transformer.transform(opcode, &mut intermediate_variables, &mut num_witness);
+
+    // The multiplication terms cannot be moved out as `x` is not solvable yet, so they are factored instead:
+    // x * t + y = 0
+    // 2 * a + 3 * b - t = 0
+    let t = Witness(4);
+    let expected_opcode = Expression {
+        mul_terms: vec![(FieldElement::one(), x, t)],
+        linear_combinations: vec![(FieldElement::one(), y)],
+        q_c: FieldElement::zero(),
+    };
+    assert_eq!(got_opcode, expected_opcode);
+    assert_eq!(intermediate_variables.len(), 1);
+    assert!(transformer.is_solvable(&x));
+}
+
+#[test]
+fn transformation_map_tracks_intermediate_opcodes() {
+    use acir::circuit::{Circuit, Opcode};
+
+    let [a, b, c, d, e] = [0, 1, 2, 3, 4].map(Witness);
+
+    // a * b + c * d - e = 0
+    let opcode = Expression {
+        mul_terms: vec![(FieldElement::one(), a, b), (FieldElement::one(), c, d)],
+        linear_combinations: vec![(-FieldElement::one(), e)],
+        q_c: FieldElement::zero(),
+    };
+    let circuit = Circuit {
+        current_witness_index: 4,
+        opcodes: vec![Opcode::Arithmetic(opcode)],
+        private_parameters: [a, b, c, d].into(),
+        ..Circuit::default()
+    };
+
+    let (circuit, transformation_map) =
+        super::transform_internal(circuit, crate::Language::R1CS, |_| true, vec![0]).unwrap();
+
+    assert_eq!(circuit.opcodes.len(), 2);
+    for opcode in &circuit.opcodes {
+        let Opcode::Arithmetic(expression) = opcode else { panic!("expected arithmetic opcode") };
+        assert!(expression.mul_terms.len() <= 1);
     }
+    assert!(circuit.current_witness_index > 4);
+    // Both opcodes are mapped back to the original one
+    assert_eq!(transformation_map.acir_opcode_positions, vec![0, 0]);
 }
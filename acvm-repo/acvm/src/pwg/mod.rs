@@ -1,10 +1,10 @@
 // Re-usable methods that backends can use to implement their PWG
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use acir::{
     brillig::ForeignCallResult,
-    circuit::{opcodes::BlockId, Opcode, OpcodeLocation},
+    circuit::{opcodes::BlockId, Circuit, Opcode, OpcodeLocation},
     native_types::{Expression, Witness, WitnessMap},
     BlackBoxFunc, FieldElement,
 };
@@ -46,6 +46,13 @@ pub enum ACVMStatus {
     ///
     /// Once this is done, the ACVM can be restarted to solve the remaining opcodes.
     RequiresForeignCall(ForeignCallWaitInfo),
+
+    /// The ACVM has encountered a request for an [ACIR call][Opcode::Call] to another circuit of the program.
+    /// The called circuit must be executed with its own witness map, and its return values passed back to
+    /// the ACVM using [`ACVM::resolve_pending_acir_call`].
+    ///
+    /// Once this is done, the ACVM can be restarted to solve the remaining opcodes.
+    RequiresAcirCall(AcirCallWaitInfo),
 }
 
 impl std::fmt::Display for ACVMStatus {
@@ -55,10 +62,32 @@ impl std::fmt::Display for ACVMStatus {
             ACVMStatus::InProgress => write!(f, "In progress"),
             ACVMStatus::Failure(_) => write!(f, "Execution failure"),
             ACVMStatus::RequiresForeignCall(_) => write!(f, "Waiting on foreign call"),
+            ACVMStatus::RequiresAcirCall(_) => write!(f, "Waiting on acir call"),
         }
     }
 }
 
+/// The inputs of an [ACIR call][Opcode::Call] which must be executed outside of the ACVM.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcirCallWaitInfo {
+    /// Index of the called circuit in the program
+    pub id: u32,
+    /// Values of the call's arguments, in order
+    pub inputs: Vec<FieldElement>,
+}
+
+impl AcirCallWaitInfo {
+    /// Returns the initial witness map used to execute the called circuit.
+    ///
+    /// The arguments of a circuit are assigned to its input witnesses in ascending order.
+    pub fn initial_witness(&self, callee: &Circuit) -> WitnessMap {
+        let arguments = callee.circuit_arguments();
+        WitnessMap::from(
+            arguments.into_iter().zip(self.inputs.iter().copied()).collect::<BTreeMap<_, _>>(),
+        )
+    }
+}
+
 pub enum StepResult<'a, B: BlackBoxFunctionSolver> {
     Status(ACVMStatus),
     IntoBrillig(BrilligSolver<'a, B>),
@@ -114,6 +143,8 @@ pub enum OpcodeResolutionError {
     BlackBoxFunctionFailed(BlackBoxFunc, String),
     #[error("Failed to solve brillig function, reason: {message}")]
     BrilligFunctionFailed { message: String, call_stack: Vec<OpcodeLocation> },
+    #[error("Acir call returned {results_size} values, but the call has {outputs_size} outputs")]
+    AcirCallOutputsMismatch { opcode_location: ErrorLocation, results_size: u32, outputs_size: u32 },
 }
 
 impl From<BlackBoxResolutionError> for OpcodeResolutionError {
@@ -145,6 +176,9 @@ pub struct ACVM<'a, B: BlackBoxFunctionSolver> {
     witness_map: WitnessMap,
 
     brillig_solver: Option<BrilligSolver<'a, B>>,
+
+    /// Return values of the pending [ACIR call][Opcode::Call], once they have been resolved.
+    acir_call_results: Option<Vec<FieldElement>>,
 }
 
 impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
@@ -158,6 +192,7 @@ impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
             instruction_pointer: 0,
             witness_map: initial_witness,
             brillig_solver: None,
+            acir_call_results: None,
         }
     }
 
@@ -241,12 +276,42 @@ impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
         self.status(ACVMStatus::InProgress);
     }
 
+    /// Sets the status of the VM to `RequiresAcirCall`.
+    /// Indicating that the VM is now waiting for an ACIR call to be resolved.
+    fn wait_for_acir_call(&mut self, acir_call: AcirCallWaitInfo) -> ACVMStatus {
+        self.status(ACVMStatus::RequiresAcirCall(acir_call))
+    }
+
+    /// Return a reference to the arguments for the next pending ACIR call, if one exists.
+    pub fn get_pending_acir_call(&self) -> Option<&AcirCallWaitInfo> {
+        if let ACVMStatus::RequiresAcirCall(acir_call) = &self.status {
+            Some(acir_call)
+        } else {
+            None
+        }
+    }
+
+    /// Resolves an ACIR call using the return values of the called circuit, calculated outside of the ACVM.
+    ///
+    /// The ACVM can then be restarted to solve the remaining ACIR opcodes.
+    pub fn resolve_pending_acir_call(&mut self, call_results: Vec<FieldElement>) {
+        if !matches!(self.status, ACVMStatus::RequiresAcirCall(_)) {
+            panic!("ACVM is not expecting an acir call response as no call was made");
+        }
+
+        self.acir_call_results = Some(call_results);
+
+        // Now that the call has been resolved then we can resume execution.
+        self.status(ACVMStatus::InProgress);
+    }
+
     /// Executes the ACVM's circuit until execution halts.
     ///
-    /// Execution can halt due to three reasons:
+    /// Execution can halt due to four reasons:
     /// 1. All opcodes have been executed successfully.
     /// 2. The circuit has been found to be unsatisfiable.
-    /// 2. A Brillig [foreign call][`ForeignCallWaitInfo`] has been encountered and must be resolved.
+    /// 3. A Brillig [foreign call][`ForeignCallWaitInfo`] has been encountered and must be resolved.
+    /// 4. An [ACIR call][`AcirCallWaitInfo`] has been encountered and must be resolved.
     pub fn solve(&mut self) -> ACVMStatus {
         while self.status == ACVMStatus::InProgress {
             self.solve_opcode();
@@ -275,6 +340,10 @@ impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
                 Ok(Some(foreign_call)) => return self.wait_for_foreign_call(foreign_call),
                 res => res.map(|_| ()),
             },
+            Opcode::Call { .. } => match self.solve_call_opcode() {
+                Ok(Some(acir_call)) => return self.wait_for_acir_call(acir_call),
+                res => res.map(|_| ()),
+            },
         };
         self.handle_opcode_resolution(resolution)
     }
@@ -303,6 +372,10 @@ impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
                     }
                     | OpcodeResolutionError::UnsatisfiedConstrain {
                         opcode_location: opcode_index,
+                    }
+                    | OpcodeResolutionError::AcirCallOutputsMismatch {
+                        opcode_location: opcode_index,
+                        ..
                     } => {
                         *opcode_index = ErrorLocation::Resolved(OpcodeLocation::Acir(
                             self.instruction_pointer(),
@@ -351,6 +424,46 @@ impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
         }
     }
 
+    fn solve_call_opcode(&mut self) -> Result<Option<AcirCallWaitInfo>, OpcodeResolutionError> {
+        let Opcode::Call { id, inputs, outputs, predicate } =
+            &self.opcodes[self.instruction_pointer]
+        else {
+            unreachable!("Not executing a Call opcode");
+        };
+
+        let witness = &mut self.witness_map;
+        if let Some(predicate) = predicate {
+            if get_value(predicate, witness)?.is_zero() {
+                for output in outputs {
+                    insert_value(output, FieldElement::zero(), witness)?;
+                }
+                return Ok(None);
+            }
+        }
+
+        // If we're resuming execution after resolving the call then
+        // its results can be written to the outputs.
+        let Some(call_results) = self.acir_call_results.take() else {
+            let inputs = inputs
+                .iter()
+                .map(|input| witness_to_value(witness, *input).copied())
+                .collect::<Result<_, _>>()?;
+            return Ok(Some(AcirCallWaitInfo { id: *id, inputs }));
+        };
+
+        if call_results.len() != outputs.len() {
+            return Err(OpcodeResolutionError::AcirCallOutputsMismatch {
+                opcode_location: ErrorLocation::Unresolved,
+                results_size: call_results.len() as u32,
+                outputs_size: outputs.len() as u32,
+            });
+        }
+        for (output, value) in outputs.iter().zip(call_results) {
+            insert_value(output, value, witness)?;
+        }
+        Ok(None)
+    }
+
     pub fn step_into_brillig_opcode(&mut self) -> StepResult<'a, B> {
         let Opcode::Brillig(brillig) = &self.opcodes[self.instruction_pointer] else {
             return StepResult::Status(self.solve_opcode());
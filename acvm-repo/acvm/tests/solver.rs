@@ -5,14 +5,17 @@ use acir::{
     circuit::{
         brillig::{Brillig, BrilligInputs, BrilligOutputs},
         opcodes::{BlockId, MemOp},
-        Opcode, OpcodeLocation,
+        Circuit, Opcode, OpcodeLocation, PublicInputs,
     },
     native_types::{Expression, Witness, WitnessMap},
     FieldElement,
 };
 
 use acvm::{
-    pwg::{ACVMStatus, ErrorLocation, ForeignCallWaitInfo, OpcodeResolutionError, ACVM},
+    pwg::{
+        ACVMStatus, AcirCallWaitInfo, ErrorLocation, ForeignCallWaitInfo, OpcodeResolutionError,
+        ACVM,
+    },
     BlackBoxFunctionSolver,
 };
 use acvm_blackbox_solver::BlackBoxResolutionError;
@@ -648,3 +651,107 @@ fn memory_operations() {
 
     assert_eq!(witness_map[&Witness(8)], FieldElement::from(6u128));
 }
+
+#[test]
+fn memory_operations_from_text() {
+    // The same circuit as in `memory_operations`, written in the textual ACIR format.
+    let circuit: Circuit = "
+        current witness index : 8
+        private parameters indices : [1, 2, 3, 4, 5, 6]
+        public parameters indices : []
+        return value indices : [8]
+        INIT (id: 0, init: [_1, _2, _3, _4, _5])
+        MEM (id: 0, read at: [ (1, _6) 0 ], value: [ (1, _7) 0 ])
+        EXPR [ (1, _7) (-1, _8) 1 ]
+    "
+    .parse()
+    .unwrap();
+
+    let initial_witness = WitnessMap::from(BTreeMap::from_iter([
+        (Witness(1), FieldElement::from(1u128)),
+        (Witness(2), FieldElement::from(2u128)),
+        (Witness(3), FieldElement::from(3u128)),
+        (Witness(4), FieldElement::from(4u128)),
+        (Witness(5), FieldElement::from(5u128)),
+        (Witness(6), FieldElement::from(4u128)),
+    ]));
+
+    let mut acvm = ACVM::new(&StubbedBackend, &circuit.opcodes, initial_witness);
+    let solver_status = acvm.solve();
+    assert_eq!(solver_status, ACVMStatus::Solved);
+    let witness_map = acvm.finalize();
+
+    assert_eq!(witness_map[&Witness(8)], FieldElement::from(6u128));
+}
+
+#[test]
+fn acir_call_with_nested_witness_map() {
+    // The callee returns the sum of its two arguments, in its own witness map.
+    let callee_opcodes = vec![Opcode::Arithmetic(Expression {
+        mul_terms: Vec::new(),
+        linear_combinations: vec![
+            (FieldElement::one(), Witness(1)),
+            (FieldElement::one(), Witness(2)),
+            (-FieldElement::one(), Witness(3)),
+        ],
+        q_c: FieldElement::zero(),
+    })];
+    let callee = Circuit {
+        current_witness_index: 3,
+        opcodes: callee_opcodes.clone(),
+        private_parameters: [Witness(1), Witness(2)].into(),
+        return_values: PublicInputs([Witness(3)].into()),
+        ..Circuit::default()
+    };
+
+    let initial_witness = WitnessMap::from(BTreeMap::from_iter([
+        (Witness(1), FieldElement::from(2u128)),
+        (Witness(2), FieldElement::from(3u128)),
+    ]));
+    let opcodes = vec![Opcode::Call {
+        id: 1,
+        inputs: vec![Witness(2), Witness(1)],
+        outputs: vec![Witness(3)],
+        predicate: None,
+    }];
+
+    let mut acvm = ACVM::new(&StubbedBackend, &opcodes, initial_witness);
+    let solver_status = acvm.solve();
+    let expected_call = AcirCallWaitInfo {
+        id: 1,
+        inputs: vec![FieldElement::from(3u128), FieldElement::from(2u128)],
+    };
+    assert_eq!(solver_status, ACVMStatus::RequiresAcirCall(expected_call.clone()));
+
+    // Solve the callee with a fresh ACVM, starting from its own witness map
+    let call_info = acvm.get_pending_acir_call().unwrap();
+    let mut callee_acvm =
+        ACVM::new(&StubbedBackend, &callee_opcodes, call_info.initial_witness(&callee));
+    assert_eq!(callee_acvm.solve(), ACVMStatus::Solved);
+    let callee_witness = callee_acvm.finalize();
+    assert_eq!(callee_witness[&Witness(1)], FieldElement::from(3u128));
+
+    acvm.resolve_pending_acir_call(vec![callee_witness[&Witness(3)]]);
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    let witness_map = acvm.finalize();
+    assert_eq!(witness_map[&Witness(3)], FieldElement::from(5u128));
+}
+
+#[test]
+fn acir_call_skipped_by_predicate() {
+    let initial_witness = WitnessMap::from(BTreeMap::from_iter([
+        (Witness(1), FieldElement::from(2u128)),
+        (Witness(2), FieldElement::zero()),
+    ]));
+    let opcodes = vec![Opcode::Call {
+        id: 1,
+        inputs: vec![Witness(1)],
+        outputs: vec![Witness(3)],
+        predicate: Some(Witness(2).into()),
+    }];
+
+    let mut acvm = ACVM::new(&StubbedBackend, &opcodes, initial_witness);
+    assert_eq!(acvm.solve(), ACVMStatus::Solved, "call should be skipped");
+    let witness_map = acvm.finalize();
+    assert_eq!(witness_map[&Witness(3)], FieldElement::zero());
+}
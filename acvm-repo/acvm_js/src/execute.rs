@@ -108,6 +108,11 @@ pub async fn execute_circuit_with_black_box_solver(
 
                 acvm.resolve_pending_foreign_call(result);
             }
+            ACVMStatus::RequiresAcirCall(_) => {
+                // Only a single circuit is available here so there is no circuit to call into.
+                let error_string = "Cannot execute an acir call outside of a program".to_string();
+                return Err(JsExecutionError::new(error_string.into(), None).into());
+            }
         }
     }
 
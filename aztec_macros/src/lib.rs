@@ -5,10 +5,10 @@ use noirc_frontend::macros_api::{
     BlockExpression, CallExpression, CastExpression, Distinctness, Expression, ExpressionKind,
     ForLoopStatement, ForRange, FunctionDefinition, FunctionReturnType, FunctionVisibility,
     HirContext, HirExpression, HirLiteral, HirStatement, Ident, ImportStatement, IndexExpression,
-    LetStatement, Literal, MemberAccessExpression, MethodCallExpression, NoirFunction, NoirStruct,
-    Param, Path, PathKind, Pattern, PrefixExpression, SecondaryAttribute, Signedness, Span,
-    Statement, StatementKind, StructType, Type, TypeImpl, UnaryOp, UnresolvedType,
-    UnresolvedTypeData, Visibility,
+    ItemVisibility, LetStatement, Literal, MemberAccessExpression, MethodCallExpression,
+    NoirFunction, NoirStruct, Param, Path, PathKind, Pattern, PrefixExpression, SecondaryAttribute,
+    Signedness, Span, Statement, StatementKind, StructType, Type, TypeImpl, UnaryOp,
+    UnresolvedType, UnresolvedTypeData, Visibility,
 };
 use noirc_frontend::macros_api::{CrateId, FileId};
 use noirc_frontend::macros_api::{MacroError, MacroProcessor};
@@ -183,7 +183,7 @@ fn index_array_variable(array: Expression, index: &str) -> Expression {
 }
 
 fn import(path: Path) -> ImportStatement {
-    ImportStatement { path, alias: None }
+    ImportStatement { visibility: ItemVisibility::Private, path, alias: None, is_glob: false }
 }
 
 //
@@ -518,6 +518,7 @@ fn generate_selector_impl(structure: &NoirStruct) -> TypeImpl {
         object_type: struct_type,
         type_span: structure.span,
         generics: vec![],
+        where_clause: vec![],
         methods: vec![NoirFunction::normal(selector_fn_def)],
     }
 }
@@ -3,13 +3,13 @@
 #![warn(unreachable_pub)]
 #![warn(clippy::semicolon_if_nothing_returned)]
 
-use clap::Args;
+use clap::{builder::PossibleValuesParser, Args};
 use fm::FileId;
 use iter_extended::vecmap;
 use noirc_abi::{AbiParameter, AbiType, ContractEvent};
 use noirc_errors::{CustomDiagnostic, FileDiagnostic};
-use noirc_evaluator::create_circuit;
 use noirc_evaluator::errors::RuntimeError;
+use noirc_evaluator::{create_program, ssa_pass_names, SsaPassOptions};
 use noirc_frontend::graph::{CrateId, CrateName};
 use noirc_frontend::hir::def_map::{Contract, CrateDefMap};
 use noirc_frontend::hir::Context;
@@ -50,6 +50,45 @@ pub struct CompileOptions {
     #[arg(long, hide = true)]
     pub show_brillig: bool,
 
+    /// Check that the SSA is well-formed after each optimization pass
+    #[arg(long, hide = true)]
+    pub verify_ssa: bool,
+
+    /// Interpret the SSA on zeroed inputs after each optimization pass and fail
+    /// if any pass changes the program's results
+    #[arg(long, hide = true)]
+    pub check_ssa: bool,
+
+    /// Emit the SSA only after the named pass. May be given multiple times
+    #[arg(long, hide = true, value_parser = PossibleValuesParser::new(ssa_pass_names()))]
+    pub show_ssa_pass: Vec<String>,
+
+    /// Skip the named SSA pass. May be given multiple times
+    #[arg(long, hide = true, value_parser = PossibleValuesParser::new(ssa_pass_names()))]
+    pub skip_ssa_pass: Vec<String>,
+
+    /// A comma-separated list of SSA passes to run on ACIR functions in place of the default pipeline
+    #[arg(
+        long,
+        hide = true,
+        value_delimiter = ',',
+        value_parser = PossibleValuesParser::new(ssa_pass_names())
+    )]
+    pub ssa_passes: Vec<String>,
+
+    /// A comma-separated list of SSA passes to run on Brillig functions in place of the default pipeline
+    #[arg(
+        long,
+        hide = true,
+        value_delimiter = ',',
+        value_parser = PossibleValuesParser::new(ssa_pass_names())
+    )]
+    pub brillig_ssa_passes: Vec<String>,
+
+    /// Skip the check for values returned from unconstrained functions which are never constrained
+    #[arg(long)]
+    pub skip_underconstrained_check: bool,
+
     /// Display the ACIR for compiled circuit
     #[arg(long)]
     pub print_acir: bool,
@@ -182,7 +221,7 @@ pub fn compile_main(
 
     if options.print_acir {
         println!("Compiled ACIR for main (unoptimized):");
-        println!("{}", compiled_program.circuit);
+        println!("{}", compiled_program.program);
     }
 
     Ok((compiled_program, warnings))
@@ -281,6 +320,20 @@ fn compile_contract_inner(
             }
         };
         warnings.extend(function.warnings);
+
+        // Contract artifacts hold a single circuit per function so folded functions,
+        // which are compiled into circuits of their own, are not supported yet.
+        let mut circuits = function.program.functions;
+        if circuits.len() != 1 {
+            let err = CustomDiagnostic::from_message(&format!(
+                "Contract function `{name}` contains `#[fold]` functions which are not yet supported in contracts"
+            ))
+            .in_file(FileId::default());
+            errors.push(err);
+            continue;
+        }
+        let bytecode = circuits.remove(0);
+
         let modifiers = context.def_interner.function_modifiers(&function_id);
         let func_type = modifiers
             .contract_function_type
@@ -293,7 +346,7 @@ fn compile_contract_inner(
             function_type,
             is_internal: modifiers.is_internal.unwrap_or(false),
             abi: function.abi,
-            bytecode: function.circuit,
+            bytecode,
             debug: function.debug,
         });
     }
@@ -333,22 +386,43 @@ pub fn compile_no_check(
     cached_program: Option<CompiledProgram>,
     force_compile: bool,
 ) -> Result<CompiledProgram, RuntimeError> {
-    let program = monomorphize(main_function, &context.def_interner);
+    let program = monomorphize(main_function, &context.def_interner)?;
 
     let hash = fxhash::hash64(&program);
     let hashes_match = cached_program.as_ref().map_or(false, |program| program.hash == hash);
 
-    // If user has specified that they want to see intermediate steps printed then we should
+    // If user has specified that they want to see or check intermediate steps then we should
     // force compilation even if the program hasn't changed.
-    let force_compile =
-        force_compile || options.print_acir || options.show_brillig || options.show_ssa;
+    let force_compile = force_compile
+        || options.print_acir
+        || options.show_brillig
+        || options.show_ssa
+        || options.verify_ssa
+        || options.check_ssa
+        || !options.show_ssa_pass.is_empty()
+        || !options.skip_ssa_pass.is_empty()
+        || !options.ssa_passes.is_empty()
+        || !options.brillig_ssa_passes.is_empty();
 
     if !force_compile && hashes_match {
         return Ok(cached_program.expect("cache must exist for hashes to match"));
     }
     let visibility = program.return_visibility;
-    let (circuit, debug, input_witnesses, return_witnesses, warnings) =
-        create_circuit(program, options.show_ssa, options.show_brillig)?;
+    let pass_options = SsaPassOptions {
+        print_after: options.show_ssa_pass.clone(),
+        skip: options.skip_ssa_pass.clone(),
+        passes: options.ssa_passes.clone(),
+        brillig_passes: options.brillig_ssa_passes.clone(),
+        check_results: options.check_ssa,
+    };
+    let (program, debug, input_witnesses, return_witnesses, warnings) = create_program(
+        program,
+        options.show_ssa,
+        options.show_brillig,
+        options.verify_ssa,
+        options.skip_underconstrained_check,
+        &pass_options,
+    )?;
 
     let abi =
         abi_gen::gen_abi(context, &main_function, input_witnesses, return_witnesses, visibility);
@@ -356,7 +430,7 @@ pub fn compile_no_check(
 
     Ok(CompiledProgram {
         hash,
-        circuit,
+        program,
         debug,
         abi,
         file_map,
@@ -129,9 +129,7 @@ impl<'block> BrilligBlock<'block> {
                     condition,
                     self.create_block_label_for_current_function(*then_destination),
                 );
-                self.brillig_context.jump_instruction(
-                    self.create_block_label_for_current_function(*else_destination),
-                );
+                self.jump_to_block(*else_destination);
             }
             TerminatorInstruction::Jmp {
                 destination: destination_block,
@@ -150,9 +148,7 @@ impl<'block> BrilligBlock<'block> {
                     let source = self.convert_ssa_value(*src, dfg);
                     self.pass_variable(source, destination);
                 }
-                self.brillig_context.jump_instruction(
-                    self.create_block_label_for_current_function(*destination_block),
-                );
+                self.jump_to_block(*destination_block);
             }
             TerminatorInstruction::Return { return_values, .. } => {
                 let return_registers: Vec<_> = return_values
@@ -167,6 +163,15 @@ impl<'block> BrilligBlock<'block> {
         }
     }
 
+    /// Jumps to the given block of the current function. No jump is needed if the
+    /// block's code directly follows that of the current block.
+    fn jump_to_block(&mut self, destination: BasicBlockId) {
+        if !self.function_context.is_next_block(self.block_id, destination) {
+            self.brillig_context
+                .jump_instruction(self.create_block_label_for_current_function(destination));
+        }
+    }
+
     /// Passes an arbitrary variable from the registers of the source to the registers of the destination
     fn pass_variable(&mut self, source: BrilligVariable, destination: BrilligVariable) {
         match (source, destination) {
@@ -61,6 +61,13 @@ impl FunctionContext {
         }
     }
 
+    /// Returns true if the code of `block` is generated directly after that of `predecessor`,
+    /// in which case `predecessor` can fall through into `block` without a jump.
+    pub(crate) fn is_next_block(&self, predecessor: BasicBlockId, block: BasicBlockId) -> bool {
+        let position = self.blocks.iter().position(|other| *other == predecessor);
+        position.and_then(|position| self.blocks.get(position + 1)) == Some(&block)
+    }
+
     pub(crate) fn all_block_parameters(&self) -> HashSet<ValueId> {
         self.block_parameters.values().flat_map(|parameters| parameters.iter()).cloned().collect()
     }
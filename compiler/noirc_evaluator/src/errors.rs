@@ -10,6 +10,7 @@
 use acvm::{acir::native_types::Expression, FieldElement};
 use iter_extended::vecmap;
 use noirc_errors::{CustomDiagnostic as Diagnostic, FileDiagnostic};
+use noirc_frontend::monomorphization::errors::MonomorphizationError;
 use thiserror::Error;
 
 use crate::ssa::ir::{dfg::CallStack, types::NumericType};
@@ -42,6 +43,15 @@ pub enum RuntimeError {
     UnknownLoopBound { call_stack: CallStack },
     #[error("Argument is not constant")]
     AssertConstantFailed { call_stack: CallStack },
+    #[error("{error}")]
+    MonomorphizationError { error: MonomorphizationError, call_stack: CallStack },
+}
+
+impl From<MonomorphizationError> for RuntimeError {
+    fn from(error: MonomorphizationError) -> RuntimeError {
+        let call_stack = CallStack::unit(error.location());
+        RuntimeError::MonomorphizationError { error, call_stack }
+    }
 }
 
 // We avoid showing the actual lhs and rhs since most of the time they are just 0
@@ -71,6 +81,12 @@ impl From<SsaReport> for FileDiagnostic {
                     InternalWarning::VerifyProof { call_stack } => {
                         ("verify_proof(...) aggregates data for the verifier, the actual verification will be done when the full proof is verified using nargo verify. nargo prove may generate an invalid proof if bad data is used as input to verify_proof".to_string(), call_stack)
                     },
+                    InternalWarning::UnconstrainedValue { call_stack } => {
+                        ("the prover may choose any value for this result, it should be checked with an assertion in constrained code".to_string(), call_stack)
+                    },
+                    InternalWarning::UnderconstrainedValue { call_stack } => {
+                        ("this result must also be constrained against the inputs of the circuit or a constant".to_string(), call_stack)
+                    },
                 };
                 let call_stack = vecmap(call_stack, |location| location);
                 let file_id = call_stack.last().map(|location| location.file).unwrap_or_default();
@@ -89,6 +105,10 @@ pub enum InternalWarning {
     ReturnConstant { call_stack: CallStack },
     #[error("Calling std::verify_proof(...) does not verify a proof")]
     VerifyProof { call_stack: CallStack },
+    #[error("Value returned from an unconstrained function is never constrained")]
+    UnconstrainedValue { call_stack: CallStack },
+    #[error("Value returned from an unconstrained function is only constrained against other unconstrained values")]
+    UnderconstrainedValue { call_stack: CallStack },
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Error)]
@@ -129,7 +149,8 @@ impl RuntimeError {
             | RuntimeError::UnknownLoopBound { call_stack }
             | RuntimeError::AssertConstantFailed { call_stack }
             | RuntimeError::IntegerOutOfBounds { call_stack, .. }
-            | RuntimeError::UnsupportedIntegerSize { call_stack, .. } => call_stack,
+            | RuntimeError::UnsupportedIntegerSize { call_stack, .. }
+            | RuntimeError::MonomorphizationError { call_stack, .. } => call_stack,
         }
     }
 }
@@ -9,12 +9,9 @@
 
 use std::collections::BTreeSet;
 
-use crate::{
-    brillig::Brillig,
-    errors::{RuntimeError, SsaReport},
-};
+use crate::errors::{InternalError, RuntimeError, SsaReport};
 use acvm::acir::{
-    circuit::{Circuit, PublicInputs},
+    circuit::{Circuit, Program as AcirProgram, PublicInputs},
     native_types::Witness,
 };
 
@@ -24,13 +21,95 @@ use noirc_frontend::{
     hir_def::function::FunctionSignature, monomorphization::ast::Program, Visibility,
 };
 
-use self::{acir_gen::GeneratedAcir, ssa_gen::Ssa};
+use self::{
+    acir_gen::GeneratedAcir,
+    interpreter::{InterpreterError, Value},
+    ir::dfg::CallStack,
+    opt::{SsaPass, SsaPassFn},
+    ssa_gen::Ssa,
+};
 
 mod acir_gen;
+#[cfg(test)]
+mod brillig_benchmark;
 pub(super) mod function_builder;
+mod interpreter;
 pub mod ir;
 mod opt;
+mod parser;
 pub mod ssa_gen;
+mod verifier;
+
+/// Options for customizing the SSA pass pipeline, e.g. to bisect a miscompilation or to
+/// measure the effect of each pass. Passes are referred to by the names in [`ssa_pass_names`].
+#[derive(Debug, Clone, Default)]
+pub struct SsaPassOptions {
+    /// Passes after which the SSA is printed
+    pub print_after: Vec<String>,
+
+    /// Passes which are not run
+    pub skip: Vec<String>,
+
+    /// If non-empty, the passes to run on ACIR functions in place of the default pipeline.
+    /// Passes which are only needed for ACIR generation are always run afterward. Compilation
+    /// fails if ACIR functions are not unrolled, flattened and free of memory instructions.
+    pub passes: Vec<String>,
+
+    /// If non-empty, the passes to run on Brillig functions in place of the default pipeline.
+    pub brillig_passes: Vec<String>,
+
+    /// If true, the program is interpreted on zeroed inputs after each pass
+    /// and compilation fails if any pass changes its results.
+    pub check_results: bool,
+}
+
+/// Returns the name of each SSA pass which can be used in [`SsaPassOptions`]
+pub fn ssa_pass_names() -> Vec<&'static str> {
+    opt::SSA_PASSES.iter().map(|pass| pass.name).collect()
+}
+
+impl SsaPassOptions {
+    /// Returns the passes to run from the given list of names, excluding any skipped passes
+    fn select_passes<'a>(
+        &self,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Vec<&'static SsaPass>, RuntimeError> {
+        names
+            .into_iter()
+            .filter(|name| !self.skip.iter().any(|skipped| skipped.as_str() == *name))
+            .map(|name| {
+                opt::lookup_pass(name).ok_or_else(|| {
+                    RuntimeError::InternalError(InternalError::General {
+                        message: format!("Unknown SSA pass `{name}`"),
+                        call_stack: CallStack::new(),
+                    })
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the passes to run from `custom` if it is non-empty, or else from `default`.
+    /// The shared passes cannot be part of a custom pipeline as they must be run on the whole program.
+    fn select_pipeline(
+        &self,
+        custom: &[String],
+        default: &[&'static str],
+    ) -> Result<Vec<&'static SsaPass>, RuntimeError> {
+        if custom.is_empty() {
+            return self.select_passes(default.iter().copied());
+        }
+        if let Some(name) = custom.iter().find(|name| opt::SHARED_PIPELINE.contains(&name.as_str()))
+        {
+            return Err(RuntimeError::InternalError(InternalError::General {
+                message: format!(
+                    "SSA pass `{name}` is always run on the whole program and cannot be part of a custom pipeline"
+                ),
+                call_stack: CallStack::new(),
+            }));
+        }
+        self.select_passes(custom.iter().map(String::as_str))
+    }
+}
 
 /// Optimize the given program by converting it into SSA
 /// form and performing optimizations there. When finished,
@@ -39,53 +118,128 @@ pub(crate) fn optimize_into_acir(
     program: Program,
     print_ssa_passes: bool,
     print_brillig_trace: bool,
-) -> Result<GeneratedAcir, RuntimeError> {
+    verify_ssa: bool,
+    skip_underconstrained_check: bool,
+    pass_options: &SsaPassOptions,
+) -> Result<Vec<GeneratedAcir>, RuntimeError> {
     let abi_distinctness = program.return_distinctness;
 
-    let ssa_builder = SsaBuilder::new(program, print_ssa_passes)?
-        .run_pass(Ssa::defunctionalize, "After Defunctionalization:")
-        .run_pass(Ssa::inline_functions, "After Inlining:")
-        // Run mem2reg with the CFG separated into blocks
-        .run_pass(Ssa::mem2reg, "After Mem2Reg:")
-        .try_run_pass(Ssa::evaluate_assert_constant, "After Assert Constant:")?
-        .try_run_pass(Ssa::unroll_loops, "After Unrolling:")?
-        .run_pass(Ssa::simplify_cfg, "After Simplifying:")
-        // Run mem2reg before flattening to handle any promotion
-        // of values that can be accessed after loop unrolling.
-        // If there are slice mergers uncovered by loop unrolling
-        // and this pass is missed, slice merging will fail inside of flattening.
-        .run_pass(Ssa::mem2reg, "After Mem2Reg:")
-        .run_pass(Ssa::flatten_cfg, "After Flattening:")
-        // Run mem2reg once more with the flattened CFG to catch any remaining loads/stores
-        .run_pass(Ssa::mem2reg, "After Mem2Reg:")
-        .run_pass(Ssa::fold_constants, "After Constant Folding:")
-        .run_pass(Ssa::dead_instruction_elimination, "After Dead Instruction Elimination:");
-
-    let brillig = ssa_builder.to_brillig(print_brillig_trace);
-
-    // Split off any passes the are not necessary for Brillig generation but are necessary for ACIR generation.
-    // We only need to fill out nested slices as we need to have a known length when dealing with memory operations
-    // in ACIR gen while this is not necessary in the Brillig IR.
-    let ssa = ssa_builder
-        .run_pass(Ssa::fill_internal_slices, "After Fill Internal Slice Dummy Data:")
-        .finish();
+    let shared_pipeline = pass_options.select_passes(opt::SHARED_PIPELINE.iter().copied())?;
+    let acir_pipeline =
+        pass_options.select_pipeline(&pass_options.passes, opt::DEFAULT_PIPELINE)?;
+    let brillig_pipeline =
+        pass_options.select_pipeline(&pass_options.brillig_passes, opt::BRILLIG_PIPELINE)?;
+    let acir_only_pipeline = pass_options.select_passes(opt::ACIR_ONLY_PIPELINE.iter().copied())?;
+
+    let mut ssa_builder = SsaBuilder::new(program, print_ssa_passes, verify_ssa)?
+        .with_passes_to_print(pass_options.print_after.clone());
+    if pass_options.check_results {
+        ssa_builder = ssa_builder.with_zeroed_interpreter_inputs();
+    }
+    for pass in shared_pipeline {
+        ssa_builder = ssa_builder.run_ssa_pass(pass)?;
+    }
+
+    // Brillig functions are not unrolled or flattened, so they are optimized by a pipeline of their own.
+    let mut brillig_builder = ssa_builder.split_off_brillig_functions();
+    for pass in brillig_pipeline {
+        brillig_builder = brillig_builder.run_ssa_pass(pass)?;
+    }
+
+    // The ACIR-only passes are run last. We only need to fill out nested slices as we need to have a known length
+    // when dealing with memory operations in ACIR gen while this is not necessary in the Brillig IR.
+    let mut ssa_builder = brillig_builder.switch_halves();
+    for pass in acir_pipeline.into_iter().chain(acir_only_pipeline) {
+        ssa_builder = ssa_builder.run_ssa_pass(pass)?;
+    }
+    if let Some(divergence) = ssa_builder.divergence() {
+        return Err(RuntimeError::InternalError(InternalError::General {
+            message: format!(
+                "SSA check failed. {} the program returned {:?} instead of {:?}",
+                divergence.pass, divergence.after, divergence.before
+            ),
+            call_stack: CallStack::new(),
+        }));
+    }
+
+    // ACIR generation needs the signatures of the Brillig functions called from ACIR functions,
+    // so both halves are merged back together before either is compiled.
+    let ssa = ssa_builder.finish();
+
+    // A custom pipeline or skipped passes may leave ACIR functions in a form which cannot be compiled
+    if let Err(error) = ssa.verify_ready_for_acir_gen() {
+        return Err(RuntimeError::InternalError(InternalError::General {
+            message: format!(
+                "{error}. The `mem2reg`, `unrolling` and `flatten_cfg` passes cannot be left out of ACIR functions"
+            ),
+            call_stack: CallStack::new(),
+        }));
+    }
+
+    let brillig = ssa.to_brillig(print_brillig_trace);
+
+    let underconstrained_warnings = if skip_underconstrained_check {
+        Vec::new()
+    } else {
+        ssa.check_for_underconstrained_values()
+    };
 
     let last_array_uses = ssa.find_last_array_uses();
-    ssa.into_acir(brillig, abi_distinctness, &last_array_uses)
+    let mut generated_acirs = ssa.into_acir(brillig, abi_distinctness, &last_array_uses)?;
+    generated_acirs[0].warnings.extend(underconstrained_warnings);
+    Ok(generated_acirs)
 }
 
-/// Compiles the [`Program`] into [`ACIR`][acvm::acir::circuit::Circuit].
+/// Compiles the [`Program`] into [`ACIR`][acvm::acir::circuit::Program].
+///
+/// The first circuit of the output program is always `main`, any further circuits hold the
+/// functions marked with `#[fold]` which are called into via [`Opcode::Call`][acvm::acir::circuit::Opcode::Call].
 ///
 /// The output ACIR is is backend-agnostic and so must go through a transformation pass before usage in proof generation.
 #[allow(clippy::type_complexity)]
-pub fn create_circuit(
+pub fn create_program(
     program: Program,
     enable_ssa_logging: bool,
     enable_brillig_logging: bool,
-) -> Result<(Circuit, DebugInfo, Vec<Witness>, Vec<Witness>, Vec<SsaReport>), RuntimeError> {
+    verify_ssa: bool,
+    skip_underconstrained_check: bool,
+    pass_options: &SsaPassOptions,
+) -> Result<(AcirProgram, DebugInfo, Vec<Witness>, Vec<Witness>, Vec<SsaReport>), RuntimeError> {
     let func_sig = program.main_function_signature.clone();
-    let mut generated_acir =
-        optimize_into_acir(program, enable_ssa_logging, enable_brillig_logging)?;
+    let generated_acirs = optimize_into_acir(
+        program,
+        enable_ssa_logging,
+        enable_brillig_logging,
+        verify_ssa,
+        skip_underconstrained_check,
+        pass_options,
+    )?;
+
+    let mut generated_acirs = generated_acirs.into_iter();
+    let main_acir = generated_acirs.next().expect("ICE: Expected a circuit for main");
+    let (main_circuit, debug_info, input_witnesses, return_witnesses, mut warnings) =
+        convert_generated_acir_into_circuit(main_acir, Some(&func_sig));
+
+    let mut functions = vec![main_circuit];
+    for generated_acir in generated_acirs {
+        // Debug information is currently only tracked for `main`. Failures within a folded
+        // function are reported at the location of the call in its caller.
+        let (circuit, _, _, _, function_warnings) =
+            convert_generated_acir_into_circuit(generated_acir, None);
+        functions.push(circuit);
+        warnings.extend(function_warnings);
+    }
+
+    Ok((AcirProgram { functions }, debug_info, input_witnesses, return_witnesses, warnings))
+}
+
+/// Converts a [`GeneratedAcir`] into an optimized [`Circuit`].
+///
+/// Only `main` has a function signature. All inputs to any other circuit are private.
+fn convert_generated_acir_into_circuit(
+    mut generated_acir: GeneratedAcir,
+    func_sig: Option<&FunctionSignature>,
+) -> (Circuit, DebugInfo, Vec<Witness>, Vec<Witness>, Vec<SsaReport>) {
     let opcodes = generated_acir.take_opcodes();
     let GeneratedAcir {
         current_witness_index,
@@ -97,8 +251,10 @@ pub fn create_circuit(
         ..
     } = generated_acir;
 
-    let (public_parameter_witnesses, private_parameters) =
-        split_public_and_private_inputs(&func_sig, &input_witnesses);
+    let (public_parameter_witnesses, private_parameters) = match func_sig {
+        Some(func_sig) => split_public_and_private_inputs(func_sig, &input_witnesses),
+        None => (BTreeSet::new(), input_witnesses.iter().copied().collect()),
+    };
 
     let public_parameters = PublicInputs(public_parameter_witnesses);
     let return_values = PublicInputs(return_witnesses.iter().copied().collect());
@@ -124,7 +280,7 @@ pub fn create_circuit(
     let (optimized_circuit, transformation_map) = acvm::compiler::optimize(circuit);
     debug_info.update_acir(transformation_map);
 
-    Ok((optimized_circuit, debug_info, input_witnesses, return_witnesses, warnings))
+    (optimized_circuit, debug_info, input_witnesses, return_witnesses, warnings)
 }
 
 // Takes each function argument and partitions the circuit's inputs witnesses according to its visibility.
@@ -164,23 +320,109 @@ fn split_public_and_private_inputs(
 // This is just a convenience object to bundle the ssa with `print_ssa_passes` for debug printing.
 struct SsaBuilder {
     ssa: Ssa,
+
+    /// The functions split off from `ssa` by `split_off_brillig_functions`. Passes are not
+    /// run on them, but they are needed to interpret calls between the two halves.
+    split_off: Option<Ssa>,
+
     print_ssa_passes: bool,
+
+    /// If true, the SSA is checked to be well-formed after each pass
+    verify_ssa: bool,
+
+    /// Names of passes after which the SSA is printed, even if `print_ssa_passes` is false
+    passes_to_print: Vec<String>,
+
+    /// Set by `with_interpreter_inputs` to check that each pass preserves the program's results
+    interpreter_check: Option<InterpreterCheck>,
+}
+
+/// The state of the differential check done by `SsaBuilder` after each pass
+struct InterpreterCheck {
+    inputs: Vec<Value>,
+
+    /// The result of interpreting the program after the previous pass
+    expected: Result<Vec<Value>, InterpreterError>,
+
+    /// The first pass found to change the program's results, if any
+    divergence: Option<PassDivergence>,
+}
+
+/// A pass which changed the results of interpreting the program
+#[derive(Debug)]
+pub(crate) struct PassDivergence {
+    pub(crate) pass: String,
+    pub(crate) before: Result<Vec<Value>, InterpreterError>,
+    pub(crate) after: Result<Vec<Value>, InterpreterError>,
 }
 
 impl SsaBuilder {
-    fn new(program: Program, print_ssa_passes: bool) -> Result<SsaBuilder, RuntimeError> {
+    fn new(
+        program: Program,
+        print_ssa_passes: bool,
+        verify_ssa: bool,
+    ) -> Result<SsaBuilder, RuntimeError> {
         let ssa = ssa_gen::generate_ssa(program)?;
-        Ok(SsaBuilder { print_ssa_passes, ssa }.print("Initial SSA:"))
+        Ok(SsaBuilder::from_ssa(ssa, print_ssa_passes, verify_ssa))
+    }
+
+    fn from_ssa(ssa: Ssa, print_ssa_passes: bool, verify_ssa: bool) -> SsaBuilder {
+        let builder = SsaBuilder {
+            print_ssa_passes,
+            verify_ssa,
+            passes_to_print: Vec::new(),
+            ssa,
+            split_off: None,
+            interpreter_check: None,
+        };
+        builder.verify("Initial SSA:").print("Initial SSA:")
+    }
+
+    fn with_passes_to_print(mut self, passes_to_print: Vec<String>) -> Self {
+        self.passes_to_print = passes_to_print;
+        self
+    }
+
+    /// Enables a debug mode in which the program is interpreted on the given inputs after each
+    /// pass. The first pass which changes the results is printed and kept in `divergence`.
+    fn with_interpreter_inputs(mut self, inputs: Vec<Value>) -> Self {
+        let expected = interpret(&self.ssa, self.split_off.as_ref(), inputs.clone());
+        self.interpreter_check = Some(InterpreterCheck { inputs, expected, divergence: None });
+        self
+    }
+
+    /// Enables the check done by `with_interpreter_inputs` with a zeroed value for each
+    /// parameter of `main`, as the actual inputs of the program are not known when compiling.
+    fn with_zeroed_interpreter_inputs(self) -> Self {
+        let main = self.ssa.main();
+        let inputs = main
+            .parameters()
+            .iter()
+            .map(|parameter| Value::zero(&main.dfg.type_of_value(*parameter)))
+            .collect::<Result<_, _>>()
+            .expect("ICE: main should only take numeric and array parameters");
+        self.with_interpreter_inputs(inputs)
     }
 
-    fn finish(self) -> Ssa {
+    /// Returns the first pass which changed the program's results when run
+    /// with the inputs given to `with_interpreter_inputs`.
+    fn divergence(&self) -> Option<&PassDivergence> {
+        self.interpreter_check.as_ref()?.divergence.as_ref()
+    }
+
+    /// Returns the program, merging any functions split off from it back in
+    fn finish(mut self) -> Ssa {
+        if let Some(split_off) = self.split_off.take() {
+            self.ssa.merge(split_off);
+        }
         self.ssa
     }
 
     /// Runs the given SSA pass and prints the SSA afterward if `print_ssa_passes` is true.
     fn run_pass(mut self, pass: fn(Ssa) -> Ssa, msg: &str) -> Self {
         self.ssa = pass(self.ssa);
-        self.print(msg)
+        self.check_results(msg);
+        self.verify(msg).print(msg)
     }
 
     /// The same as `run_pass` but for passes that may fail
@@ -190,11 +432,40 @@ impl SsaBuilder {
         msg: &str,
     ) -> Result<Self, RuntimeError> {
         self.ssa = pass(self.ssa)?;
-        Ok(self.print(msg))
+        self.check_results(msg);
+        Ok(self.verify(msg).print(msg))
+    }
+
+    /// Runs a pass from the registry in `opt`. The SSA is printed afterward if
+    /// `print_ssa_passes` is true or the pass is one of `passes_to_print`.
+    fn run_ssa_pass(self, pass: &SsaPass) -> Result<Self, RuntimeError> {
+        let builder = match pass.run {
+            SsaPassFn::Infallible(run) => self.run_pass(run, pass.msg),
+            SsaPassFn::Fallible(run) => self.try_run_pass(run, pass.msg)?,
+        };
+        if !builder.print_ssa_passes && builder.passes_to_print.iter().any(|name| name == pass.name)
+        {
+            println!("{}\n{}", pass.msg, builder.ssa);
+        }
+        Ok(builder)
+    }
+
+    /// Splits the Brillig functions off from the rest of the program so that they can be
+    /// optimized separately. Passes run afterward only see the Brillig functions until
+    /// `switch_halves` is called, while the whole program is still interpreted after each pass.
+    fn split_off_brillig_functions(mut self) -> SsaBuilder {
+        let brillig_ssa = self.ssa.split_off_brillig_functions();
+        let acir_ssa = std::mem::replace(&mut self.ssa, brillig_ssa);
+        self.split_off = Some(acir_ssa);
+        self
     }
 
-    fn to_brillig(&self, print_brillig_trace: bool) -> Brillig {
-        self.ssa.to_brillig(print_brillig_trace)
+    /// Swaps the functions passes are run on with those split off by `split_off_brillig_functions`
+    fn switch_halves(mut self) -> SsaBuilder {
+        let split_off = self.split_off.take().expect("ICE: no functions have been split off");
+        let ssa = std::mem::replace(&mut self.ssa, split_off);
+        self.split_off = Some(ssa);
+        self
     }
 
     fn print(self, msg: &str) -> Self {
@@ -203,4 +474,210 @@ impl SsaBuilder {
         }
         self
     }
+
+    /// Checks that the SSA is well-formed if `verify_ssa` is true.
+    /// Violations are reported along with the pass named by `msg` which introduced them.
+    fn verify(self, msg: &str) -> Self {
+        if self.verify_ssa {
+            if let Err(error) = self.ssa.verify() {
+                panic!("SSA verification failed after pass `{msg}`\n{error}\n{}", self.ssa);
+            }
+        }
+        self
+    }
+
+    /// Interprets the program after the pass named by `msg`, recording the pass
+    /// if it is the first to change the program's results.
+    fn check_results(&mut self, msg: &str) {
+        let Some(check) = &mut self.interpreter_check else {
+            return;
+        };
+        let after = interpret(&self.ssa, self.split_off.as_ref(), check.inputs.clone());
+        let before = std::mem::replace(&mut check.expected, after.clone());
+
+        if check.divergence.is_none() && !same_results(&before, &after) {
+            check.divergence = Some(PassDivergence { pass: msg.to_owned(), before, after });
+        }
+    }
+}
+
+/// Interprets the program made of `ssa` along with any functions split off from it
+fn interpret(
+    ssa: &Ssa,
+    split_off: Option<&Ssa>,
+    inputs: Vec<Value>,
+) -> Result<Vec<Value>, InterpreterError> {
+    match split_off {
+        Some(split_off) => ssa.interpret_split(split_off, inputs),
+        None => ssa.interpret(inputs),
+    }
+}
+
+/// Two runs of a program are considered the same if they return the same values or if both fail.
+/// The exact failure may legitimately change between passes, e.g. when a constraint is simplified.
+/// Runs which hit a feature the interpreter does not support are never considered to differ.
+fn same_results(
+    before: &Result<Vec<Value>, InterpreterError>,
+    after: &Result<Vec<Value>, InterpreterError>,
+) -> bool {
+    match (before, after) {
+        (Ok(before), Ok(after)) => before == after,
+        (Err(_), Err(_)) => true,
+        (Err(error), Ok(_)) | (Ok(_), Err(error)) => error.is_unsupported(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::FieldElement;
+
+    use super::{interpreter::Value, opt, ssa_gen::Ssa, SsaBuilder, SsaPassOptions};
+
+    const SRC: &str = "
+        acir fn main f0 {
+          b0(v0: Field):
+            v1 = allocate -> &mut Field
+            store v0 at v1
+            v2 = load v1 -> Field
+            v3 = mul v2, v2
+            return v3
+        }
+    ";
+
+    /// A deliberately incorrect pass which replaces the program with one returning a constant
+    fn return_zero(_: Ssa) -> Ssa {
+        "
+        acir fn main f0 {
+          b0(v0: Field):
+            return Field 0
+        }
+        "
+        .parse()
+        .unwrap()
+    }
+
+    #[test]
+    fn correct_passes_preserve_results() {
+        let ssa: Ssa = SRC.parse().unwrap();
+        let builder = SsaBuilder::from_ssa(ssa, false, true)
+            .with_interpreter_inputs(vec![Value::field(3u128.into())])
+            .run_pass(Ssa::mem2reg, "After Mem2Reg:")
+            .run_pass(Ssa::dead_instruction_elimination, "After Dead Instruction Elimination:");
+        assert!(builder.divergence().is_none());
+    }
+
+    #[test]
+    fn reports_first_diverging_pass() {
+        let ssa: Ssa = SRC.parse().unwrap();
+        let builder = SsaBuilder::from_ssa(ssa, false, true)
+            .with_interpreter_inputs(vec![Value::field(3u128.into())])
+            .run_pass(Ssa::mem2reg, "After Mem2Reg:")
+            .run_pass(return_zero, "After Returning Zero:")
+            .run_pass(Ssa::mem2reg, "After Mem2Reg:");
+
+        let divergence = builder.divergence().expect("Expected the broken pass to be reported");
+        assert_eq!(divergence.pass, "After Returning Zero:");
+        assert_eq!(divergence.before, Ok(vec![Value::field(9u128.into())]));
+        assert_eq!(divergence.after, Ok(vec![Value::field(FieldElement::zero())]));
+    }
+
+    #[test]
+    fn zeroed_inputs_are_interpreted() {
+        let ssa: Ssa = SRC.parse().unwrap();
+        let builder = SsaBuilder::from_ssa(ssa, false, true).with_zeroed_interpreter_inputs();
+
+        let check = builder.interpreter_check.as_ref().unwrap();
+        assert_eq!(check.inputs, vec![Value::field(FieldElement::zero())]);
+        assert_eq!(check.expected, Ok(vec![Value::field(FieldElement::zero())]));
+    }
+
+    /// A deliberately incorrect pass which makes the Brillig function of `SPLIT_SRC` return zero
+    fn brillig_returns_zero(mut ssa: Ssa) -> Ssa {
+        let broken: Ssa = "
+        brillig fn double f1 {
+          b0(v0: Field):
+            return Field 0
+        }
+        "
+        .parse()
+        .unwrap();
+        ssa.functions = broken.functions;
+        ssa
+    }
+
+    const SPLIT_SRC: &str = "
+        acir fn main f0 {
+          b0(v0: Field):
+            v2 = call f1(v0) -> Field
+            return v2
+        }
+        brillig fn double f1 {
+          b0(v0: Field):
+            v1 = add v0, v0
+            return v1
+        }
+    ";
+
+    #[test]
+    fn checks_both_halves_of_a_split_program() {
+        let ssa: Ssa = SPLIT_SRC.parse().unwrap();
+        let builder = SsaBuilder::from_ssa(ssa, false, true)
+            .with_interpreter_inputs(vec![Value::field(3u128.into())])
+            .split_off_brillig_functions()
+            .run_pass(Ssa::dead_instruction_elimination, "After Dead Instruction Elimination:")
+            .run_pass(brillig_returns_zero, "After Returning Zero:")
+            .switch_halves()
+            .run_pass(Ssa::dead_instruction_elimination, "After Dead Instruction Elimination:");
+
+        let divergence = builder.divergence().expect("Expected the broken pass to be reported");
+        assert_eq!(divergence.pass, "After Returning Zero:");
+        assert_eq!(divergence.before, Ok(vec![Value::field(6u128.into())]));
+        assert_eq!(divergence.after, Ok(vec![Value::field(FieldElement::zero())]));
+
+        let ssa = builder.finish();
+        assert_eq!(ssa.functions.len(), 2);
+    }
+
+    #[test]
+    fn default_pipeline_only_uses_registered_passes() {
+        let options = SsaPassOptions::default();
+        for pipeline in [
+            opt::SHARED_PIPELINE,
+            opt::DEFAULT_PIPELINE,
+            opt::BRILLIG_PIPELINE,
+            opt::ACIR_ONLY_PIPELINE,
+        ] {
+            let passes = options.select_passes(pipeline.iter().copied()).unwrap();
+            assert_eq!(passes.len(), pipeline.len());
+        }
+    }
+
+    #[test]
+    fn custom_pipelines_cannot_include_shared_passes() {
+        let options = SsaPassOptions::default();
+        let custom = vec!["mem2reg".to_owned(), "die".to_owned()];
+        let passes = options.select_pipeline(&custom, opt::BRILLIG_PIPELINE).unwrap();
+        assert_eq!(passes.len(), 2);
+        assert_eq!(
+            options.select_pipeline(&[], opt::BRILLIG_PIPELINE).unwrap().len(),
+            opt::BRILLIG_PIPELINE.len()
+        );
+
+        let custom = vec!["inlining".to_owned(), "die".to_owned()];
+        assert!(options.select_pipeline(&custom, opt::DEFAULT_PIPELINE).is_err());
+    }
+
+    #[test]
+    fn skipped_passes_are_not_selected() {
+        let options = SsaPassOptions { skip: vec!["mem2reg".to_owned()], ..Default::default() };
+        let pipeline = options.select_passes(["inlining", "mem2reg", "die", "mem2reg"]).unwrap();
+        let names: Vec<_> = pipeline.iter().map(|pass| pass.name).collect();
+        assert_eq!(names, vec!["inlining", "die"]);
+    }
+
+    #[test]
+    fn unknown_passes_are_an_error() {
+        let options = SsaPassOptions::default();
+        assert!(options.select_passes(["inlining", "not_a_pass"]).is_err());
+    }
 }
@@ -1255,6 +1255,50 @@ impl AcirContext {
         Ok(outputs_var)
     }
 
+    /// Generates a call to the ACIR function compiled into the circuit at index `id` of the program.
+    ///
+    /// The inputs are flattened into witnesses matching the callee's parameters and a fresh
+    /// witness is created for each (flattened) output, to be filled in by executing the callee.
+    pub(crate) fn call_acir_function(
+        &mut self,
+        id: u32,
+        inputs: Vec<AcirValue>,
+        outputs: Vec<AcirType>,
+        predicate: AcirVar,
+    ) -> Result<Vec<AcirValue>, RuntimeError> {
+        let mut input_expressions = Vec::new();
+        for input in inputs {
+            self.brillig_array_input(&mut input_expressions, input)?;
+        }
+        let input_witnesses =
+            vecmap(input_expressions, |expr| self.acir_ir.get_or_create_witness(&expr));
+
+        let mut output_witnesses = Vec::new();
+        let outputs_var = vecmap(outputs, |output| match output {
+            AcirType::NumericType(_) => {
+                let witness_index = self.acir_ir.next_witness_index();
+                output_witnesses.push(witness_index);
+                let var = self.add_data(AcirVarData::Witness(witness_index));
+                AcirValue::Var(var, output.clone())
+            }
+            AcirType::Array(element_types, size) => {
+                let (acir_value, mut witnesses) = self.brillig_array_output(&element_types, size);
+                output_witnesses.append(&mut witnesses);
+                acir_value
+            }
+        });
+
+        let predicate = self.var_to_expression(predicate)?;
+        self.acir_ir.push_opcode(Opcode::Call {
+            id,
+            inputs: input_witnesses,
+            outputs: output_witnesses,
+            predicate: Some(predicate),
+        });
+
+        Ok(outputs_var)
+    }
+
     fn brillig_array_input(
         &mut self,
         var_expressions: &mut Vec<Expression>,
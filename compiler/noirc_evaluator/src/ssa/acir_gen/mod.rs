@@ -9,8 +9,9 @@ use super::function_builder::data_bus::DataBus;
 use super::ir::dfg::CallStack;
 use super::{
     ir::{
+        bit_width::BitWidths,
         dfg::DataFlowGraph,
-        function::{Function, RuntimeType},
+        function::{Function, FunctionId, RuntimeType},
         instruction::{
             Binary, BinaryOp, Instruction, InstructionId, Intrinsic, TerminatorInstruction,
         },
@@ -35,6 +36,7 @@ use acvm::{
 use fxhash::FxHashMap as HashMap;
 use im::Vector;
 use iter_extended::{try_vecmap, vecmap};
+use noirc_frontend::monomorphization::ast::InlineType;
 use noirc_frontend::Distinctness;
 
 /// Context struct for the acir generation pass.
@@ -93,6 +95,15 @@ struct Context {
     slice_sizes: HashMap<Id<Value>, Vec<usize>>,
 
     data_bus: DataBus,
+
+    /// Maps the ids of ACIR functions which are compiled into their own circuit
+    /// (`main` and any folded functions) to the index of that circuit in the program.
+    acir_function_ids: HashMap<FunctionId, u32>,
+
+    /// Upper bounds on the bit sizes of the values in the function being converted.
+    ///
+    /// These allow us to skip truncations and range checks which are already implied.
+    bit_widths: BitWidths,
 }
 
 #[derive(Clone)]
@@ -156,39 +167,85 @@ impl AcirValue {
 }
 
 impl Ssa {
+    /// Converts the SSA into ACIR, returning one `GeneratedAcir` per circuit in the program.
+    ///
+    /// The first entry is always the `main` circuit, followed by one circuit for every
+    /// function marked as `#[fold]` in the order of their function ids.
     pub(crate) fn into_acir(
         self,
         brillig: Brillig,
         abi_distinctness: Distinctness,
         last_array_uses: &HashMap<ValueId, InstructionId>,
-    ) -> Result<GeneratedAcir, RuntimeError> {
-        let context = Context::new();
-        let mut generated_acir = context.convert_ssa(self, brillig, last_array_uses)?;
-
-        match abi_distinctness {
-            Distinctness::Distinct => {
-                // Create a witness for each return witness we have
-                // to guarantee that the return witnesses are distinct
-                let distinct_return_witness: Vec<_> = generated_acir
-                    .return_witnesses
-                    .clone()
-                    .into_iter()
-                    .map(|return_witness| {
-                        generated_acir
-                            .create_witness_for_expression(&Expression::from(return_witness))
-                    })
-                    .collect();
+    ) -> Result<Vec<GeneratedAcir>, RuntimeError> {
+        let main_func = self.main();
+        if main_func.runtime() == RuntimeType::Brillig {
+            let generated_acir =
+                Context::new(HashMap::default()).convert_brillig_main(main_func, &brillig)?;
+            return Ok(vec![apply_distinctness(generated_acir, abi_distinctness)]);
+        }
 
-                generated_acir.return_witnesses = distinct_return_witness;
-                Ok(generated_acir)
-            }
-            Distinctness::DuplicationAllowed => Ok(generated_acir),
+        // Each folded function is compiled into its own circuit which `main` (or any other
+        // circuit) can then refer to by its index in the program.
+        let mut acir_function_ids = HashMap::default();
+        acir_function_ids.insert(self.main_id, 0);
+        let folded_functions: Vec<_> =
+            self.functions.values().filter(|function| function.runtime().is_folded()).collect();
+        for function in &folded_functions {
+            let circuit_id = acir_function_ids.len() as u32;
+            acir_function_ids.insert(function.id(), circuit_id);
+        }
+
+        let mut generated_acirs = Vec::with_capacity(acir_function_ids.len());
+        let generated_acir = Context::new(acir_function_ids.clone()).convert_acir_function(
+            main_func,
+            &self,
+            &brillig,
+            last_array_uses,
+        )?;
+        generated_acirs.push(apply_distinctness(generated_acir, abi_distinctness));
+
+        for function in folded_functions {
+            let generated_acir = Context::new(acir_function_ids.clone()).convert_acir_function(
+                function,
+                &self,
+                &brillig,
+                last_array_uses,
+            )?;
+            // The outputs of a call are matched up with the return witnesses of the callee
+            // by position, so these must always be distinct witnesses.
+            generated_acirs.push(apply_distinctness(generated_acir, Distinctness::Distinct));
         }
+
+        Ok(generated_acirs)
+    }
+}
+
+fn apply_distinctness(
+    mut generated_acir: GeneratedAcir,
+    abi_distinctness: Distinctness,
+) -> GeneratedAcir {
+    match abi_distinctness {
+        Distinctness::Distinct => {
+            // Create a witness for each return witness we have
+            // to guarantee that the return witnesses are distinct
+            let distinct_return_witness: Vec<_> = generated_acir
+                .return_witnesses
+                .clone()
+                .into_iter()
+                .map(|return_witness| {
+                    generated_acir.create_witness_for_expression(&Expression::from(return_witness))
+                })
+                .collect();
+
+            generated_acir.return_witnesses = distinct_return_witness;
+            generated_acir
+        }
+        Distinctness::DuplicationAllowed => generated_acir,
     }
 }
 
 impl Context {
-    fn new() -> Context {
+    fn new(acir_function_ids: HashMap<FunctionId, u32>) -> Context {
         let mut acir_context = AcirContext::default();
         let current_side_effects_enabled_var = acir_context.add_constant(FieldElement::one());
 
@@ -203,33 +260,23 @@ impl Context {
             max_block_id: 0,
             slice_sizes: HashMap::default(),
             data_bus: DataBus::default(),
+            acir_function_ids,
+            bit_widths: BitWidths::default(),
         }
     }
 
-    /// Converts SSA into ACIR
-    fn convert_ssa(
-        self,
-        ssa: Ssa,
-        brillig: Brillig,
-        last_array_uses: &HashMap<ValueId, InstructionId>,
-    ) -> Result<GeneratedAcir, RuntimeError> {
-        let main_func = ssa.main();
-        match main_func.runtime() {
-            RuntimeType::Acir => self.convert_acir_main(main_func, &ssa, brillig, last_array_uses),
-            RuntimeType::Brillig => self.convert_brillig_main(main_func, brillig),
-        }
-    }
-
-    fn convert_acir_main(
+    /// Converts an ACIR function (either `main` or a folded function) into its own circuit
+    fn convert_acir_function(
         mut self,
-        main_func: &Function,
+        function: &Function,
         ssa: &Ssa,
-        brillig: Brillig,
+        brillig: &Brillig,
         last_array_uses: &HashMap<ValueId, InstructionId>,
     ) -> Result<GeneratedAcir, RuntimeError> {
-        let dfg = &main_func.dfg;
-        let entry_block = &dfg[main_func.entry_block()];
+        let dfg = &function.dfg;
+        let entry_block = &dfg[function.entry_block()];
         let input_witness = self.convert_ssa_block_params(entry_block.parameters(), dfg)?;
+        self.bit_widths = BitWidths::with_function(function);
 
         self.data_bus = dfg.data_bus.to_owned();
         let mut warnings = Vec::new();
@@ -238,7 +285,7 @@ impl Context {
                 *instruction_id,
                 dfg,
                 ssa,
-                &brillig,
+                brillig,
                 last_array_uses,
             )?);
         }
@@ -250,7 +297,7 @@ impl Context {
     fn convert_brillig_main(
         mut self,
         main_func: &Function,
-        brillig: Brillig,
+        brillig: &Brillig,
     ) -> Result<GeneratedAcir, RuntimeError> {
         let dfg = &main_func.dfg;
 
@@ -263,7 +310,7 @@ impl Context {
         let outputs: Vec<AcirType> =
             vecmap(main_func.returns(), |result_id| dfg.type_of_value(*result_id).into());
 
-        let code = self.gen_brillig_for(main_func, &brillig)?;
+        let code = self.gen_brillig_for(main_func, brillig)?;
 
         let output_values = self.acir_context.brillig(
             self.current_side_effects_enabled_var,
@@ -478,9 +525,23 @@ impl Context {
                     Value::Function(id) => {
                         let func = &ssa.functions[id];
                         match func.runtime() {
-                            RuntimeType::Acir => unimplemented!(
+                            RuntimeType::Acir(InlineType::Inline) => unimplemented!(
                                 "expected an intrinsic/brillig call, but found {func:?}. All ACIR methods should be inlined"
                             ),
+                            RuntimeType::Acir(InlineType::Fold) => {
+                                let circuit_id = self.acir_function_ids[id];
+
+                                let inputs = vecmap(arguments, |arg| self.convert_value(*arg, dfg));
+
+                                let outputs: Vec<AcirType> = vecmap(result_ids, |result_id| dfg.type_of_value(*result_id).into());
+
+                                let output_values = self.acir_context.call_acir_function(circuit_id, inputs, outputs, self.current_side_effects_enabled_var)?;
+
+                                // Compiler sanity check
+                                assert_eq!(result_ids.len(), output_values.len(), "ICE: The number of ACIR call output values should match the result ids in SSA");
+
+                                self.handle_call_results(result_ids, output_values, dfg)?;
+                            }
                             RuntimeType::Brillig => {
                                 let inputs = vecmap(arguments, |arg| self.convert_value(*arg, dfg));
 
@@ -493,15 +554,7 @@ impl Context {
                                 // Compiler sanity check
                                 assert_eq!(result_ids.len(), output_values.len(), "ICE: The number of Brillig output values should match the result ids in SSA");
 
-                                for result in result_ids.iter().zip(output_values) {
-                                    if let AcirValue::Array(_) = &result.1 {
-                                        let array_id = dfg.resolve(*result.0);
-                                        let block_id = self.block_id(&array_id);
-                                        let array_typ = dfg.type_of_value(array_id);
-                                        self.initialize_array(block_id, array_typ.flattened_size(), Some(result.1.clone()))?;
-                                    }
-                                    self.ssa_values.insert(*result.0, result.1);
-                                }
+                                self.handle_call_results(result_ids, output_values, dfg)?;
                             }
                         }
                     }
@@ -584,18 +637,42 @@ impl Context {
                 // Do nothing. Only Brillig needs to worry about reference counted arrays
             }
             Instruction::RangeCheck { value, max_bit_size, assert_message } => {
-                let acir_var = self.convert_numeric_value(*value, dfg)?;
-                self.acir_context.range_constrain_var(
-                    acir_var,
-                    &NumericType::Unsigned { bit_size: *max_bit_size },
-                    assert_message.clone(),
-                )?;
+                // The range check can be skipped if the value is already known to fit
+                let value_bit_size = self.bit_widths.max_bit_size(dfg, *value);
+                if value_bit_size.map_or(true, |bit_size| bit_size > *max_bit_size) {
+                    let acir_var = self.convert_numeric_value(*value, dfg)?;
+                    self.acir_context.range_constrain_var(
+                        acir_var,
+                        &NumericType::Unsigned { bit_size: *max_bit_size },
+                        assert_message.clone(),
+                    )?;
+                }
             }
         }
         self.acir_context.set_call_stack(CallStack::new());
         Ok(warnings)
     }
 
+    /// Binds the values returned from a Brillig or ACIR call to their SSA result ids,
+    /// initializing any returned arrays so that they may be accessed with a dynamic index.
+    fn handle_call_results(
+        &mut self,
+        result_ids: &[ValueId],
+        output_values: Vec<AcirValue>,
+        dfg: &DataFlowGraph,
+    ) -> Result<(), RuntimeError> {
+        for (result_id, output) in result_ids.iter().zip(output_values) {
+            if let AcirValue::Array(_) = &output {
+                let array_id = dfg.resolve(*result_id);
+                let block_id = self.block_id(&array_id);
+                let array_typ = dfg.type_of_value(array_id);
+                self.initialize_array(block_id, array_typ.flattened_size(), Some(output.clone()))?;
+            }
+            self.ssa_values.insert(*result_id, output);
+        }
+        Ok(())
+    }
+
     fn gen_brillig_for(
         &self,
         func: &Function,
@@ -1657,7 +1734,10 @@ impl Context {
                 Ok(variable)
             }
             NumericType::Unsigned { bit_size } | NumericType::Signed { bit_size } => {
-                let max_bit_size = incoming_type.bit_size();
+                let mut max_bit_size = incoming_type.bit_size();
+                if let Some(value_bit_size) = self.bit_widths.max_bit_size(dfg, *value_id) {
+                    max_bit_size = max_bit_size.min(value_bit_size);
+                }
                 if max_bit_size <= *bit_size {
                     // Incoming variable already fits into target bit size -  this is a no-op
                     return Ok(variable);
@@ -1676,6 +1756,14 @@ impl Context {
         dfg: &DataFlowGraph,
     ) -> Result<AcirVar, RuntimeError> {
         let mut var = self.convert_numeric_value(value_id, dfg)?;
+        let mut max_bit_size = max_bit_size;
+        if let Some(value_bit_size) = self.bit_widths.max_bit_size(dfg, value_id) {
+            if value_bit_size <= bit_size {
+                // The value already fits into `bit_size` bits so truncating it is a no-op
+                return Ok(var);
+            }
+            max_bit_size = max_bit_size.min(value_bit_size);
+        }
         match &dfg[value_id] {
             Value::Instruction { instruction, .. } => {
                 if matches!(
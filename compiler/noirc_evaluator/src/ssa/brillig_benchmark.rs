@@ -0,0 +1,195 @@
+//! Compares the Brillig bytecode generated by the Brillig pass pipeline against that generated
+//! by the pipeline which was previously shared by ACIR and Brillig functions.
+//!
+//! Run with `cargo test -p noirc_evaluator brillig_pipeline_benchmark -- --nocapture` to print
+//! the bytecode size and the number of opcodes executed for each program.
+use acvm::{
+    acir::brillig::{RegisterIndex, Value},
+    brillig_vm::{Registers, VMStatus, VM},
+    FieldElement,
+};
+
+use crate::brillig::{
+    brillig_gen::brillig_fn::FunctionContext,
+    brillig_ir::{tests::DummyBlackBoxSolver, BrilligContext},
+};
+
+use super::{opt, ssa_gen::Ssa, SsaBuilder, SsaPassOptions};
+
+/// The pipeline previously run on every function before Brillig generation
+const SHARED_ACIR_PIPELINE: &[&str] = &[
+    "mem2reg",
+    "assert_constant",
+    "unrolling",
+    "simplify_cfg",
+    "gvn",
+    "mem2reg",
+    "flatten_cfg",
+    "mem2reg",
+    "constant_folding",
+    "licm",
+    "die",
+];
+
+/// A loop with an invariant multiplication and a modulo by a power of two
+const LOOP: &str = "
+    brillig fn main f0 {
+      b0(v0: u32, v1: u32):
+        jmp b1(u32 0, u32 0)
+      b1(v2: u32, v3: u32):
+        v4 = lt v2, v0
+        jmpif v4 then: b2, else: b3
+      b2():
+        v5 = mul v1, v1
+        v6 = mod v2, u32 8
+        v7 = add v5, v6
+        v8 = add v3, v7
+        v9 = add v2, u32 1
+        jmp b1(v9, v8)
+      b3():
+        return v3
+    }
+";
+
+/// A loop whose body repeats the loop condition as a bounds check
+const BOUNDS_CHECK: &str = "
+    brillig fn main f0 {
+      b0(v0: u32):
+        jmp b1(u32 0, u32 0)
+      b1(v1: u32, v2: u32):
+        v3 = lt v1, v0
+        jmpif v3 then: b2, else: b3
+      b2():
+        v4 = lt v1, v0
+        constrain v4 == u1 1 'Index out of bounds'
+        v5 = add v2, v1
+        v6 = add v1, u32 1
+        jmp b1(v6, v5)
+      b3():
+        return v2
+    }
+";
+
+/// A Brillig function calling a large constrained function several times
+fn large_callee() -> String {
+    let mut callee = String::from("acir fn mix f1 {\n  b0(v0: Field):\n");
+    for i in 1..=150 {
+        let instruction = if i % 2 == 0 { "mul" } else { "add" };
+        callee.push_str(&format!("    v{i} = {instruction} v{}, v0\n", i - 1));
+    }
+    callee.push_str("    return v150\n}\n");
+
+    let main = "
+        brillig fn main f0 {
+          b0(v0: Field):
+            v2 = call f1(v0) -> Field
+            v3 = call f1(v2) -> Field
+            v4 = call f1(v3) -> Field
+            v5 = call f1(v4) -> Field
+            return v5
+        }
+    ";
+    format!("{main}{callee}")
+}
+
+/// The measurement which the Brillig pipeline is expected to improve for a program
+#[derive(Debug, Clone, Copy)]
+enum Gain {
+    BytecodeSize,
+    ExecutedOpcodes,
+}
+
+struct Measurement {
+    bytecode_size: usize,
+    executed_opcodes: usize,
+    result: Value,
+}
+
+/// Runs `pipeline` on the inlined program and measures the bytecode of its Brillig `main`.
+fn measure(ssa: Ssa, pipeline: &[&str], inputs: &[u128]) -> Measurement {
+    let passes = SsaPassOptions::default().select_passes(pipeline.iter().copied()).unwrap();
+    let mut builder = SsaBuilder::from_ssa(ssa, false, true);
+    for pass in passes {
+        builder = builder.run_ssa_pass(pass).unwrap();
+    }
+    let ssa = builder.finish();
+    let brillig = ssa.to_brillig(false);
+
+    let main = ssa.main();
+    let mut entry_point = BrilligContext::new_entry_point_artifact(
+        FunctionContext::parameters(main),
+        FunctionContext::return_values(main),
+        FunctionContext::function_id_to_function_label(main.id()),
+    );
+    while let Some(label) = entry_point.first_unresolved_function_call() {
+        let artifact = brillig.find_by_function_label(label).expect("Expected a linked function");
+        entry_point.link_with(artifact);
+    }
+    let bytecode = entry_point.finish().byte_code;
+
+    let registers = inputs.iter().map(|input| Value::from(FieldElement::from(*input))).collect();
+    let mut vm =
+        VM::new(Registers { inner: registers }, vec![], &bytecode, vec![], &DummyBlackBoxSolver);
+    let mut executed_opcodes = 0;
+    loop {
+        executed_opcodes += 1;
+        match vm.process_opcode() {
+            VMStatus::InProgress => (),
+            VMStatus::Finished => break,
+            status => panic!("Unexpected VM status {status:?}"),
+        }
+    }
+
+    let result = vm.get_registers().get(RegisterIndex(0));
+    Measurement { bytecode_size: bytecode.len(), executed_opcodes, result }
+}
+
+#[test]
+fn brillig_pipeline_benchmark() {
+    let programs = [
+        ("loop", LOOP.to_owned(), vec![100, 3], Gain::ExecutedOpcodes),
+        ("bounds_check", BOUNDS_CHECK.to_owned(), vec![100], Gain::ExecutedOpcodes),
+        ("large_callee", large_callee(), vec![2], Gain::BytecodeSize),
+    ];
+
+    println!(
+        "{:<14} {:>14} {:>14} {:>16} {:>16}",
+        "program", "size (before)", "size (after)", "executed (before)", "executed (after)"
+    );
+    for (name, src, inputs, gain) in programs {
+        let ssa: Ssa = src.parse().unwrap();
+        // Previously every constrained callee was inlined into Brillig functions
+        let before = measure(
+            ssa.inline_functions_with_brillig_size_limit(usize::MAX),
+            SHARED_ACIR_PIPELINE,
+            &inputs,
+        );
+
+        let ssa: Ssa = src.parse().unwrap();
+        let after = measure(ssa.inline_functions(), opt::BRILLIG_PIPELINE, &inputs);
+
+        println!(
+            "{name:<14} {:>14} {:>14} {:>16} {:>16}",
+            before.bytecode_size,
+            after.bytecode_size,
+            before.executed_opcodes,
+            after.executed_opcodes
+        );
+        assert_eq!(before.result, after.result, "{name} returned a different result");
+        assert!(after.bytecode_size <= before.bytecode_size, "{name} grew in size");
+        match gain {
+            Gain::BytecodeSize => assert!(
+                after.bytecode_size < before.bytecode_size,
+                "{name} did not shrink: {} opcodes before, {} after",
+                before.bytecode_size,
+                after.bytecode_size
+            ),
+            Gain::ExecutedOpcodes => assert!(
+                after.executed_opcodes < before.executed_opcodes,
+                "{name} did not run faster: {} opcodes executed before, {} after",
+                before.executed_opcodes,
+                after.executed_opcodes
+            ),
+        }
+    }
+}
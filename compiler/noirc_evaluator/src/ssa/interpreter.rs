@@ -0,0 +1,598 @@
+//! An interpreter for the SSA IR which evaluates an [Ssa] program on concrete inputs.
+//!
+//! This is used for differential testing of optimization passes: a pass which is
+//! correct must not change the result of interpreting the program it optimizes.
+//! The semantics here follow those of ACIR generation and of the constant folding
+//! done when instructions are simplified. In particular integer arithmetic is not
+//! wrapped - overflows are caught by the `range_check` and `truncate` instructions
+//! inserted during SSA generation instead.
+use std::{cell::RefCell, rc::Rc};
+
+use acvm::FieldElement;
+use fxhash::FxHashMap as HashMap;
+use iter_extended::vecmap;
+use num_bigint::BigUint;
+use thiserror::Error;
+
+use super::{
+    ir::{
+        basic_block::BasicBlockId,
+        function::{Function, FunctionId},
+        instruction::{
+            Binary, BinaryOp, Instruction, InstructionId, Intrinsic, TerminatorInstruction,
+        },
+        types::{NumericType, Type},
+        value::{self as ir_value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+
+mod intrinsics;
+mod value;
+
+pub(crate) use value::Value;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub(crate) enum InterpreterError {
+    #[error("Failed constraint: {lhs} == {rhs}{}", format_message(.message))]
+    ConstraintFailed { lhs: Value, rhs: Value, message: Option<String> },
+    #[error("Range check failed: {value} does not fit in {max_bit_size} bits{}", format_message(.message))]
+    RangeCheckFailed { value: Value, max_bit_size: u32, message: Option<String> },
+    #[error("Division by zero")]
+    DivisionByZero,
+    #[error("Index {index} is out of bounds for an array of length {length}")]
+    IndexOutOfBounds { index: usize, length: usize },
+    #[error("Load from a reference which was never stored to")]
+    UninitializedLoad,
+    #[error("Expected {expected} but found {found}")]
+    TypeError { expected: &'static str, found: String },
+    #[error("Value {0} is used before it is defined")]
+    UndefinedValue(ValueId),
+    #[error("Expected {expected} argument(s) but {actual} were given")]
+    ArgumentCountMismatch { expected: usize, actual: usize },
+    #[error("Block {0} has no terminator instruction")]
+    MissingTerminator(BasicBlockId),
+    #[error("The intrinsic `{0}` is not supported by the interpreter")]
+    UnsupportedIntrinsic(Intrinsic),
+    #[error("The foreign function `{0}` is not supported by the interpreter")]
+    UnsupportedForeignCall(String),
+}
+
+impl InterpreterError {
+    /// True if this error is due to a limitation of the interpreter rather than the program
+    pub(crate) fn is_unsupported(&self) -> bool {
+        matches!(
+            self,
+            InterpreterError::UnsupportedIntrinsic(_) | InterpreterError::UnsupportedForeignCall(_)
+        )
+    }
+}
+
+fn format_message(message: &Option<String>) -> String {
+    match message {
+        Some(message) => format!(" '{message}'"),
+        None => String::new(),
+    }
+}
+
+impl Ssa {
+    /// Evaluates the main function of this program on the given inputs, returning its results.
+    pub(crate) fn interpret(&self, inputs: Vec<Value>) -> Result<Vec<Value>, InterpreterError> {
+        Interpreter { programs: vec![self] }.call_function(self.main_id, inputs)
+    }
+
+    /// The same as `interpret` for a program whose functions have been split between
+    /// `self` and `other` by `split_off_brillig_functions`.
+    pub(crate) fn interpret_split(
+        &self,
+        other: &Ssa,
+        inputs: Vec<Value>,
+    ) -> Result<Vec<Value>, InterpreterError> {
+        Interpreter { programs: vec![self, other] }.call_function(self.main_id, inputs)
+    }
+}
+
+struct Interpreter<'ssa> {
+    /// The programs holding the functions which may be called
+    programs: Vec<&'ssa Ssa>,
+}
+
+/// The state of a single function call
+struct CallFrame<'ssa> {
+    function: &'ssa Function,
+    values: HashMap<ValueId, Value>,
+
+    /// Set by `enable_side_effects`. While this is false, operations which would otherwise
+    /// fail (such as out of bounds array accesses) instead return zeroed values, as they
+    /// do in ACIR.
+    side_effects_enabled: bool,
+}
+
+impl<'ssa> Interpreter<'ssa> {
+    fn call_function(
+        &mut self,
+        function_id: FunctionId,
+        mut arguments: Vec<Value>,
+    ) -> Result<Vec<Value>, InterpreterError> {
+        let function = self
+            .programs
+            .iter()
+            .copied()
+            .find_map(|ssa| ssa.functions.get(&function_id))
+            .expect("ICE: called a function which is not part of the program");
+        let mut frame =
+            CallFrame { function, values: HashMap::default(), side_effects_enabled: true };
+        let mut block_id = function.entry_block();
+
+        loop {
+            let block = &function.dfg[block_id];
+            let parameters = block.parameters();
+            if parameters.len() != arguments.len() {
+                return Err(InterpreterError::ArgumentCountMismatch {
+                    expected: parameters.len(),
+                    actual: arguments.len(),
+                });
+            }
+            frame.values.extend(parameters.iter().copied().zip(arguments));
+
+            for instruction in block.instructions() {
+                self.interpret_instruction(&mut frame, *instruction)?;
+            }
+
+            match block.terminator() {
+                Some(TerminatorInstruction::Jmp {
+                    destination, arguments: jmp_arguments, ..
+                }) => {
+                    arguments = frame.lookup_all(jmp_arguments)?;
+                    block_id = *destination;
+                }
+                Some(TerminatorInstruction::JmpIf {
+                    condition,
+                    then_destination,
+                    else_destination,
+                }) => {
+                    let condition = frame.lookup(*condition)?.as_field()?;
+                    block_id =
+                        if condition.is_zero() { *else_destination } else { *then_destination };
+                    arguments = Vec::new();
+                }
+                Some(TerminatorInstruction::Return { return_values, .. }) => {
+                    return frame.lookup_all(return_values);
+                }
+                None => return Err(InterpreterError::MissingTerminator(block_id)),
+            }
+        }
+    }
+
+    fn interpret_instruction(
+        &mut self,
+        frame: &mut CallFrame<'ssa>,
+        instruction_id: InstructionId,
+    ) -> Result<(), InterpreterError> {
+        let function = frame.function;
+        let dfg = &function.dfg;
+        let results = dfg.instruction_results(instruction_id);
+
+        let result = match &dfg[instruction_id] {
+            Instruction::Binary(binary) => self.interpret_binary(frame, binary)?,
+            Instruction::Cast(value, typ) => {
+                let (value, _) = frame.lookup(*value)?.as_numeric()?;
+                match typ {
+                    Type::Numeric(typ) => cast(value, *typ),
+                    other => {
+                        return Err(InterpreterError::TypeError {
+                            expected: "a numeric type",
+                            found: other.to_string(),
+                        })
+                    }
+                }
+            }
+            Instruction::Not(value) => {
+                let (value, typ) = frame.lookup(*value)?.as_numeric()?;
+                let bit_size = integer_bit_size(typ)?;
+                let max = (BigUint::from(1u128) << bit_size) - 1u128;
+                let value = max - to_biguint(value) % (BigUint::from(1u128) << bit_size);
+                Value::Numeric { value: from_biguint(&value), typ }
+            }
+            Instruction::Truncate { value, bit_size, .. } => {
+                let (value, typ) = frame.lookup(*value)?.as_numeric()?;
+                Value::Numeric { value: truncate(value, *bit_size), typ }
+            }
+            Instruction::Constrain(lhs, rhs, message) => {
+                let lhs = frame.lookup(*lhs)?;
+                let rhs = frame.lookup(*rhs)?;
+                if lhs != rhs {
+                    let message = message.clone();
+                    return Err(InterpreterError::ConstraintFailed { lhs, rhs, message });
+                }
+                return Ok(());
+            }
+            Instruction::RangeCheck { value, max_bit_size, assert_message } => {
+                let value = frame.lookup(*value)?;
+                if value.as_field()?.num_bits() > *max_bit_size {
+                    return Err(InterpreterError::RangeCheckFailed {
+                        value,
+                        max_bit_size: *max_bit_size,
+                        message: assert_message.clone(),
+                    });
+                }
+                return Ok(());
+            }
+            Instruction::Call { func, arguments } => {
+                let arguments = frame.lookup_all(arguments)?;
+                let result_values = match frame.lookup(*func)? {
+                    Value::Function(id) => self.call_function(id, arguments)?,
+                    Value::Intrinsic(intrinsic) => {
+                        let result_types = vecmap(results, |result| dfg.type_of_value(*result));
+                        intrinsics::call_intrinsic(intrinsic, arguments, result_types)?
+                    }
+                    // Foreign calls without results (such as `print`) have no effect on the
+                    // program's result so they can be skipped.
+                    Value::ForeignFunction(_) if results.is_empty() => Vec::new(),
+                    Value::ForeignFunction(name) => {
+                        return Err(InterpreterError::UnsupportedForeignCall(name))
+                    }
+                    other => {
+                        return Err(InterpreterError::TypeError {
+                            expected: "a function",
+                            found: other.to_string(),
+                        })
+                    }
+                };
+                if result_values.len() != results.len() {
+                    return Err(InterpreterError::ArgumentCountMismatch {
+                        expected: results.len(),
+                        actual: result_values.len(),
+                    });
+                }
+                frame.values.extend(results.iter().copied().zip(result_values));
+                return Ok(());
+            }
+            Instruction::Allocate => Value::Reference(Rc::new(RefCell::new(None))),
+            Instruction::Load { address } => {
+                let address = frame.lookup(*address)?;
+                let value = address.as_reference()?.borrow().clone();
+                value.ok_or(InterpreterError::UninitializedLoad)?
+            }
+            Instruction::Store { address, value } => {
+                let address = frame.lookup(*address)?;
+                let value = frame.lookup(*value)?;
+                *address.as_reference()?.borrow_mut() = Some(value);
+                return Ok(());
+            }
+            Instruction::EnableSideEffects { condition } => {
+                frame.side_effects_enabled = !frame.lookup(*condition)?.as_field()?.is_zero();
+                return Ok(());
+            }
+            Instruction::ArrayGet { array, index } => {
+                let array = frame.lookup(*array)?;
+                let (elements, _) = array.as_array()?;
+                let index = frame.lookup(*index)?.as_field()?.to_u128() as usize;
+                match elements.get(index) {
+                    Some(element) => element.clone(),
+                    None if !frame.side_effects_enabled => {
+                        Value::zero(&dfg.type_of_value(results[0]))?
+                    }
+                    None => {
+                        let length = elements.len();
+                        return Err(InterpreterError::IndexOutOfBounds { index, length });
+                    }
+                }
+            }
+            Instruction::ArraySet { array, index, value } => {
+                let array = frame.lookup(*array)?;
+                let (elements, typ) = array.as_array()?;
+                let index = frame.lookup(*index)?.as_field()?.to_u128() as usize;
+                let value = frame.lookup(*value)?;
+                if index < elements.len() {
+                    let elements = elements.update(index, value);
+                    Value::Array { elements, typ: typ.clone() }
+                } else if !frame.side_effects_enabled {
+                    array.clone()
+                } else {
+                    let length = elements.len();
+                    return Err(InterpreterError::IndexOutOfBounds { index, length });
+                }
+            }
+            // Reference counts only affect the performance of Brillig code
+            Instruction::IncrementRc { .. } => return Ok(()),
+        };
+
+        frame.values.insert(results[0], result);
+        Ok(())
+    }
+
+    fn interpret_binary(
+        &self,
+        frame: &CallFrame<'ssa>,
+        binary: &Binary,
+    ) -> Result<Value, InterpreterError> {
+        let (lhs, typ) = frame.lookup(binary.lhs)?.as_numeric()?;
+        let (rhs, _) = frame.lookup(binary.rhs)?.as_numeric()?;
+
+        let value = match binary.operator {
+            // Arithmetic is done over the field as in ACIR.
+            // Any overflow is checked separately by a following instruction.
+            BinaryOp::Add => lhs + rhs,
+            BinaryOp::Sub => lhs - rhs,
+            BinaryOp::Mul => lhs * rhs,
+            BinaryOp::Eq => return Ok(Value::bool(lhs == rhs)),
+            BinaryOp::Div | BinaryOp::Mod if is_zero_divisor(rhs, typ) => {
+                if frame.side_effects_enabled {
+                    return Err(InterpreterError::DivisionByZero);
+                }
+                FieldElement::zero()
+            }
+            BinaryOp::Div if typ == NumericType::NativeField => lhs / rhs,
+            BinaryOp::Lt if typ == NumericType::NativeField => return Ok(Value::bool(lhs < rhs)),
+            operator => match typ {
+                NumericType::NativeField => {
+                    return Err(InterpreterError::TypeError {
+                        expected: "an integer operand",
+                        found: format!("Field operand to `{operator}`"),
+                    })
+                }
+                NumericType::Unsigned { bit_size } => {
+                    let lhs = truncate_u128(lhs.to_u128(), bit_size);
+                    let rhs = truncate_u128(rhs.to_u128(), bit_size);
+                    match operator {
+                        BinaryOp::Div => (lhs / rhs).into(),
+                        BinaryOp::Mod => (lhs % rhs).into(),
+                        BinaryOp::Lt => return Ok(Value::bool(lhs < rhs)),
+                        BinaryOp::And => (lhs & rhs).into(),
+                        BinaryOp::Or => (lhs | rhs).into(),
+                        BinaryOp::Xor => (lhs ^ rhs).into(),
+                        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Eq => {
+                            unreachable!("Handled above")
+                        }
+                    }
+                }
+                NumericType::Signed { bit_size } => {
+                    let lhs = to_signed(lhs.to_u128(), bit_size);
+                    let rhs = to_signed(rhs.to_u128(), bit_size);
+                    let result = match operator {
+                        BinaryOp::Div => lhs.wrapping_div(rhs),
+                        BinaryOp::Mod => lhs.wrapping_rem(rhs),
+                        BinaryOp::Lt => return Ok(Value::bool(lhs < rhs)),
+                        BinaryOp::And => lhs & rhs,
+                        BinaryOp::Or => lhs | rhs,
+                        BinaryOp::Xor => lhs ^ rhs,
+                        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Eq => {
+                            unreachable!("Handled above")
+                        }
+                    };
+                    truncate_u128(result as u128, bit_size).into()
+                }
+            },
+        };
+        Ok(Value::Numeric { value, typ })
+    }
+}
+
+impl<'ssa> CallFrame<'ssa> {
+    /// Returns the runtime value of the given IR value
+    fn lookup(&self, id: ValueId) -> Result<Value, InterpreterError> {
+        let dfg = &self.function.dfg;
+        let id = dfg.resolve(id);
+        match &dfg[id] {
+            ir_value::Value::NumericConstant { constant, typ } => match typ {
+                Type::Numeric(typ) => Ok(Value::Numeric { value: *constant, typ: *typ }),
+                other => Err(InterpreterError::TypeError {
+                    expected: "a numeric type",
+                    found: other.to_string(),
+                }),
+            },
+            ir_value::Value::Array { array, typ } => {
+                let mut elements = im::Vector::new();
+                for element in array {
+                    elements.push_back(self.lookup(*element)?);
+                }
+                Ok(Value::Array { elements, typ: typ.clone() })
+            }
+            ir_value::Value::Function(id) => Ok(Value::Function(*id)),
+            ir_value::Value::Intrinsic(intrinsic) => Ok(Value::Intrinsic(*intrinsic)),
+            ir_value::Value::ForeignFunction(name) => Ok(Value::ForeignFunction(name.clone())),
+            ir_value::Value::Param { .. } | ir_value::Value::Instruction { .. } => {
+                self.values.get(&id).cloned().ok_or(InterpreterError::UndefinedValue(id))
+            }
+        }
+    }
+
+    fn lookup_all(&self, ids: &[ValueId]) -> Result<Vec<Value>, InterpreterError> {
+        ids.iter().map(|id| self.lookup(*id)).collect()
+    }
+}
+
+fn integer_bit_size(typ: NumericType) -> Result<u32, InterpreterError> {
+    match typ {
+        NumericType::Signed { bit_size } | NumericType::Unsigned { bit_size } => Ok(bit_size),
+        NumericType::NativeField => {
+            Err(InterpreterError::TypeError { expected: "an integer type", found: typ.to_string() })
+        }
+    }
+}
+
+/// True if `rhs` is zero once truncated to the bit size of the operand type
+fn is_zero_divisor(rhs: FieldElement, typ: NumericType) -> bool {
+    match typ {
+        NumericType::NativeField => rhs.is_zero(),
+        NumericType::Signed { bit_size } | NumericType::Unsigned { bit_size } => {
+            truncate_u128(rhs.to_u128(), bit_size) == 0
+        }
+    }
+}
+
+/// Casts a value to the given type, truncating it if the type is an integer type
+fn cast(value: FieldElement, typ: NumericType) -> Value {
+    let value = match typ {
+        NumericType::NativeField => value,
+        NumericType::Signed { bit_size } | NumericType::Unsigned { bit_size } => {
+            truncate(value, bit_size)
+        }
+    };
+    Value::Numeric { value, typ }
+}
+
+/// Returns `value % 2^bit_size`
+fn truncate(value: FieldElement, bit_size: u32) -> FieldElement {
+    let modulus = BigUint::from(1u128) << bit_size;
+    from_biguint(&(to_biguint(value) % modulus))
+}
+
+fn truncate_u128(value: u128, bit_size: u32) -> u128 {
+    if bit_size >= 128 {
+        value
+    } else {
+        value % (1 << bit_size)
+    }
+}
+
+/// Interprets the lower `bit_size` bits of `value` as a two's complement integer
+fn to_signed(value: u128, bit_size: u32) -> i128 {
+    let value = truncate_u128(value, bit_size);
+    if bit_size < 128 && value >= 1 << (bit_size - 1) {
+        value as i128 - (1 << bit_size)
+    } else {
+        value as i128
+    }
+}
+
+fn to_biguint(value: FieldElement) -> BigUint {
+    BigUint::from_bytes_be(&value.to_be_bytes())
+}
+
+fn from_biguint(value: &BigUint) -> FieldElement {
+    FieldElement::from_be_bytes_reduce(&value.to_bytes_be())
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::FieldElement;
+
+    use crate::ssa::{ir::types::NumericType, ssa_gen::Ssa};
+
+    use super::{InterpreterError, Value};
+
+    fn interpret(src: &str, inputs: Vec<Value>) -> Result<Vec<Value>, InterpreterError> {
+        let ssa: Ssa = src.parse().unwrap();
+        ssa.interpret(inputs)
+    }
+
+    fn u32_value(value: u128) -> Value {
+        Value::Numeric { value: value.into(), typ: NumericType::Unsigned { bit_size: 32 } }
+    }
+
+    fn i8_value(value: i128) -> Value {
+        let value = if value < 0 { value + 256 } else { value };
+        Value::Numeric { value: value.into(), typ: NumericType::Signed { bit_size: 8 } }
+    }
+
+    #[test]
+    fn field_arithmetic() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: Field, v1: Field):
+                v2 = add v0, v1
+                v3 = mul v2, v0
+                v4 = div v3, v1
+                return v4
+            }
+        ";
+        let inputs = vec![Value::field(3u128.into()), Value::field(4u128.into())];
+        let expected = FieldElement::from(21u128) / FieldElement::from(4u128);
+        assert_eq!(interpret(src, inputs), Ok(vec![Value::field(expected)]));
+    }
+
+    #[test]
+    fn integer_arithmetic() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: i8, v1: i8):
+                v2 = div v0, v1
+                v3 = mod v0, v1
+                v4 = lt v0, v1
+                return v2, v3, v4
+            }
+        ";
+        let results = interpret(src, vec![i8_value(-7), i8_value(2)]).unwrap();
+        assert_eq!(results, vec![i8_value(-3), i8_value(-1), Value::bool(true)]);
+    }
+
+    #[test]
+    fn loops() {
+        let src = "
+            brillig fn main f0 {
+              b0(v0: u32):
+                jmp b1(u32 0)
+              b1(v2: u32):
+                v3 = lt v2, v0
+                jmpif v3 then: b2, else: b3
+              b2():
+                v5 = add v2, u32 1
+                jmp b1(v5)
+              b3():
+                return v2
+            }
+        ";
+        assert_eq!(interpret(src, vec![u32_value(5)]), Ok(vec![u32_value(5)]));
+    }
+
+    #[test]
+    fn memory_and_calls() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: Field):
+                v1 = allocate -> &mut Field
+                store v0 at v1
+                v2 = load v1 -> Field
+                v4 = call f1(v2) -> Field
+                store v4 at v1
+                v5 = load v1 -> Field
+                return v5
+            }
+            acir fn double f1 {
+              b0(v0: Field):
+                v1 = add v0, v0
+                return v1
+            }
+        ";
+        let result = interpret(src, vec![Value::field(5u128.into())]);
+        assert_eq!(result, Ok(vec![Value::field(10u128.into())]));
+    }
+
+    #[test]
+    fn failing_constraint() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: Field):
+                constrain v0 == Field 1 'v0 must be one'
+                return
+            }
+        ";
+        assert_eq!(interpret(src, vec![Value::field(1u128.into())]), Ok(Vec::new()));
+
+        let error = interpret(src, vec![Value::field(2u128.into())]).unwrap_err();
+        assert!(matches!(
+            error,
+            InterpreterError::ConstraintFailed { message: Some(message), .. } if message == "v0 must be one"
+        ));
+    }
+
+    #[test]
+    fn out_of_bounds_reads_are_zero_when_side_effects_are_disabled() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: u1, v1: u32):
+                enable_side_effects v0
+                v4 = array_get [Field 1, Field 2] of [Field; 2], index v1 -> Field
+                return v4
+            }
+        ";
+        let inputs = vec![Value::bool(false), u32_value(7)];
+        assert_eq!(interpret(src, inputs), Ok(vec![Value::field(FieldElement::zero())]));
+
+        let inputs = vec![Value::bool(true), u32_value(7)];
+        let error = InterpreterError::IndexOutOfBounds { index: 7, length: 2 };
+        assert_eq!(interpret(src, inputs), Err(error));
+    }
+}
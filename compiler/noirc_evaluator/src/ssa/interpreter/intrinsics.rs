@@ -0,0 +1,196 @@
+use acvm::FieldElement;
+use num_bigint::BigUint;
+
+use crate::ssa::ir::{
+    instruction::{Endian, Intrinsic},
+    types::{NumericType, Type},
+};
+
+use super::{cast, InterpreterError, Value};
+
+/// Evaluates a call to an intrinsic function. `result_types` are the types of
+/// the call's results, which determine the element types of some results.
+///
+/// Slices are passed to and returned from intrinsics as a (length, contents) pair.
+pub(super) fn call_intrinsic(
+    intrinsic: Intrinsic,
+    arguments: Vec<Value>,
+    result_types: Vec<Type>,
+) -> Result<Vec<Value>, InterpreterError> {
+    match intrinsic {
+        Intrinsic::ArrayLen => {
+            let length = match &arguments[0] {
+                Value::Array { elements, typ: Type::Array(element_types, _) } => {
+                    elements.len() / element_types.len()
+                }
+                other => other.as_field()?.to_u128() as usize,
+            };
+            Ok(vec![Value::field((length as u128).into())])
+        }
+        Intrinsic::AssertConstant => Ok(Vec::new()),
+        Intrinsic::StrAsBytes => Ok(arguments),
+        Intrinsic::AsField => {
+            let (value, _) = arguments[0].as_numeric()?;
+            Ok(vec![cast(value, NumericType::NativeField)])
+        }
+        Intrinsic::FromField => {
+            let value = arguments[0].as_field()?;
+            match &result_types[0] {
+                Type::Numeric(typ) => Ok(vec![cast(value, *typ)]),
+                other => Err(InterpreterError::TypeError {
+                    expected: "a numeric type",
+                    found: other.to_string(),
+                }),
+            }
+        }
+        Intrinsic::Sort => {
+            let (elements, typ) = arguments[0].as_array()?;
+            let mut sorted = Vec::with_capacity(elements.len());
+            for element in elements {
+                let (value, typ) = element.as_numeric()?;
+                sorted.push((value, typ));
+            }
+            sorted.sort_by_key(|(value, _)| *value);
+            let elements =
+                sorted.into_iter().map(|(value, typ)| Value::Numeric { value, typ }).collect();
+            Ok(vec![Value::Array { elements, typ: typ.clone() }])
+        }
+        Intrinsic::ToBits(endian) => {
+            let field = arguments[0].as_field()?;
+            let limb_count = arguments[1].as_field()?.to_u128() as u32;
+            Ok(to_radix(endian, field, 2, limb_count, &result_types))
+        }
+        Intrinsic::ToRadix(endian) => {
+            let field = arguments[0].as_field()?;
+            let radix = arguments[1].as_field()?.to_u128() as u32;
+            let limb_count = arguments[2].as_field()?.to_u128() as u32;
+            Ok(to_radix(endian, field, radix, limb_count, &result_types))
+        }
+        Intrinsic::SlicePushBack => {
+            let (length, mut elements, typ) = slice_argument(&arguments)?;
+            elements.extend(arguments[2..].iter().cloned());
+            Ok(slice_results(length + 1, elements, typ))
+        }
+        Intrinsic::SlicePushFront => {
+            let (length, mut elements, typ) = slice_argument(&arguments)?;
+            for element in arguments[2..].iter().rev() {
+                elements.push_front(element.clone());
+            }
+            Ok(slice_results(length + 1, elements, typ))
+        }
+        Intrinsic::SlicePopBack => {
+            let (length, mut elements, typ) = slice_argument(&arguments)?;
+            let element_count = slice_element_count(&typ);
+            check_index(length, 0)?;
+            let popped = elements.split_off(elements.len() - element_count);
+
+            // The original slice is returned unchanged, only its length is decremented
+            let mut results = slice_results(length - 1, elements, typ);
+            results[1] = arguments[1].clone();
+            results.extend(popped);
+            Ok(results)
+        }
+        Intrinsic::SlicePopFront => {
+            let (length, mut elements, typ) = slice_argument(&arguments)?;
+            let element_count = slice_element_count(&typ);
+            check_index(length, 0)?;
+            let remaining = elements.split_off(element_count);
+
+            let mut results: Vec<_> = elements.into_iter().collect();
+            results.extend(slice_results(length - 1, remaining, typ));
+            Ok(results)
+        }
+        Intrinsic::SliceInsert => {
+            let (length, mut elements, typ) = slice_argument(&arguments)?;
+            let index = arguments[2].as_field()?.to_u128() as usize;
+            check_index(length + 1, index)?;
+
+            let index = index * slice_element_count(&typ);
+            for (offset, element) in arguments[3..].iter().enumerate() {
+                elements.insert(index + offset, element.clone());
+            }
+            Ok(slice_results(length + 1, elements, typ))
+        }
+        Intrinsic::SliceRemove => {
+            let (length, mut elements, typ) = slice_argument(&arguments)?;
+            let index = arguments[2].as_field()?.to_u128() as usize;
+            check_index(length, index)?;
+
+            let element_count = slice_element_count(&typ);
+            let removed: Vec<_> =
+                (0..element_count).map(|_| elements.remove(index * element_count)).collect();
+
+            let mut results = slice_results(length - 1, elements, typ);
+            results.extend(removed);
+            Ok(results)
+        }
+        Intrinsic::BlackBox(_) => Err(InterpreterError::UnsupportedIntrinsic(intrinsic)),
+    }
+}
+
+/// Returns the length, contents, and type of the (length, slice) pair at the start of `arguments`
+fn slice_argument(
+    arguments: &[Value],
+) -> Result<(usize, im::Vector<Value>, Type), InterpreterError> {
+    let length = arguments[0].as_field()?.to_u128() as usize;
+    let (elements, typ) = arguments[1].as_array()?;
+    Ok((length, elements.clone(), typ.clone()))
+}
+
+fn slice_results(length: usize, elements: im::Vector<Value>, typ: Type) -> Vec<Value> {
+    vec![Value::field((length as u128).into()), Value::Array { elements, typ }]
+}
+
+/// The number of flattened values making up each element of a slice
+fn slice_element_count(typ: &Type) -> usize {
+    match typ {
+        Type::Slice(element_types) | Type::Array(element_types, _) => element_types.len(),
+        _ => 1,
+    }
+}
+
+fn check_index(length: usize, index: usize) -> Result<(), InterpreterError> {
+    if index < length {
+        Ok(())
+    } else {
+        Err(InterpreterError::IndexOutOfBounds { index, length })
+    }
+}
+
+/// Decomposes `field` into `limb_count` limbs of the given radix. Mirrors the
+/// constant folding of these intrinsics in `ir::instruction::call`.
+fn to_radix(
+    endian: Endian,
+    field: FieldElement,
+    radix: u32,
+    limb_count: u32,
+    result_types: &[Type],
+) -> Vec<Value> {
+    let bit_size = u32::BITS - (radix - 1).leading_zeros();
+    let big_integer = BigUint::from_bytes_be(&field.to_be_bytes());
+
+    let decomposed_integer = big_integer.to_radix_le(radix);
+    let limb_type = NumericType::Unsigned { bit_size };
+    let mut limbs: im::Vector<Value> = (0..limb_count)
+        .map(|i| {
+            let value = match decomposed_integer.get(i as usize) {
+                Some(digit) => FieldElement::from(*digit as u128),
+                None => FieldElement::zero(),
+            };
+            Value::Numeric { value, typ: limb_type }
+        })
+        .collect();
+    if endian == Endian::Big {
+        limbs = limbs.into_iter().rev().collect();
+    }
+
+    let array_type = result_types.last().cloned().unwrap_or_else(|| {
+        Type::Array(std::rc::Rc::new(vec![Type::Numeric(limb_type)]), limb_count as usize)
+    });
+    let array = Value::Array { elements: limbs, typ: array_type };
+    if result_types.len() == 2 {
+        vec![Value::field((limb_count as u128).into()), array]
+    } else {
+        vec![array]
+    }
+}
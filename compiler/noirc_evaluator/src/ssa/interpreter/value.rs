@@ -0,0 +1,124 @@
+use std::{cell::RefCell, rc::Rc};
+
+use acvm::FieldElement;
+use iter_extended::vecmap;
+
+use crate::ssa::ir::{
+    function::FunctionId,
+    instruction::Intrinsic,
+    types::{NumericType, Type},
+};
+
+use super::InterpreterError;
+
+/// A runtime value of the SSA interpreter.
+///
+/// Arrays and slices are flattened in the same way as in the IR: an array of
+/// tuples stores each field of each tuple as a separate element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Value {
+    Numeric {
+        value: FieldElement,
+        typ: NumericType,
+    },
+
+    /// The result of an `allocate` instruction. The inner value is None until it is first
+    /// stored to. References compare equal if the values they point to are equal.
+    Reference(Rc<RefCell<Option<Value>>>),
+
+    Array {
+        elements: im::Vector<Value>,
+        typ: Type,
+    },
+
+    Function(FunctionId),
+    Intrinsic(Intrinsic),
+    ForeignFunction(String),
+}
+
+impl Value {
+    pub(crate) fn field(value: FieldElement) -> Value {
+        Value::Numeric { value, typ: NumericType::NativeField }
+    }
+
+    pub(crate) fn bool(value: bool) -> Value {
+        Value::Numeric { value: value.into(), typ: NumericType::Unsigned { bit_size: 1 } }
+    }
+
+    /// Returns the zero value of the given type, used as the result of operations
+    /// which would fail but are disabled by an `enable_side_effects` predicate.
+    pub(crate) fn zero(typ: &Type) -> Result<Value, InterpreterError> {
+        match typ {
+            Type::Numeric(typ) => Ok(Value::Numeric { value: FieldElement::zero(), typ: *typ }),
+            Type::Array(element_types, length) => {
+                let mut elements = im::Vector::new();
+                for _ in 0..*length {
+                    for element_type in element_types.iter() {
+                        elements.push_back(Value::zero(element_type)?);
+                    }
+                }
+                Ok(Value::Array { elements, typ: typ.clone() })
+            }
+            Type::Slice(_) => Ok(Value::Array { elements: im::Vector::new(), typ: typ.clone() }),
+            Type::Reference(_) | Type::Function => Err(InterpreterError::TypeError {
+                expected: "a numeric or array type",
+                found: typ.to_string(),
+            }),
+        }
+    }
+
+    /// Returns the numeric contents of this value, erroring if it is not a numeric value
+    pub(crate) fn as_numeric(&self) -> Result<(FieldElement, NumericType), InterpreterError> {
+        match self {
+            Value::Numeric { value, typ } => Ok((*value, *typ)),
+            other => Err(InterpreterError::TypeError {
+                expected: "a numeric value",
+                found: other.to_string(),
+            }),
+        }
+    }
+
+    pub(crate) fn as_field(&self) -> Result<FieldElement, InterpreterError> {
+        self.as_numeric().map(|(value, _)| value)
+    }
+
+    /// Returns the elements and type of this array or slice, erroring if it is not one
+    pub(crate) fn as_array(&self) -> Result<(&im::Vector<Value>, &Type), InterpreterError> {
+        match self {
+            Value::Array { elements, typ } => Ok((elements, typ)),
+            other => Err(InterpreterError::TypeError {
+                expected: "an array or slice",
+                found: other.to_string(),
+            }),
+        }
+    }
+
+    pub(crate) fn as_reference(&self) -> Result<&Rc<RefCell<Option<Value>>>, InterpreterError> {
+        match self {
+            Value::Reference(reference) => Ok(reference),
+            other => Err(InterpreterError::TypeError {
+                expected: "a reference",
+                found: other.to_string(),
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Numeric { value, typ } => write!(f, "{typ} {value}"),
+            Value::Reference(reference) => match reference.borrow().as_ref() {
+                Some(value) => write!(f, "&mut {value}"),
+                None => write!(f, "&mut <uninitialized>"),
+            },
+            Value::Array { elements, .. } => {
+                let elements = vecmap(elements, ToString::to_string);
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Value::Function(id) => write!(f, "{id}"),
+            Value::Intrinsic(intrinsic) => write!(f, "{intrinsic}"),
+            Value::ForeignFunction(name) => write!(f, "{name}"),
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! Bit-width inference for the numeric values of a function.
+//!
+//! For each value we track an upper bound on the number of bits needed to represent it as an
+//! integer, i.e. a `max_bit_size` such that `value < 2^max_bit_size` holds for every execution.
+//! This lets ACIR generation skip truncations and range checks which are already implied by the
+//! operations that produced a value, e.g. the result of XOR-ing two `u8`s or of a comparison.
+//!
+//! Note that the bit size of a value's type is not a bound on its own: the result of adding two
+//! `u8`s has type `u8` but may need 9 bits until it is truncated.
+use acvm::FieldElement;
+use fxhash::FxHashMap as HashMap;
+
+use super::{
+    dfg::DataFlowGraph,
+    function::Function,
+    instruction::{Binary, BinaryOp, Instruction},
+    post_order::PostOrder,
+    types::{NumericType, Type},
+    value::{Value, ValueId},
+};
+
+#[derive(Default)]
+pub(crate) struct BitWidths {
+    /// Maps each value with a known bound to the maximum number of bits it can occupy.
+    max_bit_sizes: HashMap<ValueId, u32>,
+}
+
+impl BitWidths {
+    /// Infers the maximum bit sizes of the values defined within `func`.
+    ///
+    /// The parameters of the entry block are assumed to have been range constrained to their
+    /// types, as is the case for the inputs of an ACIR function.
+    pub(crate) fn with_function(func: &Function) -> Self {
+        let mut bit_widths = BitWidths::default();
+        let dfg = &func.dfg;
+
+        for parameter in dfg[func.entry_block()].parameters() {
+            if let Some(bit_size) = integer_bit_size(&dfg.type_of_value(*parameter)) {
+                bit_widths.max_bit_sizes.insert(*parameter, bit_size);
+            }
+        }
+
+        // Visiting blocks in reverse post-order ensures that values are seen before their uses,
+        // except for the parameters of loop headers which are left without a bound.
+        let mut reverse_post_order = PostOrder::with_function(func).into_vec();
+        reverse_post_order.reverse();
+        for block in reverse_post_order {
+            for instruction_id in dfg[block].instructions() {
+                let results = dfg.instruction_results(*instruction_id);
+                if results.len() != 1 {
+                    continue;
+                }
+                let max_bit_size = bit_widths.instruction_max_bit_size(&dfg[*instruction_id], dfg);
+                // A bound which does not fit within the field tells us nothing as the value may
+                // have wrapped around the field modulus.
+                if let Some(max_bit_size) =
+                    max_bit_size.filter(|bits| *bits < FieldElement::max_num_bits())
+                {
+                    bit_widths.max_bit_sizes.insert(results[0], max_bit_size);
+                }
+            }
+        }
+
+        bit_widths
+    }
+
+    /// Returns the maximum number of bits `value` can occupy, if a bound is known.
+    pub(crate) fn max_bit_size(&self, dfg: &DataFlowGraph, value: ValueId) -> Option<u32> {
+        let value = dfg.resolve(value);
+        match &dfg[value] {
+            Value::NumericConstant { constant, .. } => Some(constant.num_bits()),
+            _ => self.max_bit_sizes.get(&value).copied(),
+        }
+    }
+
+    /// Computes a bound on the result of `instruction` from the bounds on its arguments.
+    fn instruction_max_bit_size(
+        &self,
+        instruction: &Instruction,
+        dfg: &DataFlowGraph,
+    ) -> Option<u32> {
+        match instruction {
+            Instruction::Binary(Binary { lhs, rhs, operator }) => {
+                let lhs_bits = self.max_bit_size(dfg, *lhs);
+                let rhs_bits = self.max_bit_size(dfg, *rhs);
+                let typ = dfg.type_of_value(*lhs);
+                let is_unsigned = matches!(typ, Type::Numeric(NumericType::Unsigned { .. }));
+                match operator {
+                    BinaryOp::Add => Some(lhs_bits?.max(rhs_bits?) + 1),
+                    BinaryOp::Mul => Some(lhs_bits? + rhs_bits?),
+                    // The result of an unsigned division is never larger than the dividend and
+                    // the remainder is also smaller than the divisor.
+                    BinaryOp::Div if is_unsigned => lhs_bits,
+                    BinaryOp::Mod if is_unsigned => min_bound(lhs_bits, rhs_bits),
+                    BinaryOp::Eq | BinaryOp::Lt => Some(1),
+                    // Bitwise operations are performed on values of the bit size of their type.
+                    BinaryOp::And => {
+                        min_bound(min_bound(lhs_bits, rhs_bits), integer_bit_size(&typ))
+                    }
+                    BinaryOp::Or | BinaryOp::Xor => {
+                        let operand_bits = lhs_bits.zip(rhs_bits).map(|(lhs, rhs)| lhs.max(rhs));
+                        min_bound(operand_bits, integer_bit_size(&typ))
+                    }
+                    // Subtractions may underflow and wrap around the field modulus.
+                    BinaryOp::Sub | BinaryOp::Div | BinaryOp::Mod => None,
+                }
+            }
+            Instruction::Cast(value, typ) => {
+                let value_bits = self.max_bit_size(dfg, *value);
+                match integer_bit_size(typ) {
+                    // Casting into a `Field` is a no-op.
+                    None => value_bits,
+                    Some(bit_size) => match value_bits {
+                        Some(value_bits) if value_bits <= bit_size => Some(value_bits),
+                        // Casting from a wider type truncates the value.
+                        _ if type_bit_size(&dfg.type_of_value(*value)) > bit_size => Some(bit_size),
+                        _ => value_bits,
+                    },
+                }
+            }
+            Instruction::Truncate { value, bit_size, .. } => {
+                let value_bits = self.max_bit_size(dfg, *value);
+                Some(value_bits.map_or(*bit_size, |value_bits| value_bits.min(*bit_size)))
+            }
+            Instruction::Not(value) => {
+                // `!x` is computed as `2^bit_size - 1 - x` which only fits when `x` does.
+                let bit_size = integer_bit_size(&dfg.type_of_value(*value))?;
+                (self.max_bit_size(dfg, *value)? <= bit_size).then_some(bit_size)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns the bit size of an integer type, or `None` for any other type.
+fn integer_bit_size(typ: &Type) -> Option<u32> {
+    match typ {
+        Type::Numeric(NumericType::Unsigned { bit_size } | NumericType::Signed { bit_size }) => {
+            Some(*bit_size)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the bit size of a numeric type, where a `Field` occupies the full field.
+fn type_bit_size(typ: &Type) -> u32 {
+    integer_bit_size(typ).unwrap_or_else(FieldElement::max_num_bits)
+}
+
+/// Returns the tighter of two optional bounds.
+fn min_bound(lhs: Option<u32>, rhs: Option<u32>) -> Option<u32> {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => Some(lhs.min(rhs)),
+        (bound, None) | (None, bound) => bound,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noirc_frontend::monomorphization::ast::InlineType;
+
+    use crate::ssa::{
+        function_builder::FunctionBuilder,
+        ir::{function::RuntimeType, instruction::BinaryOp, map::Id, types::Type},
+    };
+
+    use super::BitWidths;
+
+    #[test]
+    fn bitwise_operations_are_bounded_by_their_operands() {
+        // fn main f0 {
+        //   b0(v0: u8, v1: u8):
+        //     v2 = and v0, u8 15
+        //     v3 = xor v2, v1
+        //     v4 = add v2, v2
+        //     v5 = mul v4, v2
+        //     v6 = sub v2, v1
+        //     v7 = lt v6, v1
+        //     return v3, v5, v6, v7
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
+        let v0 = builder.add_parameter(Type::unsigned(8));
+        let v1 = builder.add_parameter(Type::unsigned(8));
+        let mask = builder.numeric_constant(15u128, Type::unsigned(8));
+        let v2 = builder.insert_binary(v0, BinaryOp::And, mask);
+        let v3 = builder.insert_binary(v2, BinaryOp::Xor, v1);
+        let v4 = builder.insert_binary(v2, BinaryOp::Add, v2);
+        let v5 = builder.insert_binary(v4, BinaryOp::Mul, v2);
+        let v6 = builder.insert_binary(v2, BinaryOp::Sub, v1);
+        let v7 = builder.insert_binary(v6, BinaryOp::Lt, v1);
+        builder.terminate_with_return(vec![v3, v5, v6, v7]);
+
+        let ssa = builder.finish();
+        let main = ssa.main();
+        let bit_widths = BitWidths::with_function(main);
+
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v0), Some(8));
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v2), Some(4));
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v3), Some(8));
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v4), Some(5));
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v5), Some(9));
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v6), None);
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v7), Some(1));
+    }
+
+    #[test]
+    fn casts_and_truncations_narrow_values() {
+        // fn main f0 {
+        //   b0(v0: Field, v1: u8):
+        //     v2 = cast v0 as u8
+        //     v3 = cast v1 as u32
+        //     v4 = cast v1 as Field
+        //     v5 = mul v4, v4
+        //     v6 = truncate v5 to 16 bits, max_bit_size: 254
+        //     v7 = truncate v0 to 32 bits, max_bit_size: 254
+        //     v8 = not v1
+        //     v9 = cast v5 as u8
+        //     v10 = not v9
+        //     return v2, v3, v6, v7, v8, v10
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
+        let v0 = builder.add_parameter(Type::field());
+        let v1 = builder.add_parameter(Type::unsigned(8));
+        let v2 = builder.insert_cast(v0, Type::unsigned(8));
+        let v3 = builder.insert_cast(v1, Type::unsigned(32));
+        let v4 = builder.insert_cast(v1, Type::field());
+        let v5 = builder.insert_binary(v4, BinaryOp::Mul, v4);
+        let v6 = builder.insert_truncate(v5, 16, 254);
+        let v7 = builder.insert_truncate(v0, 32, 254);
+        let v8 = builder.insert_not(v1);
+        let v9 = builder.insert_cast(v5, Type::unsigned(8));
+        let v10 = builder.insert_not(v9);
+        builder.terminate_with_return(vec![v2, v3, v6, v7, v8, v10]);
+
+        let ssa = builder.finish();
+        let main = ssa.main();
+        let bit_widths = BitWidths::with_function(main);
+
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v0), None);
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v2), Some(8));
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v3), Some(8));
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v4), Some(8));
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v5), Some(16));
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v6), Some(16));
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v7), Some(32));
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v8), Some(8));
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v9), Some(8));
+        assert_eq!(bit_widths.max_bit_size(&main.dfg, v10), Some(8));
+    }
+}
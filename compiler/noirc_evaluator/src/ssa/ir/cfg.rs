@@ -2,7 +2,7 @@ use std::collections::BTreeSet;
 
 use super::{
     basic_block::{BasicBlock, BasicBlockId},
-    function::Function,
+    function::{Function, RuntimeType},
 };
 use fxhash::FxHashMap as HashMap;
 
@@ -24,6 +24,10 @@ struct CfgNode {
 /// basic blocks.
 pub(crate) struct ControlFlowGraph {
     data: HashMap<BasicBlockId, CfgNode>,
+
+    /// Only Brillig functions may contain blocks with more than two predecessors,
+    /// since `break` and `continue` each add another edge to the end or start of their loop.
+    allow_many_predecessors: bool,
 }
 
 impl ControlFlowGraph {
@@ -37,7 +41,8 @@ impl ControlFlowGraph {
         let mut data = HashMap::default();
         data.insert(entry_block, empty_node);
 
-        let mut cfg = ControlFlowGraph { data };
+        let allow_many_predecessors = func.runtime() == RuntimeType::Brillig;
+        let mut cfg = ControlFlowGraph { data, allow_many_predecessors };
         cfg.compute(func);
         cfg
     }
@@ -96,7 +101,7 @@ impl ControlFlowGraph {
         predecessor_node.successors.insert(to);
         let successor_node = self.data.entry(to).or_default();
         assert!(
-            successor_node.predecessors.len() < 2,
+            self.allow_many_predecessors || successor_node.predecessors.len() < 2,
             "ICE: A cfg node cannot have more than two predecessors"
         );
         successor_node.predecessors.insert(from);
@@ -136,6 +141,7 @@ mod tests {
     };
 
     use super::{super::function::Function, ControlFlowGraph};
+    use crate::ssa::ssa_gen::Ssa;
 
     #[test]
     fn empty() {
@@ -276,4 +282,39 @@ mod tests {
             assert!(block2_successors.contains(&ret_block_id));
         }
     }
+
+    /// A function with a block which is jumped to from three other blocks
+    fn three_predecessors(runtime: &str) -> Ssa {
+        let src = format!(
+            "
+            {runtime} fn main f0 {{
+              b0(v0: u1, v1: u1):
+                jmpif v0 then: b1, else: b2
+              b1():
+                jmpif v1 then: b3, else: b4
+              b2():
+                jmp b3()
+              b3():
+                return
+              b4():
+                jmp b3()
+            }}
+            "
+        );
+        src.parse().unwrap()
+    }
+
+    #[test]
+    fn brillig_blocks_may_have_many_predecessors() {
+        let ssa = three_predecessors("brillig");
+        let cfg = ControlFlowGraph::with_function(ssa.main());
+        assert_eq!(cfg.predecessors(Id::test_new(3)).len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot have more than two predecessors")]
+    fn acir_blocks_have_at_most_two_predecessors() {
+        let ssa = three_predecessors("acir");
+        ControlFlowGraph::with_function(ssa.main());
+    }
 }
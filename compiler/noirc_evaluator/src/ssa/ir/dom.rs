@@ -258,6 +258,7 @@ mod tests {
             types::Type,
         },
     };
+    use noirc_frontend::monomorphization::ast::InlineType;
 
     #[test]
     fn empty() {
@@ -286,7 +287,8 @@ mod tests {
         //     return ()
         // }
         let func_id = Id::test_new(0);
-        let mut builder = FunctionBuilder::new("func".into(), func_id, RuntimeType::Acir);
+        let mut builder =
+            FunctionBuilder::new("func".into(), func_id, RuntimeType::Acir(InlineType::default()));
 
         let cond = builder.add_parameter(Type::unsigned(1));
         let block1_id = builder.insert_block();
@@ -395,7 +397,8 @@ mod tests {
         //     jump block1()
         // }
         let func_id = Id::test_new(0);
-        let mut builder = FunctionBuilder::new("func".into(), func_id, RuntimeType::Acir);
+        let mut builder =
+            FunctionBuilder::new("func".into(), func_id, RuntimeType::Acir(InlineType::default()));
         let block1_id = builder.insert_block();
         let block2_id = builder.insert_block();
 
@@ -1,6 +1,7 @@
 use std::collections::BTreeSet;
 
 use iter_extended::vecmap;
+use noirc_frontend::monomorphization::ast::InlineType;
 
 use super::basic_block::BasicBlockId;
 use super::dfg::DataFlowGraph;
@@ -12,11 +13,19 @@ use super::value::ValueId;
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
 pub(crate) enum RuntimeType {
     // A noir function, to be compiled in ACIR and executed by ACVM
-    Acir,
+    Acir(InlineType),
     // Unconstrained function, to be compiled to brillig and executed by the Brillig VM
     Brillig,
 }
 
+impl RuntimeType {
+    /// Returns true for ACIR functions which are kept as a separate circuit
+    /// rather than being inlined into their callers.
+    pub(crate) fn is_folded(&self) -> bool {
+        matches!(self, RuntimeType::Acir(InlineType::Fold))
+    }
+}
+
 /// A function holds a list of instructions.
 /// These instructions are further grouped into Basic blocks
 ///
@@ -47,7 +56,7 @@ impl Function {
     pub(crate) fn new(name: String, id: FunctionId) -> Self {
         let mut dfg = DataFlowGraph::default();
         let entry_block = dfg.make_block();
-        Self { name, id, entry_block, dfg, runtime: RuntimeType::Acir }
+        Self { name, id, entry_block, dfg, runtime: RuntimeType::Acir(InlineType::default()) }
     }
 
     /// The name of the function.
@@ -129,7 +138,8 @@ impl Function {
 impl std::fmt::Display for RuntimeType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            RuntimeType::Acir => write!(f, "acir"),
+            RuntimeType::Acir(InlineType::Inline) => write!(f, "acir"),
+            RuntimeType::Acir(InlineType::Fold) => write!(f, "acir(fold)"),
             RuntimeType::Brillig => write!(f, "brillig"),
         }
     }
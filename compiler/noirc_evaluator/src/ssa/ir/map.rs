@@ -19,9 +19,9 @@ pub(crate) struct Id<T> {
 
 impl<T> Id<T> {
     /// Constructs a new Id for the given index.
-    /// This constructor is deliberately private to prevent
-    /// constructing invalid IDs.
-    fn new(index: usize) -> Self {
+    /// Outside of the maps in this module this should only be used when the index
+    /// is known to be valid, e.g. when reading back function ids from printed SSA.
+    pub(crate) fn new(index: usize) -> Self {
         Self { index, _marker: std::marker::PhantomData }
     }
 
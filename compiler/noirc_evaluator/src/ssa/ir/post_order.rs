@@ -80,6 +80,7 @@ mod tests {
             types::Type,
         },
     };
+    use noirc_frontend::monomorphization::ast::InlineType;
 
     #[test]
     fn single_block() {
@@ -112,7 +113,8 @@ mod tests {
         // D, F, E, B, A, (C dropped as unreachable)
 
         let func_id = Id::test_new(0);
-        let mut builder = FunctionBuilder::new("func".into(), func_id, RuntimeType::Acir);
+        let mut builder =
+            FunctionBuilder::new("func".into(), func_id, RuntimeType::Acir(InlineType::default()));
         let block_b_id = builder.insert_block();
         let block_c_id = builder.insert_block();
         let block_d_id = builder.insert_block();
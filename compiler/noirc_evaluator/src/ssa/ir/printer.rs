@@ -68,13 +68,12 @@ fn value(function: &Function, id: ValueId) -> String {
         }
         Value::Function(id) => id.to_string(),
         Value::Intrinsic(intrinsic) => intrinsic.to_string(),
-        Value::Array { array, .. } => {
+        Value::Array { array, typ } => {
             let elements = vecmap(array, |element| value(function, *element));
-            format!("[{}]", elements.join(", "))
-        }
-        Value::Param { .. } | Value::Instruction { .. } | Value::ForeignFunction(_) => {
-            id.to_string()
+            format!("[{}] of {typ}", elements.join(", "))
         }
+        Value::ForeignFunction(name) => name.clone(),
+        Value::Param { .. } | Value::Instruction { .. } => id.to_string(),
     }
 }
 
@@ -93,6 +92,18 @@ fn value_list(function: &Function, values: &[ValueId]) -> String {
     vecmap(values, |id| value(function, *id)).join(", ")
 }
 
+/// Display the types of an instruction's results, e.g. ` -> Field` or ` -> (Field, u1)`.
+/// These are needed for instructions whose result types cannot be inferred from their
+/// arguments so that the printed SSA can be parsed back in.
+fn result_types(function: &Function, results: &[ValueId]) -> String {
+    let types = vecmap(results, |result| function.dfg.type_of_value(*result).to_string());
+    match types.len() {
+        0 => String::new(),
+        1 => format!(" -> {}", types[0]),
+        _ => format!(" -> ({})", types.join(", ")),
+    }
+}
+
 /// Display a terminator instruction
 pub(crate) fn display_terminator(
     function: &Function,
@@ -150,10 +161,13 @@ pub(crate) fn display_instruction(
             None => writeln!(f, "constrain {} == {}", show(*lhs), show(*rhs)),
         },
         Instruction::Call { func, arguments } => {
-            writeln!(f, "call {}({})", show(*func), value_list(function, arguments))
+            let arguments = value_list(function, arguments);
+            writeln!(f, "call {}({arguments}){}", show(*func), result_types(function, results))
+        }
+        Instruction::Allocate => writeln!(f, "allocate{}", result_types(function, results)),
+        Instruction::Load { address } => {
+            writeln!(f, "load {}{}", show(*address), result_types(function, results))
         }
-        Instruction::Allocate => writeln!(f, "allocate"),
-        Instruction::Load { address } => writeln!(f, "load {}", show(*address)),
         Instruction::Store { address, value } => {
             writeln!(f, "store {} at {}", show(*value), show(*address))
         }
@@ -161,7 +175,8 @@ pub(crate) fn display_instruction(
             writeln!(f, "enable_side_effects {}", show(*condition))
         }
         Instruction::ArrayGet { array, index } => {
-            writeln!(f, "array_get {}, index {}", show(*array), show(*index))
+            let types = result_types(function, results);
+            writeln!(f, "array_get {}, index {}{types}", show(*array), show(*index))
         }
         Instruction::ArraySet { array, index, value } => {
             writeln!(
@@ -175,8 +190,14 @@ pub(crate) fn display_instruction(
         Instruction::IncrementRc { value } => {
             writeln!(f, "inc_rc {}", show(*value))
         }
-        Instruction::RangeCheck { value, max_bit_size, .. } => {
-            writeln!(f, "range_check {} to {} bits", show(*value), *max_bit_size,)
+        Instruction::RangeCheck { value, max_bit_size, assert_message } => {
+            let value = show(*value);
+            match assert_message {
+                Some(message) => {
+                    writeln!(f, "range_check {value} to {max_bit_size} bits '{message}'")
+                }
+                None => writeln!(f, "range_check {value} to {max_bit_size} bits"),
+            }
         }
     }
 }
@@ -0,0 +1,168 @@
+//! Condition propagation replaces the condition of a branch with a constant within the blocks
+//! which can only be reached by taking that branch.
+//!
+//! Brillig functions keep their branches, so a condition checked by a loop header or an `if`
+//! is often checked again within the branch. For example, the bounds check of `slice[i]` within
+//! `for i in 0..slice.len()` compares the index against the same length as the loop header.
+//! Once global value numbering has merged both comparisons into the loop condition, this pass
+//! replaces the condition within the loop body with `true` and constant folding then removes
+//! the check entirely.
+//!
+//! ACIR functions are skipped since their branches are flattened, after which each instruction
+//! is guarded by the predicate of its branch instead.
+use acvm::FieldElement;
+
+use crate::ssa::{
+    ir::{
+        basic_block::BasicBlockId,
+        cfg::ControlFlowGraph,
+        dom::DominatorTree,
+        function::{Function, RuntimeType},
+        instruction::TerminatorInstruction,
+        types::Type,
+        value::ValueId,
+    },
+    ssa_gen::Ssa,
+};
+
+impl Ssa {
+    /// Replaces the condition of each branch of a Brillig function with a constant
+    /// within the blocks which can only be reached through that branch.
+    ///
+    /// See [`condition_propagation`][self] module for more information.
+    pub(crate) fn propagate_conditions(mut self) -> Ssa {
+        for function in self.functions.values_mut() {
+            if function.runtime() == RuntimeType::Brillig {
+                propagate_conditions(function);
+            }
+        }
+        self
+    }
+}
+
+fn propagate_conditions(function: &mut Function) {
+    let cfg = ControlFlowGraph::with_function(function);
+    let mut dom_tree = DominatorTree::with_function(function);
+    let blocks = function.reachable_blocks();
+
+    for block in &blocks {
+        let Some(TerminatorInstruction::JmpIf { condition, then_destination, else_destination }) =
+            function.dfg[*block].terminator().cloned()
+        else {
+            continue;
+        };
+        let condition = function.dfg.resolve(condition);
+        if then_destination == else_destination
+            || function.dfg.get_numeric_constant(condition).is_some()
+        {
+            continue;
+        }
+
+        for (destination, value) in [(then_destination, true), (else_destination, false)] {
+            // A destination reached from anywhere else may run without this branch being taken
+            if cfg.predecessors(destination).len() != 1 {
+                continue;
+            }
+
+            let constant = function.dfg.make_constant(FieldElement::from(value), Type::bool());
+            let dominated: Vec<_> = blocks
+                .iter()
+                .copied()
+                .filter(|block| dom_tree.dominates(destination, *block))
+                .collect();
+            for block in dominated {
+                replace_value(function, block, condition, constant);
+            }
+        }
+    }
+}
+
+/// Replaces each use of `old` within the given block with `new`
+fn replace_value(function: &mut Function, block: BasicBlockId, old: ValueId, new: ValueId) {
+    let dfg = &mut function.dfg;
+    for instruction_id in dfg[block].instructions().to_vec() {
+        let instruction =
+            dfg[instruction_id]
+                .map_values(|value| if dfg.resolve(value) == old { new } else { value });
+        dfg[instruction_id] = instruction;
+    }
+
+    let terminator = dfg[block].unwrap_terminator().map_values(|value| {
+        if dfg.resolve(value) == old {
+            new
+        } else {
+            value
+        }
+    });
+    dfg[block].set_terminator(terminator);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ssa::{
+        ir::{instruction::Instruction, map::Id},
+        ssa_gen::Ssa,
+    };
+
+    #[test]
+    fn removes_checks_implied_by_the_loop_condition() {
+        // The constraint within the loop body repeats the loop condition,
+        // so it always holds and is removed once the condition is propagated.
+        // The exit block only runs once the condition is false.
+        let src = "
+            brillig fn main f0 {
+              b0(v0: u32):
+                jmp b1(u32 0)
+              b1(v1: u32):
+                v2 = lt v1, v0
+                jmpif v2 then: b2, else: b3
+              b2():
+                constrain v2 == u1 1 'Index out of bounds'
+                v3 = add v1, u32 1
+                jmp b1(v3)
+              b3():
+                return v2
+            }
+        ";
+        let ssa: Ssa = src.parse().unwrap();
+        let ssa = ssa.propagate_conditions().fold_constants();
+
+        let main = ssa.main();
+        let body = &main.dfg[Id::test_new(2)];
+        assert!(
+            body.instructions()
+                .iter()
+                .all(|instruction| !matches!(main.dfg[*instruction], Instruction::Constrain(..))),
+            "Expected the constraint to be removed"
+        );
+
+        let exit = main.dfg[Id::test_new(3)].unwrap_terminator();
+        let mut return_values = Vec::new();
+        exit.for_each_value(|value| return_values.push(value));
+        assert_eq!(main.dfg.get_numeric_constant(return_values[0]), Some(0u128.into()));
+    }
+
+    #[test]
+    fn keeps_conditions_of_blocks_with_several_predecessors() {
+        // b3 is reached both when v1 is true and when it is false
+        let src = "
+            brillig fn main f0 {
+              b0(v0: u1, v1: u1):
+                jmpif v1 then: b1, else: b2
+              b1():
+                jmpif v0 then: b3, else: b2
+              b2():
+                jmp b3()
+              b3():
+                constrain v1 == u1 1
+                return
+            }
+        ";
+        let ssa: Ssa = src.parse().unwrap();
+        let ssa = ssa.propagate_conditions().fold_constants();
+
+        let main = ssa.main();
+        let exit = &main.dfg[Id::test_new(3)];
+        assert_eq!(exit.instructions().len(), 1);
+    }
+}
@@ -189,6 +189,7 @@ mod test {
             value::{Value, ValueId},
         },
     };
+    use noirc_frontend::monomorphization::ast::InlineType;
 
     #[test]
     fn simple_constant_fold() {
@@ -204,7 +205,8 @@ mod test {
         let main_id = Id::test_new(0);
 
         // Compiling main
-        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
         let v0 = builder.add_parameter(Type::field());
 
         let one = builder.field_constant(1u128);
@@ -259,7 +261,8 @@ mod test {
         let main_id = Id::test_new(0);
 
         // Compiling main
-        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
         let v0 = builder.add_parameter(Type::field());
         let one = builder.field_constant(1u128);
         let v1 = builder.insert_binary(v0, BinaryOp::Add, one);
@@ -306,7 +309,8 @@ mod test {
         let main_id = Id::test_new(0);
 
         // Compiling main
-        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
         let v0 = builder.add_parameter(Type::field());
 
         let v1 = builder.insert_cast(v0, Type::unsigned(32));
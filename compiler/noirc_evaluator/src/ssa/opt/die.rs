@@ -170,6 +170,7 @@ mod test {
             types::Type,
         },
     };
+    use noirc_frontend::monomorphization::ast::InlineType;
 
     #[test]
     fn dead_instruction_elimination() {
@@ -194,7 +195,8 @@ mod test {
         let main_id = Id::test_new(0);
 
         // Compiling main
-        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
         let v0 = builder.add_parameter(Type::field());
         let b1 = builder.insert_block();
 
@@ -66,7 +66,7 @@ impl Ssa {
             // process Brillig functions.
             // The pass is also currently only setup to handle a function with a single flattened block.
             // For complex Brillig functions we can expect this pass to panic.
-            if function.runtime() == RuntimeType::Acir {
+            if matches!(function.runtime(), RuntimeType::Acir(_)) {
                 let databus = function.dfg.data_bus.clone();
                 let mut context = Context::new(function);
                 context.process_blocks();
@@ -611,6 +611,7 @@ mod tests {
             value::ValueId,
         },
     };
+    use noirc_frontend::monomorphization::ast::InlineType;
 
     #[test]
     fn test_simple_nested_slice() {
@@ -632,7 +633,8 @@ mod tests {
         // }
 
         let main_id = Id::test_new(0);
-        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
 
         let main_v0 = builder.add_parameter(Type::field());
 
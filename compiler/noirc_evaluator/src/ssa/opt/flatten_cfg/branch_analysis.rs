@@ -117,6 +117,7 @@ mod test {
         ir::{cfg::ControlFlowGraph, function::RuntimeType, map::Id, types::Type},
         opt::flatten_cfg::branch_analysis::find_branch_ends,
     };
+    use noirc_frontend::monomorphization::ast::InlineType;
 
     #[test]
     fn nested_branch_analysis() {
@@ -134,7 +135,8 @@ mod test {
         //      ↘   ↙
         //       b9
         let main_id = Id::test_new(0);
-        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
 
         let b1 = builder.insert_block();
         let b2 = builder.insert_block();
@@ -195,7 +197,8 @@ mod test {
         //        ↘    ↙
         //          b15
         let main_id = Id::test_new(0);
-        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
 
         let b1 = builder.insert_block();
         let b2 = builder.insert_block();
@@ -0,0 +1,236 @@
+//! Global value numbering removes [pure][Instruction::is_pure()] instructions which duplicate
+//! an instruction in a dominating block.
+//!
+//! The [constant folding][super::constant_folding] pass only reuses the results of duplicate
+//! instructions found earlier in the same block. This is enough for ACIR functions once their CFG
+//! has been flattened into a single block, but before flattening and in Brillig functions, which
+//! are never flattened, the same values may be recomputed in several blocks.
+//!
+//! The pass works as follows:
+//! - Walk the dominator tree of each function in pre-order, so that each block is visited after
+//!   every block which dominates it.
+//! - Keep a scoped table of the pure instructions in the current block and in the blocks which
+//!   dominate it, along with their results.
+//! - If an instruction with fully resolved inputs is already in the table, remove it and replace
+//!   its results with those of the earlier instruction. This is sound since the earlier
+//!   instruction is executed on every path which reaches the later one.
+use crate::ssa::{
+    ir::{
+        basic_block::BasicBlockId, dom::DominatorTree, function::Function,
+        instruction::Instruction, value::ValueId,
+    },
+    ssa_gen::Ssa,
+};
+use fxhash::FxHashMap as HashMap;
+
+impl Ssa {
+    /// Removes pure instructions which are duplicated in a dominating block.
+    ///
+    /// See [`gvn`][self] module for more information.
+    pub(crate) fn global_value_numbering(mut self) -> Ssa {
+        for function in self.functions.values_mut() {
+            global_value_numbering(function);
+        }
+        self
+    }
+}
+
+fn global_value_numbering(function: &mut Function) {
+    let dom_tree = DominatorTree::with_function(function);
+
+    // The dominator tree only stores each block's immediate dominator, so find each block's
+    // children in the tree. `reachable_blocks` is sorted so the order of the walk is deterministic.
+    let mut children: HashMap<BasicBlockId, Vec<BasicBlockId>> = HashMap::default();
+    for block in function.reachable_blocks() {
+        if let Some(dominator) = dom_tree.immediate_dominator(block) {
+            children.entry(dominator).or_default().push(block);
+        }
+    }
+
+    let mut context = Context::default();
+    let mut stack = vec![Visit::Enter(function.entry_block())];
+
+    while let Some(visit) = stack.pop() {
+        match visit {
+            Visit::Enter(block) => {
+                let scope_start = context.scoped_instructions.len();
+                context.number_block(function, block);
+
+                // Forget the instructions in this block once all of the blocks it dominates
+                // have been visited.
+                stack.push(Visit::Exit(scope_start));
+                if let Some(children) = children.get(&block) {
+                    stack.extend(children.iter().rev().map(|child| Visit::Enter(*child)));
+                }
+            }
+            Visit::Exit(scope_start) => context.exit_scope(scope_start),
+        }
+    }
+}
+
+enum Visit {
+    Enter(BasicBlockId),
+    /// Leave the scope of a block, given the length of `scoped_instructions` before entering it
+    Exit(usize),
+}
+
+#[derive(Default)]
+struct Context {
+    /// Pure instructions in the current block or a block dominating it, along with their results
+    available_instructions: HashMap<Instruction, Vec<ValueId>>,
+
+    /// The instructions in `available_instructions` in the order they were added,
+    /// used to remove them when leaving the scope of their block.
+    scoped_instructions: Vec<Instruction>,
+}
+
+impl Context {
+    fn number_block(&mut self, function: &mut Function, block: BasicBlockId) {
+        let instructions = function.dfg[block].take_instructions();
+        let mut remaining_instructions = Vec::with_capacity(instructions.len());
+
+        for instruction_id in instructions {
+            let dfg = &mut function.dfg;
+
+            // Resolve any inputs to ensure that we're comparing like-for-like instructions.
+            let instruction = dfg[instruction_id].map_values(|value| dfg.resolve(value));
+            let results = dfg.instruction_results(instruction_id).to_vec();
+
+            if !instruction.is_pure(dfg) {
+                remaining_instructions.push(instruction_id);
+                continue;
+            }
+
+            if let Some(existing_results) = self.available_instructions.get(&instruction) {
+                for (result, existing_result) in results.iter().zip(existing_results) {
+                    dfg.set_value_from_id(*result, *existing_result);
+                }
+            } else {
+                self.available_instructions.insert(instruction.clone(), results);
+                self.scoped_instructions.push(instruction);
+                remaining_instructions.push(instruction_id);
+            }
+        }
+
+        *function.dfg[block].instructions_mut() = remaining_instructions;
+    }
+
+    fn exit_scope(&mut self, scope_start: usize) {
+        for instruction in self.scoped_instructions.drain(scope_start..) {
+            self.available_instructions.remove(&instruction);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ssa::{
+        function_builder::FunctionBuilder,
+        ir::{
+            function::RuntimeType,
+            instruction::{BinaryOp, Instruction, TerminatorInstruction},
+            map::Id,
+            types::Type,
+        },
+    };
+
+    #[test]
+    fn removes_instruction_duplicated_in_dominated_block() {
+        // brillig fn main f0 {
+        //   b0(v0: u32, v1: u1):
+        //     v2 = cast v0 as Field
+        //     jmpif v1 then: b1, else: b2
+        //   b1():
+        //     v3 = cast v0 as Field
+        //     v4 = mul v3, v3
+        //     return v4
+        //   b2():
+        //     return v2
+        // }
+        //
+        // v3 is a duplicate of v2 which is defined in b0, which dominates b1.
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Brillig);
+        let v0 = builder.add_parameter(Type::unsigned(32));
+        let v1 = builder.add_parameter(Type::bool());
+
+        let b1 = builder.insert_block();
+        let b2 = builder.insert_block();
+
+        let v2 = builder.insert_cast(v0, Type::field());
+        builder.terminate_with_jmpif(v1, b1, b2);
+
+        builder.switch_to_block(b1);
+        let v3 = builder.insert_cast(v0, Type::field());
+        let v4 = builder.insert_binary(v3, BinaryOp::Mul, v3);
+        builder.terminate_with_return(vec![v4]);
+
+        builder.switch_to_block(b2);
+        builder.terminate_with_return(vec![v2]);
+
+        let ssa = builder.finish().global_value_numbering();
+        let main = ssa.main();
+        assert_eq!(main.dfg[main.entry_block()].instructions().len(), 1);
+
+        // The cast in b1 is removed and the multiplication now uses v2
+        let instructions = main.dfg[b1].instructions();
+        assert_eq!(instructions.len(), 1);
+        let multiplication = main.dfg[instructions[0]].map_values(|value| main.dfg.resolve(value));
+        let expected = Instruction::binary(BinaryOp::Mul, v2, v2);
+        assert_eq!(multiplication, expected);
+    }
+
+    #[test]
+    fn keeps_instructions_in_sibling_blocks() {
+        // brillig fn main f0 {
+        //   b0(v0: u32, v1: u1):
+        //     jmpif v1 then: b1, else: b2
+        //   b1():
+        //     v2 = cast v0 as Field
+        //     jmp b3(v2)
+        //   b2():
+        //     v3 = cast v0 as Field
+        //     jmp b3(v3)
+        //   b3(v4: Field):
+        //     v5 = cast v0 as Field
+        //     return v4, v5
+        // }
+        //
+        // Neither b1 nor b2 dominates the other, or b3, so no cast can be removed.
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Brillig);
+        let v0 = builder.add_parameter(Type::unsigned(32));
+        let v1 = builder.add_parameter(Type::bool());
+
+        let b1 = builder.insert_block();
+        let b2 = builder.insert_block();
+        let b3 = builder.insert_block();
+
+        builder.terminate_with_jmpif(v1, b1, b2);
+
+        builder.switch_to_block(b1);
+        let v2 = builder.insert_cast(v0, Type::field());
+        builder.terminate_with_jmp(b3, vec![v2]);
+
+        builder.switch_to_block(b2);
+        let v3 = builder.insert_cast(v0, Type::field());
+        builder.terminate_with_jmp(b3, vec![v3]);
+
+        builder.switch_to_block(b3);
+        let v4 = builder.add_block_parameter(b3, Type::field());
+        let v5 = builder.insert_cast(v0, Type::field());
+        builder.terminate_with_return(vec![v4, v5]);
+
+        let ssa = builder.finish().global_value_numbering();
+        let main = ssa.main();
+        for block in [b1, b2, b3] {
+            assert_eq!(main.dfg[block].instructions().len(), 1);
+        }
+
+        let Some(TerminatorInstruction::Return { return_values, .. }) = main.dfg[b3].terminator()
+        else {
+            unreachable!("b3 should have a return terminator");
+        };
+        assert_eq!(main.dfg.resolve(return_values[1]), v5);
+    }
+}
@@ -1,10 +1,15 @@
 //! This module defines the function inlining pass for the SSA IR.
 //! The purpose of this pass is to inline the instructions of each function call
 //! within the function caller. If all function calls are known, there will only
-//! be a single function remaining when the pass finishes.
-use std::collections::{BTreeSet, HashSet};
+//! be a single function remaining when the pass finishes, along with any Brillig
+//! functions and ACIR functions marked to be kept as separate circuits.
+//!
+//! Constrained functions called from Brillig functions are inlined into them as well, unless
+//! they are larger than a size limit. Such callees are instead compiled into separate Brillig
+//! functions so that their bytecode is only generated once rather than at every call site.
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
-use iter_extended::{btree_map, vecmap};
+use iter_extended::vecmap;
 
 use crate::ssa::{
     function_builder::FunctionBuilder,
@@ -18,11 +23,16 @@ use crate::ssa::{
     ssa_gen::Ssa,
 };
 use fxhash::FxHashMap as HashMap;
+use noirc_frontend::monomorphization::ast::InlineType;
 
 /// An arbitrary limit to the maximum number of recursive call
 /// frames at any point in time.
 const RECURSION_LIMIT: u32 = 1000;
 
+/// The maximum size, as estimated by [`BrilligCallees::inlined_size`], of a constrained function
+/// which is inlined into a Brillig function. Larger functions are called as Brillig functions instead.
+const BRILLIG_INLINE_SIZE_LIMIT: usize = 100;
+
 impl Ssa {
     /// Inline all functions within the IR.
     ///
@@ -36,12 +46,32 @@ impl Ssa {
     /// changes. This is because if the function's id later becomes known by a later
     /// pass, we would need to re-run all of inlining anyway to inline it, so we might
     /// as well save the work for later instead of performing it twice.
-    pub(crate) fn inline_functions(mut self) -> Ssa {
-        self.functions = btree_map(get_entry_point_functions(&self), |entry_point| {
-            let new_function = InlineContext::new(&self, entry_point).inline_all(&self);
-            (entry_point, new_function)
-        });
+    pub(crate) fn inline_functions(self) -> Ssa {
+        self.inline_functions_with_brillig_size_limit(BRILLIG_INLINE_SIZE_LIMIT)
+    }
+
+    /// The same as `inline_functions`, but with the given limit on the size of the constrained
+    /// functions which are inlined into Brillig functions.
+    pub(crate) fn inline_functions_with_brillig_size_limit(mut self, size_limit: usize) -> Ssa {
+        let mut brillig_callees = BrilligCallees::new(size_limit);
+        let mut functions = BTreeMap::new();
+
+        for entry_point in get_entry_point_functions(&self) {
+            let context = InlineContext::new(&self, entry_point, brillig_callees);
+            let (new_function, callees) = context.inline_all(&self);
+            functions.insert(entry_point, new_function);
+            brillig_callees = callees;
+        }
+
+        // Building a Brillig copy may find further callees which are too large to inline into it
+        while let Some((source, copy)) = brillig_callees.queue.pop() {
+            let context = InlineContext::new_brillig_copy(&self, source, copy, brillig_callees);
+            let (new_function, callees) = context.inline_all(&self);
+            functions.insert(copy, new_function);
+            brillig_callees = callees;
+        }
 
+        self.functions = functions;
         self
     }
 }
@@ -59,6 +89,23 @@ struct InlineContext {
 
     // The FunctionId of the entry point function we're inlining into in the old, unmodified Ssa.
     entry_point: FunctionId,
+
+    brillig_callees: BrilligCallees,
+}
+
+/// Tracks the constrained functions which are too large to inline into Brillig functions.
+/// Each of these is compiled into a Brillig function of its own which is called instead.
+struct BrilligCallees {
+    size_limit: usize,
+
+    /// Maps each function to the id of its Brillig copy
+    copies: HashMap<FunctionId, FunctionId>,
+
+    /// Brillig copies which are still to be built, along with the function each is copied from
+    queue: Vec<(FunctionId, FunctionId)>,
+
+    /// The result of `inlined_size` for each function visited so far
+    sizes: HashMap<FunctionId, usize>,
 }
 
 /// The per-function inlining context contains information that is only valid for one function.
@@ -94,11 +141,13 @@ struct PerFunctionContext<'function> {
 
 /// The entry point functions are each function we should inline into - and each function that
 /// should be left in the final program. This is usually just `main` but also includes any
-/// brillig functions used.
+/// brillig functions and folded ACIR functions used.
 fn get_entry_point_functions(ssa: &Ssa) -> BTreeSet<FunctionId> {
     let functions = ssa.functions.iter();
     let mut entry_points = functions
-        .filter(|(_, function)| function.runtime() == RuntimeType::Brillig)
+        .filter(|(_, function)| {
+            function.runtime() == RuntimeType::Brillig || function.runtime().is_folded()
+        })
         .map(|(id, _)| *id)
         .collect::<BTreeSet<_>>();
 
@@ -112,14 +161,39 @@ impl InlineContext {
     /// The function being inlined into will always be the main function, although it is
     /// actually a copy that is created in case the original main is still needed from a function
     /// that could not be inlined calling it.
-    fn new(ssa: &Ssa, entry_point: FunctionId) -> InlineContext {
+    fn new(ssa: &Ssa, entry_point: FunctionId, brillig_callees: BrilligCallees) -> InlineContext {
         let source = &ssa.functions[&entry_point];
         let builder = FunctionBuilder::new(source.name().to_owned(), entry_point, source.runtime());
-        Self { builder, recursion_level: 0, entry_point, call_stack: CallStack::new() }
+        Self {
+            builder,
+            recursion_level: 0,
+            entry_point,
+            call_stack: CallStack::new(),
+            brillig_callees,
+        }
+    }
+
+    /// Create a new context for building the Brillig function `copy` from the function `source`.
+    fn new_brillig_copy(
+        ssa: &Ssa,
+        source: FunctionId,
+        copy: FunctionId,
+        brillig_callees: BrilligCallees,
+    ) -> InlineContext {
+        let name = ssa.functions[&source].name().to_owned();
+        let builder = FunctionBuilder::new(name, copy, RuntimeType::Brillig);
+        Self {
+            builder,
+            recursion_level: 0,
+            entry_point: source,
+            call_stack: CallStack::new(),
+            brillig_callees,
+        }
     }
 
     /// Start inlining the entry point function and all functions reachable from it.
-    fn inline_all(mut self, ssa: &Ssa) -> Function {
+    /// The tracked Brillig callees are returned along with the new function.
+    fn inline_all(mut self, ssa: &Ssa) -> (Function, BrilligCallees) {
         let entry_point = &ssa.functions[&self.entry_point];
 
         let mut context = PerFunctionContext::new(&mut self, entry_point);
@@ -147,7 +221,7 @@ impl InlineContext {
         assert_eq!(new_ssa.functions.len(), 1);
         let mut new_func = new_ssa.functions.pop_first().unwrap().1;
         new_func.dfg.data_bus = databus;
-        new_func
+        (new_func, self.brillig_callees)
     }
 
     /// Inlines a function into the current function and returns the translated return values
@@ -348,10 +422,15 @@ impl<'function> PerFunctionContext<'function> {
         for id in block.instructions() {
             match &self.source_function.dfg[*id] {
                 Instruction::Call { func, arguments } => match self.get_function(*func) {
-                    Some(function) => match ssa.functions[&function].runtime() {
-                        RuntimeType::Acir => self.inline_function(ssa, *id, function, arguments),
-                        RuntimeType::Brillig => self.push_instruction(*id),
-                    },
+                    Some(function) => {
+                        if self.should_inline_call(ssa, function) {
+                            self.inline_function(ssa, *id, function, arguments);
+                        } else if self.should_call_brillig_copy(ssa, function) {
+                            self.call_brillig_copy(ssa, *id, function);
+                        } else {
+                            self.push_instruction(*id);
+                        }
+                    }
                     None => self.push_instruction(*id),
                 },
                 _ => self.push_instruction(*id),
@@ -359,6 +438,42 @@ impl<'function> PerFunctionContext<'function> {
         }
     }
 
+    /// Returns true if a call to the given function should be inlined into the function being built.
+    fn should_inline_call(&mut self, ssa: &Ssa, callee: FunctionId) -> bool {
+        let in_brillig = self.context.builder.current_function.runtime() == RuntimeType::Brillig;
+        match ssa.functions[&callee].runtime() {
+            // Brillig functions cannot call into a separate ACIR circuit so folded functions
+            // are inlined into them like any other constrained function, unless too large.
+            RuntimeType::Acir(_) if in_brillig => {
+                !self.context.brillig_callees.is_too_large(ssa, callee)
+            }
+            RuntimeType::Acir(InlineType::Inline) => true,
+            RuntimeType::Acir(InlineType::Fold) => false,
+            RuntimeType::Brillig => false,
+        }
+    }
+
+    /// Returns true if a call to the given function which is not inlined must instead call
+    /// a Brillig copy of it, as Brillig functions can only call other Brillig functions.
+    fn should_call_brillig_copy(&self, ssa: &Ssa, callee: FunctionId) -> bool {
+        self.context.builder.current_function.runtime() == RuntimeType::Brillig
+            && ssa.functions[&callee].runtime() != RuntimeType::Brillig
+    }
+
+    /// Push the given call instruction from the source_function, calling the Brillig copy
+    /// of `callee` in place of `callee` itself.
+    fn call_brillig_copy(&mut self, ssa: &Ssa, id: InstructionId, callee: FunctionId) {
+        let copy = self.context.brillig_callees.get_or_insert_copy(ssa, callee);
+        let func = self.context.builder.import_function(copy);
+        let arguments = match &self.source_function.dfg[id] {
+            Instruction::Call { arguments, .. } => {
+                vecmap(arguments, |arg| self.translate_value(*arg))
+            }
+            _ => unreachable!("Expected a call instruction"),
+        };
+        self.push_translated_instruction(id, Instruction::Call { func, arguments });
+    }
+
     /// Inline a function call and remember the inlined return values in the values map
     fn inline_function(
         &mut self,
@@ -392,7 +507,12 @@ impl<'function> PerFunctionContext<'function> {
     /// function being inlined into.
     fn push_instruction(&mut self, id: InstructionId) {
         let instruction = self.source_function.dfg[id].map_values(|id| self.translate_value(id));
+        self.push_translated_instruction(id, instruction);
+    }
 
+    /// Push `instruction`, whose values have already been translated, in place of the
+    /// instruction `id` from the source_function.
+    fn push_translated_instruction(&mut self, id: InstructionId, instruction: Instruction) {
         let mut call_stack = self.context.call_stack.clone();
         call_stack.append(self.source_function.dfg.get_call_stack(id));
 
@@ -505,6 +625,71 @@ impl<'function> PerFunctionContext<'function> {
     }
 }
 
+impl BrilligCallees {
+    fn new(size_limit: usize) -> Self {
+        Self {
+            size_limit,
+            copies: HashMap::default(),
+            queue: Vec::new(),
+            sizes: HashMap::default(),
+        }
+    }
+
+    fn is_too_large(&mut self, ssa: &Ssa, function: FunctionId) -> bool {
+        self.inlined_size(ssa, function) > self.size_limit
+    }
+
+    /// Returns the id of the Brillig copy of `function`, queueing the copy to be built
+    /// if it has not been created yet.
+    fn get_or_insert_copy(&mut self, ssa: &Ssa, function: FunctionId) -> FunctionId {
+        if let Some(copy) = self.copies.get(&function) {
+            return *copy;
+        }
+        let copy = ssa.next_id.next();
+        self.copies.insert(function, copy);
+        self.queue.push((function, copy));
+        copy
+    }
+
+    /// Estimates the number of instructions inlining `function` adds to a Brillig function,
+    /// including those of the constrained functions it inlines in turn. Recursive functions
+    /// can never be fully inlined so their size, and that of their callers, is `usize::MAX`.
+    fn inlined_size(&mut self, ssa: &Ssa, function: FunctionId) -> usize {
+        if let Some(size) = self.sizes.get(&function) {
+            return *size;
+        }
+        // Reaching this function again before its size is known means it is recursive
+        self.sizes.insert(function, usize::MAX);
+
+        let source = &ssa.functions[&function];
+        let mut size: usize = 0;
+        for block in source.reachable_blocks() {
+            for instruction in source.dfg[block].instructions() {
+                let instruction_size = match &source.dfg[*instruction] {
+                    Instruction::Call { func, .. } => match source.dfg[*func] {
+                        Value::Function(callee)
+                            if ssa.functions[&callee].runtime() != RuntimeType::Brillig =>
+                        {
+                            match self.inlined_size(ssa, callee) {
+                                usize::MAX => usize::MAX,
+                                // Callees which are too large are called rather than inlined
+                                callee_size if callee_size > self.size_limit => 1,
+                                callee_size => callee_size,
+                            }
+                        }
+                        _ => 1,
+                    },
+                    _ => 1,
+                };
+                size = size.saturating_add(instruction_size);
+            }
+        }
+
+        self.sizes.insert(function, size);
+        size
+    }
+}
+
 #[cfg(test)]
 mod test {
     use acvm::FieldElement;
@@ -514,11 +699,14 @@ mod test {
         ir::{
             basic_block::BasicBlockId,
             function::RuntimeType,
-            instruction::{BinaryOp, Intrinsic, TerminatorInstruction},
+            instruction::{BinaryOp, Instruction, Intrinsic, TerminatorInstruction},
             map::Id,
             types::Type,
+            value::Value,
         },
     };
+    use iter_extended::vecmap;
+    use noirc_frontend::monomorphization::ast::InlineType;
 
     #[test]
     fn basic_inlining() {
@@ -532,14 +720,15 @@ mod test {
         //     return 72
         // }
         let foo_id = Id::test_new(0);
-        let mut builder = FunctionBuilder::new("foo".into(), foo_id, RuntimeType::Acir);
+        let mut builder =
+            FunctionBuilder::new("foo".into(), foo_id, RuntimeType::Acir(InlineType::default()));
 
         let bar_id = Id::test_new(1);
         let bar = builder.import_function(bar_id);
         let results = builder.insert_call(bar, Vec::new(), vec![Type::field()]).to_vec();
         builder.terminate_with_return(results);
 
-        builder.new_function("bar".into(), bar_id);
+        builder.new_function("bar".into(), bar_id, InlineType::default());
         let expected_return = 72u128;
         let seventy_two = builder.field_constant(expected_return);
         builder.terminate_with_return(vec![seventy_two]);
@@ -581,7 +770,8 @@ mod test {
         let id2_id = Id::test_new(3);
 
         // Compiling main
-        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
         let main_v0 = builder.add_parameter(Type::field());
 
         let main_f1 = builder.import_function(square_id);
@@ -594,18 +784,18 @@ mod test {
         builder.terminate_with_return(vec![main_v16]);
 
         // Compiling square f1
-        builder.new_function("square".into(), square_id);
+        builder.new_function("square".into(), square_id, InlineType::default());
         let square_v0 = builder.add_parameter(Type::field());
         let square_v2 = builder.insert_binary(square_v0, BinaryOp::Mul, square_v0);
         builder.terminate_with_return(vec![square_v2]);
 
         // Compiling id1 f2
-        builder.new_function("id1".into(), id1_id);
+        builder.new_function("id1".into(), id1_id, InlineType::default());
         let id1_v0 = builder.add_parameter(Type::Function);
         builder.terminate_with_return(vec![id1_v0]);
 
         // Compiling id2 f3
-        builder.new_function("id2".into(), id2_id);
+        builder.new_function("id2".into(), id2_id, InlineType::default());
         let id2_v0 = builder.add_parameter(Type::Function);
         builder.terminate_with_return(vec![id2_v0]);
 
@@ -637,7 +827,8 @@ mod test {
         //     return v4
         // }
         let main_id = Id::test_new(0);
-        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
 
         let factorial_id = Id::test_new(1);
         let factorial = builder.import_function(factorial_id);
@@ -646,7 +837,7 @@ mod test {
         let results = builder.insert_call(factorial, vec![five], vec![Type::field()]).to_vec();
         builder.terminate_with_return(results);
 
-        builder.new_function("factorial".into(), factorial_id);
+        builder.new_function("factorial".into(), factorial_id, InlineType::default());
         let b1 = builder.insert_block();
         let b2 = builder.insert_block();
 
@@ -737,7 +928,8 @@ mod test {
         //     jmp b3(Field 2)
         // }
         let main_id = Id::test_new(0);
-        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
 
         let main_cond = builder.add_parameter(Type::bool());
         let inner1_id = Id::test_new(1);
@@ -747,14 +939,14 @@ mod test {
         builder.insert_call(assert_constant, vec![main_v2], vec![]);
         builder.terminate_with_return(vec![]);
 
-        builder.new_function("inner1".into(), inner1_id);
+        builder.new_function("inner1".into(), inner1_id, InlineType::default());
         let inner1_cond = builder.add_parameter(Type::bool());
         let inner2_id = Id::test_new(2);
         let inner2 = builder.import_function(inner2_id);
         let inner1_v2 = builder.insert_call(inner2, vec![inner1_cond], vec![Type::field()])[0];
         builder.terminate_with_return(vec![inner1_v2]);
 
-        builder.new_function("inner2".into(), inner2_id);
+        builder.new_function("inner2".into(), inner2_id, InlineType::default());
         let inner2_cond = builder.add_parameter(Type::bool());
         let then_block = builder.insert_block();
         let else_block = builder.insert_block();
@@ -786,4 +978,166 @@ mod test {
         let main = ssa.main();
         assert_eq!(main.reachable_blocks().len(), 4);
     }
+
+    #[test]
+    fn does_not_inline_folded_functions() {
+        // acir fn main f0 {
+        //   b0(v0: Field):
+        //     v2 = call f1(v0)
+        //     v3 = call f1(v2)
+        //     return v3
+        // }
+        // acir(fold) fn double f1 {
+        //   b0(v0: Field):
+        //     v1 = call f2(v0)
+        //     return v1
+        // }
+        // acir fn add_self f2 {
+        //   b0(v0: Field):
+        //     v1 = add v0, v0
+        //     return v1
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
+        let main_v0 = builder.add_parameter(Type::field());
+        let double_id = Id::test_new(1);
+        let double = builder.import_function(double_id);
+        let main_v2 = builder.insert_call(double, vec![main_v0], vec![Type::field()])[0];
+        let main_v3 = builder.insert_call(double, vec![main_v2], vec![Type::field()])[0];
+        builder.terminate_with_return(vec![main_v3]);
+
+        builder.new_function("double".into(), double_id, InlineType::Fold);
+        let double_v0 = builder.add_parameter(Type::field());
+        let add_self_id = Id::test_new(2);
+        let add_self = builder.import_function(add_self_id);
+        let double_v1 = builder.insert_call(add_self, vec![double_v0], vec![Type::field()])[0];
+        builder.terminate_with_return(vec![double_v1]);
+
+        builder.new_function("add_self".into(), add_self_id, InlineType::default());
+        let add_self_v0 = builder.add_parameter(Type::field());
+        let add_self_v1 = builder.insert_binary(add_self_v0, BinaryOp::Add, add_self_v0);
+        builder.terminate_with_return(vec![add_self_v1]);
+
+        let ssa = builder.finish().inline_functions();
+
+        // `double` is kept, with `add_self` inlined into it, and both calls to it are kept in main
+        assert_eq!(ssa.functions.len(), 2);
+        let main = ssa.main();
+        assert_eq!(main.dfg[main.entry_block()].instructions().len(), 2);
+
+        let double = &ssa.functions[&double_id];
+        assert!(double.runtime().is_folded());
+        let instructions = double.dfg[double.entry_block()].instructions();
+        assert_eq!(instructions.len(), 1);
+        assert!(matches!(double.dfg[instructions[0]], Instruction::Binary(_)));
+    }
+
+    #[test]
+    fn large_callees_of_brillig_functions_are_called() {
+        // brillig fn main f0 {
+        //   b0(v0: Field):
+        //     v2 = call f1(v0)
+        //     v3 = call f1(v2)
+        //     v4 = call f2(v3)
+        //     return v4
+        // }
+        // acir fn large f1 {
+        //   b0(v0: Field):
+        //     v1 = add v0, v0
+        //     v2 = mul v1, v1
+        //     v3 = add v2, v0
+        //     return v3
+        // }
+        // acir fn small f2 {
+        //   b0(v0: Field):
+        //     v1 = add v0, v0
+        //     return v1
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Brillig);
+        let main_v0 = builder.add_parameter(Type::field());
+        let large_id = Id::test_new(1);
+        let large = builder.import_function(large_id);
+        let small_id = Id::test_new(2);
+        let small = builder.import_function(small_id);
+        let main_v2 = builder.insert_call(large, vec![main_v0], vec![Type::field()])[0];
+        let main_v3 = builder.insert_call(large, vec![main_v2], vec![Type::field()])[0];
+        let main_v4 = builder.insert_call(small, vec![main_v3], vec![Type::field()])[0];
+        builder.terminate_with_return(vec![main_v4]);
+
+        builder.new_function("large".into(), large_id, InlineType::default());
+        let large_v0 = builder.add_parameter(Type::field());
+        let large_v1 = builder.insert_binary(large_v0, BinaryOp::Add, large_v0);
+        let large_v2 = builder.insert_binary(large_v1, BinaryOp::Mul, large_v1);
+        let large_v3 = builder.insert_binary(large_v2, BinaryOp::Add, large_v0);
+        builder.terminate_with_return(vec![large_v3]);
+
+        builder.new_function("small".into(), small_id, InlineType::default());
+        let small_v0 = builder.add_parameter(Type::field());
+        let small_v1 = builder.insert_binary(small_v0, BinaryOp::Add, small_v0);
+        builder.terminate_with_return(vec![small_v1]);
+
+        let ssa = builder.finish().inline_functions_with_brillig_size_limit(2);
+
+        // `small` is inlined while both calls to `large` are made to a single Brillig copy of it
+        assert_eq!(ssa.functions.len(), 2);
+        let main = ssa.main();
+        let instructions = main.dfg[main.entry_block()].instructions();
+        assert_eq!(instructions.len(), 3);
+        let callees = vecmap(&instructions[..2], |instruction| match &main.dfg[*instruction] {
+            Instruction::Call { func, .. } => match main.dfg[*func] {
+                Value::Function(callee) => callee,
+                _ => panic!("Expected a call to a known function"),
+            },
+            _ => panic!("Expected a call instruction"),
+        });
+        assert_eq!(callees[0], callees[1]);
+        assert_ne!(callees[0], large_id);
+
+        let copy = &ssa.functions[&callees[0]];
+        assert_eq!(copy.runtime(), RuntimeType::Brillig);
+        assert_eq!(copy.dfg[copy.entry_block()].instructions().len(), 3);
+    }
+
+    #[test]
+    fn recursive_callees_of_brillig_functions_are_called() {
+        // brillig fn main f0 {
+        //   b0(v0: Field):
+        //     v2 = call f1(v0)
+        //     return v2
+        // }
+        // acir fn recursive f1 {
+        //   b0(v0: Field):
+        //     v2 = call f1(v0)
+        //     return v2
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Brillig);
+        let main_v0 = builder.add_parameter(Type::field());
+        let recursive_id = Id::test_new(1);
+        let recursive = builder.import_function(recursive_id);
+        let main_v2 = builder.insert_call(recursive, vec![main_v0], vec![Type::field()])[0];
+        builder.terminate_with_return(vec![main_v2]);
+
+        builder.new_function("recursive".into(), recursive_id, InlineType::default());
+        let recursive_v0 = builder.add_parameter(Type::field());
+        let recursive = builder.import_function(recursive_id);
+        let recursive_v2 =
+            builder.insert_call(recursive, vec![recursive_v0], vec![Type::field()])[0];
+        builder.terminate_with_return(vec![recursive_v2]);
+
+        // Inlining would otherwise recur until the recursion limit is reached
+        let ssa = builder.finish().inline_functions();
+
+        assert_eq!(ssa.functions.len(), 2);
+        let (copy_id, copy) = ssa.functions.last_key_value().unwrap();
+        assert_eq!(copy.runtime(), RuntimeType::Brillig);
+        let instructions = copy.dfg[copy.entry_block()].instructions();
+        assert_eq!(instructions.len(), 1);
+        let Instruction::Call { func, .. } = &copy.dfg[instructions[0]] else {
+            panic!("Expected the copy to call itself");
+        };
+        assert_eq!(copy.dfg[*func], Value::Function(*copy_id));
+    }
 }
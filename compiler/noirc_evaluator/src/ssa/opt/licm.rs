@@ -0,0 +1,340 @@
+//! Loop-invariant code motion hoists instructions whose results are the same on every iteration
+//! of a loop out of the loop, into the loop's pre-header.
+//!
+//! Loops in Brillig functions are never unrolled, so without this pass any invariant
+//! computation within a loop is re-executed by the Brillig VM on each iteration.
+//! ACIR functions are skipped since all of their loops are unrolled.
+//!
+//! The pass works as follows:
+//! - Find each natural loop of the function from the back-edges of its CFG, i.e. the edges
+//!   whose destination dominates their source. Loops sharing a header are merged.
+//! - Visit the loops from innermost to outermost so that instructions hoisted out of an inner
+//!   loop may then be hoisted out of the enclosing loop as well.
+//! - Only loops with a single pre-header, which ends in an unconditional jump to the loop header,
+//!   are optimized. Instructions are moved to the end of this pre-header.
+//! - An instruction is hoisted if it is [pure][Instruction::is_pure()], each of its inputs is
+//!   defined outside of the loop or by an instruction which was already hoisted, and executing
+//!   it in the pre-header cannot introduce a failure which would not otherwise occur. This is
+//!   true of instructions which cannot fail, as well as any instruction in a block which
+//!   dominates every exit of the loop, since such instructions are executed at least once
+//!   whenever the loop is entered.
+use std::collections::HashSet;
+
+use crate::ssa::{
+    ir::{
+        basic_block::BasicBlockId,
+        cfg::ControlFlowGraph,
+        dfg::DataFlowGraph,
+        dom::DominatorTree,
+        function::{Function, RuntimeType},
+        instruction::{Instruction, InstructionId, Intrinsic, TerminatorInstruction},
+        post_order::PostOrder,
+        types::Type,
+        value::{Value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+use fxhash::FxHashMap as HashMap;
+
+use super::unrolling::{find_blocks_in_loop, Loop};
+
+impl Ssa {
+    /// Hoists loop-invariant instructions out of the loops of each Brillig function.
+    ///
+    /// See [`licm`][self] module for more information.
+    pub(crate) fn loop_invariant_code_motion(mut self) -> Ssa {
+        for function in self.functions.values_mut() {
+            if function.runtime() == RuntimeType::Brillig {
+                loop_invariant_code_motion(function);
+            }
+        }
+        self
+    }
+}
+
+fn loop_invariant_code_motion(function: &mut Function) {
+    let cfg = ControlFlowGraph::with_function(function);
+    let post_order = PostOrder::with_function(function);
+    let mut dom_tree = DominatorTree::with_cfg_and_post_order(&cfg, &post_order);
+
+    // Blocks in reverse post-order, so that definitions are visited before their uses
+    // other than those reached through a back-edge.
+    let mut reverse_post_order = post_order.into_vec();
+    reverse_post_order.reverse();
+
+    for loop_ in find_loops(function, &cfg, &mut dom_tree) {
+        let Some(pre_header) = get_pre_header(function, &cfg, &loop_) else {
+            continue;
+        };
+
+        // Blocks with a successor outside of the loop
+        let exiting_blocks: Vec<_> = loop_
+            .blocks
+            .iter()
+            .copied()
+            .filter(|block| {
+                cfg.successors(*block).any(|successor| !loop_.blocks.contains(&successor))
+            })
+            .collect();
+
+        // Instructions within the loop. Hoisted instructions are removed from this set.
+        let mut loop_instructions: HashSet<InstructionId> = loop_
+            .blocks
+            .iter()
+            .flat_map(|block| function.dfg[*block].instructions().iter().copied())
+            .collect();
+
+        for block in reverse_post_order.iter().filter(|block| loop_.blocks.contains(block)) {
+            let runs_whenever_loop_is_entered =
+                exiting_blocks.iter().all(|exit| dom_tree.dominates(*block, *exit));
+
+            let instructions = function.dfg[*block].take_instructions();
+            let mut remaining_instructions = Vec::with_capacity(instructions.len());
+
+            for instruction_id in instructions {
+                let dfg = &function.dfg;
+                let can_hoist = is_loop_invariant(dfg, instruction_id, &loop_, &loop_instructions)
+                    && (runs_whenever_loop_is_entered || cannot_fail(dfg, instruction_id));
+
+                if can_hoist {
+                    loop_instructions.remove(&instruction_id);
+                    function.dfg[pre_header].instructions_mut().push(instruction_id);
+                } else {
+                    remaining_instructions.push(instruction_id);
+                }
+            }
+
+            *function.dfg[*block].instructions_mut() = remaining_instructions;
+        }
+    }
+}
+
+/// Finds each natural loop in the function, ordered from the innermost loops to the outermost.
+fn find_loops(
+    function: &Function,
+    cfg: &ControlFlowGraph,
+    dom_tree: &mut DominatorTree,
+) -> Vec<Loop> {
+    let mut loops: HashMap<BasicBlockId, Loop> = HashMap::default();
+
+    for block in function.reachable_blocks() {
+        for predecessor in cfg.predecessors(block) {
+            if dom_tree.is_reachable(predecessor) && dom_tree.dominates(block, predecessor) {
+                // predecessor -> block is the back-edge of a loop
+                let new_loop = find_blocks_in_loop(block, predecessor, cfg);
+                match loops.get_mut(&block) {
+                    Some(existing) => existing.blocks.extend(new_loop.blocks),
+                    None => {
+                        loops.insert(block, new_loop);
+                    }
+                }
+            }
+        }
+    }
+
+    // An inner loop always contains fewer blocks than the loops enclosing it.
+    // Ties are broken by header to keep the order deterministic.
+    let mut loops: Vec<_> = loops.into_values().collect();
+    loops.sort_by_key(|loop_| (loop_.blocks.len(), loop_.header));
+    loops
+}
+
+/// Returns the single block outside of the loop which jumps to its header, provided that this
+/// block ends in an unconditional jump. Instructions appended to this block execute exactly
+/// once each time the loop is entered.
+fn get_pre_header(
+    function: &Function,
+    cfg: &ControlFlowGraph,
+    loop_: &Loop,
+) -> Option<BasicBlockId> {
+    let mut entries = cfg.predecessors(loop_.header).filter(|block| !loop_.blocks.contains(block));
+    let pre_header = entries.next()?;
+    if entries.next().is_some() {
+        return None;
+    }
+
+    match function.dfg[pre_header].terminator() {
+        Some(TerminatorInstruction::Jmp { .. }) => Some(pre_header),
+        _ => None,
+    }
+}
+
+/// True if the instruction is pure and each of its inputs is defined outside of the loop
+fn is_loop_invariant(
+    dfg: &DataFlowGraph,
+    instruction_id: InstructionId,
+    loop_: &Loop,
+    loop_instructions: &HashSet<InstructionId>,
+) -> bool {
+    let instruction = &dfg[instruction_id];
+    if !instruction.is_pure(dfg) {
+        return false;
+    }
+
+    let mut invariant = true;
+    instruction.for_each_value(|value| {
+        invariant &= !is_defined_in_loop(dfg, value, loop_, loop_instructions);
+    });
+    invariant
+}
+
+/// True if `value` is a parameter of a block in the loop or the result of an instruction
+/// in `loop_instructions`, or if it is an array containing such a value.
+fn is_defined_in_loop(
+    dfg: &DataFlowGraph,
+    value: ValueId,
+    loop_: &Loop,
+    loop_instructions: &HashSet<InstructionId>,
+) -> bool {
+    match &dfg[dfg.resolve(value)] {
+        Value::Param { block, .. } => loop_.blocks.contains(block),
+        Value::Instruction { instruction, .. } => loop_instructions.contains(instruction),
+        Value::Array { array, .. } => {
+            array.iter().any(|element| is_defined_in_loop(dfg, *element, loop_, loop_instructions))
+        }
+        _ => false,
+    }
+}
+
+/// True if executing this instruction can never cause the program to fail
+fn cannot_fail(dfg: &DataFlowGraph, instruction_id: InstructionId) -> bool {
+    let instruction = &dfg[instruction_id];
+    match instruction {
+        // Array accesses fail when out of bounds, which is only known not to happen for a
+        // constant index into an array of known length.
+        Instruction::ArrayGet { array, index } | Instruction::ArraySet { array, index, .. } => {
+            match (dfg.type_of_value(*array), dfg.get_numeric_constant(*index)) {
+                (Type::Array(element_types, length), Some(index)) => {
+                    index.to_u128() < (length * element_types.len()) as u128
+                }
+                _ => false,
+            }
+        }
+        // Slice intrinsics may fail on an empty slice and decompositions fail if the value
+        // does not fit in the requested number of limbs.
+        Instruction::Call { func, .. } => {
+            matches!(
+                dfg[*func],
+                Value::Intrinsic(
+                    Intrinsic::BlackBox(_)
+                        | Intrinsic::Sort
+                        | Intrinsic::ArrayLen
+                        | Intrinsic::StrAsBytes
+                        | Intrinsic::AsField
+                        | Intrinsic::FromField
+                )
+            ) && instruction.is_pure(dfg)
+        }
+        _ => !instruction.has_side_effects(dfg),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use crate::ssa::{
+        function_builder::FunctionBuilder,
+        ir::{
+            function::RuntimeType,
+            instruction::{BinaryOp, Intrinsic},
+            map::Id,
+            types::Type,
+            value::ValueId,
+        },
+        ssa_gen::Ssa,
+    };
+
+    /// Builds the following function, with the given loop body inserted into b2:
+    ///
+    /// brillig fn main f0 {
+    ///   b0(v0: u32, v1: [Field; 2]):
+    ///     jmp b1(u32 0)
+    ///   b1(v2: u32):
+    ///     v3 = lt v2, v0
+    ///     jmpif v3 then: b2, else: b3
+    ///   b2():
+    ///     ... body ...
+    ///     v4 = add v2, u32 1
+    ///     jmp b1(v4)
+    ///   b3():
+    ///     return
+    /// }
+    fn build_loop(body: impl FnOnce(&mut FunctionBuilder, [ValueId; 3])) -> Ssa {
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Brillig);
+        let v0 = builder.add_parameter(Type::unsigned(32));
+        let v1 = builder.add_parameter(Type::Array(Rc::new(vec![Type::field()]), 2));
+
+        let b1 = builder.insert_block();
+        let b2 = builder.insert_block();
+        let b3 = builder.insert_block();
+
+        let zero = builder.numeric_constant(0u128, Type::unsigned(32));
+        builder.terminate_with_jmp(b1, vec![zero]);
+
+        builder.switch_to_block(b1);
+        let v2 = builder.add_block_parameter(b1, Type::unsigned(32));
+        let v3 = builder.insert_binary(v2, BinaryOp::Lt, v0);
+        builder.terminate_with_jmpif(v3, b2, b3);
+
+        builder.switch_to_block(b2);
+        body(&mut builder, [v0, v1, v2]);
+        let one = builder.numeric_constant(1u128, Type::unsigned(32));
+        let v4 = builder.insert_binary(v2, BinaryOp::Add, one);
+        builder.terminate_with_jmp(b1, vec![v4]);
+
+        builder.switch_to_block(b3);
+        builder.terminate_with_return(vec![]);
+        builder.finish()
+    }
+
+    #[test]
+    fn hoists_invariant_instructions() {
+        let ssa = build_loop(|builder, [v0, _, v2]| {
+            // Both the cast and the multiplication of it are invariant
+            let cast = builder.insert_cast(v0, Type::field());
+            let square = builder.insert_binary(cast, BinaryOp::Mul, cast);
+            // This depends on the induction variable so must stay in the loop
+            let induction = builder.insert_cast(v2, Type::field());
+            builder.insert_binary(square, BinaryOp::Add, induction);
+        });
+
+        let ssa = ssa.loop_invariant_code_motion();
+        let main = ssa.main();
+        assert_eq!(main.dfg[main.entry_block()].instructions().len(), 2);
+        assert_eq!(main.dfg[Id::test_new(2)].instructions().len(), 3);
+    }
+
+    #[test]
+    fn only_hoists_array_reads_which_cannot_fail() {
+        let ssa = build_loop(|builder, [v0, v1, _]| {
+            // In bounds so this cannot fail
+            let one = builder.numeric_constant(1u128, Type::unsigned(32));
+            builder.insert_array_get(v1, one, Type::field());
+            // This may be out of bounds and the loop body may never run,
+            // so it cannot be executed before the loop.
+            builder.insert_array_get(v1, v0, Type::field());
+        });
+
+        let ssa = ssa.loop_invariant_code_motion();
+        let main = ssa.main();
+        assert_eq!(main.dfg[main.entry_block()].instructions().len(), 1);
+        assert_eq!(main.dfg[Id::test_new(2)].instructions().len(), 2);
+    }
+
+    #[test]
+    fn does_not_hoist_impure_instructions() {
+        let ssa = build_loop(|builder, [v0, _, _]| {
+            let assert_constant = builder.import_intrinsic_id(Intrinsic::AssertConstant);
+            builder.insert_call(assert_constant, vec![v0], vec![]);
+            let zero = builder.numeric_constant(0u128, Type::unsigned(32));
+            builder.insert_constrain(v0, zero, None);
+        });
+
+        let ssa = ssa.loop_invariant_code_motion();
+        let main = ssa.main();
+        assert_eq!(main.dfg[main.entry_block()].instructions().len(), 0);
+        assert_eq!(main.dfg[Id::test_new(2)].instructions().len(), 3);
+    }
+}
@@ -5,12 +5,166 @@
 //! Generally, these passes are also expected to minimize the final amount of instructions.
 mod array_use;
 mod assert_constant;
+mod condition_propagation;
 mod constant_folding;
 mod defunctionalize;
 mod die;
 mod fill_internal_slices;
 pub(crate) mod flatten_cfg;
+mod gvn;
 mod inlining;
+mod licm;
 mod mem2reg;
 mod simplify_cfg;
+mod strength_reduction;
+mod underconstrained_values;
 mod unrolling;
+
+use crate::errors::RuntimeError;
+
+use super::ssa_gen::Ssa;
+
+/// The function implementing an SSA pass
+#[derive(Clone, Copy)]
+pub(crate) enum SsaPassFn {
+    Infallible(fn(Ssa) -> Ssa),
+    Fallible(fn(Ssa) -> Result<Ssa, RuntimeError>),
+}
+
+/// An SSA pass which can be referred to by name, e.g. to print the SSA after it from the CLI.
+pub(crate) struct SsaPass {
+    /// The name used to refer to this pass
+    pub(crate) name: &'static str,
+    /// The header printed before the SSA after this pass has run
+    pub(crate) msg: &'static str,
+    pub(crate) run: SsaPassFn,
+}
+
+/// Every SSA pass which can be run by name
+pub(crate) const SSA_PASSES: &[SsaPass] = &[
+    SsaPass {
+        name: "defunctionalize",
+        msg: "After Defunctionalization:",
+        run: SsaPassFn::Infallible(Ssa::defunctionalize),
+    },
+    SsaPass {
+        name: "inlining",
+        msg: "After Inlining:",
+        run: SsaPassFn::Infallible(Ssa::inline_functions),
+    },
+    SsaPass { name: "mem2reg", msg: "After Mem2Reg:", run: SsaPassFn::Infallible(Ssa::mem2reg) },
+    SsaPass {
+        name: "assert_constant",
+        msg: "After Assert Constant:",
+        run: SsaPassFn::Fallible(Ssa::evaluate_assert_constant),
+    },
+    SsaPass {
+        name: "unrolling",
+        msg: "After Unrolling:",
+        run: SsaPassFn::Fallible(Ssa::unroll_loops),
+    },
+    SsaPass {
+        name: "simplify_cfg",
+        msg: "After Simplifying:",
+        run: SsaPassFn::Infallible(Ssa::simplify_cfg),
+    },
+    SsaPass {
+        name: "gvn",
+        msg: "After Global Value Numbering:",
+        run: SsaPassFn::Infallible(Ssa::global_value_numbering),
+    },
+    SsaPass {
+        name: "condition_propagation",
+        msg: "After Condition Propagation:",
+        run: SsaPassFn::Infallible(Ssa::propagate_conditions),
+    },
+    SsaPass {
+        name: "flatten_cfg",
+        msg: "After Flattening:",
+        run: SsaPassFn::Infallible(Ssa::flatten_cfg),
+    },
+    SsaPass {
+        name: "constant_folding",
+        msg: "After Constant Folding:",
+        run: SsaPassFn::Infallible(Ssa::fold_constants),
+    },
+    SsaPass {
+        name: "licm",
+        msg: "After Loop Invariant Code Motion:",
+        run: SsaPassFn::Infallible(Ssa::loop_invariant_code_motion),
+    },
+    SsaPass {
+        name: "strength_reduction",
+        msg: "After Strength Reduction:",
+        run: SsaPassFn::Infallible(Ssa::strength_reduction),
+    },
+    SsaPass {
+        name: "die",
+        msg: "After Dead Instruction Elimination:",
+        run: SsaPassFn::Infallible(Ssa::dead_instruction_elimination),
+    },
+    SsaPass {
+        name: "fill_internal_slices",
+        msg: "After Fill Internal Slice Dummy Data:",
+        run: SsaPassFn::Infallible(Ssa::fill_internal_slices),
+    },
+];
+
+/// The passes run on every function, before the Brillig functions are split off from the rest
+/// of the program. These decide which functions remain in the program so they cannot be run on
+/// the ACIR or Brillig functions alone.
+pub(crate) const SHARED_PIPELINE: &[&str] = &["defunctionalize", "inlining"];
+
+/// The passes run on the ACIR functions after the shared passes, in order.
+pub(crate) const DEFAULT_PIPELINE: &[&str] = &[
+    // Run mem2reg with the CFG separated into blocks
+    "mem2reg",
+    "assert_constant",
+    "unrolling",
+    "simplify_cfg",
+    // Remove values recomputed across blocks before they are merged by flattening
+    "gvn",
+    // Run mem2reg before flattening to handle any promotion
+    // of values that can be accessed after loop unrolling.
+    // If there are slice mergers uncovered by loop unrolling
+    // and this pass is missed, slice merging will fail inside of flattening.
+    "mem2reg",
+    "flatten_cfg",
+    // Run mem2reg once more with the flattened CFG to catch any remaining loads/stores
+    "mem2reg",
+    "constant_folding",
+    "die",
+];
+
+/// The passes run on the Brillig functions after the shared passes, in order.
+///
+/// Loops in Brillig functions are not unrolled and their CFG is not flattened, so loops and
+/// branches are instead optimized in place.
+pub(crate) const BRILLIG_PIPELINE: &[&str] = &[
+    "mem2reg",
+    "assert_constant",
+    "simplify_cfg",
+    "gvn",
+    // Branches are kept, so checks repeating a condition which holds within a branch can be removed
+    // once the checks have been merged with the condition by `gvn`.
+    "condition_propagation",
+    // Promote any loads and stores made redundant by the previous passes
+    "mem2reg",
+    "constant_folding",
+    "licm",
+    // Fold any instructions which have become duplicates after being hoisted out of loops
+    "constant_folding",
+    "strength_reduction",
+    "die",
+    // Remove any branches on conditions which have become constant
+    "simplify_cfg",
+];
+
+/// The passes run last on the ACIR functions which are needed for ACIR generation rather than
+/// being optimizations. These are run even when the pipeline is customized.
+pub(crate) const ACIR_ONLY_PIPELINE: &[&str] = &["fill_internal_slices"];
+
+/// Returns the pass with the given name, if any
+pub(crate) fn lookup_pass(name: &str) -> Option<&'static SsaPass> {
+    SSA_PASSES.iter().find(|pass| pass.name == name)
+}
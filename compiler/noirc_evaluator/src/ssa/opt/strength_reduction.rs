@@ -0,0 +1,121 @@
+//! Strength reduction replaces instructions in Brillig functions with cheaper equivalents.
+//!
+//! The Brillig VM has no modulo opcode, so each `mod` instruction is compiled into a division,
+//! a multiplication and a subtraction. The remainder of an unsigned division by a power of two
+//! is computed by a single bitwise `and` with a mask instead.
+//!
+//! ACIR functions are skipped as the cost of their instructions is measured in constraints
+//! rather than opcodes, and is already taken into account by ACIR generation.
+use acvm::FieldElement;
+
+use crate::ssa::{
+    ir::{
+        function::{Function, RuntimeType},
+        instruction::{Binary, BinaryOp, Instruction},
+        types::{NumericType, Type},
+    },
+    ssa_gen::Ssa,
+};
+
+impl Ssa {
+    /// Replaces instructions in Brillig functions with cheaper equivalents.
+    ///
+    /// See [`strength_reduction`][self] module for more information.
+    pub(crate) fn strength_reduction(mut self) -> Ssa {
+        for function in self.functions.values_mut() {
+            if function.runtime() == RuntimeType::Brillig {
+                strength_reduction(function);
+            }
+        }
+        self
+    }
+}
+
+fn strength_reduction(function: &mut Function) {
+    for block in function.reachable_blocks() {
+        for instruction_id in function.dfg[block].instructions().to_vec() {
+            let Instruction::Binary(Binary { lhs, rhs, operator: BinaryOp::Mod }) =
+                function.dfg[instruction_id]
+            else {
+                continue;
+            };
+            let typ = function.dfg.type_of_value(lhs);
+            if !matches!(typ, Type::Numeric(NumericType::Unsigned { .. })) {
+                continue;
+            }
+            let Some(divisor) =
+                function.dfg.get_numeric_constant(rhs).and_then(|divisor| divisor.try_into_u128())
+            else {
+                continue;
+            };
+            if divisor.is_power_of_two() {
+                let mask = function.dfg.make_constant(FieldElement::from(divisor - 1), typ);
+                function.dfg[instruction_id] =
+                    Instruction::Binary(Binary { lhs, rhs: mask, operator: BinaryOp::And });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ssa::{
+        function_builder::FunctionBuilder,
+        ir::{
+            function::RuntimeType,
+            instruction::{Binary, BinaryOp, Instruction},
+            map::Id,
+            types::Type,
+        },
+    };
+
+    #[test]
+    fn unsigned_modulo_by_power_of_two_is_replaced() {
+        // brillig fn main f0 {
+        //   b0(v0: u32, v1: i32):
+        //     v3 = mod v0, u32 8
+        //     v5 = mod v0, u32 6
+        //     v7 = mod v1, i32 8
+        //     return v3, v5, v7
+        // }
+        //
+        // After strength reduction only the unsigned modulo by a power of two is replaced:
+        // brillig fn main f0 {
+        //   b0(v0: u32, v1: i32):
+        //     v3 = and v0, u32 7
+        //     v5 = mod v0, u32 6
+        //     v7 = mod v1, i32 8
+        //     return v3, v5, v7
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Brillig);
+        let v0 = builder.add_parameter(Type::unsigned(32));
+        let v1 = builder.add_parameter(Type::signed(32));
+        let eight = builder.numeric_constant(8u128, Type::unsigned(32));
+        let v3 = builder.insert_binary(v0, BinaryOp::Mod, eight);
+        let six = builder.numeric_constant(6u128, Type::unsigned(32));
+        let v5 = builder.insert_binary(v0, BinaryOp::Mod, six);
+        let signed_eight = builder.numeric_constant(8u128, Type::signed(32));
+        let v7 = builder.insert_binary(v1, BinaryOp::Mod, signed_eight);
+        builder.terminate_with_return(vec![v3, v5, v7]);
+
+        let ssa = builder.finish().strength_reduction();
+        let main = ssa.main();
+        let operators: Vec<_> = main.dfg[main.entry_block()]
+            .instructions()
+            .iter()
+            .map(|instruction| match &main.dfg[*instruction] {
+                Instruction::Binary(Binary { operator, .. }) => *operator,
+                instruction => panic!("Expected a binary instruction, found {instruction:?}"),
+            })
+            .collect();
+        assert_eq!(operators, vec![BinaryOp::And, BinaryOp::Mod, BinaryOp::Mod]);
+
+        let Instruction::Binary(Binary { rhs, .. }) =
+            &main.dfg[main.dfg[main.entry_block()].instructions()[0]]
+        else {
+            unreachable!()
+        };
+        assert_eq!(main.dfg.get_numeric_constant(*rhs), Some(7u128.into()));
+    }
+}
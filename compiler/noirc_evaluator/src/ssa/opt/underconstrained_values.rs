@@ -0,0 +1,381 @@
+//! This module contains an analysis which looks for values returned from unconstrained (Brillig)
+//! calls that are never properly constrained in the ACIR functions which make the calls.
+//!
+//! The results of a Brillig call are chosen freely by the prover, so constrained code must tie
+//! them back to the rest of the circuit through constraints. For each Brillig call result we
+//! check that it:
+//! - Is used by an assertion, a return value or a call to a constrained function, either
+//!   directly or through the values computed from it. Uses which only feed into other
+//!   unconstrained calls do not count.
+//! - Is constrained together with some value which is not itself unconstrained, i.e. the
+//!   function's parameters, its return values or a constant. Asserting that two Brillig results
+//!   are equal to each other does not constrain either of them.
+use std::collections::HashSet;
+
+use fxhash::FxHashMap as HashMap;
+
+use crate::errors::{InternalWarning, SsaReport};
+use crate::ssa::{
+    ir::{
+        dfg::DataFlowGraph,
+        function::{Function, RuntimeType},
+        instruction::{Instruction, InstructionId, TerminatorInstruction},
+        post_order::PostOrder,
+        value::{Value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+
+impl Ssa {
+    /// Reports every Brillig call within an ACIR function whose results are left underconstrained.
+    pub(crate) fn check_for_underconstrained_values(&self) -> Vec<SsaReport> {
+        self.functions
+            .values()
+            .filter(|function| matches!(function.runtime(), RuntimeType::Acir(_)))
+            .flat_map(|function| self.check_function(function))
+            .collect()
+    }
+
+    fn check_function(&self, function: &Function) -> Vec<SsaReport> {
+        let dfg = &function.dfg;
+
+        let mut reverse_post_order = PostOrder::with_function(function).into_vec();
+        reverse_post_order.reverse();
+        let instructions: Vec<InstructionId> = reverse_post_order
+            .iter()
+            .flat_map(|block| dfg[*block].instructions().iter().copied())
+            .collect();
+
+        let is_brillig_call = |instruction: InstructionId| match &dfg[instruction] {
+            Instruction::Call { func, .. } => match &dfg[dfg.resolve(*func)] {
+                Value::Function(id) => self.functions[id].runtime() == RuntimeType::Brillig,
+                _ => false,
+            },
+            _ => false,
+        };
+
+        let mut analysis = DependencyAnalysis::default();
+        for parameter in dfg[function.entry_block()].parameters() {
+            analysis.ground(*parameter);
+        }
+        for block in &reverse_post_order {
+            if let Some(TerminatorInstruction::Return { return_values, .. }) =
+                dfg[*block].terminator()
+            {
+                for return_value in return_values {
+                    analysis.mark_used(dfg, *return_value);
+                    for value in referenced_values(dfg, *return_value) {
+                        analysis.ground(value);
+                    }
+                }
+            }
+        }
+
+        // Walk backwards to find every value which (indirectly) feeds into a constraint.
+        for instruction_id in instructions.iter().rev() {
+            let instruction = &dfg[*instruction_id];
+            let results = dfg.instruction_results(*instruction_id);
+            let is_sink = match instruction {
+                Instruction::Constrain(..) => true,
+                Instruction::Call { .. } => !is_brillig_call(*instruction_id),
+                _ => false,
+            };
+            let results_used = results.iter().any(|result| analysis.is_used(*result));
+            if (is_sink || results_used) && !is_brillig_call(*instruction_id) {
+                instruction.for_each_value(|value| analysis.mark_used(dfg, value));
+            }
+        }
+
+        // Group the used values which are constrained together.
+        for instruction_id in &instructions {
+            let instruction = &dfg[*instruction_id];
+            if is_brillig_call(*instruction_id) {
+                continue;
+            }
+            if let Instruction::Constrain(lhs, rhs, _) = instruction {
+                let lhs_values = referenced_values(dfg, *lhs);
+                let rhs_values = referenced_values(dfg, *rhs);
+                // Asserting equality with a constant fully determines the other side
+                if lhs_values.is_empty() || rhs_values.is_empty() {
+                    for value in lhs_values.iter().chain(&rhs_values) {
+                        analysis.ground(*value);
+                    }
+                }
+            }
+
+            let results = dfg.instruction_results(*instruction_id);
+            let mut values: Vec<ValueId> = results.to_vec();
+            instruction.for_each_value(|value| values.extend(referenced_values(dfg, value)));
+            if values.iter().any(|value| analysis.is_used(*value)) {
+                analysis.union_all(&values);
+            }
+        }
+
+        // Calls generated by the compiler have no location of their own, so their warnings are
+        // reported at the first located instruction of the function instead.
+        let function_call_stack = instructions
+            .iter()
+            .map(|instruction_id| dfg.get_call_stack(*instruction_id))
+            .find(|call_stack| !call_stack.is_empty());
+
+        let mut warnings = Vec::new();
+        for instruction_id in &instructions {
+            let instruction_id = *instruction_id;
+            if !is_brillig_call(instruction_id) {
+                continue;
+            }
+            let results = dfg.instruction_results(instruction_id);
+            let call_stack = dfg.get_call_stack(instruction_id);
+            let call_stack = match (call_stack.is_empty(), &function_call_stack) {
+                (false, _) => call_stack,
+                (true, Some(function_call_stack)) => function_call_stack.clone(),
+                // There is nowhere to report the warning in a function without any locations
+                (true, None) => continue,
+            };
+            if results.iter().any(|result| !analysis.is_used(*result)) {
+                warnings
+                    .push(SsaReport::Warning(InternalWarning::UnconstrainedValue { call_stack }));
+            } else if results.iter().any(|result| !analysis.is_grounded(*result)) {
+                warnings.push(SsaReport::Warning(InternalWarning::UnderconstrainedValue {
+                    call_stack,
+                }));
+            }
+        }
+        warnings
+    }
+}
+
+/// Tracks which values are used by constraints and which groups of values are constrained
+/// together, using a union-find over the values of a function.
+#[derive(Default)]
+struct DependencyAnalysis {
+    used: HashSet<ValueId>,
+    parents: HashMap<ValueId, ValueId>,
+    grounded: HashSet<ValueId>,
+}
+
+impl DependencyAnalysis {
+    fn mark_used(&mut self, dfg: &DataFlowGraph, value: ValueId) {
+        for value in referenced_values(dfg, value) {
+            self.used.insert(value);
+        }
+    }
+
+    fn is_used(&self, value: ValueId) -> bool {
+        self.used.contains(&value)
+    }
+
+    fn find(&self, mut value: ValueId) -> ValueId {
+        while let Some(parent) = self.parents.get(&value) {
+            value = *parent;
+        }
+        value
+    }
+
+    fn union_all(&mut self, values: &[ValueId]) {
+        let Some((first, rest)) = values.split_first() else {
+            return;
+        };
+        let root = self.find(*first);
+        for value in rest {
+            let other = self.find(*value);
+            if other != root {
+                self.parents.insert(other, root);
+                if self.grounded.remove(&other) {
+                    self.grounded.insert(root);
+                }
+            }
+        }
+    }
+
+    /// Marks the group containing `value` as being tied to the rest of the circuit.
+    fn ground(&mut self, value: ValueId) {
+        let root = self.find(value);
+        self.grounded.insert(root);
+    }
+
+    fn is_grounded(&self, value: ValueId) -> bool {
+        self.grounded.contains(&self.find(value))
+    }
+}
+
+/// Returns the non-constant values which `value` refers to, looking through array literals.
+fn referenced_values(dfg: &DataFlowGraph, value: ValueId) -> Vec<ValueId> {
+    let value = dfg.resolve(value);
+    match &dfg[value] {
+        Value::Array { array, .. } => {
+            array.iter().flat_map(|element| referenced_values(dfg, *element)).collect()
+        }
+        Value::NumericConstant { .. }
+        | Value::Function(_)
+        | Value::Intrinsic(_)
+        | Value::ForeignFunction(_) => Vec::new(),
+        Value::Instruction { .. } | Value::Param { .. } => vec![value],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use iter_extended::vecmap;
+    use noirc_errors::Location;
+    use noirc_frontend::monomorphization::ast::InlineType;
+
+    use crate::{
+        errors::{InternalWarning, SsaReport},
+        ssa::{
+            function_builder::FunctionBuilder,
+            ir::{
+                function::RuntimeType, instruction::BinaryOp, map::Id, types::Type, value::ValueId,
+            },
+            ssa_gen::Ssa,
+        },
+    };
+
+    /// Builds a main function which passes its parameter to a Brillig function `f1` returning
+    /// a `Field`, letting `constrain` decide how the result is used.
+    fn build_with_brillig_call(
+        constrain: impl FnOnce(&mut FunctionBuilder, ValueId, ValueId),
+    ) -> Ssa {
+        let main_id = Id::test_new(0);
+        let brillig_id = Id::test_new(1);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
+        builder.set_location(Location::dummy());
+        let v0 = builder.add_parameter(Type::field());
+        let brillig_function = builder.import_function(brillig_id);
+        let v2 = builder.insert_call(brillig_function, vec![v0], vec![Type::field()])[0];
+        constrain(&mut builder, v0, v2);
+
+        builder.new_brillig_function("unconstrained_fn".into(), brillig_id);
+        let v0 = builder.add_parameter(Type::field());
+        builder.terminate_with_return(vec![v0]);
+        builder.finish()
+    }
+
+    fn warnings(ssa: &Ssa) -> Vec<InternalWarning> {
+        vecmap(ssa.check_for_underconstrained_values(), |SsaReport::Warning(warning)| warning)
+    }
+
+    #[test]
+    fn constrained_result_is_not_reported() {
+        // acir fn main f0 {
+        //   b0(v0: Field):
+        //     v2 = call f1(v0) -> Field
+        //     v3 = mul v2, v2
+        //     constrain v3 == v0
+        //     return
+        // }
+        let ssa = build_with_brillig_call(|builder, v0, v2| {
+            let v3 = builder.insert_binary(v2, BinaryOp::Mul, v2);
+            builder.insert_constrain(v3, v0, None);
+            builder.terminate_with_return(vec![]);
+        });
+        assert!(warnings(&ssa).is_empty());
+    }
+
+    #[test]
+    fn calls_without_a_location_are_reported_at_their_function() {
+        // acir fn main f0 {
+        //   b0(v0: Field):
+        //     v2 = call f1(v0) -> Field
+        //     constrain v0 == Field 1
+        //     return
+        // }
+        // where only the constraint has a location.
+        let main_id = Id::test_new(0);
+        let brillig_id = Id::test_new(1);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
+        let v0 = builder.add_parameter(Type::field());
+        let brillig_function = builder.import_function(brillig_id);
+        builder.insert_call(brillig_function, vec![v0], vec![Type::field()]);
+        builder.set_location(Location::dummy());
+        let one = builder.field_constant(1u128);
+        builder.insert_constrain(v0, one, None);
+        builder.terminate_with_return(vec![]);
+
+        builder.new_brillig_function("unconstrained_fn".into(), brillig_id);
+        let v0 = builder.add_parameter(Type::field());
+        builder.terminate_with_return(vec![v0]);
+        let ssa = builder.finish();
+
+        match &warnings(&ssa)[..] {
+            [InternalWarning::UnconstrainedValue { call_stack }] => {
+                assert_eq!(call_stack.last(), Some(&Location::dummy()));
+            }
+            warnings => panic!("Expected a single unconstrained value warning, got {warnings:?}"),
+        }
+    }
+
+    #[test]
+    fn unused_result_is_reported() {
+        // acir fn main f0 {
+        //   b0(v0: Field):
+        //     v2 = call f1(v0) -> Field
+        //     return
+        // }
+        let ssa = build_with_brillig_call(|builder, _, _| {
+            builder.terminate_with_return(vec![]);
+        });
+        assert!(matches!(warnings(&ssa)[..], [InternalWarning::UnconstrainedValue { .. }]));
+    }
+
+    #[test]
+    fn result_only_passed_to_brillig_is_reported() {
+        // acir fn main f0 {
+        //   b0(v0: Field):
+        //     v2 = call f1(v0) -> Field
+        //     v3 = add v2, v0
+        //     v4 = call f1(v3) -> Field
+        //     constrain v4 == Field 1
+        //     return
+        // }
+        let ssa = build_with_brillig_call(|builder, v0, v2| {
+            let v3 = builder.insert_binary(v2, BinaryOp::Add, v0);
+            let brillig_function = builder.import_function(Id::test_new(1));
+            let v4 = builder.insert_call(brillig_function, vec![v3], vec![Type::field()])[0];
+            let one = builder.field_constant(1u128);
+            builder.insert_constrain(v4, one, None);
+            builder.terminate_with_return(vec![]);
+        });
+        // Only the first call is underconstrained, the second is asserted to be a constant.
+        assert!(matches!(warnings(&ssa)[..], [InternalWarning::UnconstrainedValue { .. }]));
+    }
+
+    #[test]
+    fn result_only_constrained_against_brillig_results_is_reported() {
+        // acir fn main f0 {
+        //   b0(v0: Field):
+        //     v2 = call f1(v0) -> Field
+        //     v3 = call f1(v0) -> Field
+        //     constrain v2 == v3
+        //     return
+        // }
+        let ssa = build_with_brillig_call(|builder, v0, v2| {
+            let brillig_function = builder.import_function(Id::test_new(1));
+            let v3 = builder.insert_call(brillig_function, vec![v0], vec![Type::field()])[0];
+            builder.insert_constrain(v2, v3, None);
+            builder.terminate_with_return(vec![]);
+        });
+        assert!(matches!(
+            warnings(&ssa)[..],
+            [
+                InternalWarning::UnderconstrainedValue { .. },
+                InternalWarning::UnderconstrainedValue { .. }
+            ]
+        ));
+    }
+
+    #[test]
+    fn returned_result_is_not_reported() {
+        // acir fn main f0 {
+        //   b0(v0: Field):
+        //     v2 = call f1(v0) -> Field
+        //     return v2
+        // }
+        let ssa = build_with_brillig_call(|builder, _, v2| {
+            builder.terminate_with_return(vec![v2]);
+        });
+        assert!(warnings(&ssa).is_empty());
+    }
+}
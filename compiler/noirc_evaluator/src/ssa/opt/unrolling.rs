@@ -47,21 +47,21 @@ impl Ssa {
 
             // This check is always true with the addition of the above guard, but I'm
             // keeping it in case the guard on brillig functions is ever removed.
-            let abort_on_error = function.runtime() == RuntimeType::Acir;
+            let abort_on_error = matches!(function.runtime(), RuntimeType::Acir(_));
             find_all_loops(function).unroll_each_loop(function, abort_on_error)?;
         }
         Ok(self)
     }
 }
 
-struct Loop {
+pub(super) struct Loop {
     /// The header block of a loop is the block which dominates all the
     /// other blocks in the loop.
-    header: BasicBlockId,
+    pub(super) header: BasicBlockId,
 
     /// The start of the back_edge n -> d is the block n at the end of
     /// the loop that jumps back to the header block d which restarts the loop.
-    back_edge_start: BasicBlockId,
+    pub(super) back_edge_start: BasicBlockId,
 
     /// All the blocks contained within the loop, including `header` and `back_edge_start`.
     pub(crate) blocks: HashSet<BasicBlockId>,
@@ -147,7 +147,7 @@ impl Loops {
 
 /// Return each block that is in a loop starting in the given header block.
 /// Expects back_edge_start -> header to be the back edge of the loop.
-fn find_blocks_in_loop(
+pub(super) fn find_blocks_in_loop(
     header: BasicBlockId,
     back_edge_start: BasicBlockId,
     cfg: &ControlFlowGraph,
@@ -465,6 +465,7 @@ mod tests {
         function_builder::FunctionBuilder,
         ir::{function::RuntimeType, instruction::BinaryOp, map::Id, types::Type},
     };
+    use noirc_frontend::monomorphization::ast::InlineType;
 
     #[test]
     fn unroll_nested_loops() {
@@ -502,7 +503,8 @@ mod tests {
         let main_id = Id::test_new(0);
 
         // Compiling main
-        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
 
         let b1 = builder.insert_block();
         let b2 = builder.insert_block();
@@ -604,7 +606,8 @@ mod tests {
         //     return Field 0
         // }
         let main_id = Id::test_new(0);
-        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let mut builder =
+            FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir(InlineType::default()));
 
         let b1 = builder.insert_block();
         let b2 = builder.insert_block();
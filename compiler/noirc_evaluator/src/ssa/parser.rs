@@ -0,0 +1,787 @@
+//! This module parses the textual SSA format produced by `ssa::ir::printer`, i.e. the
+//! output of `--show-ssa`, back into an [Ssa] object by replaying it through a [FunctionBuilder].
+//!
+//! This allows optimization passes to be tested on hand-written SSA and bugs to be
+//! reproduced from printed SSA. Because the [FunctionBuilder] is used, instructions are
+//! simplified as they are inserted and value ids are renumbered, so the parsed SSA may
+//! differ from its source in these respects. Function ids are preserved, and the first
+//! function in the source is taken to be the program's main function.
+use std::{rc::Rc, str::FromStr};
+
+use acvm::FieldElement;
+use fxhash::FxHashMap as HashMap;
+use noirc_frontend::monomorphization::ast::InlineType;
+use thiserror::Error;
+
+use super::{
+    function_builder::FunctionBuilder,
+    ir::{
+        basic_block::BasicBlockId,
+        function::{FunctionId, RuntimeType},
+        instruction::{BinaryOp, Instruction, Intrinsic},
+        map::Id,
+        types::{NumericType, Type},
+        value::ValueId,
+    },
+    ssa_gen::Ssa,
+};
+
+use lexer::{Lexer, SpannedToken, Token};
+
+mod lexer;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub(crate) enum SsaParseError {
+    #[error("Unexpected character '{character}' on line {line}")]
+    UnexpectedCharacter { character: char, line: usize },
+    #[error("Expected {expected} but found {found} on line {line}")]
+    UnexpectedToken { expected: String, found: Token, line: usize },
+    #[error("Use of undefined value {name} on line {line}")]
+    UndefinedValue { name: String, line: usize },
+    #[error("Instruction on line {line} has {actual} result(s) but {expected} were named")]
+    MismatchedResults { expected: usize, actual: usize, line: usize },
+}
+
+impl FromStr for Ssa {
+    type Err = SsaParseError;
+
+    fn from_str(source: &str) -> Result<Ssa, SsaParseError> {
+        let tokens = Lexer::new(source).lex()?;
+        SsaParser::new(tokens).parse_ssa()
+    }
+}
+
+struct SsaParser {
+    tokens: Vec<SpannedToken>,
+    position: usize,
+
+    /// Maps each value name in the function being parsed to its value in the new function
+    values: HashMap<String, ValueId>,
+
+    /// Maps each block name in the function being parsed to its block in the new function
+    blocks: HashMap<String, BasicBlockId>,
+}
+
+/// The `{runtime} fn {name} {id}` line starting each function
+struct FunctionHeader {
+    runtime: RuntimeType,
+    name: String,
+    id: FunctionId,
+}
+
+impl SsaParser {
+    fn new(tokens: Vec<SpannedToken>) -> Self {
+        Self { tokens, position: 0, values: HashMap::default(), blocks: HashMap::default() }
+    }
+
+    fn parse_ssa(mut self) -> Result<Ssa, SsaParseError> {
+        let header = self.parse_function_header()?;
+        let mut builder = FunctionBuilder::new(header.name, header.id, header.runtime);
+        self.parse_function_body(&mut builder)?;
+
+        while self.peek() != &Token::Eof {
+            let FunctionHeader { runtime, name, id } = self.parse_function_header()?;
+            match runtime {
+                RuntimeType::Acir(inline_type) => builder.new_function(name, id, inline_type),
+                RuntimeType::Brillig => builder.new_brillig_function(name, id),
+            }
+            self.parse_function_body(&mut builder)?;
+        }
+
+        Ok(builder.finish())
+    }
+
+    fn parse_function_header(&mut self) -> Result<FunctionHeader, SsaParseError> {
+        let runtime = match self.peek() {
+            Token::Ident(runtime) if runtime == "acir" => RuntimeType::Acir(InlineType::Inline),
+            Token::Ident(runtime) if runtime == "brillig" => RuntimeType::Brillig,
+            _ => return self.expected("a function runtime (`acir` or `brillig`)"),
+        };
+        self.advance();
+        let runtime = if runtime != RuntimeType::Brillig && self.eat_if(Token::LeftParen) {
+            self.eat_keyword("fold")?;
+            self.eat(Token::RightParen)?;
+            RuntimeType::Acir(InlineType::Fold)
+        } else {
+            runtime
+        };
+        self.eat_keyword("fn")?;
+        let name = self.parse_ident("a function name")?;
+        let id = self.parse_function_id()?;
+        Ok(FunctionHeader { runtime, name, id })
+    }
+
+    fn parse_function_body(&mut self, builder: &mut FunctionBuilder) -> Result<(), SsaParseError> {
+        self.values.clear();
+        self.blocks.clear();
+
+        self.eat(Token::LeftBrace)?;
+
+        // The first block printed is always the entry block
+        let entry_name = self.parse_block_name()?;
+        let entry_block = builder.current_block();
+        self.blocks.insert(entry_name, entry_block);
+        self.parse_block(builder, entry_block)?;
+
+        while self.peek() != &Token::RightBrace {
+            let name = self.parse_block_name()?;
+            let block = self.lookup_block(builder, name);
+            self.parse_block(builder, block)?;
+        }
+
+        self.eat(Token::RightBrace)
+    }
+
+    /// Parses a block after its name: its parameters, instructions and terminator.
+    fn parse_block(
+        &mut self,
+        builder: &mut FunctionBuilder,
+        block: BasicBlockId,
+    ) -> Result<(), SsaParseError> {
+        builder.switch_to_block(block);
+
+        self.eat(Token::LeftParen)?;
+        if !self.eat_if(Token::RightParen) {
+            loop {
+                let name = self.parse_value_name()?;
+                self.eat(Token::Colon)?;
+                let typ = self.parse_type()?;
+                let parameter = builder.add_block_parameter(block, typ);
+                self.values.insert(name, parameter);
+
+                if !self.eat_if(Token::Comma) {
+                    break;
+                }
+            }
+            self.eat(Token::RightParen)?;
+        }
+        self.eat(Token::Colon)?;
+
+        loop {
+            match self.peek() {
+                Token::Ident(keyword) if keyword == "jmp" => {
+                    self.advance();
+                    let destination = self.parse_block_name()?;
+                    let destination = self.lookup_block(builder, destination);
+                    let arguments = self.parse_arguments(builder)?;
+                    builder.terminate_with_jmp(destination, arguments);
+                    return Ok(());
+                }
+                Token::Ident(keyword) if keyword == "jmpif" => {
+                    self.advance();
+                    let condition = self.parse_value(builder)?;
+                    self.eat_keyword("then")?;
+                    self.eat(Token::Colon)?;
+                    let then_destination = self.parse_block_name()?;
+                    let then_destination = self.lookup_block(builder, then_destination);
+                    self.eat(Token::Comma)?;
+                    self.eat_keyword("else")?;
+                    self.eat(Token::Colon)?;
+                    let else_destination = self.parse_block_name()?;
+                    let else_destination = self.lookup_block(builder, else_destination);
+                    builder.terminate_with_jmpif(condition, then_destination, else_destination);
+                    return Ok(());
+                }
+                Token::Ident(keyword) if keyword == "return" => {
+                    self.advance();
+                    let mut return_values = Vec::new();
+                    if !self.at_block_end() {
+                        return_values.push(self.parse_value(builder)?);
+                        while self.eat_if(Token::Comma) {
+                            return_values.push(self.parse_value(builder)?);
+                        }
+                    }
+                    builder.terminate_with_return(return_values);
+                    return Ok(());
+                }
+                Token::LeftParen => {
+                    // `(no terminator instruction)`
+                    self.advance();
+                    self.eat_keyword("no")?;
+                    self.eat_keyword("terminator")?;
+                    self.eat_keyword("instruction")?;
+                    return self.eat(Token::RightParen);
+                }
+                _ => self.parse_instruction(builder)?,
+            }
+        }
+    }
+
+    fn parse_instruction(&mut self, builder: &mut FunctionBuilder) -> Result<(), SsaParseError> {
+        let mut result_names = Vec::new();
+        if matches!(self.peek(), Token::Ident(name) if parse_id(name, 'v').is_some()) {
+            result_names.push(self.parse_value_name()?);
+            while self.eat_if(Token::Comma) {
+                result_names.push(self.parse_value_name()?);
+            }
+            self.eat(Token::Assign)?;
+        }
+
+        let line = self.line();
+        let keyword = self.parse_ident("an instruction")?;
+        let (instruction, result_types) = match keyword.as_str() {
+            "cast" => {
+                let value = self.parse_value(builder)?;
+                self.eat_keyword("as")?;
+                (Instruction::Cast(value, self.parse_type()?), None)
+            }
+            "not" => (Instruction::Not(self.parse_value(builder)?), None),
+            "truncate" => {
+                let value = self.parse_value(builder)?;
+                self.eat_keyword("to")?;
+                let bit_size = self.parse_u32()?;
+                self.eat_keyword("bits")?;
+                self.eat(Token::Comma)?;
+                self.eat_keyword("max_bit_size")?;
+                self.eat(Token::Colon)?;
+                let max_bit_size = self.parse_u32()?;
+                (Instruction::Truncate { value, bit_size, max_bit_size }, None)
+            }
+            "constrain" => {
+                let lhs = self.parse_value(builder)?;
+                self.eat(Token::Equal)?;
+                let rhs = self.parse_value(builder)?;
+                (Instruction::Constrain(lhs, rhs, self.parse_message()), None)
+            }
+            "range_check" => {
+                let value = self.parse_value(builder)?;
+                self.eat_keyword("to")?;
+                let max_bit_size = self.parse_u32()?;
+                self.eat_keyword("bits")?;
+                let assert_message = self.parse_message();
+                (Instruction::RangeCheck { value, max_bit_size, assert_message }, None)
+            }
+            "call" => {
+                let func = self.parse_value(builder)?;
+                let arguments = self.parse_arguments(builder)?;
+                (Instruction::Call { func, arguments }, Some(self.parse_result_types()?))
+            }
+            "allocate" => (Instruction::Allocate, Some(self.parse_result_types()?)),
+            "load" => {
+                let address = self.parse_value(builder)?;
+                (Instruction::Load { address }, Some(self.parse_result_types()?))
+            }
+            "store" => {
+                let value = self.parse_value(builder)?;
+                self.eat_keyword("at")?;
+                let address = self.parse_value(builder)?;
+                (Instruction::Store { address, value }, None)
+            }
+            "enable_side_effects" => {
+                let condition = self.parse_value(builder)?;
+                (Instruction::EnableSideEffects { condition }, None)
+            }
+            "array_get" => {
+                let array = self.parse_value(builder)?;
+                self.eat(Token::Comma)?;
+                self.eat_keyword("index")?;
+                let index = self.parse_value(builder)?;
+                (Instruction::ArrayGet { array, index }, Some(self.parse_result_types()?))
+            }
+            "array_set" => {
+                let array = self.parse_value(builder)?;
+                self.eat(Token::Comma)?;
+                self.eat_keyword("index")?;
+                let index = self.parse_value(builder)?;
+                self.eat(Token::Comma)?;
+                self.eat_keyword("value")?;
+                let value = self.parse_value(builder)?;
+                (Instruction::ArraySet { array, index, value }, None)
+            }
+            "inc_rc" => (Instruction::IncrementRc { value: self.parse_value(builder)? }, None),
+            other => match binary_operator(other) {
+                Some(operator) => {
+                    let lhs = self.parse_value(builder)?;
+                    self.eat(Token::Comma)?;
+                    let rhs = self.parse_value(builder)?;
+                    (Instruction::binary(operator, lhs, rhs), None)
+                }
+                None => {
+                    let found = Token::Ident(other.to_owned());
+                    let expected = "an instruction".to_owned();
+                    return Err(SsaParseError::UnexpectedToken { expected, found, line });
+                }
+            },
+        };
+
+        let results = builder.insert_instruction(instruction, result_types).results().into_owned();
+        if results.len() != result_names.len() {
+            return Err(SsaParseError::MismatchedResults {
+                expected: result_names.len(),
+                actual: results.len(),
+                line,
+            });
+        }
+        self.values.extend(result_names.into_iter().zip(results));
+        Ok(())
+    }
+
+    /// Parses the `-> type` or `-> (type, type)` suffix printed after instructions whose
+    /// result types are unknown. A missing suffix means the instruction has no results.
+    fn parse_result_types(&mut self) -> Result<Vec<Type>, SsaParseError> {
+        if !self.eat_if(Token::Arrow) {
+            return Ok(Vec::new());
+        }
+        if !self.eat_if(Token::LeftParen) {
+            return Ok(vec![self.parse_type()?]);
+        }
+        let mut types = vec![self.parse_type()?];
+        while self.eat_if(Token::Comma) {
+            types.push(self.parse_type()?);
+        }
+        self.eat(Token::RightParen)?;
+        Ok(types)
+    }
+
+    /// Parses an optional trailing assert message
+    fn parse_message(&mut self) -> Option<String> {
+        match self.peek().clone() {
+            Token::Str(message) => {
+                self.advance();
+                Some(message)
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses a parenthesized, comma-separated list of values
+    fn parse_arguments(
+        &mut self,
+        builder: &mut FunctionBuilder,
+    ) -> Result<Vec<ValueId>, SsaParseError> {
+        self.eat(Token::LeftParen)?;
+        let mut arguments = Vec::new();
+        if !self.eat_if(Token::RightParen) {
+            arguments.push(self.parse_value(builder)?);
+            while self.eat_if(Token::Comma) {
+                arguments.push(self.parse_value(builder)?);
+            }
+            self.eat(Token::RightParen)?;
+        }
+        Ok(arguments)
+    }
+
+    fn parse_value(&mut self, builder: &mut FunctionBuilder) -> Result<ValueId, SsaParseError> {
+        let line = self.line();
+        match self.peek().clone() {
+            Token::Ident(name) => {
+                self.advance();
+                if let Some(typ) = numeric_type(&name) {
+                    let constant = self.parse_int()?;
+                    Ok(builder.numeric_constant(constant, Type::Numeric(typ)))
+                } else if parse_id(&name, 'v').is_some() {
+                    let value = self.values.get(&name).copied();
+                    value.ok_or(SsaParseError::UndefinedValue { name, line })
+                } else if let Some(index) = parse_id(&name, 'f') {
+                    Ok(builder.import_function(Id::new(index)))
+                } else if let Some(intrinsic) = Intrinsic::lookup(&name) {
+                    Ok(builder.import_intrinsic_id(intrinsic))
+                } else {
+                    Ok(builder.import_foreign_function(&name))
+                }
+            }
+            Token::LeftBracket => {
+                self.advance();
+                let mut elements = im::Vector::new();
+                if !self.eat_if(Token::RightBracket) {
+                    elements.push_back(self.parse_value(builder)?);
+                    while self.eat_if(Token::Comma) {
+                        elements.push_back(self.parse_value(builder)?);
+                    }
+                    self.eat(Token::RightBracket)?;
+                }
+                self.eat_keyword("of")?;
+                let typ = self.parse_type()?;
+                if !matches!(typ, Type::Array(..) | Type::Slice(_)) {
+                    return self.expected("an array or slice type");
+                }
+                Ok(builder.array_constant(elements, typ))
+            }
+            _ => self.expected("a value"),
+        }
+    }
+
+    fn parse_type(&mut self) -> Result<Type, SsaParseError> {
+        match self.peek().clone() {
+            Token::Ident(name) if name == "function" => {
+                self.advance();
+                Ok(Type::Function)
+            }
+            Token::Ident(name) => match numeric_type(&name) {
+                Some(typ) => {
+                    self.advance();
+                    Ok(Type::Numeric(typ))
+                }
+                None => self.expected("a type"),
+            },
+            Token::Ampersand => {
+                self.advance();
+                self.eat_keyword("mut")?;
+                Ok(Type::Reference(Rc::new(self.parse_type()?)))
+            }
+            Token::LeftBracket => {
+                self.advance();
+                let mut element_types = vec![self.parse_type()?];
+                while self.eat_if(Token::Comma) {
+                    element_types.push(self.parse_type()?);
+                }
+                if self.eat_if(Token::Semicolon) {
+                    let length = self.parse_u32()? as usize;
+                    self.eat(Token::RightBracket)?;
+                    Ok(Type::Array(Rc::new(element_types), length))
+                } else {
+                    self.eat(Token::RightBracket)?;
+                    Ok(Type::Slice(Rc::new(element_types)))
+                }
+            }
+            _ => self.expected("a type"),
+        }
+    }
+
+    fn parse_value_name(&mut self) -> Result<String, SsaParseError> {
+        match self.peek() {
+            Token::Ident(name) if parse_id(name, 'v').is_some() => self.parse_ident("a value name"),
+            _ => self.expected("a value name"),
+        }
+    }
+
+    fn parse_block_name(&mut self) -> Result<String, SsaParseError> {
+        match self.peek() {
+            Token::Ident(name) if parse_id(name, 'b').is_some() => self.parse_ident("a block name"),
+            _ => self.expected("a block name"),
+        }
+    }
+
+    fn parse_function_id(&mut self) -> Result<FunctionId, SsaParseError> {
+        match self.peek() {
+            Token::Ident(name) => match parse_id(name, 'f') {
+                Some(index) => {
+                    self.advance();
+                    Ok(Id::new(index))
+                }
+                None => self.expected("a function id"),
+            },
+            _ => self.expected("a function id"),
+        }
+    }
+
+    fn parse_ident(&mut self, expected: &str) -> Result<String, SsaParseError> {
+        match self.peek().clone() {
+            Token::Ident(name) => {
+                self.advance();
+                Ok(name)
+            }
+            _ => self.expected(expected),
+        }
+    }
+
+    fn parse_int(&mut self) -> Result<FieldElement, SsaParseError> {
+        match self.peek().clone() {
+            Token::Int(value) => {
+                self.advance();
+                Ok(value)
+            }
+            _ => self.expected("an integer"),
+        }
+    }
+
+    fn parse_u32(&mut self) -> Result<u32, SsaParseError> {
+        match self.peek().clone() {
+            Token::Int(value) if value.num_bits() <= 32 => {
+                self.advance();
+                Ok(value.to_u128() as u32)
+            }
+            _ => self.expected("a 32-bit integer"),
+        }
+    }
+
+    /// Returns the block with the given name, creating it if this is its first use
+    fn lookup_block(&mut self, builder: &mut FunctionBuilder, name: String) -> BasicBlockId {
+        *self.blocks.entry(name).or_insert_with(|| builder.insert_block())
+    }
+
+    /// True if the next tokens end the current block: either the start of the
+    /// next block (`b1(`) or the end of the function.
+    fn at_block_end(&self) -> bool {
+        match self.peek() {
+            Token::RightBrace => true,
+            Token::Ident(name) => {
+                parse_id(name, 'b').is_some() && self.peek_next() == &Token::LeftParen
+            }
+            _ => false,
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> Result<(), SsaParseError> {
+        match self.peek() {
+            Token::Ident(name) if name == keyword => {
+                self.advance();
+                Ok(())
+            }
+            _ => self.expected(&format!("`{keyword}`")),
+        }
+    }
+
+    fn eat(&mut self, token: Token) -> Result<(), SsaParseError> {
+        if self.eat_if(token.clone()) {
+            Ok(())
+        } else {
+            self.expected(&format!("`{token}`"))
+        }
+    }
+
+    /// Advances past the next token if it is the given token, returning true if so
+    fn eat_if(&mut self, token: Token) -> bool {
+        if self.peek() == &token {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expected<T>(&self, expected: &str) -> Result<T, SsaParseError> {
+        Err(SsaParseError::UnexpectedToken {
+            expected: expected.to_owned(),
+            found: self.peek().clone(),
+            line: self.line(),
+        })
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position].0
+    }
+
+    fn peek_next(&self) -> &Token {
+        let index = std::cmp::min(self.position + 1, self.tokens.len() - 1);
+        &self.tokens[index].0
+    }
+
+    fn line(&self) -> usize {
+        self.tokens[self.position].1
+    }
+
+    fn advance(&mut self) {
+        // Never advance past the trailing Eof token
+        if self.position + 1 < self.tokens.len() {
+            self.position += 1;
+        }
+    }
+}
+
+/// Parses the index out of an id such as `v3` for the given prefix
+fn parse_id(name: &str, prefix: char) -> Option<usize> {
+    let index = name.strip_prefix(prefix)?;
+    if index.is_empty() || !index.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    index.parse().ok()
+}
+
+fn numeric_type(name: &str) -> Option<NumericType> {
+    if name == "Field" {
+        return Some(NumericType::NativeField);
+    }
+    if let Some(bit_size) = parse_id(name, 'u') {
+        return Some(NumericType::Unsigned { bit_size: bit_size.try_into().ok()? });
+    }
+    let bit_size = parse_id(name, 'i')?;
+    Some(NumericType::Signed { bit_size: bit_size.try_into().ok()? })
+}
+
+fn binary_operator(name: &str) -> Option<BinaryOp> {
+    let operator = match name {
+        "add" => BinaryOp::Add,
+        "sub" => BinaryOp::Sub,
+        "mul" => BinaryOp::Mul,
+        "div" => BinaryOp::Div,
+        "mod" => BinaryOp::Mod,
+        "eq" => BinaryOp::Eq,
+        "lt" => BinaryOp::Lt,
+        "and" => BinaryOp::And,
+        "or" => BinaryOp::Or,
+        "xor" => BinaryOp::Xor,
+        _ => return None,
+    };
+    Some(operator)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ssa::{
+        ir::instruction::{Instruction, TerminatorInstruction},
+        ssa_gen::Ssa,
+    };
+
+    use super::SsaParseError;
+
+    /// Parses the given SSA and checks that printing it back out gives the same text,
+    /// ignoring indentation.
+    fn assert_ssa_roundtrip(src: &str) {
+        let ssa: Ssa = src.parse().unwrap();
+        let printed = ssa.to_string();
+        let expected: Vec<_> = src.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+        let actual: Vec<_> =
+            printed.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+        assert_eq!(actual, expected, "Printed SSA:\n{printed}");
+    }
+
+    #[test]
+    fn roundtrip_arithmetic() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: Field, v1: Field):
+                v2 = add v0, v1
+                v4 = mul v2, Field 2
+                constrain v4 == Field 10 'sum must be five'
+                return v4
+            }
+        ";
+        assert_ssa_roundtrip(src);
+    }
+
+    #[test]
+    fn roundtrip_constants() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: Field):
+                v2 = add v0, Field -1
+                v4 = mul v2, Field 2⁶⁴
+                v6 = add v4, Field 2³²×5
+                return v6
+            }
+        ";
+        assert_ssa_roundtrip(src);
+    }
+
+    #[test]
+    fn roundtrip_loop() {
+        let src = "
+            brillig fn main f0 {
+              b0(v0: u32):
+                jmp b1(u32 0)
+              b1(v2: u32):
+                v3 = lt v2, v0
+                jmpif v3 then: b2, else: b3
+              b2():
+                v5 = add v2, u32 1
+                jmp b1(v5)
+              b3():
+                return v2
+            }
+        ";
+        assert_ssa_roundtrip(src);
+    }
+
+    #[test]
+    fn roundtrip_memory_arrays_and_calls() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: Field):
+                v1 = allocate -> &mut [Field; 2]
+                store [v0, Field 1] of [Field; 2] at v1
+                v4 = load v1 -> [Field; 2]
+                v6 = array_get v4, index u32 0 -> Field
+                v8, v9 = call f1(v6) -> (Field, u8)
+                return v8, v9
+            }
+            brillig fn foo f1 {
+              b0(v0: Field):
+                v2 = mul v0, Field 3
+                return v2, u8 7
+            }
+        ";
+        assert_ssa_roundtrip(src);
+    }
+
+    #[test]
+    fn roundtrip_folded_function() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: Field):
+                v2 = call f1(v0) -> Field
+                return v2
+            }
+            acir(fold) fn foo f1 {
+              b0(v0: Field):
+                v2 = add v0, Field 1
+                return v2
+            }
+        ";
+        assert_ssa_roundtrip(src);
+    }
+
+    #[test]
+    fn roundtrip_remaining_instructions() {
+        let src = "
+            brillig fn main f0 {
+              b0(v0: u8, v1: [Field]):
+                v2 = cast v0 as u32
+                v3 = not v2
+                v4 = truncate v3 to 8 bits, max_bit_size: 32
+                range_check v4 to 8 bits 'out of range'
+                enable_side_effects u1 1
+                inc_rc v1
+                v8 = array_set v1, index u32 0, value Field 5
+                call print(v8)
+                v11 = call to_le_bits(v2, u32 8) -> [u1; 8]
+                return v11
+            }
+        ";
+        assert_ssa_roundtrip(src);
+    }
+
+    #[test]
+    fn parsed_ssa_can_be_optimized() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: Field):
+                v1 = allocate -> &mut Field
+                store v0 at v1
+                v2 = load v1 -> Field
+                return v2
+            }
+        ";
+        let ssa: Ssa = src.parse().unwrap();
+        let ssa = ssa.mem2reg();
+
+        let func = ssa.main();
+        let block_id = func.entry_block();
+        let instructions = func.dfg[block_id].instructions();
+        assert!(!instructions
+            .iter()
+            .any(|instruction| matches!(func.dfg[*instruction], Instruction::Load { .. })));
+
+        let return_value = match func.dfg[block_id].terminator().unwrap() {
+            TerminatorInstruction::Return { return_values, .. } => return_values[0],
+            _ => unreachable!(),
+        };
+        let parameter = func.dfg.block_parameters(block_id)[0];
+        assert_eq!(func.dfg[return_value], func.dfg[parameter]);
+    }
+
+    #[test]
+    fn undefined_value() {
+        let src = "
+            acir fn main f0 {
+              b0():
+                return v0
+            }
+        ";
+        let error = src.parse::<Ssa>().err().unwrap();
+        assert_eq!(error, SsaParseError::UndefinedValue { name: "v0".to_owned(), line: 4 });
+    }
+
+    #[test]
+    fn mismatched_result_count() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: Field):
+                v1, v2 = add v0, v0
+                return v1
+            }
+        ";
+        let error = src.parse::<Ssa>().err().unwrap();
+        assert!(matches!(error, SsaParseError::MismatchedResults { expected: 2, actual: 1, .. }));
+    }
+}
@@ -0,0 +1,201 @@
+use acvm::FieldElement;
+use num_bigint::BigUint;
+
+use super::SsaParseError;
+
+/// The tokens of the textual SSA format printed by `ssa::ir::printer`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Token {
+    /// Keywords, value names (`v0`), block names (`b0`), function ids (`f0`),
+    /// types (`Field`, `u32`), intrinsics and foreign functions.
+    Ident(String),
+    Int(FieldElement),
+    /// The contents of a quoted assert message, e.g. `'x must be 1'`
+    Str(String),
+    LeftParen,
+    RightParen,
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    Comma,
+    Colon,
+    Semicolon,
+    Assign,
+    Equal,
+    Ampersand,
+    Arrow,
+    Eof,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Ident(name) => write!(f, "{name}"),
+            Token::Int(value) => write!(f, "{value}"),
+            Token::Str(message) => write!(f, "'{message}'"),
+            Token::LeftParen => write!(f, "("),
+            Token::RightParen => write!(f, ")"),
+            Token::LeftBrace => write!(f, "{{"),
+            Token::RightBrace => write!(f, "}}"),
+            Token::LeftBracket => write!(f, "["),
+            Token::RightBracket => write!(f, "]"),
+            Token::Comma => write!(f, ","),
+            Token::Colon => write!(f, ":"),
+            Token::Semicolon => write!(f, ";"),
+            Token::Assign => write!(f, "="),
+            Token::Equal => write!(f, "=="),
+            Token::Ampersand => write!(f, "&"),
+            Token::Arrow => write!(f, "->"),
+            Token::Eof => write!(f, "end of input"),
+        }
+    }
+}
+
+/// A token along with the line it was found on
+pub(crate) type SpannedToken = (Token, usize);
+
+pub(crate) struct Lexer<'src> {
+    source: &'src str,
+    position: usize,
+    line: usize,
+}
+
+impl<'src> Lexer<'src> {
+    pub(crate) fn new(source: &'src str) -> Self {
+        Self { source, position: 0, line: 1 }
+    }
+
+    /// Splits the source into tokens. The returned tokens always end with `Token::Eof`.
+    pub(crate) fn lex(mut self) -> Result<Vec<SpannedToken>, SsaParseError> {
+        let mut tokens = Vec::new();
+        loop {
+            let token = self.next_token()?;
+            let done = token == Token::Eof;
+            tokens.push((token, self.line));
+            if done {
+                return Ok(tokens);
+            }
+        }
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.source[self.position..].chars().next()
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let char = self.peek_char()?;
+        self.position += char.len_utf8();
+        if char == '\n' {
+            self.line += 1;
+        }
+        Some(char)
+    }
+
+    /// Consumes characters for as long as `predicate` holds, returning the consumed slice
+    fn eat_while(&mut self, predicate: impl Fn(char) -> bool) -> &'src str {
+        let start = self.position;
+        while self.peek_char().map_or(false, &predicate) {
+            self.next_char();
+        }
+        &self.source[start..self.position]
+    }
+
+    fn next_token(&mut self) -> Result<Token, SsaParseError> {
+        self.eat_while(char::is_whitespace);
+
+        let Some(char) = self.next_char() else {
+            return Ok(Token::Eof);
+        };
+
+        let token = match char {
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '{' => Token::LeftBrace,
+            '}' => Token::RightBrace,
+            '[' => Token::LeftBracket,
+            ']' => Token::RightBracket,
+            ',' => Token::Comma,
+            ':' => Token::Colon,
+            ';' => Token::Semicolon,
+            '&' => Token::Ampersand,
+            '=' if self.peek_char() == Some('=') => {
+                self.next_char();
+                Token::Equal
+            }
+            '=' => Token::Assign,
+            '-' if self.peek_char() == Some('>') => {
+                self.next_char();
+                Token::Arrow
+            }
+            '-' if self.peek_char().map_or(false, |char| char.is_ascii_digit()) => {
+                let first_digit = self.next_char().unwrap();
+                Token::Int(-self.lex_integer(first_digit))
+            }
+            '\'' => self.lex_message(),
+            char if char.is_ascii_digit() => Token::Int(self.lex_integer(char)),
+            char if char.is_ascii_alphabetic() || char == '_' => {
+                let start = self.position - char.len_utf8();
+                self.eat_while(|char| char.is_ascii_alphanumeric() || char == '_');
+                Token::Ident(self.source[start..self.position].to_owned())
+            }
+            character => {
+                return Err(SsaParseError::UnexpectedCharacter { character, line: self.line })
+            }
+        };
+        Ok(token)
+    }
+
+    /// Lexes an integer in any of the forms `FieldElement`'s Display impl produces:
+    /// `123`, a power of two such as `2⁶⁴`, or a multiple of one such as `2³²×5`.
+    fn lex_integer(&mut self, first_digit: char) -> FieldElement {
+        let start = self.position - first_digit.len_utf8();
+        self.eat_while(|char| char.is_ascii_digit());
+        let mut value: BigUint = self.source[start..self.position].parse().unwrap();
+
+        let exponent = self.eat_while(|char| superscript_digit(char).is_some());
+        if !exponent.is_empty() {
+            let exponent = exponent
+                .chars()
+                .fold(0, |exponent, char| exponent * 10 + superscript_digit(char).unwrap());
+            value = value.pow(exponent);
+
+            if self.peek_char() == Some('×') {
+                self.next_char();
+                let multiplier = self.eat_while(|char| char.is_ascii_digit());
+                value *= multiplier.parse::<BigUint>().unwrap_or_default();
+            }
+        }
+        FieldElement::from_be_bytes_reduce(&value.to_bytes_be())
+    }
+
+    /// Lexes an assert message. The printer does not escape quotes within messages,
+    /// so the message extends up to the last quote on the current line.
+    fn lex_message(&mut self) -> Token {
+        let rest_of_line = self.source[self.position..].split('\n').next().unwrap_or_default();
+        let message = match rest_of_line.rfind('\'') {
+            Some(end) => &rest_of_line[..end],
+            None => rest_of_line,
+        };
+        self.position += message.len();
+        self.next_char(); // The closing quote, if any
+        Token::Str(message.to_owned())
+    }
+}
+
+fn superscript_digit(char: char) -> Option<u32> {
+    let digit = match char {
+        '⁰' => 0,
+        '¹' => 1,
+        '²' => 2,
+        '³' => 3,
+        '⁴' => 4,
+        '⁵' => 5,
+        '⁶' => 6,
+        '⁷' => 7,
+        '⁸' => 8,
+        '⁹' => 9,
+        _ => return None,
+    };
+    Some(digit)
+}
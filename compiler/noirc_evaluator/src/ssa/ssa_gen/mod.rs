@@ -56,7 +56,7 @@ pub(crate) fn generate_ssa(program: Program) -> Result<Ssa, RuntimeError> {
     let mut function_context = FunctionContext::new(
         main.name.clone(),
         &main.parameters,
-        if main.unconstrained { RuntimeType::Brillig } else { RuntimeType::Acir },
+        if main.unconstrained { RuntimeType::Brillig } else { RuntimeType::Acir(main.inline_type) },
         &context,
     );
 
@@ -133,6 +133,8 @@ impl<'a> FunctionContext<'a> {
             Expression::Index(index) => self.codegen_index(index),
             Expression::Cast(cast) => self.codegen_cast(cast),
             Expression::For(for_expr) => self.codegen_for(for_expr),
+            Expression::While(while_expr) => self.codegen_while(while_expr),
+            Expression::Loop(block) => self.codegen_loop(block),
             Expression::If(if_expr) => self.codegen_if(if_expr),
             Expression::Tuple(tuple) => self.codegen_tuple(tuple),
             Expression::ExtractTupleField(tuple, index) => {
@@ -145,6 +147,8 @@ impl<'a> FunctionContext<'a> {
             }
             Expression::Assign(assign) => self.codegen_assign(assign),
             Expression::Semi(semi) => self.codegen_semi(semi),
+            Expression::Break => Ok(self.codegen_break()),
+            Expression::Continue => Ok(self.codegen_continue()),
         }
     }
 
@@ -488,7 +492,9 @@ impl<'a> FunctionContext<'a> {
         // Compile the loop body
         self.builder.switch_to_block(loop_body);
         self.define(for_expr.index_variable, loop_index.into());
+        self.enter_loop(loop_entry, Some(loop_index), loop_end);
         self.codegen_expression(&for_expr.block)?;
+        self.exit_loop();
         let new_loop_index = self.make_offset(loop_index, 1);
         self.builder.terminate_with_jmp(loop_entry, vec![new_loop_index]);
 
@@ -497,6 +503,69 @@ impl<'a> FunctionContext<'a> {
         Ok(Self::unit_value())
     }
 
+    /// Codegens a while loop, which may only appear in unconstrained code since its
+    /// number of iterations is not known at compile-time.
+    /// The return value of a while loop is always a unit literal.
+    ///
+    /// For example, the loop `while cond { body }` is codegen'd as:
+    ///
+    ///   br loop_entry()
+    /// loop_entry():
+    ///   v0 = ... codegen cond ...
+    ///   brif v0, then: loop_body, else: loop_end
+    /// loop_body():
+    ///   v1 = ... codegen body ...
+    ///   br loop_entry()
+    /// loop_end():
+    ///   ... This is the current insert point after codegen_while finishes ...
+    fn codegen_while(&mut self, while_expr: &ast::While) -> Result<Values, RuntimeError> {
+        let loop_entry = self.builder.insert_block();
+        let loop_body = self.builder.insert_block();
+        let loop_end = self.builder.insert_block();
+
+        self.builder.terminate_with_jmp(loop_entry, Vec::new());
+
+        self.builder.switch_to_block(loop_entry);
+        let condition = self.codegen_non_tuple_expression(&while_expr.condition)?;
+        self.builder.terminate_with_jmpif(condition, loop_body, loop_end);
+
+        self.builder.switch_to_block(loop_body);
+        self.enter_loop(loop_entry, None, loop_end);
+        self.codegen_expression(&while_expr.block)?;
+        self.exit_loop();
+        self.builder.terminate_with_jmp(loop_entry, Vec::new());
+
+        self.builder.switch_to_block(loop_end);
+        Ok(Self::unit_value())
+    }
+
+    /// Codegens an unconditional loop, which can only be exited through a `break`.
+    /// The return value of a loop is always a unit literal.
+    ///
+    /// For example, the loop `loop { body }` is codegen'd as:
+    ///
+    ///   br loop_body()
+    /// loop_body():
+    ///   v0 = ... codegen body ...
+    ///   br loop_body()
+    /// loop_end():
+    ///   ... This is the current insert point after codegen_loop finishes ...
+    fn codegen_loop(&mut self, block: &Expression) -> Result<Values, RuntimeError> {
+        let loop_body = self.builder.insert_block();
+        let loop_end = self.builder.insert_block();
+
+        self.builder.terminate_with_jmp(loop_body, Vec::new());
+
+        self.builder.switch_to_block(loop_body);
+        self.enter_loop(loop_body, None, loop_end);
+        self.codegen_expression(block)?;
+        self.exit_loop();
+        self.builder.terminate_with_jmp(loop_body, Vec::new());
+
+        self.builder.switch_to_block(loop_end);
+        Ok(Self::unit_value())
+    }
+
     /// Codegens an if expression, handling the case of what to do if there is no 'else'.
     ///
     /// For example, the expression `if cond { a } else { b }` is codegen'd as:
@@ -687,4 +756,32 @@ impl<'a> FunctionContext<'a> {
         self.codegen_expression(expr)?;
         Ok(Self::unit_value())
     }
+
+    /// Jumps to the end of the current loop. Any code following the `break` is
+    /// unreachable, so it is placed in a fresh block with no predecessors.
+    fn codegen_break(&mut self) -> Values {
+        let loop_end = self.current_loop().loop_end;
+        self.builder.terminate_with_jmp(loop_end, Vec::new());
+
+        let unreachable_block = self.builder.insert_block();
+        self.builder.switch_to_block(unreachable_block);
+        Self::unit_value()
+    }
+
+    /// Jumps back to the start of the current loop with the next loop index, if any.
+    /// As with `break`, any code following the `continue` is placed in an unreachable block.
+    fn codegen_continue(&mut self) -> Values {
+        let loop_ = self.current_loop();
+
+        // Must remember to increment i before jumping
+        let arguments = match loop_.loop_index {
+            Some(loop_index) => vec![self.make_offset(loop_index, 1)],
+            None => Vec::new(),
+        };
+        self.builder.terminate_with_jmp(loop_.loop_entry, arguments);
+
+        let unreachable_block = self.builder.insert_block();
+        self.builder.switch_to_block(unreachable_block);
+        Self::unit_value()
+    }
 }
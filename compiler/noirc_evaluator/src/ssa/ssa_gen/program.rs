@@ -3,7 +3,7 @@ use std::{collections::BTreeMap, fmt::Display};
 use iter_extended::btree_map;
 
 use crate::ssa::ir::{
-    function::{Function, FunctionId},
+    function::{Function, FunctionId, RuntimeType},
     map::AtomicCounter,
 };
 
@@ -49,6 +49,28 @@ impl Ssa {
         self.functions.insert(new_id, function);
         new_id
     }
+
+    /// Moves the Brillig functions out of this program into a program of their own, so that
+    /// they can be optimized separately from the ACIR functions.
+    ///
+    /// The returned program keeps the id of `main` even if it does not contain it. New functions
+    /// must not be added to either program before they are merged back together.
+    pub(crate) fn split_off_brillig_functions(&mut self) -> Ssa {
+        let (brillig_functions, acir_functions): (BTreeMap<_, _>, BTreeMap<_, _>) =
+            std::mem::take(&mut self.functions)
+                .into_iter()
+                .partition(|(_, function)| function.runtime() == RuntimeType::Brillig);
+        self.functions = acir_functions;
+
+        let max_id = self.functions.keys().chain(brillig_functions.keys()).max();
+        let next_id = AtomicCounter::starting_after(*max_id.unwrap_or(&self.main_id));
+        Ssa { functions: brillig_functions, main_id: self.main_id, next_id }
+    }
+
+    /// Moves the functions of a program split off by `split_off_brillig_functions` back into this program.
+    pub(crate) fn merge(&mut self, other: Ssa) {
+        self.functions.extend(other.functions);
+    }
 }
 
 impl Display for Ssa {
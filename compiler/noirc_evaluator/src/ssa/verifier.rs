@@ -0,0 +1,520 @@
+//! Checks that an [Ssa] program is well-formed.
+//!
+//! Optimization passes assume the SSA they are given is well-formed. When a pass breaks one of
+//! these invariants the resulting failure is often only seen much later, e.g. as a panic during
+//! ACIR generation. Running the verifier after each pass (see `SsaBuilder`) instead catches the
+//! violation directly after the pass which introduced it.
+//!
+//! The verifier checks that:
+//! - Every value is defined in a block which dominates each of its uses, and before its uses
+//!   within the same block.
+//! - The operands of each instruction have the types the instruction expects.
+//! - Every reachable block has a terminator, and each jump passes as many arguments as its
+//!   destination block has parameters.
+//!
+//! Once all passes have been run, `verify_ready_for_acir_gen` additionally checks that the
+//! functions compiled to ACIR have been flattened into a single block and that no `allocate`,
+//! `load` or `store` remains in them.
+use fxhash::FxHashMap as HashMap;
+use thiserror::Error;
+
+use super::{
+    ir::{
+        basic_block::BasicBlockId,
+        dfg::DataFlowGraph,
+        dom::DominatorTree,
+        function::{Function, RuntimeType},
+        instruction::{Instruction, InstructionId, TerminatorInstruction},
+        types::Type,
+        value::{Value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub(crate) enum SsaVerifierError {
+    #[error("In function {function}: {value} is used in {block} but its definition does not dominate this use")]
+    UndominatedUse { function: String, value: ValueId, block: BasicBlockId },
+    #[error("In function {function}: {value} is defined by an instruction which is not in any reachable block")]
+    DetachedValue { function: String, value: ValueId },
+    #[error("In function {function}: {context} expected {expected} but found {found}")]
+    TypeMismatch { function: String, context: String, expected: String, found: Type },
+    #[error(
+        "In function {function}: {context} expected {expected} argument(s) but found {actual}"
+    )]
+    ArityMismatch { function: String, context: String, expected: usize, actual: usize },
+    #[error("In function {function}: {block} has no terminator instruction")]
+    MissingTerminator { function: String, block: BasicBlockId },
+    #[error(
+        "In function {function}: `{instruction}` in {block} was not removed before ACIR generation"
+    )]
+    MemoryInstructionInAcir { function: String, instruction: String, block: BasicBlockId },
+    #[error("In function {function}: {blocks} blocks were not flattened into one before ACIR generation")]
+    UnflattenedAcirFunction { function: String, blocks: usize },
+}
+
+impl Ssa {
+    /// Checks that every function in this program is well-formed, returning the first
+    /// violation found.
+    pub(crate) fn verify(&self) -> Result<(), SsaVerifierError> {
+        for function in self.functions.values() {
+            FunctionVerifier::new(self, function).verify()?;
+        }
+        Ok(())
+    }
+
+    /// Checks that each function which is compiled to ACIR consists of a single block without
+    /// any memory instructions. This only holds once `unrolling`, `flatten_cfg` and the final
+    /// `mem2reg` pass have been run.
+    pub(crate) fn verify_ready_for_acir_gen(&self) -> Result<(), SsaVerifierError> {
+        for function in self.functions.values() {
+            let is_compiled_to_acir =
+                function.id() == self.main_id || function.runtime().is_folded();
+            if !matches!(function.runtime(), RuntimeType::Acir(_)) || !is_compiled_to_acir {
+                continue;
+            }
+            let blocks = function.reachable_blocks();
+            if blocks.len() > 1 {
+                return Err(SsaVerifierError::UnflattenedAcirFunction {
+                    function: function.name().to_owned(),
+                    blocks: blocks.len(),
+                });
+            }
+            for block in blocks {
+                for instruction in function.dfg[block].instructions() {
+                    let instruction = &function.dfg[*instruction];
+                    if matches!(
+                        instruction,
+                        Instruction::Allocate
+                            | Instruction::Load { .. }
+                            | Instruction::Store { .. }
+                    ) {
+                        return Err(SsaVerifierError::MemoryInstructionInAcir {
+                            function: function.name().to_owned(),
+                            instruction: instruction_name(instruction),
+                            block,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+struct FunctionVerifier<'f> {
+    ssa: &'f Ssa,
+    function: &'f Function,
+    dom_tree: DominatorTree,
+
+    /// The block and position within that block of each instruction in a reachable block
+    instruction_locations: HashMap<InstructionId, (BasicBlockId, usize)>,
+}
+
+impl<'f> FunctionVerifier<'f> {
+    fn new(ssa: &'f Ssa, function: &'f Function) -> Self {
+        let mut instruction_locations = HashMap::default();
+        for block in function.reachable_blocks() {
+            for (index, instruction) in function.dfg[block].instructions().iter().enumerate() {
+                instruction_locations.insert(*instruction, (block, index));
+            }
+        }
+        let dom_tree = DominatorTree::with_function(function);
+        Self { ssa, function, dom_tree, instruction_locations }
+    }
+
+    fn dfg(&self) -> &'f DataFlowGraph {
+        &self.function.dfg
+    }
+
+    fn verify(mut self) -> Result<(), SsaVerifierError> {
+        for block in self.function.reachable_blocks() {
+            for (index, instruction) in self.dfg()[block].instructions().iter().enumerate() {
+                let mut operands = Vec::new();
+                self.dfg()[*instruction].for_each_value(|value| operands.push(value));
+                for value in operands {
+                    self.check_dominates_use(value, block, Some(index))?;
+                }
+                self.check_instruction_types(*instruction)?;
+            }
+
+            let Some(terminator) = self.dfg()[block].terminator() else {
+                return Err(SsaVerifierError::MissingTerminator {
+                    function: self.function.name().to_owned(),
+                    block,
+                });
+            };
+            let mut operands = Vec::new();
+            terminator.for_each_value(|value| operands.push(value));
+            for value in operands {
+                self.check_dominates_use(value, block, None)?;
+            }
+            self.check_terminator(terminator)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the definition of `value` dominates its use in `block`. `position` is the
+    /// index of the using instruction within the block, or None if it is used by the terminator.
+    fn check_dominates_use(
+        &mut self,
+        value: ValueId,
+        block: BasicBlockId,
+        position: Option<usize>,
+    ) -> Result<(), SsaVerifierError> {
+        let value = self.dfg().resolve(value);
+        let (definition_block, definition_position) = match &self.dfg()[value] {
+            Value::Param { block, .. } => (*block, None),
+            Value::Instruction { instruction, .. } => {
+                match self.instruction_locations.get(instruction) {
+                    Some((block, index)) => (*block, Some(*index)),
+                    None => {
+                        return Err(SsaVerifierError::DetachedValue {
+                            function: self.function.name().to_owned(),
+                            value,
+                        })
+                    }
+                }
+            }
+            // Constants, functions and intrinsics are available everywhere
+            _ => return Ok(()),
+        };
+
+        let dominates = if definition_block == block {
+            match (definition_position, position) {
+                (Some(definition), Some(usage)) => definition < usage,
+                _ => true,
+            }
+        } else {
+            self.dom_tree.is_reachable(definition_block)
+                && self.dom_tree.dominates(definition_block, block)
+        };
+
+        if dominates {
+            Ok(())
+        } else {
+            Err(SsaVerifierError::UndominatedUse {
+                function: self.function.name().to_owned(),
+                value,
+                block,
+            })
+        }
+    }
+
+    fn check_instruction_types(&self, instruction: InstructionId) -> Result<(), SsaVerifierError> {
+        let dfg = self.dfg();
+        let typ = |value: ValueId| dfg.type_of_value(value);
+        let context = || format!("`{}`", instruction_name(&dfg[instruction]));
+
+        match &dfg[instruction] {
+            Instruction::Binary(binary) => {
+                self.expect_numeric(typ(binary.lhs), context)?;
+                self.expect_type(&typ(binary.lhs), typ(binary.rhs), context)
+            }
+            Instruction::Cast(value, _)
+            | Instruction::Not(value)
+            | Instruction::Truncate { value, .. }
+            | Instruction::RangeCheck { value, .. }
+            | Instruction::EnableSideEffects { condition: value } => {
+                self.expect_numeric(typ(*value), context)
+            }
+            Instruction::Constrain(lhs, rhs, _) => self.expect_type(&typ(*lhs), typ(*rhs), context),
+            Instruction::Call { func, arguments } => self.check_call(*func, arguments),
+            Instruction::Allocate | Instruction::IncrementRc { .. } => Ok(()),
+            Instruction::Load { address } => {
+                let element_type = self.expect_reference(typ(*address), context)?;
+                let result = dfg.instruction_results(instruction)[0];
+                self.expect_type(&element_type, typ(result), context)
+            }
+            Instruction::Store { address, value } => {
+                let element_type = self.expect_reference(typ(*address), context)?;
+                self.expect_type(&element_type, typ(*value), context)
+            }
+            Instruction::ArrayGet { array, index } => {
+                self.expect_array(typ(*array), context)?;
+                self.expect_numeric(typ(*index), context)
+            }
+            Instruction::ArraySet { array, index, value } => {
+                let array_type = typ(*array);
+                let element_types = self.expect_array(array_type.clone(), context)?;
+                self.expect_numeric(typ(*index), context)?;
+                let value_type = typ(*value);
+                if element_types.contains(&value_type) {
+                    Ok(())
+                } else {
+                    let expected = format!("an element type of {array_type}");
+                    Err(self.type_mismatch(context(), expected, value_type))
+                }
+            }
+        }
+    }
+
+    /// Checks that calls to other functions in this program pass arguments
+    /// matching the parameters of the called function
+    fn check_call(&self, func: ValueId, arguments: &[ValueId]) -> Result<(), SsaVerifierError> {
+        let dfg = self.dfg();
+        let callee = match &dfg[dfg.resolve(func)] {
+            Value::Function(id) => match self.ssa.functions.get(id) {
+                Some(callee) => callee,
+                None => return Ok(()),
+            },
+            Value::Intrinsic(_) | Value::ForeignFunction(_) => return Ok(()),
+            _ => {
+                let found = dfg.type_of_value(func);
+                return self.expect_type(&Type::Function, found, || "`call`".to_owned());
+            }
+        };
+
+        let context = || format!("call to {}", callee.name());
+        let parameters = callee.parameters();
+        self.expect_arity(parameters.len(), arguments.len(), context)?;
+        for (parameter, argument) in parameters.iter().zip(arguments) {
+            let expected = callee.dfg.type_of_value(*parameter);
+            self.expect_type(&expected, dfg.type_of_value(*argument), context)?;
+        }
+        Ok(())
+    }
+
+    fn check_terminator(&self, terminator: &TerminatorInstruction) -> Result<(), SsaVerifierError> {
+        let dfg = self.dfg();
+        match terminator {
+            TerminatorInstruction::Jmp { destination, arguments, .. } => {
+                let context = || format!("jmp to {destination}");
+                let parameters = dfg[*destination].parameters();
+                self.expect_arity(parameters.len(), arguments.len(), context)?;
+                for (parameter, argument) in parameters.iter().zip(arguments) {
+                    let expected = dfg.type_of_value(*parameter);
+                    self.expect_type(&expected, dfg.type_of_value(*argument), context)?;
+                }
+                Ok(())
+            }
+            TerminatorInstruction::JmpIf { condition, then_destination, else_destination } => {
+                let context = || "jmpif".to_owned();
+                self.expect_numeric(dfg.type_of_value(*condition), context)?;
+                // A jmpif cannot pass arguments to its destinations
+                for destination in [then_destination, else_destination] {
+                    let parameters = dfg[*destination].parameters().len();
+                    self.expect_arity(parameters, 0, || format!("jmpif to {destination}"))?;
+                }
+                Ok(())
+            }
+            TerminatorInstruction::Return { .. } => Ok(()),
+        }
+    }
+
+    fn expect_type(
+        &self,
+        expected: &Type,
+        found: Type,
+        context: impl Fn() -> String,
+    ) -> Result<(), SsaVerifierError> {
+        if *expected == found {
+            Ok(())
+        } else {
+            Err(self.type_mismatch(context(), expected.to_string(), found))
+        }
+    }
+
+    fn expect_numeric(
+        &self,
+        found: Type,
+        context: impl Fn() -> String,
+    ) -> Result<(), SsaVerifierError> {
+        match found {
+            Type::Numeric(_) => Ok(()),
+            found => Err(self.type_mismatch(context(), "a numeric type".to_owned(), found)),
+        }
+    }
+
+    /// Returns the element type of the given reference type
+    fn expect_reference(
+        &self,
+        found: Type,
+        context: impl Fn() -> String,
+    ) -> Result<Type, SsaVerifierError> {
+        match found {
+            Type::Reference(element) => Ok(element.as_ref().clone()),
+            found => Err(self.type_mismatch(context(), "a reference".to_owned(), found)),
+        }
+    }
+
+    /// Returns the element types of the given array or slice type
+    fn expect_array(
+        &self,
+        found: Type,
+        context: impl Fn() -> String,
+    ) -> Result<Vec<Type>, SsaVerifierError> {
+        match found {
+            Type::Array(elements, _) | Type::Slice(elements) => Ok(elements.as_ref().clone()),
+            found => Err(self.type_mismatch(context(), "an array or slice".to_owned(), found)),
+        }
+    }
+
+    fn expect_arity(
+        &self,
+        expected: usize,
+        actual: usize,
+        context: impl Fn() -> String,
+    ) -> Result<(), SsaVerifierError> {
+        if expected == actual {
+            Ok(())
+        } else {
+            let function = self.function.name().to_owned();
+            Err(SsaVerifierError::ArityMismatch { function, context: context(), expected, actual })
+        }
+    }
+
+    fn type_mismatch(&self, context: String, expected: String, found: Type) -> SsaVerifierError {
+        let function = self.function.name().to_owned();
+        SsaVerifierError::TypeMismatch { function, context, expected, found }
+    }
+}
+
+/// The name of an instruction as it is shown in printed SSA
+fn instruction_name(instruction: &Instruction) -> String {
+    match instruction {
+        Instruction::Binary(binary) => binary.operator.to_string(),
+        Instruction::Cast(..) => "cast".to_owned(),
+        Instruction::Not(_) => "not".to_owned(),
+        Instruction::Truncate { .. } => "truncate".to_owned(),
+        Instruction::Constrain(..) => "constrain".to_owned(),
+        Instruction::RangeCheck { .. } => "range_check".to_owned(),
+        Instruction::Call { .. } => "call".to_owned(),
+        Instruction::Allocate => "allocate".to_owned(),
+        Instruction::Load { .. } => "load".to_owned(),
+        Instruction::Store { .. } => "store".to_owned(),
+        Instruction::EnableSideEffects { .. } => "enable_side_effects".to_owned(),
+        Instruction::ArrayGet { .. } => "array_get".to_owned(),
+        Instruction::ArraySet { .. } => "array_set".to_owned(),
+        Instruction::IncrementRc { .. } => "inc_rc".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ssa::ssa_gen::Ssa;
+
+    use super::SsaVerifierError;
+
+    fn verify(src: &str) -> Result<(), SsaVerifierError> {
+        let ssa: Ssa = src.parse().unwrap();
+        ssa.verify()
+    }
+
+    #[test]
+    fn accepts_well_formed_ssa() {
+        let src = "
+            brillig fn main f0 {
+              b0(v0: u32):
+                v1 = allocate -> &mut u32
+                store v0 at v1
+                jmp b1(u32 0)
+              b1(v2: u32):
+                v3 = lt v2, v0
+                jmpif v3 then: b2, else: b3
+              b2():
+                v4 = load v1 -> u32
+                v5 = add v2, v4
+                jmp b1(v5)
+              b3():
+                return v2
+            }
+        ";
+        assert_eq!(verify(src), Ok(()));
+    }
+
+    #[test]
+    fn rejects_use_in_non_dominated_block() {
+        // v2 is defined in b1 but used in b3, which can be reached without passing through b1
+        let src = "
+            acir fn main f0 {
+              b0(v0: u1, v1: Field):
+                jmpif v0 then: b1, else: b2
+              b1():
+                v2 = add v1, Field 1
+                jmp b3()
+              b2():
+                jmp b3()
+              b3():
+                v3 = mul v2, v1
+                return v3
+            }
+        ";
+        assert!(matches!(verify(src), Err(SsaVerifierError::UndominatedUse { .. })));
+    }
+
+    #[test]
+    fn rejects_mismatched_operand_types() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: Field, v1: u32):
+                v2 = add v0, v1
+                return v2
+            }
+        ";
+        assert!(matches!(verify(src), Err(SsaVerifierError::TypeMismatch { .. })));
+    }
+
+    #[test]
+    fn rejects_jump_with_wrong_number_of_arguments() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: Field):
+                jmp b1(v0)
+              b1(v1: Field, v2: Field):
+                return v1
+            }
+        ";
+        let error = verify(src).unwrap_err();
+        assert!(
+            matches!(error, SsaVerifierError::ArityMismatch { expected: 2, actual: 1, .. }),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn rejects_memory_instructions_in_acir_functions() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: Field):
+                v1 = allocate -> &mut Field
+                store v0 at v1
+                v2 = load v1 -> Field
+                return v2
+            }
+        ";
+        let ssa: Ssa = src.parse().unwrap();
+        assert_eq!(ssa.verify(), Ok(()));
+        assert!(matches!(
+            ssa.verify_ready_for_acir_gen(),
+            Err(SsaVerifierError::MemoryInstructionInAcir { .. })
+        ));
+
+        // mem2reg removes the load and store, leaving the allocation unused
+        let ssa = ssa.mem2reg().dead_instruction_elimination();
+        assert_eq!(ssa.verify_ready_for_acir_gen(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_unflattened_acir_functions() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: u1):
+                jmpif v0 then: b1, else: b2
+              b1():
+                jmp b2()
+              b2():
+                return
+            }
+        ";
+        let ssa: Ssa = src.parse().unwrap();
+        assert_eq!(ssa.verify(), Ok(()));
+        assert!(matches!(
+            ssa.verify_ready_for_acir_gen(),
+            Err(SsaVerifierError::UnflattenedAcirFunction { blocks: 3, .. })
+        ));
+
+        let ssa = ssa.flatten_cfg();
+        assert_eq!(ssa.verify_ready_for_acir_gen(), Ok(()));
+    }
+}
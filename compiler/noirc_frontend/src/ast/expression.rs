@@ -23,6 +23,8 @@ pub enum ExpressionKind {
     Cast(Box<CastExpression>),
     Infix(Box<InfixExpression>),
     If(Box<IfExpression>),
+    Match(Box<MatchExpression>),
+    Comptime(BlockExpression),
     Variable(Path),
     Tuple(Vec<Expression>),
     Lambda(Box<Lambda>),
@@ -354,6 +356,32 @@ pub struct IfExpression {
     pub alternative: Option<Expression>,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MatchExpression {
+    pub expression: Expression,
+    pub rules: Vec<(MatchPattern, Expression)>,
+}
+
+/// The pattern on the left hand side of a match arm.
+///
+/// A plain, single-segment identifier such as `x` or `_` is always a binding which matches
+/// any value. Enum variants must be written with their enum's path, e.g. `Option::None`
+/// or `Option::Some(x)`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MatchPattern {
+    Binding(Pattern),
+    Variant(Path, Vec<Pattern>, Span),
+}
+
+impl MatchPattern {
+    pub fn span(&self) -> Span {
+        match self {
+            MatchPattern::Binding(pattern) => pattern.span(),
+            MatchPattern::Variant(_, _, span) => *span,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Lambda {
     pub parameters: Vec<(Pattern, UnresolvedType)>,
@@ -377,6 +405,10 @@ pub struct FunctionDefinition {
     /// True if this function was defined with the 'unconstrained' keyword
     pub is_unconstrained: bool,
 
+    /// True if this function was defined with the 'comptime' keyword and
+    /// may only be evaluated at compile-time
+    pub is_comptime: bool,
+
     /// Indicate if this function was defined with the 'pub' keyword
     pub visibility: FunctionVisibility,
 
@@ -491,6 +523,8 @@ impl Display for ExpressionKind {
             Cast(cast) => cast.fmt(f),
             Infix(infix) => infix.fmt(f),
             If(if_expr) => if_expr.fmt(f),
+            Match(match_expr) => match_expr.fmt(f),
+            Comptime(block) => write!(f, "comptime {block}"),
             Variable(path) => path.fmt(f),
             Constructor(constructor) => constructor.fmt(f),
             MemberAccess(access) => access.fmt(f),
@@ -645,6 +679,29 @@ impl Display for IfExpression {
     }
 }
 
+impl Display for MatchExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "match {} {{", self.expression)?;
+        for (pattern, branch) in &self.rules {
+            writeln!(f, "    {pattern} => {branch},")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl Display for MatchPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchPattern::Binding(pattern) => pattern.fmt(f),
+            MatchPattern::Variant(path, fields, _) if fields.is_empty() => path.fmt(f),
+            MatchPattern::Variant(path, fields, _) => {
+                let fields = vecmap(fields, ToString::to_string);
+                write!(f, "{path}({})", fields.join(", "))
+            }
+        }
+    }
+}
+
 impl Display for Lambda {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let parameters = vecmap(&self.parameters, |(name, r#type)| format!("{name}: {type}"));
@@ -677,6 +734,7 @@ impl FunctionDefinition {
             is_open: false,
             is_internal: false,
             is_unconstrained: false,
+            is_comptime: false,
             visibility: FunctionVisibility::Private,
             generics: generics.clone(),
             parameters: p,
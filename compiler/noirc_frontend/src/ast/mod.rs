@@ -4,6 +4,7 @@
 //!
 //! Noir's Ast is produced by the parser and taken as input to name resolution,
 //! where it is converted into the Hir (defined in the hir_def module).
+mod enumeration;
 mod expression;
 mod function;
 mod statement;
@@ -11,6 +12,7 @@ mod structure;
 mod traits;
 mod type_alias;
 
+pub use enumeration::*;
 pub use expression::*;
 pub use function::*;
 
@@ -50,6 +52,9 @@ pub enum UnresolvedTypeData {
     /// A Trait as return type or parameter of function, including its generics
     TraitAsType(Path, Vec<UnresolvedType>),
 
+    /// An associated type of a trait accessed through a type: `<T as Trait>::Output`
+    AsTraitPath(Box<UnresolvedType>, Path, Ident),
+
     /// &mut T
     MutableReference(Box<UnresolvedType>),
 
@@ -156,6 +161,7 @@ impl std::fmt::Display for UnresolvedTypeData {
             Error => write!(f, "error"),
             Unspecified => write!(f, "unspecified"),
             Parenthesized(typ) => write!(f, "({typ})"),
+            AsTraitPath(typ, trait_path, name) => write!(f, "<{typ} as {trait_path}>::{name}"),
         }
     }
 }
@@ -285,6 +291,15 @@ pub enum FunctionVisibility {
     PublicCrate,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// Represents whether a `use` declaration is marked `pub`.
+/// Imported items can be referenced from outside their module either way, so this only
+/// records how the declaration was written.
+pub enum ItemVisibility {
+    Public,
+    Private,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 /// Represents whether the parameter is public or known only to the prover.
 pub enum Visibility {
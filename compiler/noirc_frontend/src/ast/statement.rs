@@ -5,8 +5,8 @@ use crate::lexer::token::SpannedToken;
 use crate::parser::{ParserError, ParserErrorReason};
 use crate::token::Token;
 use crate::{
-    BlockExpression, Expression, ExpressionKind, IndexExpression, MemberAccessExpression,
-    MethodCallExpression, UnresolvedType,
+    BlockExpression, Expression, ExpressionKind, IndexExpression, ItemVisibility,
+    MemberAccessExpression, MethodCallExpression, UnresolvedType,
 };
 use acvm::FieldElement;
 use iter_extended::vecmap;
@@ -35,6 +35,10 @@ pub enum StatementKind {
     Expression(Expression),
     Assign(AssignStatement),
     For(ForLoopStatement),
+    While(WhileStatement),
+    Loop(Expression),
+    Break,
+    Continue,
     // This is an expression with a trailing semi-colon
     Semi(Expression),
     // This statement is the result of a recovered parse error.
@@ -58,6 +62,8 @@ impl Statement {
             StatementKind::Let(_)
             | StatementKind::Constrain(_)
             | StatementKind::Assign(_)
+            | StatementKind::Break
+            | StatementKind::Continue
             | StatementKind::Semi(_)
             | StatementKind::Error => {
                 // To match rust, statements always require a semicolon, even at the end of a block
@@ -66,13 +72,16 @@ impl Statement {
                 }
                 self.kind
             }
-            // A semicolon on a for loop is optional and does nothing
-            StatementKind::For(_) => self.kind,
+            // A semicolon on a loop is optional and does nothing
+            StatementKind::For(_) | StatementKind::While(_) | StatementKind::Loop(_) => self.kind,
 
             StatementKind::Expression(expr) => {
                 match (&expr.kind, semi, last_statement_in_block) {
                     // Semicolons are optional for these expressions
-                    (ExpressionKind::Block(_), semi, _) | (ExpressionKind::If(_), semi, _) => {
+                    (ExpressionKind::Block(_), semi, _)
+                    | (ExpressionKind::If(_), semi, _)
+                    | (ExpressionKind::Match(_), semi, _)
+                    | (ExpressionKind::Comptime(_), semi, _) => {
                         if semi.is_some() {
                             StatementKind::Semi(expr)
                         } else {
@@ -242,8 +251,12 @@ pub trait Recoverable {
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ImportStatement {
+    pub visibility: ItemVisibility,
     pub path: Path,
     pub alias: Option<Ident>,
+    /// True for `use path::*`, in which case `path` refers to the module to import every
+    /// visible item from.
+    pub is_glob: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
@@ -278,6 +291,7 @@ impl Display for UseTree {
                 let tree = vecmap(trees, ToString::to_string).join(", ");
                 write!(f, "{tree}}}")
             }
+            UseTreeKind::Glob => write!(f, "::*"),
         }
     }
 }
@@ -286,10 +300,11 @@ impl Display for UseTree {
 pub enum UseTreeKind {
     Path(Ident, Option<Ident>),
     List(Vec<UseTree>),
+    Glob,
 }
 
 impl UseTree {
-    pub fn desugar(self, root: Option<Path>) -> Vec<ImportStatement> {
+    pub fn desugar(self, root: Option<Path>, visibility: ItemVisibility) -> Vec<ImportStatement> {
         let prefix = if let Some(mut root) = root {
             root.segments.extend(self.prefix.segments);
             root
@@ -299,10 +314,14 @@ impl UseTree {
 
         match self.kind {
             UseTreeKind::Path(name, alias) => {
-                vec![ImportStatement { path: prefix.join(name), alias }]
+                vec![ImportStatement { visibility, path: prefix.join(name), alias, is_glob: false }]
             }
-            UseTreeKind::List(trees) => {
-                trees.into_iter().flat_map(|tree| tree.desugar(Some(prefix.clone()))).collect()
+            UseTreeKind::List(trees) => trees
+                .into_iter()
+                .flat_map(|tree| tree.desugar(Some(prefix.clone()), visibility))
+                .collect(),
+            UseTreeKind::Glob => {
+                vec![ImportStatement { visibility, path: prefix, alias: None, is_glob: true }]
             }
         }
     }
@@ -614,6 +633,15 @@ pub struct ForLoopStatement {
     pub span: Span,
 }
 
+/// `while condition { block }`. Since the number of iterations is only known at runtime,
+/// these are only allowed in unconstrained functions.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct WhileStatement {
+    pub condition: Expression,
+    pub block: Expression,
+    pub span: Span,
+}
+
 impl Display for StatementKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -622,6 +650,10 @@ impl Display for StatementKind {
             StatementKind::Expression(expression) => expression.fmt(f),
             StatementKind::Assign(assign) => assign.fmt(f),
             StatementKind::For(for_loop) => for_loop.fmt(f),
+            StatementKind::While(while_loop) => while_loop.fmt(f),
+            StatementKind::Loop(block) => write!(f, "loop {block}"),
+            StatementKind::Break => write!(f, "break"),
+            StatementKind::Continue => write!(f, "continue"),
             StatementKind::Semi(semi) => write!(f, "{semi};"),
             StatementKind::Error => write!(f, "Error"),
         }
@@ -676,7 +708,13 @@ impl Display for PathKind {
 
 impl Display for ImportStatement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.visibility == ItemVisibility::Public {
+            write!(f, "pub ")?;
+        }
         write!(f, "use {}", self.path)?;
+        if self.is_glob {
+            write!(f, "::*")?;
+        }
         if let Some(alias) = &self.alias {
             write!(f, " as {alias}")?;
         }
@@ -711,3 +749,9 @@ impl Display for ForLoopStatement {
         write!(f, "for {} in {range} {}", self.identifier, self.block)
     }
 }
+
+impl Display for WhileStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "while {} {}", self.condition, self.block)
+    }
+}
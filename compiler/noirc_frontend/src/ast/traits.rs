@@ -42,12 +42,13 @@ pub enum TraitItem {
 }
 
 /// Ast node for an impl of a concrete type
-/// `impl object_type<generics> { ... methods ... }`
+/// `impl object_type<generics> where where_clauses { ... methods ... }`
 #[derive(Clone, Debug)]
 pub struct TypeImpl {
     pub object_type: UnresolvedType,
     pub type_span: Span,
     pub generics: UnresolvedGenerics,
+    pub where_clause: Vec<UnresolvedTraitConstraint>,
     pub methods: Vec<NoirFunction>,
 }
 
@@ -99,7 +100,14 @@ impl Display for TypeImpl {
         let generics = vecmap(&self.generics, |generic| generic.to_string());
         let generics = if generics.is_empty() { "".into() } else { generics.join(", ") };
 
-        writeln!(f, "impl{} {} {{", generics, self.object_type)?;
+        write!(f, "impl{} {}", generics, self.object_type)?;
+
+        if !self.where_clause.is_empty() {
+            let where_clause = vecmap(&self.where_clause, ToString::to_string);
+            write!(f, " where {}", where_clause.join(", "))?;
+        }
+
+        writeln!(f, " {{")?;
 
         for method in self.methods.iter() {
             let method = method.to_string();
@@ -154,10 +162,10 @@ impl Display for TraitItem {
                 }
             }
             TraitItem::Constant { name, typ, default_value } => {
-                write!(f, "let {name}: {typ}")?;
+                write!(f, "const {name}: {typ}")?;
 
                 if let Some(default_value) = default_value {
-                    write!(f, "{default_value};")
+                    write!(f, " = {default_value};")
                 } else {
                     write!(f, ";")
                 }
@@ -208,7 +216,7 @@ impl Display for TraitImplItem {
             TraitImplItem::Function(function) => function.fmt(f),
             TraitImplItem::Type { name, alias } => write!(f, "type {name} = {alias};"),
             TraitImplItem::Constant(name, typ, value) => {
-                write!(f, "let {name}: {typ} = {value};")
+                write!(f, "const {name}: {typ} = {value};")
             }
         }
     }
@@ -0,0 +1,184 @@
+use noirc_errors::{CustomDiagnostic as Diagnostic, Location};
+use thiserror::Error;
+
+use crate::Type;
+
+/// Errors which may occur while evaluating `comptime` code.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum InterpreterError {
+    #[error("Non-comptime variable referenced in comptime code")]
+    NonComptimeVarReferenced { name: String, location: Location },
+    #[error("Comptime function used as a value")]
+    ComptimeFunctionUsedAsValue { name: String, location: Location },
+    #[error("Non-comptime function called from a comptime global")]
+    NonComptimeFunctionCallInGlobal { name: String, location: Location },
+    #[error("Function cannot be called at compile-time")]
+    UnsupportedFunction { name: String, location: Location },
+    #[error("Expression cannot be evaluated at compile-time")]
+    UnsupportedExpression { expression: &'static str, location: Location },
+    #[error("Type is not known at compile-time")]
+    TypeNotKnown { typ: Type, location: Location },
+    #[error("Integer out of range")]
+    IntegerOutOfRange { typ: Type, location: Location },
+    #[error("Division by zero")]
+    DivisionByZero { location: Location },
+    #[error("Index out of bounds")]
+    IndexOutOfBounds { index: u128, length: usize, location: Location },
+    #[error("Failed constraint")]
+    FailingConstraint { message: Option<String>, location: Location },
+    #[error("Value cannot be used outside of comptime code")]
+    CannotSplice { typ: Type, location: Location },
+    #[error("Comptime global used as an array length is not an integer")]
+    NonIntegerArrayLength { location: Location },
+    #[error("Unexpected value")]
+    TypeMismatch { expected: &'static str, location: Location },
+    #[error("Comptime call depth exceeded")]
+    CallDepthExceeded { limit: usize, location: Location },
+    #[error("Comptime evaluation step limit exceeded")]
+    StepLimitExceeded { limit: usize, location: Location },
+
+    // These cases are not errors but are used to stop evaluating the rest of a
+    // loop's body until the enclosing loop is reached. They are only reported if
+    // no loop catches them, with the location of the block they were found in.
+    #[error("Break")]
+    Break { location: Location },
+    #[error("Continue")]
+    Continue { location: Location },
+}
+
+impl InterpreterError {
+    pub fn location(&self) -> Location {
+        match self {
+            InterpreterError::NonComptimeVarReferenced { location, .. }
+            | InterpreterError::ComptimeFunctionUsedAsValue { location, .. }
+            | InterpreterError::NonComptimeFunctionCallInGlobal { location, .. }
+            | InterpreterError::UnsupportedFunction { location, .. }
+            | InterpreterError::UnsupportedExpression { location, .. }
+            | InterpreterError::TypeNotKnown { location, .. }
+            | InterpreterError::IntegerOutOfRange { location, .. }
+            | InterpreterError::DivisionByZero { location }
+            | InterpreterError::IndexOutOfBounds { location, .. }
+            | InterpreterError::FailingConstraint { location, .. }
+            | InterpreterError::CannotSplice { location, .. }
+            | InterpreterError::NonIntegerArrayLength { location }
+            | InterpreterError::TypeMismatch { location, .. }
+            | InterpreterError::CallDepthExceeded { location, .. }
+            | InterpreterError::StepLimitExceeded { location, .. }
+            | InterpreterError::Break { location }
+            | InterpreterError::Continue { location } => *location,
+        }
+    }
+}
+
+impl From<InterpreterError> for Diagnostic {
+    fn from(error: InterpreterError) -> Diagnostic {
+        let span = error.location().span;
+        match error {
+            InterpreterError::NonComptimeVarReferenced { name, .. } => Diagnostic::simple_error(
+                format!("Non-comptime variable `{name}` referenced in comptime code"),
+                "Non-comptime variables can't be used in comptime code".to_string(),
+                span,
+            ),
+            InterpreterError::ComptimeFunctionUsedAsValue { name, .. } => Diagnostic::simple_error(
+                format!("Comptime function `{name}` can only be called"),
+                "Comptime functions can't be used as values at runtime".to_string(),
+                span,
+            ),
+            InterpreterError::NonComptimeFunctionCallInGlobal { name, .. } => {
+                let mut diagnostic = Diagnostic::simple_error(
+                    format!(
+                        "Function `{name}` must be `comptime` to be called from a comptime global"
+                    ),
+                    "Called from a comptime global".to_string(),
+                    span,
+                );
+                diagnostic.add_note("Comptime globals are evaluated before the rest of the crate is type checked, so they can only call comptime functions of the current crate".to_string());
+                diagnostic
+            }
+            InterpreterError::UnsupportedFunction { name, .. } => Diagnostic::simple_error(
+                format!("`{name}` cannot be called at compile-time"),
+                "Not supported in comptime code".to_string(),
+                span,
+            ),
+            InterpreterError::UnsupportedExpression { expression, .. } => Diagnostic::simple_error(
+                format!("{expression} cannot be evaluated at compile-time"),
+                "Not supported in comptime code".to_string(),
+                span,
+            ),
+            InterpreterError::TypeNotKnown { typ, .. } => Diagnostic::simple_error(
+                format!("Type `{typ}` is not known at compile-time"),
+                "Comptime code can't depend on generic types".to_string(),
+                span,
+            ),
+            InterpreterError::IntegerOutOfRange { typ, .. } => Diagnostic::simple_error(
+                format!("Integer is out of range for type `{typ}`"),
+                "Overflow in comptime code".to_string(),
+                span,
+            ),
+            InterpreterError::DivisionByZero { .. } => Diagnostic::simple_error(
+                "Attempted to divide by zero".to_string(),
+                "Division by zero in comptime code".to_string(),
+                span,
+            ),
+            InterpreterError::IndexOutOfBounds { index, length, .. } => Diagnostic::simple_error(
+                format!("Index out of bounds: the length is {length} but the index is {index}"),
+                "Index out of bounds in comptime code".to_string(),
+                span,
+            ),
+            InterpreterError::FailingConstraint { message, .. } => {
+                let secondary = match message {
+                    Some(message) => format!("Failed assertion: '{message}'"),
+                    None => "Failed assertion".to_string(),
+                };
+                Diagnostic::simple_error(
+                    "Assertion failed in comptime code".to_string(),
+                    secondary,
+                    span,
+                )
+            }
+            InterpreterError::CannotSplice { typ, .. } => Diagnostic::simple_error(
+                format!("Value of type `{typ}` cannot be used outside of comptime code"),
+                "Only data types can be the result of comptime code".to_string(),
+                span,
+            ),
+            InterpreterError::NonIntegerArrayLength { .. } => Diagnostic::simple_error(
+                "Comptime global used as an array length must evaluate to an integer".to_string(),
+                "Not an integer".to_string(),
+                span,
+            ),
+            InterpreterError::TypeMismatch { expected, .. } => Diagnostic::simple_error(
+                format!("Expected a value of type {expected} in comptime code"),
+                "Unexpected value".to_string(),
+                span,
+            ),
+            InterpreterError::CallDepthExceeded { limit, .. } => {
+                let mut diagnostic = Diagnostic::simple_error(
+                    format!("Comptime code exceeded the maximum call depth of {limit}"),
+                    "Called here".to_string(),
+                    span,
+                );
+                diagnostic.add_note("This is usually caused by infinite recursion".to_string());
+                diagnostic
+            }
+            InterpreterError::StepLimitExceeded { limit, .. } => {
+                let mut diagnostic = Diagnostic::simple_error(
+                    format!("Comptime code did not finish after evaluating {limit} expressions"),
+                    "Evaluation stopped here".to_string(),
+                    span,
+                );
+                diagnostic.add_note("This is usually caused by an infinite loop".to_string());
+                diagnostic
+            }
+            InterpreterError::Break { .. } => Diagnostic::simple_error(
+                "break is only allowed within loops".to_string(),
+                "Found break outside of a loop in this block".to_string(),
+                span,
+            ),
+            InterpreterError::Continue { .. } => Diagnostic::simple_error(
+                "continue is only allowed within loops".to_string(),
+                "Found continue outside of a loop in this block".to_string(),
+                span,
+            ),
+        }
+    }
+}
@@ -0,0 +1,1172 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use acvm::FieldElement;
+use iter_extended::{try_vecmap, vecmap};
+use noirc_errors::Location;
+
+use crate::{
+    hir_def::{
+        expr::{
+            HirArrayLiteral, HirBlockExpression, HirCallExpression, HirCastExpression,
+            HirConstructorExpression, HirEnumConstructorExpression, HirExpression, HirIdent,
+            HirIfExpression, HirIndexExpression, HirInfixExpression, HirLambda, HirLiteral,
+            HirMatchExpression, HirMatchPattern, HirMemberAccess, HirPrefixExpression,
+        },
+        stmt::{HirForStatement, HirLValue, HirPattern, HirStatement, HirWhileStatement},
+    },
+    node_interner::{
+        DefinitionKind, ExprId, FuncId, NodeInterner, StmtId, TraitImplKind, TraitMethodId,
+    },
+    BinaryOpKind, FunctionKind, Signedness, Type, TypeBinding, TypeBindings, TypeVariable, UnaryOp,
+};
+
+use super::{
+    errors::InterpreterError,
+    value::{check_range, digits_to_array, integer_value, into_elements, Value},
+};
+
+type IResult<T> = Result<T, InterpreterError>;
+
+/// Limits on the evaluation of `comptime` code. Without these, an infinitely recursive
+/// function would overflow the compiler's stack and an infinite loop would never finish.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InterpreterLimits {
+    /// The maximum number of nested function and closure calls
+    pub max_call_depth: usize,
+
+    /// The maximum number of expressions evaluated for a single comptime global, block or call
+    pub max_steps: usize,
+}
+
+impl Default for InterpreterLimits {
+    fn default() -> Self {
+        Self { max_call_depth: 128, max_steps: 10_000_000 }
+    }
+}
+
+/// A tree-walking interpreter over the type checked HIR. This is used to evaluate
+/// `comptime` code during elaboration.
+pub struct Interpreter<'interner> {
+    interner: &'interner mut NodeInterner,
+
+    /// Each scope maps the variables defined in it to their current value. Values are
+    /// shared so that mutable references to a variable can be taken.
+    scopes: Vec<HashMap<crate::node_interner::DefinitionId, Rc<RefCell<Value>>>>,
+
+    limits: InterpreterLimits,
+
+    /// The number of function and closure calls currently being evaluated
+    call_depth: usize,
+
+    /// The number of expressions evaluated so far
+    steps: usize,
+}
+
+impl<'interner> Interpreter<'interner> {
+    pub fn new(interner: &'interner mut NodeInterner) -> Self {
+        Self {
+            interner,
+            scopes: vec![HashMap::new()],
+            limits: InterpreterLimits::default(),
+            call_depth: 0,
+            steps: 0,
+        }
+    }
+
+    pub fn with_limits(mut self, limits: InterpreterLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn evaluate(&mut self, id: ExprId) -> IResult<Value> {
+        self.steps += 1;
+        if self.steps > self.limits.max_steps {
+            let location = self.interner.expr_location(&id);
+            return Err(InterpreterError::StepLimitExceeded {
+                limit: self.limits.max_steps,
+                location,
+            });
+        }
+
+        match self.interner.expression(&id) {
+            HirExpression::Ident(ident) => self.evaluate_ident(ident, id),
+            HirExpression::Literal(literal) => self.evaluate_literal(literal, id),
+            HirExpression::Block(block) | HirExpression::Comptime(block) => {
+                self.evaluate_block(block, id)
+            }
+            HirExpression::Prefix(prefix) => self.evaluate_prefix(prefix, id),
+            HirExpression::Infix(infix) => self.evaluate_infix(infix, id),
+            HirExpression::Index(index) => self.evaluate_index(index, id),
+            HirExpression::Constructor(constructor) => self.evaluate_constructor(constructor),
+            HirExpression::EnumConstructor(constructor) => {
+                self.evaluate_enum_constructor(constructor)
+            }
+            HirExpression::MemberAccess(access) => self.evaluate_access(access, id),
+            HirExpression::Call(call) => self.evaluate_call(call, id),
+            HirExpression::Cast(cast) => self.evaluate_cast(cast, id),
+            HirExpression::If(if_expr) => self.evaluate_if(if_expr, id),
+            HirExpression::Match(match_expr) => self.evaluate_match(match_expr, id),
+            HirExpression::Tuple(fields) => {
+                Ok(Value::Tuple(try_vecmap(fields, |field| self.evaluate(field))?))
+            }
+            HirExpression::Lambda(lambda) => self.evaluate_lambda(lambda, id),
+            HirExpression::TraitMethodReference(method) => {
+                let function_type = self.interner.id_type(id);
+                self.resolve_trait_method(id, function_type, method)
+            }
+            HirExpression::MethodCall(_) => {
+                unreachable!("Method calls are replaced by function calls during type checking")
+            }
+            HirExpression::Error => {
+                unreachable!("Encountered Error node during comptime evaluation")
+            }
+        }
+    }
+
+    fn evaluate_ident(&mut self, ident: HirIdent, id: ExprId) -> IResult<Value> {
+        let definition = self.interner.definition(ident.id);
+        match definition.kind.clone() {
+            DefinitionKind::Function(function) => {
+                let bindings = self.interner.get_instantiation_bindings(id);
+                Ok(Value::Function(function, follow_bindings(bindings)))
+            }
+            DefinitionKind::Global(expr) => self.evaluate(expr),
+            DefinitionKind::Local(_) => self.lookup(ident),
+            DefinitionKind::GenericType(type_variable) => {
+                let value = match &*type_variable.borrow() {
+                    TypeBinding::Bound(binding) => binding.evaluate_to_u64(),
+                    TypeBinding::Unbound(_) => None,
+                };
+                match value {
+                    Some(value) => Ok(Value::Field((value as u128).into())),
+                    None => {
+                        let typ =
+                            Type::NamedGeneric(type_variable, Rc::new(definition.name.clone()));
+                        Err(InterpreterError::TypeNotKnown { typ, location: ident.location })
+                    }
+                }
+            }
+        }
+    }
+
+    fn lookup(&self, ident: HirIdent) -> IResult<Value> {
+        Ok(self.lookup_slot(ident)?.borrow().clone())
+    }
+
+    fn lookup_slot(&self, ident: HirIdent) -> IResult<Rc<RefCell<Value>>> {
+        for scope in self.scopes.iter().rev() {
+            if let Some(value) = scope.get(&ident.id) {
+                return Ok(value.clone());
+            }
+        }
+
+        // Any variable not defined by the interpreter is a runtime variable
+        let name = self.interner.definition(ident.id).name.clone();
+        Err(InterpreterError::NonComptimeVarReferenced { name, location: ident.location })
+    }
+
+    fn define_pattern(
+        &mut self,
+        pattern: &HirPattern,
+        value: Value,
+        location: Location,
+    ) -> IResult<()> {
+        match pattern {
+            HirPattern::Identifier(ident) => {
+                let scope = self.scopes.last_mut().expect("There should always be a scope");
+                scope.insert(ident.id, Rc::new(RefCell::new(value)));
+                Ok(())
+            }
+            HirPattern::Mutable(pattern, _) => self.define_pattern(pattern, value, location),
+            HirPattern::Tuple(patterns, _) => match value {
+                Value::Tuple(fields) => {
+                    for (pattern, field) in patterns.iter().zip(fields) {
+                        self.define_pattern(pattern, field, location)?;
+                    }
+                    Ok(())
+                }
+                _ => Err(InterpreterError::TypeMismatch { expected: "tuple", location }),
+            },
+            HirPattern::Struct(_, patterns, _) => match value {
+                Value::Struct(mut fields) => {
+                    for (name, pattern) in patterns {
+                        let field = fields.remove(&name.0.contents).ok_or(
+                            InterpreterError::TypeMismatch { expected: "struct", location },
+                        )?;
+                        self.define_pattern(pattern, field, location)?;
+                    }
+                    Ok(())
+                }
+                _ => Err(InterpreterError::TypeMismatch { expected: "struct", location }),
+            },
+        }
+    }
+
+    /// Evaluate `f` within a new scope
+    fn in_new_scope<T>(&mut self, f: impl FnOnce(&mut Self) -> IResult<T>) -> IResult<T> {
+        self.scopes.push(HashMap::new());
+        let result = f(self);
+        self.scopes.pop();
+        result
+    }
+
+    pub fn evaluate_block(&mut self, block: HirBlockExpression, id: ExprId) -> IResult<Value> {
+        let location = self.interner.expr_location(&id);
+        self.in_new_scope(|this| {
+            let mut result = Value::Unit;
+            for statement in block.statements() {
+                result = this.evaluate_statement(*statement, location)?;
+            }
+            Ok(result)
+        })
+    }
+
+    /// Statements have no location of their own, so `block_location` is used for
+    /// any `break` or `continue` within the block.
+    fn evaluate_statement(
+        &mut self,
+        statement: StmtId,
+        block_location: Location,
+    ) -> IResult<Value> {
+        match self.interner.statement(&statement) {
+            HirStatement::Let(let_statement) => {
+                let location = self.interner.expr_location(&let_statement.expression);
+                let value = self.evaluate(let_statement.expression)?;
+                self.define_pattern(&let_statement.pattern, value, location)?;
+                Ok(Value::Unit)
+            }
+            HirStatement::Constrain(constrain) => {
+                let location = self.interner.expr_location(&constrain.0);
+                match self.evaluate(constrain.0)? {
+                    Value::Bool(true) => Ok(Value::Unit),
+                    Value::Bool(false) => {
+                        Err(InterpreterError::FailingConstraint { message: constrain.2, location })
+                    }
+                    _ => Err(InterpreterError::TypeMismatch { expected: "bool", location }),
+                }
+            }
+            HirStatement::Assign(assign) => {
+                let value = self.evaluate(assign.expression)?;
+                let location = self.interner.expr_location(&assign.expression);
+                self.store_lvalue(assign.lvalue, value, location)?;
+                Ok(Value::Unit)
+            }
+            HirStatement::For(for_loop) => self.evaluate_for(for_loop),
+            HirStatement::While(while_loop) => self.evaluate_while(while_loop),
+            HirStatement::Loop(block) => self.evaluate_loop(block),
+            HirStatement::Break => Err(InterpreterError::Break { location: block_location }),
+            HirStatement::Continue => Err(InterpreterError::Continue { location: block_location }),
+            HirStatement::Expression(expression) => self.evaluate(expression),
+            HirStatement::Semi(expression) => {
+                self.evaluate(expression)?;
+                Ok(Value::Unit)
+            }
+            HirStatement::Error => {
+                unreachable!("Encountered Error node during comptime evaluation")
+            }
+        }
+    }
+
+    fn evaluate_for(&mut self, for_loop: HirForStatement) -> IResult<Value> {
+        let location = self.interner.expr_location(&for_loop.start_range);
+        let start = self.evaluate(for_loop.start_range)?;
+        let end = self.evaluate(for_loop.end_range)?;
+
+        // Loop over the range as i128 but define the index with the type of the range
+        let (start, end, make_index): (i128, i128, Box<dyn Fn(i128) -> Value>) = match (start, end)
+        {
+            (Value::Field(start), Value::Field(end)) => {
+                let start = field_to_i128(start, location)?;
+                let end = field_to_i128(end, location)?;
+                (start, end, Box::new(|index| Value::Field((index as u128).into())))
+            }
+            (Value::Unsigned(start, bit_size), Value::Unsigned(end, _)) => {
+                let start = unsigned_to_i128(start, bit_size, location)?;
+                let end = unsigned_to_i128(end, bit_size, location)?;
+                (start, end, Box::new(move |index| Value::Unsigned(index as u128, bit_size)))
+            }
+            (Value::Signed(start, bit_size), Value::Signed(end, _)) => {
+                (start, end, Box::new(move |index| Value::Signed(index, bit_size)))
+            }
+            _ => return Err(InterpreterError::TypeMismatch { expected: "integer", location }),
+        };
+
+        for index in start..end {
+            let result = self.in_new_scope(|this| {
+                let index = Rc::new(RefCell::new(make_index(index)));
+                this.scopes.last_mut().unwrap().insert(for_loop.identifier.id, index);
+                this.evaluate(for_loop.block)
+            });
+            match result {
+                Ok(_) | Err(InterpreterError::Continue { .. }) => (),
+                Err(InterpreterError::Break { .. }) => break,
+                Err(other) => return Err(other),
+            }
+        }
+        Ok(Value::Unit)
+    }
+
+    fn evaluate_while(&mut self, while_loop: HirWhileStatement) -> IResult<Value> {
+        let location = self.interner.expr_location(&while_loop.condition);
+        loop {
+            match self.evaluate(while_loop.condition)? {
+                Value::Bool(true) => (),
+                Value::Bool(false) => break,
+                _ => return Err(InterpreterError::TypeMismatch { expected: "bool", location }),
+            }
+            match self.evaluate(while_loop.block) {
+                Ok(_) | Err(InterpreterError::Continue { .. }) => (),
+                Err(InterpreterError::Break { .. }) => break,
+                Err(other) => return Err(other),
+            }
+        }
+        Ok(Value::Unit)
+    }
+
+    fn evaluate_loop(&mut self, block: ExprId) -> IResult<Value> {
+        loop {
+            match self.evaluate(block) {
+                Ok(_) | Err(InterpreterError::Continue { .. }) => (),
+                Err(InterpreterError::Break { .. }) => return Ok(Value::Unit),
+                Err(other) => return Err(other),
+            }
+        }
+    }
+
+    /// Assign to an lvalue by copying its root value, updating the copy and storing it back.
+    fn store_lvalue(&mut self, lvalue: HirLValue, value: Value, location: Location) -> IResult<()> {
+        match lvalue {
+            HirLValue::Ident(ident, _) => {
+                *self.lookup_slot(ident)?.borrow_mut() = value;
+                Ok(())
+            }
+            HirLValue::Dereference { lvalue, .. } => {
+                match self.evaluate_lvalue(&lvalue, location)? {
+                    Value::Pointer(pointer) => {
+                        *pointer.borrow_mut() = value;
+                        Ok(())
+                    }
+                    _ => Err(InterpreterError::TypeMismatch { expected: "reference", location }),
+                }
+            }
+            HirLValue::MemberAccess { object, field_name, .. } => {
+                let mut object_value = self.evaluate_lvalue(&object, location)?;
+                match &mut object_value {
+                    Value::Struct(fields) => {
+                        fields.insert(field_name.0.contents, value);
+                    }
+                    Value::Tuple(fields) => {
+                        let index = tuple_index(&field_name.0.contents);
+                        fields[index] = value;
+                    }
+                    _ => {
+                        return Err(InterpreterError::TypeMismatch { expected: "struct", location })
+                    }
+                }
+                self.store_lvalue(*object, object_value, location)
+            }
+            HirLValue::Index { array, index, .. } => {
+                let index_location = self.interner.expr_location(&index);
+                let index = self.evaluate(index)?;
+                let mut array_value = self.evaluate_lvalue(&array, location)?;
+                match &mut array_value {
+                    Value::Array(elements) => {
+                        let index = checked_index(index, elements.len(), index_location)?;
+                        elements[index] = value;
+                    }
+                    _ => {
+                        return Err(InterpreterError::TypeMismatch { expected: "array", location })
+                    }
+                }
+                self.store_lvalue(*array, array_value, location)
+            }
+        }
+    }
+
+    fn evaluate_lvalue(&mut self, lvalue: &HirLValue, location: Location) -> IResult<Value> {
+        match lvalue {
+            HirLValue::Ident(ident, _) => self.lookup(*ident),
+            HirLValue::Dereference { lvalue, .. } => {
+                match self.evaluate_lvalue(lvalue, location)? {
+                    Value::Pointer(pointer) => Ok(pointer.borrow().clone()),
+                    _ => Err(InterpreterError::TypeMismatch { expected: "reference", location }),
+                }
+            }
+            HirLValue::MemberAccess { object, field_name, .. } => {
+                let object = self.evaluate_lvalue(object, location)?;
+                get_field(object, &field_name.0.contents, location)
+            }
+            HirLValue::Index { array, index, .. } => {
+                let index_location = self.interner.expr_location(index);
+                let index = self.evaluate(*index)?;
+                let elements = into_elements(self.evaluate_lvalue(array, location)?, location)?;
+                let index = checked_index(index, elements.len(), index_location)?;
+                Ok(elements[index].clone())
+            }
+        }
+    }
+
+    fn evaluate_literal(&mut self, literal: HirLiteral, id: ExprId) -> IResult<Value> {
+        let location = self.interner.expr_location(&id);
+        match literal {
+            HirLiteral::Unit => Ok(Value::Unit),
+            HirLiteral::Bool(value) => Ok(Value::Bool(value)),
+            HirLiteral::Integer(value, is_negative) => {
+                self.evaluate_integer(value, is_negative, id)
+            }
+            HirLiteral::Str(value) => Ok(Value::String(value)),
+            HirLiteral::FmtStr(..) => Err(InterpreterError::UnsupportedExpression {
+                expression: "Format strings",
+                location,
+            }),
+            HirLiteral::Array(HirArrayLiteral::Standard(elements)) => {
+                Ok(Value::Array(try_vecmap(elements, |element| self.evaluate(element))?))
+            }
+            HirLiteral::Array(HirArrayLiteral::Repeated { repeated_element, length }) => {
+                let element = self.evaluate(repeated_element)?;
+                match length.evaluate_to_u64() {
+                    Some(length) => Ok(Value::Array(vec![element; length as usize])),
+                    None => Err(InterpreterError::TypeNotKnown { typ: length, location }),
+                }
+            }
+        }
+    }
+
+    fn evaluate_integer(
+        &self,
+        value: FieldElement,
+        is_negative: bool,
+        id: ExprId,
+    ) -> IResult<Value> {
+        let location = self.interner.expr_location(&id);
+        let typ = self.interner.id_type(id).follow_bindings();
+        let out_of_range = || InterpreterError::IntegerOutOfRange { typ: typ.clone(), location };
+
+        match &typ {
+            Type::Integer(Signedness::Unsigned, bit_size) => {
+                let value = value.try_into_u128().ok_or_else(out_of_range)?;
+                if is_negative && value != 0 {
+                    return Err(out_of_range());
+                }
+                check_range(Value::Unsigned(value, *bit_size), location)
+            }
+            Type::Integer(Signedness::Signed, bit_size) => {
+                let value = value.try_into_u128().ok_or_else(out_of_range)?;
+                let value = i128::try_from(value).map_err(|_| out_of_range())?;
+                let value = if is_negative { -value } else { value };
+                check_range(Value::Signed(value, *bit_size), location)
+            }
+            // Integer literals which are still polymorphic default to Fields
+            Type::FieldElement | Type::TypeVariable(..) => {
+                Ok(Value::Field(if is_negative { -value } else { value }))
+            }
+            _ => Err(InterpreterError::TypeNotKnown { typ, location }),
+        }
+    }
+
+    fn evaluate_prefix(&mut self, prefix: HirPrefixExpression, id: ExprId) -> IResult<Value> {
+        let location = self.interner.expr_location(&id);
+        if self.interner.get_selected_impl_for_ident(id).is_some() {
+            // This is an operator on a non-primitive type, so call its trait impl instead
+            let method = self
+                .interner
+                .get_prefix_operator_trait_method(prefix.operator)
+                .expect("Operators with a selected impl should have a trait");
+            let rhs = self.evaluate(prefix.rhs)?;
+            return self.call_operator_trait(id, method, vec![(rhs, prefix.rhs)], location);
+        }
+
+        if let UnaryOp::MutableReference = prefix.operator {
+            // Reference the variable itself so that writes through the reference are visible to it
+            if let HirExpression::Ident(ident) = self.interner.expression(&prefix.rhs) {
+                if let DefinitionKind::Local(_) = self.interner.definition(ident.id).kind {
+                    return Ok(Value::Pointer(self.lookup_slot(ident)?));
+                }
+            }
+            let value = self.evaluate(prefix.rhs)?;
+            return Ok(Value::Pointer(Rc::new(RefCell::new(value))));
+        }
+
+        let rhs = self.evaluate(prefix.rhs)?;
+        match (prefix.operator, rhs) {
+            (UnaryOp::Minus, Value::Field(value)) => Ok(Value::Field(-value)),
+            (UnaryOp::Minus, Value::Signed(value, bit_size)) => {
+                check_range(Value::Signed(-value, bit_size), location)
+            }
+            (UnaryOp::Minus, Value::Unsigned(0, bit_size)) => Ok(Value::Unsigned(0, bit_size)),
+            (UnaryOp::Minus, Value::Unsigned(_, bit_size)) => {
+                Err(InterpreterError::IntegerOutOfRange {
+                    typ: Type::Integer(Signedness::Unsigned, bit_size),
+                    location,
+                })
+            }
+            (UnaryOp::Not, Value::Bool(value)) => Ok(Value::Bool(!value)),
+            (UnaryOp::Not, Value::Unsigned(value, bit_size)) => {
+                Ok(Value::Unsigned(!value & mask(bit_size), bit_size))
+            }
+            (UnaryOp::Not, Value::Signed(value, bit_size)) => Ok(Value::Signed(!value, bit_size)),
+            (UnaryOp::Dereference { .. }, Value::Pointer(pointer)) => Ok(pointer.borrow().clone()),
+            _ => Err(InterpreterError::TypeMismatch { expected: "operand", location }),
+        }
+    }
+
+    fn evaluate_infix(&mut self, infix: HirInfixExpression, id: ExprId) -> IResult<Value> {
+        let location = self.interner.expr_location(&id);
+        let lhs = self.evaluate(infix.lhs)?;
+        let rhs = self.evaluate(infix.rhs)?;
+        let operator = infix.operator.kind;
+
+        if self.interner.get_selected_impl_for_ident(id).is_none() {
+            return evaluate_binary_operation(lhs, operator, rhs, location);
+        }
+
+        // This is an operator on a non-primitive type, so call its trait impl instead
+        let method = self
+            .interner
+            .get_operator_trait_method(operator)
+            .expect("Operators with a selected impl should have a trait");
+        let arguments = vec![(lhs, infix.lhs), (rhs, infix.rhs)];
+        let result = self.call_operator_trait(id, method, arguments, location)?;
+
+        // `!=` negates `Eq::eq` and the ordering operators compare the tag of `Ord::cmp`'s
+        // result: Less = 0, Equal = 1, Greater = 2
+        let tag = match &result {
+            Value::Enum(tag, _) => *tag,
+            Value::Bool(value) if operator == BinaryOpKind::NotEqual => {
+                return Ok(Value::Bool(!value))
+            }
+            _ => return Ok(result),
+        };
+        match operator {
+            BinaryOpKind::Less => Ok(Value::Bool(tag == 0)),
+            BinaryOpKind::LessEqual => Ok(Value::Bool(tag != 2)),
+            BinaryOpKind::Greater => Ok(Value::Bool(tag == 2)),
+            BinaryOpKind::GreaterEqual => Ok(Value::Bool(tag != 0)),
+            _ => Ok(result),
+        }
+    }
+
+    /// Call the impl of the given operator trait method that was selected for `id`
+    fn call_operator_trait(
+        &mut self,
+        id: ExprId,
+        method: TraitMethodId,
+        arguments: Vec<(Value, ExprId)>,
+        location: Location,
+    ) -> IResult<Value> {
+        let argument_types = vecmap(&arguments, |(_, argument)| self.interner.id_type(argument));
+        let return_type = self.interner.next_type_variable();
+        let function_type =
+            Type::Function(argument_types, Box::new(return_type), Box::new(Type::Unit));
+
+        let function = self.resolve_trait_method(id, function_type, method)?;
+        let arguments = vecmap(arguments, |(argument, _)| argument);
+        self.call(function, arguments, id, location)
+    }
+
+    /// Find the impl method a trait method reference refers to, along with the bindings
+    /// of the impl's own generics.
+    fn resolve_trait_method(
+        &mut self,
+        id: ExprId,
+        function_type: Type,
+        method: TraitMethodId,
+    ) -> IResult<Value> {
+        let location = self.interner.expr_location(&id);
+        let trait_impl = self
+            .interner
+            .get_selected_impl_for_ident(id)
+            .expect("ICE: missing trait impl - should be caught during type checking");
+
+        let impl_id = match trait_impl {
+            TraitImplKind::Normal(impl_id) => impl_id,
+            TraitImplKind::Assumed { object_type } => {
+                match self.interner.lookup_trait_implementation(&object_type, method.trait_id) {
+                    Ok(TraitImplKind::Normal(impl_id)) => impl_id,
+                    _ => {
+                        let typ = object_type.follow_bindings();
+                        return Err(InterpreterError::TypeNotKnown { typ, location });
+                    }
+                }
+            }
+        };
+
+        let function =
+            self.interner.get_trait_implementation(impl_id).borrow().methods[method.method_index];
+        let (impl_function_type, bindings) =
+            self.interner.function_meta(&function).typ.instantiate(self.interner);
+        if impl_function_type.try_unify(&function_type).is_err() {
+            unreachable!("Trait impl method type should unify with the trait method's type");
+        }
+        Ok(Value::Function(function, follow_bindings(&bindings)))
+    }
+
+    fn evaluate_index(&mut self, index: HirIndexExpression, id: ExprId) -> IResult<Value> {
+        let location = self.interner.expr_location(&id);
+        let collection = self.evaluate(index.collection)?;
+        let index_location = self.interner.expr_location(&index.index);
+        let index = self.evaluate(index.index)?;
+
+        let elements = match collection {
+            // Indexing a reference to an array implicitly dereferences it
+            Value::Pointer(pointer) => into_elements(pointer.borrow().clone(), location)?,
+            other => into_elements(other, location)?,
+        };
+        let index = checked_index(index, elements.len(), index_location)?;
+        Ok(elements[index].clone())
+    }
+
+    fn evaluate_constructor(&mut self, constructor: HirConstructorExpression) -> IResult<Value> {
+        let fields = try_vecmap(constructor.fields, |(name, field)| {
+            Ok((name.0.contents, self.evaluate(field)?))
+        })?;
+        Ok(Value::Struct(fields.into_iter().collect()))
+    }
+
+    fn evaluate_enum_constructor(
+        &mut self,
+        constructor: HirEnumConstructorExpression,
+    ) -> IResult<Value> {
+        let arguments = try_vecmap(constructor.arguments, |argument| self.evaluate(argument))?;
+        Ok(Value::Enum(constructor.variant_index, arguments))
+    }
+
+    fn evaluate_access(&mut self, access: HirMemberAccess, id: ExprId) -> IResult<Value> {
+        let location = self.interner.expr_location(&id);
+        let object = self.evaluate(access.lhs)?;
+        get_field(object, &access.rhs.0.contents, location)
+    }
+
+    fn evaluate_call(&mut self, call: HirCallExpression, id: ExprId) -> IResult<Value> {
+        let function = self.evaluate(call.func)?;
+        let arguments = try_vecmap(call.arguments, |argument| self.evaluate(argument))?;
+        self.call(function, arguments, id, call.location)
+    }
+
+    fn call(
+        &mut self,
+        function: Value,
+        arguments: Vec<Value>,
+        call: ExprId,
+        location: Location,
+    ) -> IResult<Value> {
+        if self.call_depth >= self.limits.max_call_depth {
+            let limit = self.limits.max_call_depth;
+            return Err(InterpreterError::CallDepthExceeded { limit, location });
+        }
+
+        self.call_depth += 1;
+        let result = match function {
+            Value::Function(function, bindings) => {
+                self.call_function(function, bindings, arguments, call, location)
+            }
+            Value::Closure(lambda, environment) => {
+                self.call_closure(lambda, environment, arguments, location)
+            }
+            _ => Err(InterpreterError::TypeMismatch { expected: "function", location }),
+        };
+        self.call_depth -= 1;
+        result
+    }
+
+    fn call_function(
+        &mut self,
+        function: FuncId,
+        bindings: TypeBindings,
+        arguments: Vec<Value>,
+        call: ExprId,
+        location: Location,
+    ) -> IResult<Value> {
+        let meta = self.interner.function_meta(&function);
+        let name = self.interner.function_name(&function).to_owned();
+        match meta.kind {
+            FunctionKind::Normal => (),
+            FunctionKind::Builtin => {
+                let builtin = self.interner.function_attributes(&function).function.clone();
+                let builtin = builtin.and_then(|attribute| attribute.builtin());
+                return self.call_builtin(builtin.as_deref(), name, arguments, call, location);
+            }
+            FunctionKind::LowLevel | FunctionKind::Oracle => {
+                return Err(InterpreterError::UnsupportedFunction { name, location });
+            }
+        }
+
+        let body = *self.interner.function(&function).as_expr();
+        if self.interner.id_type(body) == Type::Error {
+            // Only comptime globals are evaluated before every function is type checked
+            return Err(InterpreterError::NonComptimeFunctionCallInGlobal { name, location });
+        }
+
+        // A function can't see the variables of its caller
+        let caller_scopes = std::mem::replace(&mut self.scopes, vec![HashMap::new()]);
+        let previous_bindings = bind_generics(&bindings);
+
+        let mut result = Ok(());
+        for ((pattern, _, _), argument) in meta.parameters.iter().zip(arguments) {
+            result = result.and_then(|_| self.define_pattern(pattern, argument, location));
+        }
+        let result = result.and_then(|_| self.evaluate(body));
+
+        restore_generics(previous_bindings);
+        self.scopes = caller_scopes;
+        result
+    }
+
+    fn call_closure(
+        &mut self,
+        lambda: Box<HirLambda>,
+        environment: Vec<Value>,
+        arguments: Vec<Value>,
+        location: Location,
+    ) -> IResult<Value> {
+        let caller_scopes = std::mem::replace(&mut self.scopes, vec![HashMap::new()]);
+
+        let mut result = Ok(());
+        for (capture, value) in lambda.captures.iter().zip(environment) {
+            let scope = self.scopes.last_mut().unwrap();
+            scope.insert(capture.ident.id, Rc::new(RefCell::new(value)));
+        }
+        for ((pattern, _), argument) in lambda.parameters.iter().zip(arguments) {
+            result = result.and_then(|_| self.define_pattern(pattern, argument, location));
+        }
+        let result = result.and_then(|_| self.evaluate(lambda.body));
+
+        self.scopes = caller_scopes;
+        result
+    }
+
+    fn call_builtin(
+        &mut self,
+        builtin: Option<&str>,
+        name: String,
+        arguments: Vec<Value>,
+        call: ExprId,
+        location: Location,
+    ) -> IResult<Value> {
+        let return_type = self.interner.id_type(call);
+        let mut arguments = arguments.into_iter();
+        let mut argument =
+            || arguments.next().expect("Builtins should be called with the right arguments");
+
+        match builtin {
+            Some("array_len") => {
+                let length = into_elements(argument(), location)?.len();
+                integer_value(length as u128, &return_type, location)
+            }
+            Some("as_field") => cast(argument(), &Type::FieldElement, location),
+            Some("from_field") => cast(argument(), &return_type, location),
+            Some("assert_constant") => Ok(Value::Unit),
+            Some("modulus_num_bits") => {
+                integer_value(FieldElement::max_num_bits() as u128, &return_type, location)
+            }
+            Some("modulus_le_bits") => {
+                let bits = FieldElement::modulus().to_radix_le(2);
+                Ok(digits_to_array(vecmap(bits, |bit| bit as u32), 1))
+            }
+            Some("modulus_be_bits") => {
+                let bits = FieldElement::modulus().to_radix_be(2);
+                Ok(digits_to_array(vecmap(bits, |bit| bit as u32), 1))
+            }
+            Some("modulus_le_bytes") => {
+                let bytes = FieldElement::modulus().to_bytes_le();
+                Ok(digits_to_array(vecmap(bytes, |byte| byte as u32), 8))
+            }
+            Some("modulus_be_bytes") => {
+                let bytes = FieldElement::modulus().to_bytes_be();
+                Ok(digits_to_array(vecmap(bytes, |byte| byte as u32), 8))
+            }
+            Some(radix @ ("to_le_bits" | "to_be_bits" | "to_le_radix" | "to_be_radix")) => {
+                let value = cast(argument(), &Type::FieldElement, location)?;
+                let (radix_value, limbs) = if radix.ends_with("bits") {
+                    (2, to_index(argument(), location)?)
+                } else {
+                    (to_index(argument(), location)? as u32, to_index(argument(), location)?)
+                };
+                let digits = match value {
+                    Value::Field(value) => to_radix_le(value, radix_value, limbs, location)?,
+                    _ => unreachable!("Values are cast to a Field above"),
+                };
+                let digits = if radix.contains("_be_") {
+                    digits.into_iter().rev().collect()
+                } else {
+                    digits
+                };
+                let bit_size = if radix.ends_with("bits") { 1 } else { 8 };
+                Ok(digits_to_array(digits, bit_size))
+            }
+            Some("str_as_bytes") => match argument() {
+                Value::String(string) => {
+                    Ok(digits_to_array(vecmap(string.bytes(), |byte| byte as u32), 8))
+                }
+                _ => Err(InterpreterError::TypeMismatch { expected: "str", location }),
+            },
+            Some("slice_push_back") => {
+                let mut elements = into_elements(argument(), location)?;
+                elements.push(argument());
+                Ok(Value::Array(elements))
+            }
+            Some("slice_push_front") => {
+                let mut elements = into_elements(argument(), location)?;
+                elements.insert(0, argument());
+                Ok(Value::Array(elements))
+            }
+            Some("slice_pop_back") => {
+                let mut elements = into_elements(argument(), location)?;
+                let last = elements.pop().ok_or(InterpreterError::IndexOutOfBounds {
+                    index: 0,
+                    length: 0,
+                    location,
+                })?;
+                Ok(Value::Tuple(vec![Value::Array(elements), last]))
+            }
+            Some("slice_pop_front") => {
+                let mut elements = into_elements(argument(), location)?;
+                if elements.is_empty() {
+                    return Err(InterpreterError::IndexOutOfBounds {
+                        index: 0,
+                        length: 0,
+                        location,
+                    });
+                }
+                let first = elements.remove(0);
+                Ok(Value::Tuple(vec![first, Value::Array(elements)]))
+            }
+            Some("slice_insert") => {
+                let mut elements = into_elements(argument(), location)?;
+                let index = checked_index(argument(), elements.len() + 1, location)?;
+                elements.insert(index, argument());
+                Ok(Value::Array(elements))
+            }
+            Some("slice_remove") => {
+                let mut elements = into_elements(argument(), location)?;
+                let index = checked_index(argument(), elements.len(), location)?;
+                let element = elements.remove(index);
+                Ok(Value::Tuple(vec![Value::Array(elements), element]))
+            }
+            _ => Err(InterpreterError::UnsupportedFunction { name, location }),
+        }
+    }
+
+    fn evaluate_cast(&mut self, cast_expr: HirCastExpression, id: ExprId) -> IResult<Value> {
+        let location = self.interner.expr_location(&id);
+        let value = self.evaluate(cast_expr.lhs)?;
+        cast(value, &cast_expr.r#type, location)
+    }
+
+    fn evaluate_if(&mut self, if_expr: HirIfExpression, id: ExprId) -> IResult<Value> {
+        let location = self.interner.expr_location(&id);
+        match self.evaluate(if_expr.condition)? {
+            Value::Bool(true) => self.evaluate(if_expr.consequence),
+            Value::Bool(false) => match if_expr.alternative {
+                Some(alternative) => self.evaluate(alternative),
+                None => Ok(Value::Unit),
+            },
+            _ => Err(InterpreterError::TypeMismatch { expected: "bool", location }),
+        }
+    }
+
+    fn evaluate_match(&mut self, match_expr: HirMatchExpression, id: ExprId) -> IResult<Value> {
+        let location = self.interner.expr_location(&id);
+        let value = self.evaluate(match_expr.expression)?;
+
+        for (pattern, body) in match_expr.rules {
+            match pattern {
+                HirMatchPattern::Binding(pattern) => {
+                    return self.in_new_scope(|this| {
+                        this.define_pattern(&pattern, value, location)?;
+                        this.evaluate(body)
+                    });
+                }
+                HirMatchPattern::Variant { variant_index, fields, .. } => match &value {
+                    Value::Enum(tag, arguments) if *tag == variant_index => {
+                        let arguments = arguments.clone();
+                        return self.in_new_scope(|this| {
+                            for (pattern, argument) in fields.iter().zip(arguments) {
+                                this.define_pattern(pattern, argument, location)?;
+                            }
+                            this.evaluate(body)
+                        });
+                    }
+                    _ => (),
+                },
+                HirMatchPattern::Error(_) => (),
+            }
+        }
+        unreachable!("Match expressions are checked to be exhaustive during type checking")
+    }
+
+    fn evaluate_lambda(&mut self, lambda: HirLambda, id: ExprId) -> IResult<Value> {
+        let location = self.interner.expr_location(&id);
+        let environment = try_vecmap(&lambda.captures, |capture| {
+            self.lookup(HirIdent { location, ..capture.ident })
+        })?;
+        Ok(Value::Closure(Box::new(lambda), environment))
+    }
+}
+
+/// Follow the bindings of each instantiated generic so that they can be bound later
+/// without referring to the type variables of the caller.
+fn follow_bindings(bindings: &TypeBindings) -> TypeBindings {
+    bindings
+        .iter()
+        .map(|(id, (var, binding))| (*id, (var.clone(), binding.follow_bindings())))
+        .collect()
+}
+
+/// Bind each generic to its type for the duration of a call, returning their
+/// previous bindings so they can be restored afterward.
+fn bind_generics(bindings: &TypeBindings) -> Vec<(TypeVariable, TypeBinding)> {
+    let mut previous = Vec::with_capacity(bindings.len());
+    for (var, binding) in bindings.values() {
+        // A generic bound to itself (from an unknown generic of the caller) must stay unbound
+        if let Type::NamedGeneric(other, _) | Type::TypeVariable(other, _) = binding {
+            if other == var {
+                continue;
+            }
+        }
+        previous.push((var.clone(), var.borrow().clone()));
+        *var.borrow_mut() = TypeBinding::Bound(binding.clone());
+    }
+    previous
+}
+
+fn restore_generics(previous: Vec<(TypeVariable, TypeBinding)>) {
+    for (var, binding) in previous.into_iter().rev() {
+        *var.borrow_mut() = binding;
+    }
+}
+
+fn evaluate_binary_operation(
+    lhs: Value,
+    operator: BinaryOpKind,
+    rhs: Value,
+    location: Location,
+) -> IResult<Value> {
+    use BinaryOpKind::*;
+    let division_by_zero = InterpreterError::DivisionByZero { location };
+    let mismatch = InterpreterError::TypeMismatch { expected: "operand", location };
+
+    match operator {
+        Equal => return Ok(Value::Bool(lhs.equals(&rhs))),
+        NotEqual => return Ok(Value::Bool(!lhs.equals(&rhs))),
+        _ => (),
+    }
+
+    match (lhs, rhs) {
+        (Value::Field(lhs), Value::Field(rhs)) => match operator {
+            Add => Ok(Value::Field(lhs + rhs)),
+            Subtract => Ok(Value::Field(lhs - rhs)),
+            Multiply => Ok(Value::Field(lhs * rhs)),
+            Divide if rhs.is_zero() => Err(division_by_zero),
+            Divide => Ok(Value::Field(lhs / rhs)),
+            Less => Ok(Value::Bool(lhs < rhs)),
+            LessEqual => Ok(Value::Bool(lhs <= rhs)),
+            Greater => Ok(Value::Bool(lhs > rhs)),
+            GreaterEqual => Ok(Value::Bool(lhs >= rhs)),
+            _ => Err(mismatch),
+        },
+        (Value::Unsigned(lhs, bit_size), Value::Unsigned(rhs, _)) => {
+            let overflow = InterpreterError::IntegerOutOfRange {
+                typ: Type::Integer(Signedness::Unsigned, bit_size),
+                location,
+            };
+            let result = match operator {
+                Add => lhs.checked_add(rhs).ok_or(overflow)?,
+                Subtract => lhs.checked_sub(rhs).ok_or(overflow)?,
+                Multiply => lhs.checked_mul(rhs).ok_or(overflow)?,
+                Divide => lhs.checked_div(rhs).ok_or(division_by_zero)?,
+                Modulo => lhs.checked_rem(rhs).ok_or(division_by_zero)?,
+                And => lhs & rhs,
+                Or => lhs | rhs,
+                Xor => lhs ^ rhs,
+                ShiftLeft => shift_left(lhs, rhs) & mask(bit_size),
+                ShiftRight => {
+                    u32::try_from(rhs).ok().and_then(|rhs| lhs.checked_shr(rhs)).unwrap_or(0)
+                }
+                Less => return Ok(Value::Bool(lhs < rhs)),
+                LessEqual => return Ok(Value::Bool(lhs <= rhs)),
+                Greater => return Ok(Value::Bool(lhs > rhs)),
+                GreaterEqual => return Ok(Value::Bool(lhs >= rhs)),
+                Equal | NotEqual => unreachable!(),
+            };
+            check_range(Value::Unsigned(result, bit_size), location)
+        }
+        (Value::Signed(lhs, bit_size), Value::Signed(rhs, _)) => {
+            let overflow = InterpreterError::IntegerOutOfRange {
+                typ: Type::Integer(Signedness::Signed, bit_size),
+                location,
+            };
+            let result = match operator {
+                Add => lhs.checked_add(rhs).ok_or(overflow)?,
+                Subtract => lhs.checked_sub(rhs).ok_or(overflow)?,
+                Multiply => lhs.checked_mul(rhs).ok_or(overflow)?,
+                Divide if rhs == 0 => return Err(division_by_zero),
+                Divide => lhs.checked_div(rhs).ok_or(overflow)?,
+                Modulo if rhs == 0 => return Err(division_by_zero),
+                Modulo => lhs.checked_rem(rhs).ok_or(overflow)?,
+                And => lhs & rhs,
+                Or => lhs | rhs,
+                Xor => lhs ^ rhs,
+                ShiftLeft => {
+                    let shifted = shift_left(lhs as u128, rhs as u128) & mask(bit_size);
+                    sign_extend(shifted, bit_size)
+                }
+                ShiftRight => lhs >> rhs.clamp(0, 127),
+                Less => return Ok(Value::Bool(lhs < rhs)),
+                LessEqual => return Ok(Value::Bool(lhs <= rhs)),
+                Greater => return Ok(Value::Bool(lhs > rhs)),
+                GreaterEqual => return Ok(Value::Bool(lhs >= rhs)),
+                Equal | NotEqual => unreachable!(),
+            };
+            check_range(Value::Signed(result, bit_size), location)
+        }
+        (Value::Bool(lhs), Value::Bool(rhs)) => match operator {
+            And => Ok(Value::Bool(lhs & rhs)),
+            Or => Ok(Value::Bool(lhs | rhs)),
+            Xor => Ok(Value::Bool(lhs ^ rhs)),
+            Less => Ok(Value::Bool(!lhs & rhs)),
+            LessEqual => Ok(Value::Bool(lhs <= rhs)),
+            Greater => Ok(Value::Bool(lhs & !rhs)),
+            GreaterEqual => Ok(Value::Bool(lhs >= rhs)),
+            _ => Err(mismatch),
+        },
+        _ => Err(mismatch),
+    }
+}
+
+/// Cast a value to the given numeric type, truncating it as the runtime would
+fn cast(value: Value, typ: &Type, location: Location) -> IResult<Value> {
+    // The value as a Field, along with its low 128 bits
+    let (field, low_bits) = match value {
+        Value::Field(value) => (value, field_low_bits(value)),
+        Value::Unsigned(value, _) => (value.into(), value),
+        Value::Signed(value, bit_size) => {
+            let value = value as u128 & mask(bit_size);
+            (value.into(), value)
+        }
+        Value::Bool(value) => (value.into(), value as u128),
+        _ => return Err(InterpreterError::TypeMismatch { expected: "integer", location }),
+    };
+
+    match typ.follow_bindings() {
+        Type::FieldElement => Ok(Value::Field(field)),
+        Type::Integer(Signedness::Unsigned, bit_size) => {
+            Ok(Value::Unsigned(low_bits & mask(bit_size), bit_size))
+        }
+        Type::Integer(Signedness::Signed, bit_size) => {
+            Ok(Value::Signed(sign_extend(low_bits & mask(bit_size), bit_size), bit_size))
+        }
+        Type::Bool => Ok(Value::Bool(!field.is_zero())),
+        typ => Err(InterpreterError::TypeNotKnown { typ, location }),
+    }
+}
+
+fn get_field(object: Value, field_name: &str, location: Location) -> IResult<Value> {
+    match object {
+        Value::Struct(mut fields) => fields
+            .remove(field_name)
+            .ok_or(InterpreterError::TypeMismatch { expected: "struct", location }),
+        Value::Tuple(mut fields) => Ok(fields.swap_remove(tuple_index(field_name))),
+        Value::Pointer(pointer) => get_field(pointer.borrow().clone(), field_name, location),
+        _ => Err(InterpreterError::TypeMismatch { expected: "struct", location }),
+    }
+}
+
+fn tuple_index(field_name: &str) -> usize {
+    field_name.parse().expect("Tuple fields are accessed by index")
+}
+
+fn to_index(value: Value, location: Location) -> IResult<usize> {
+    let index = match value {
+        Value::Field(value) => value.try_into_u128(),
+        Value::Unsigned(value, _) => Some(value),
+        Value::Signed(value, _) => u128::try_from(value).ok(),
+        _ => return Err(InterpreterError::TypeMismatch { expected: "integer", location }),
+    };
+    index.and_then(|index| usize::try_from(index).ok()).ok_or(InterpreterError::IndexOutOfBounds {
+        index: u128::MAX,
+        length: 0,
+        location,
+    })
+}
+
+fn checked_index(index: Value, length: usize, location: Location) -> IResult<usize> {
+    let index = to_index(index, location)?;
+    if index < length {
+        Ok(index)
+    } else {
+        Err(InterpreterError::IndexOutOfBounds { index: index as u128, length, location })
+    }
+}
+
+fn field_to_i128(value: FieldElement, location: Location) -> IResult<i128> {
+    value
+        .try_into_u128()
+        .and_then(|value| i128::try_from(value).ok())
+        .ok_or(InterpreterError::IntegerOutOfRange { typ: Type::FieldElement, location })
+}
+
+fn unsigned_to_i128(value: u128, bit_size: u32, location: Location) -> IResult<i128> {
+    i128::try_from(value).map_err(|_| InterpreterError::IntegerOutOfRange {
+        typ: Type::Integer(Signedness::Unsigned, bit_size),
+        location,
+    })
+}
+
+/// The low 128 bits of a Field element
+fn field_low_bits(value: FieldElement) -> u128 {
+    let bytes = value.to_be_bytes();
+    let mut low_bytes = [0u8; 16];
+    low_bytes.copy_from_slice(&bytes[bytes.len() - 16..]);
+    u128::from_be_bytes(low_bytes)
+}
+
+/// A mask of the low `bit_size` bits
+fn mask(bit_size: u32) -> u128 {
+    if bit_size >= 128 {
+        u128::MAX
+    } else {
+        (1 << bit_size) - 1
+    }
+}
+
+fn shift_left(lhs: u128, rhs: u128) -> u128 {
+    u32::try_from(rhs).ok().and_then(|rhs| lhs.checked_shl(rhs)).unwrap_or(0)
+}
+
+/// Interpret the low `bit_size` bits of `value` as a two's complement integer
+fn sign_extend(value: u128, bit_size: u32) -> i128 {
+    if bit_size >= 128 {
+        return value as i128;
+    }
+    let shift = 128 - bit_size;
+    ((value << shift) as i128) >> shift
+}
+
+/// Decompose a Field element into `limbs` little-endian digits of the given radix
+fn to_radix_le(
+    value: FieldElement,
+    radix: u32,
+    limbs: usize,
+    location: Location,
+) -> IResult<Vec<u32>> {
+    // Repeatedly divide the big-endian bytes of the value by the radix
+    let mut bytes = value.to_be_bytes();
+    let mut digits = Vec::with_capacity(limbs);
+    while bytes.iter().any(|byte| *byte != 0) {
+        let mut remainder = 0u32;
+        for byte in bytes.iter_mut() {
+            let current = (remainder << 8) | *byte as u32;
+            *byte = (current / radix) as u8;
+            remainder = current % radix;
+        }
+        digits.push(remainder);
+    }
+
+    if digits.len() > limbs {
+        let message = format!("Field failed to decompose into specified {limbs} limbs");
+        return Err(InterpreterError::FailingConstraint { message: Some(message), location });
+    }
+    digits.resize(limbs, 0);
+    Ok(digits)
+}
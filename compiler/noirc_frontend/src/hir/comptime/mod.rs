@@ -0,0 +1,13 @@
+//! This module implements `comptime` evaluation. After a crate is type checked, `comptime`
+//! globals, `comptime { ... }` blocks and calls to `comptime` functions are evaluated by a
+//! tree-walking interpreter over the HIR, and each is replaced with the value it produced.
+//! The monomorphizer therefore never sees any comptime code.
+mod errors;
+mod interpreter;
+mod scan;
+mod value;
+
+pub use errors::InterpreterError;
+pub use interpreter::{Interpreter, InterpreterLimits};
+pub(crate) use scan::{evaluate_comptime_code, evaluate_comptime_globals};
+pub use value::Value;
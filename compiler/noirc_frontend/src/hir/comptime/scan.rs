@@ -0,0 +1,255 @@
+//! Finds the `comptime` code within type checked globals and functions, evaluates it,
+//! and splices the resulting values back into the program.
+use fm::FileId;
+
+use crate::{
+    hir::def_collector::dc_crate::CompilationError,
+    hir_def::{
+        expr::{HirArrayLiteral, HirExpression, HirLiteral, HirMatchPattern},
+        stmt::{HirLValue, HirStatement},
+    },
+    node_interner::{DefinitionKind, ExprId, FuncId, NodeInterner, StmtId},
+    Type, TypeBinding,
+};
+
+use super::{
+    errors::InterpreterError,
+    interpreter::{Interpreter, InterpreterLimits},
+    value::Value,
+};
+
+/// Evaluate each `comptime` global, replacing its expression with the resulting value.
+/// Globals used as array lengths are also bound to their value here.
+pub(crate) fn evaluate_comptime_globals(
+    interner: &mut NodeInterner,
+    globals: &[(FileId, StmtId)],
+    limits: InterpreterLimits,
+) -> Vec<(CompilationError, FileId)> {
+    let mut errors = Vec::new();
+    for (_, global) in globals {
+        let (length, used_as_length) = match interner.get_comptime_global(global) {
+            Some(comptime_global) => comptime_global,
+            None => continue,
+        };
+        let expression = match interner.statement(global) {
+            HirStatement::Let(let_statement) => let_statement.expression,
+            _ => unreachable!("Globals are always let statements"),
+        };
+
+        let mut interpreter = Interpreter::new(interner).with_limits(limits);
+        let result = interpreter.evaluate(expression).and_then(|value| {
+            match array_length(&value) {
+                Some(value) => *length.borrow_mut() = TypeBinding::Bound(Type::Constant(value)),
+                None if used_as_length => {
+                    let location = interner.expr_location(&expression);
+                    return Err(InterpreterError::NonIntegerArrayLength { location });
+                }
+                None => (),
+            }
+            splice(interner, expression, value)
+        });
+
+        if let Err(error) = result {
+            let file = error.location().file;
+            errors.push((error.into(), file));
+        }
+    }
+    errors
+}
+
+/// Evaluate each `comptime` block and call to a `comptime` function within the given
+/// functions, replacing each with the resulting value.
+pub(crate) fn evaluate_comptime_code(
+    interner: &mut NodeInterner,
+    functions: &[(FileId, FuncId)],
+    limits: InterpreterLimits,
+) -> Vec<(CompilationError, FileId)> {
+    let mut errors = Vec::new();
+    for (_, function) in functions {
+        let body = *interner.function(function).as_expr();
+        let mut scanner = Scanner { interner, limits, errors: Vec::new() };
+        scanner.scan_expression(body);
+
+        errors.extend(scanner.errors.into_iter().map(|error| {
+            let file = error.location().file;
+            (error.into(), file)
+        }));
+    }
+    errors
+}
+
+/// The value of a comptime global if it can be used as an array length
+fn array_length(value: &Value) -> Option<u64> {
+    match value {
+        Value::Field(value) => value.try_into_u128().and_then(|value| value.try_into().ok()),
+        Value::Unsigned(value, _) => (*value).try_into().ok(),
+        Value::Signed(value, _) => (*value).try_into().ok(),
+        _ => None,
+    }
+}
+
+fn evaluate_and_splice(
+    interner: &mut NodeInterner,
+    id: ExprId,
+    limits: InterpreterLimits,
+) -> Result<(), InterpreterError> {
+    let value = Interpreter::new(interner).with_limits(limits).evaluate(id)?;
+    splice(interner, id, value)
+}
+
+fn splice(interner: &mut NodeInterner, id: ExprId, value: Value) -> Result<(), InterpreterError> {
+    let typ = interner.id_type(id);
+    let location = interner.expr_location(&id);
+    let expression = value.into_hir_expression(interner, &typ, location)?;
+    interner.replace_expr(&id, expression);
+    Ok(())
+}
+
+struct Scanner<'interner> {
+    interner: &'interner mut NodeInterner,
+    limits: InterpreterLimits,
+    errors: Vec<InterpreterError>,
+}
+
+impl<'interner> Scanner<'interner> {
+    fn scan_expression(&mut self, id: ExprId) {
+        match self.interner.expression(&id) {
+            HirExpression::Comptime(_) => self.evaluate(id),
+            HirExpression::Call(call) => {
+                if self.is_comptime_function(call.func) {
+                    self.evaluate(id);
+                } else {
+                    self.scan_expression(call.func);
+                    for argument in call.arguments {
+                        self.scan_expression(argument);
+                    }
+                }
+            }
+            HirExpression::Ident(ident) => {
+                if self.is_comptime_function(id) {
+                    let name = self.interner.definition_name(ident.id).to_owned();
+                    let location = ident.location;
+                    self.errors
+                        .push(InterpreterError::ComptimeFunctionUsedAsValue { name, location });
+                }
+            }
+            HirExpression::Literal(HirLiteral::Array(HirArrayLiteral::Standard(elements)))
+            | HirExpression::Literal(HirLiteral::FmtStr(_, elements))
+            | HirExpression::Tuple(elements) => {
+                for element in elements {
+                    self.scan_expression(element);
+                }
+            }
+            HirExpression::Literal(HirLiteral::Array(HirArrayLiteral::Repeated {
+                repeated_element,
+                ..
+            })) => self.scan_expression(repeated_element),
+            HirExpression::Literal(_) => (),
+            HirExpression::Block(block) => {
+                for statement in block.statements() {
+                    self.scan_statement(*statement);
+                }
+            }
+            HirExpression::Prefix(prefix) => self.scan_expression(prefix.rhs),
+            HirExpression::Infix(infix) => {
+                self.scan_expression(infix.lhs);
+                self.scan_expression(infix.rhs);
+            }
+            HirExpression::Index(index) => {
+                self.scan_expression(index.collection);
+                self.scan_expression(index.index);
+            }
+            HirExpression::Constructor(constructor) => {
+                for (_, field) in constructor.fields {
+                    self.scan_expression(field);
+                }
+            }
+            HirExpression::EnumConstructor(constructor) => {
+                for argument in constructor.arguments {
+                    self.scan_expression(argument);
+                }
+            }
+            HirExpression::MemberAccess(access) => self.scan_expression(access.lhs),
+            HirExpression::MethodCall(method_call) => {
+                self.scan_expression(method_call.object);
+                for argument in method_call.arguments {
+                    self.scan_expression(argument);
+                }
+            }
+            HirExpression::Cast(cast) => self.scan_expression(cast.lhs),
+            HirExpression::If(if_expr) => {
+                self.scan_expression(if_expr.condition);
+                self.scan_expression(if_expr.consequence);
+                if let Some(alternative) = if_expr.alternative {
+                    self.scan_expression(alternative);
+                }
+            }
+            HirExpression::Match(match_expr) => {
+                self.scan_expression(match_expr.expression);
+                for (pattern, body) in match_expr.rules {
+                    if !matches!(pattern, HirMatchPattern::Error(_)) {
+                        self.scan_expression(body);
+                    }
+                }
+            }
+            HirExpression::Lambda(lambda) => self.scan_expression(lambda.body),
+            HirExpression::TraitMethodReference(_) | HirExpression::Error => (),
+        }
+    }
+
+    fn scan_statement(&mut self, id: StmtId) {
+        match self.interner.statement(&id) {
+            HirStatement::Let(let_statement) => self.scan_expression(let_statement.expression),
+            HirStatement::Constrain(constrain) => self.scan_expression(constrain.0),
+            HirStatement::Assign(assign) => {
+                self.scan_lvalue(&assign.lvalue);
+                self.scan_expression(assign.expression);
+            }
+            HirStatement::For(for_loop) => {
+                self.scan_expression(for_loop.start_range);
+                self.scan_expression(for_loop.end_range);
+                self.scan_expression(for_loop.block);
+            }
+            HirStatement::While(while_loop) => {
+                self.scan_expression(while_loop.condition);
+                self.scan_expression(while_loop.block);
+            }
+            HirStatement::Loop(block) => self.scan_expression(block),
+            HirStatement::Expression(expression) | HirStatement::Semi(expression) => {
+                self.scan_expression(expression);
+            }
+            HirStatement::Break | HirStatement::Continue | HirStatement::Error => (),
+        }
+    }
+
+    fn scan_lvalue(&mut self, lvalue: &HirLValue) {
+        match lvalue {
+            HirLValue::Ident(..) => (),
+            HirLValue::MemberAccess { object, .. } => self.scan_lvalue(object),
+            HirLValue::Index { array, index, .. } => {
+                self.scan_lvalue(array);
+                self.scan_expression(*index);
+            }
+            HirLValue::Dereference { lvalue, .. } => self.scan_lvalue(lvalue),
+        }
+    }
+
+    /// True if the given expression refers to a `comptime` function
+    fn is_comptime_function(&self, id: ExprId) -> bool {
+        match self.interner.expression(&id) {
+            HirExpression::Ident(ident) => match self.interner.definition(ident.id).kind {
+                DefinitionKind::Function(function) => {
+                    self.interner.function_modifiers(&function).is_comptime
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn evaluate(&mut self, id: ExprId) {
+        if let Err(error) = evaluate_and_splice(self.interner, id, self.limits) {
+            self.errors.push(error);
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use acvm::FieldElement;
+use iter_extended::{try_vecmap, vecmap};
+use noirc_errors::Location;
+
+use crate::{
+    hir_def::expr::{
+        HirArrayLiteral, HirConstructorExpression, HirEnumConstructorExpression, HirExpression,
+        HirLambda, HirLiteral,
+    },
+    node_interner::{ExprId, FuncId, NodeInterner},
+    Ident, Signedness, Type, TypeBindings,
+};
+
+use super::errors::InterpreterError;
+
+/// A value computed by the comptime interpreter.
+#[derive(Debug, Clone)]
+pub enum Value {
+    Unit,
+    Bool(bool),
+    Field(FieldElement),
+
+    /// An unsigned integer along with its bit size
+    Unsigned(u128, u32),
+
+    /// A signed integer along with its bit size
+    Signed(i128, u32),
+
+    String(String),
+
+    /// Both arrays and slices are represented as a list of their elements.
+    Array(Vec<Value>),
+    Tuple(Vec<Value>),
+    Struct(HashMap<String, Value>),
+
+    /// An enum value is the index of its variant along with the variant's fields.
+    Enum(usize, Vec<Value>),
+
+    /// A function along with the (fully followed) bindings of its generics
+    Function(FuncId, TypeBindings),
+
+    /// A closure along with the values of its captured variables
+    Closure(Box<HirLambda>, Vec<Value>),
+
+    /// A mutable reference to a value
+    Pointer(Rc<RefCell<Value>>),
+}
+
+impl Value {
+    /// Structural equality of two data values. Functions and references are never equal.
+    pub fn equals(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Unit, Value::Unit) => true,
+            (Value::Bool(lhs), Value::Bool(rhs)) => lhs == rhs,
+            (Value::Field(lhs), Value::Field(rhs)) => lhs == rhs,
+            (Value::Unsigned(lhs, _), Value::Unsigned(rhs, _)) => lhs == rhs,
+            (Value::Signed(lhs, _), Value::Signed(rhs, _)) => lhs == rhs,
+            (Value::String(lhs), Value::String(rhs)) => lhs == rhs,
+            (Value::Array(lhs), Value::Array(rhs)) | (Value::Tuple(lhs), Value::Tuple(rhs)) => {
+                lhs.len() == rhs.len() && lhs.iter().zip(rhs).all(|(lhs, rhs)| lhs.equals(rhs))
+            }
+            (Value::Struct(lhs), Value::Struct(rhs)) => {
+                lhs.iter().all(|(name, lhs)| rhs.get(name).is_some_and(|rhs| lhs.equals(rhs)))
+            }
+            (Value::Enum(lhs_tag, lhs), Value::Enum(rhs_tag, rhs)) => {
+                lhs_tag == rhs_tag && lhs.iter().zip(rhs).all(|(lhs, rhs)| lhs.equals(rhs))
+            }
+            _ => false,
+        }
+    }
+
+    /// Convert this value into a HIR expression of the given type so that it can be spliced
+    /// into the program in place of the comptime code which produced it.
+    pub(crate) fn into_hir_expression(
+        self,
+        interner: &mut NodeInterner,
+        typ: &Type,
+        location: Location,
+    ) -> Result<HirExpression, InterpreterError> {
+        let typ = typ.follow_bindings();
+        let expression = match (self, &typ) {
+            (Value::Unit, _) => HirExpression::Literal(HirLiteral::Unit),
+            (Value::Bool(value), _) => HirExpression::Literal(HirLiteral::Bool(value)),
+            (Value::Field(value), _) => HirExpression::Literal(HirLiteral::Integer(value, false)),
+            (Value::Unsigned(value, _), _) => {
+                HirExpression::Literal(HirLiteral::Integer(value.into(), false))
+            }
+            (Value::Signed(value, _), _) => {
+                let magnitude = value.unsigned_abs().into();
+                HirExpression::Literal(HirLiteral::Integer(magnitude, value < 0))
+            }
+            (Value::String(value), _) => HirExpression::Literal(HirLiteral::Str(value)),
+            (Value::Array(elements), Type::Array(_, element_type)) => {
+                let elements = try_vecmap(elements, |element| {
+                    element.into_hir_expression_id(interner, element_type, location)
+                })?;
+                HirExpression::Literal(HirLiteral::Array(HirArrayLiteral::Standard(elements)))
+            }
+            (Value::Tuple(fields), Type::Tuple(field_types)) => {
+                let fields = try_vecmap(fields.into_iter().zip(field_types), |(field, typ)| {
+                    field.into_hir_expression_id(interner, typ, location)
+                })?;
+                HirExpression::Tuple(fields)
+            }
+            (Value::Struct(mut fields), Type::Struct(struct_type, generics)) => {
+                let field_types = struct_type.borrow().get_fields(generics);
+                let fields = try_vecmap(field_types, |(name, typ)| {
+                    let field = fields
+                        .remove(&name)
+                        .ok_or(InterpreterError::TypeMismatch { expected: "struct", location })?;
+                    let field = field.into_hir_expression_id(interner, &typ, location)?;
+                    Ok((Ident::new(name, location.span), field))
+                })?;
+                HirExpression::Constructor(HirConstructorExpression {
+                    r#type: struct_type.clone(),
+                    struct_generics: generics.clone(),
+                    fields,
+                })
+            }
+            (Value::Enum(variant_index, arguments), Type::Enum(enum_type, generics)) => {
+                let argument_types = enum_type.borrow().get_variant(variant_index, generics);
+                let arguments =
+                    try_vecmap(arguments.into_iter().zip(argument_types), |(argument, typ)| {
+                        argument.into_hir_expression_id(interner, &typ, location)
+                    })?;
+                HirExpression::EnumConstructor(HirEnumConstructorExpression {
+                    r#type: enum_type.clone(),
+                    enum_generics: generics.clone(),
+                    variant_index,
+                    arguments,
+                })
+            }
+            _ => return Err(InterpreterError::CannotSplice { typ, location }),
+        };
+        Ok(expression)
+    }
+
+    fn into_hir_expression_id(
+        self,
+        interner: &mut NodeInterner,
+        typ: &Type,
+        location: Location,
+    ) -> Result<ExprId, InterpreterError> {
+        let expression = self.into_hir_expression(interner, typ, location)?;
+        let id = interner.push_expr(expression);
+        interner.push_expr_location(id, location.span, location.file);
+        interner.push_expr_type(&id, typ.follow_bindings());
+        Ok(id)
+    }
+}
+
+/// Create the value of an integer of the given type, which may also be a Field.
+pub(super) fn integer_value(
+    value: u128,
+    typ: &Type,
+    location: Location,
+) -> Result<Value, InterpreterError> {
+    match typ.follow_bindings() {
+        Type::Integer(Signedness::Unsigned, bit_size) => {
+            let value = Value::Unsigned(value, bit_size);
+            check_range(value, location)
+        }
+        Type::Integer(Signedness::Signed, bit_size) => match i128::try_from(value) {
+            Ok(value) => check_range(Value::Signed(value, bit_size), location),
+            Err(_) => Err(InterpreterError::IntegerOutOfRange {
+                typ: Type::Integer(Signedness::Signed, bit_size),
+                location,
+            }),
+        },
+        _ => Ok(Value::Field(value.into())),
+    }
+}
+
+/// Returns an error if the given integer value does not fit in its bit size
+pub(super) fn check_range(value: Value, location: Location) -> Result<Value, InterpreterError> {
+    let in_range = match &value {
+        Value::Unsigned(value, bit_size) => *bit_size >= 128 || *value < (1u128 << bit_size),
+        Value::Signed(value, bit_size) => {
+            *bit_size >= 128 || {
+                let max = 1i128 << (bit_size - 1);
+                (-max..max).contains(value)
+            }
+        }
+        _ => true,
+    };
+
+    if in_range {
+        Ok(value)
+    } else {
+        let typ = match value {
+            Value::Unsigned(_, bit_size) => Type::Integer(Signedness::Unsigned, bit_size),
+            Value::Signed(_, bit_size) => Type::Integer(Signedness::Signed, bit_size),
+            _ => unreachable!(),
+        };
+        Err(InterpreterError::IntegerOutOfRange { typ, location })
+    }
+}
+
+/// Returns the elements of the given value if it is an array or slice
+pub(super) fn into_elements(
+    value: Value,
+    location: Location,
+) -> Result<Vec<Value>, InterpreterError> {
+    match value {
+        Value::Array(elements) => Ok(elements),
+        _ => Err(InterpreterError::TypeMismatch { expected: "array", location }),
+    }
+}
+
+/// Convert each digit into an unsigned integer value of the given bit size
+pub(super) fn digits_to_array(digits: Vec<u32>, bit_size: u32) -> Value {
+    Value::Array(vecmap(digits, |digit| Value::Unsigned(digit as u128, bit_size)))
+}
@@ -1,31 +1,36 @@
-use super::dc_mod::collect_defs;
+use super::dc_mod::{collect_defs, collect_generated_trait_impls};
 use super::errors::{DefCollectorErrorKind, DuplicateType};
 use crate::graph::CrateId;
-use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleId};
+use crate::hir::comptime::{evaluate_comptime_code, evaluate_comptime_globals, InterpreterError};
+use crate::hir::def_map::{CrateDefMap, ImportConflict, LocalModuleId, ModuleId};
 use crate::hir::resolution::errors::ResolverError;
 
 use crate::hir::resolution::import::{resolve_imports, ImportDirective};
 use crate::hir::resolution::resolver::Resolver;
 use crate::hir::resolution::{
-    collect_impls, collect_trait_impls, resolve_free_functions, resolve_globals, resolve_impls,
-    resolve_structs, resolve_trait_by_path, resolve_trait_impls, resolve_traits,
+    collect_impls, collect_trait_impls, resolve_enums, resolve_free_functions, resolve_globals,
+    resolve_impls, resolve_structs, resolve_trait_by_path, resolve_trait_impls, resolve_traits,
     resolve_type_aliases,
 };
 use crate::hir::type_check::{type_check_func, TypeCheckError, TypeChecker};
 use crate::hir::Context;
 
 use crate::macros_api::MacroProcessor;
-use crate::node_interner::{FuncId, NodeInterner, StmtId, StructId, TraitId, TypeAliasId};
+use crate::node_interner::{EnumId, FuncId, NodeInterner, StmtId, StructId, TraitId, TypeAliasId};
 
 use crate::parser::{ParserError, SortedModule};
+use crate::token::SecondaryAttribute;
 use crate::{
-    ExpressionKind, LetStatement, Literal, NoirFunction, NoirStruct, NoirTrait, NoirTypeAlias,
-    Path, Type, UnresolvedGenerics, UnresolvedTraitConstraint, UnresolvedType,
+    BinaryOpKind, BlockExpression, CallExpression, ConstructorExpression, Expression,
+    ExpressionKind, FunctionDefinition, FunctionReturnType, Ident, InfixExpression, LetStatement,
+    Literal, MemberAccessExpression, NoirEnum, NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl,
+    NoirTypeAlias, Path, PathKind, Statement, StatementKind, TraitBound, TraitImplItem, Type,
+    TypeBinding, UnresolvedGenerics, UnresolvedTraitConstraint, UnresolvedType, UnresolvedTypeData,
 };
 use fm::FileId;
 use iter_extended::vecmap;
-use noirc_errors::{CustomDiagnostic, Span};
-use std::collections::{BTreeMap, HashMap};
+use noirc_errors::{CustomDiagnostic, Span, Spanned};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use std::vec;
 
@@ -75,6 +80,12 @@ pub struct UnresolvedStruct {
     pub struct_def: NoirStruct,
 }
 
+pub struct UnresolvedEnum {
+    pub file_id: FileId,
+    pub module_id: LocalModuleId,
+    pub enum_def: NoirEnum,
+}
+
 #[derive(Clone)]
 pub struct UnresolvedTrait {
     pub file_id: FileId,
@@ -91,6 +102,8 @@ pub struct UnresolvedTraitImpl {
     pub trait_path: Path,
     pub object_type: UnresolvedType,
     pub methods: UnresolvedFunctions,
+    pub types: Vec<(Ident, UnresolvedType)>,
+    pub constants: Vec<(Ident, UnresolvedType, Expression)>,
     pub generics: UnresolvedGenerics,
     pub where_clause: Vec<UnresolvedTraitConstraint>,
 }
@@ -116,6 +129,7 @@ pub struct DefCollector {
     pub(crate) collected_imports: Vec<ImportDirective>,
     pub(crate) collected_functions: Vec<UnresolvedFunctions>,
     pub(crate) collected_types: BTreeMap<StructId, UnresolvedStruct>,
+    pub(crate) collected_enums: BTreeMap<EnumId, UnresolvedEnum>,
     pub(crate) collected_type_aliases: BTreeMap<TypeAliasId, UnresolvedTypeAlias>,
     pub(crate) collected_traits: BTreeMap<TraitId, UnresolvedTrait>,
     pub(crate) collected_globals: Vec<UnresolvedGlobal>,
@@ -139,6 +153,7 @@ pub enum CompilationError {
     DefinitionError(DefCollectorErrorKind),
     ResolverError(ResolverError),
     TypeError(TypeCheckError),
+    InterpreterError(InterpreterError),
 }
 
 impl From<CompilationError> for CustomDiagnostic {
@@ -148,6 +163,7 @@ impl From<CompilationError> for CustomDiagnostic {
             CompilationError::DefinitionError(error) => error.into(),
             CompilationError::ResolverError(error) => error.into(),
             CompilationError::TypeError(error) => error.into(),
+            CompilationError::InterpreterError(error) => error.into(),
         }
     }
 }
@@ -175,6 +191,12 @@ impl From<TypeCheckError> for CompilationError {
     }
 }
 
+impl From<InterpreterError> for CompilationError {
+    fn from(value: InterpreterError) -> Self {
+        CompilationError::InterpreterError(value)
+    }
+}
+
 impl DefCollector {
     fn new(def_map: CrateDefMap) -> DefCollector {
         DefCollector {
@@ -182,6 +204,7 @@ impl DefCollector {
             collected_imports: vec![],
             collected_functions: vec![],
             collected_types: BTreeMap::new(),
+            collected_enums: BTreeMap::new(),
             collected_type_aliases: BTreeMap::new(),
             collected_traits: BTreeMap::new(),
             collected_impls: HashMap::new(),
@@ -243,40 +266,20 @@ impl DefCollector {
             context,
         ));
 
+        // Expand any `#[derive(...)]` attributes into trait impls so that they are
+        // resolved and type checked along with the impls written in the source
+        errors.extend(collect_derived_impls(&mut def_collector, context, crate_id));
+
         // Add the current crate to the collection of DefMaps
         context.def_maps.insert(crate_id, def_collector.def_map);
 
         // Resolve unresolved imports collected from the crate
-        let (resolved, unresolved_imports) =
-            resolve_imports(crate_id, def_collector.collected_imports, &context.def_maps);
-
-        {
-            let current_def_map = context.def_maps.get(&crate_id).unwrap();
-            errors.extend(vecmap(unresolved_imports, |(error, module_id)| {
-                let file_id = current_def_map.file_id(module_id);
-                let error = DefCollectorErrorKind::PathResolutionError(error);
-                (error.into(), file_id)
-            }));
-        };
-
-        // Populate module namespaces according to the imports used
-        let current_def_map = context.def_maps.get_mut(&crate_id).unwrap();
-        for resolved_import in resolved {
-            let name = resolved_import.name;
-            for ns in resolved_import.resolved_namespace.iter_defs() {
-                let result = current_def_map.modules[resolved_import.module_scope.0]
-                    .import(name.clone(), ns);
-
-                if let Err((first_def, second_def)) = result {
-                    let err = DefCollectorErrorKind::Duplicate {
-                        typ: DuplicateType::Import,
-                        first_def,
-                        second_def,
-                    };
-                    errors.push((err.into(), root_file_id));
-                }
-            }
-        }
+        errors.extend(resolve_and_import(
+            context,
+            crate_id,
+            def_collector.collected_imports,
+            root_file_id,
+        ));
 
         // We must first resolve and intern the globals before we can resolve any stmts inside each function.
         // Each function uses its own resolver with a newly created ScopeForest, and must be resolved again to be within a function's scope
@@ -295,8 +298,9 @@ impl DefCollector {
         ));
 
         errors.extend(resolve_traits(context, def_collector.collected_traits, crate_id));
-        // Must resolve structs before we resolve globals.
+        // Must resolve structs and enums before we resolve globals.
         errors.extend(resolve_structs(context, def_collector.collected_types, crate_id));
+        errors.extend(resolve_enums(context, def_collector.collected_enums, crate_id));
 
         // We must wait to resolve non-integer globals until after we resolve structs since structs
         // globals will need to reference the struct type they're initialized to to ensure they are valid.
@@ -348,16 +352,131 @@ impl DefCollector {
         for macro_processor in macro_processors {
             macro_processor.process_typed_ast(&crate_id, context);
         }
+        let globals = resolved_globals.globals.clone();
         errors.extend(type_check_globals(&mut context.def_interner, resolved_globals.globals));
 
+        // Comptime functions are type checked first so that comptime globals, which may be
+        // used in the types of other functions, can be evaluated before those are checked.
+        let interner = &context.def_interner;
+        let is_comptime =
+            |(_, func): &(FileId, FuncId)| interner.function_modifiers(func).is_comptime;
+        let (comptime_funcs, file_func_ids): (Vec<_>, Vec<_>) =
+            file_func_ids.into_iter().partition(is_comptime);
+        let (comptime_methods, file_method_ids): (Vec<_>, Vec<_>) =
+            file_method_ids.into_iter().partition(is_comptime);
+        let (comptime_trait_impls, file_trait_impls_ids): (Vec<_>, Vec<_>) =
+            file_trait_impls_ids.into_iter().partition(is_comptime);
+
+        errors.extend(type_check_functions(&mut context.def_interner, comptime_funcs));
+        errors.extend(type_check_functions(&mut context.def_interner, comptime_methods));
+        errors.extend(type_check_functions(&mut context.def_interner, comptime_trait_impls));
+
+        // Comptime code is only evaluated if the crate is otherwise free of errors
+        let has_errors = |errors: &[(CompilationError, FileId)]| {
+            errors.iter().any(|(error, _)| CustomDiagnostic::from(error.clone()).is_error())
+        };
+        if !has_errors(&errors) {
+            errors.extend(evaluate_comptime_globals(
+                &mut context.def_interner,
+                &globals,
+                context.comptime_limits,
+            ));
+        }
+
         // Type check all of the functions in the crate
+        let functions: Vec<_> = file_func_ids
+            .iter()
+            .chain(&file_method_ids)
+            .chain(&file_trait_impls_ids)
+            .copied()
+            .collect();
         errors.extend(type_check_functions(&mut context.def_interner, file_func_ids));
         errors.extend(type_check_functions(&mut context.def_interner, file_method_ids));
         errors.extend(type_check_functions(&mut context.def_interner, file_trait_impls_ids));
+
+        if !has_errors(&errors) {
+            errors.extend(evaluate_comptime_code(
+                &mut context.def_interner,
+                &functions,
+                context.comptime_limits,
+            ));
+        }
         errors
     }
 }
 
+/// Resolves each import and brings the items it refers to into its module's scope.
+///
+/// An import may refer to an item which is only brought into scope by another import, so the
+/// remaining imports are retried, and glob imports re-expanded, until no module's scope changes.
+fn resolve_and_import(
+    context: &mut Context,
+    crate_id: CrateId,
+    imports: Vec<ImportDirective>,
+    root_file_id: FileId,
+) -> Vec<(CompilationError, FileId)> {
+    let mut errors = Vec::new();
+    let (glob_imports, mut pending_imports): (Vec<_>, Vec<_>) =
+        imports.into_iter().partition(|import| import.is_glob);
+
+    // Glob imports are expanded again each round, so only report each ambiguity once
+    let mut ambiguous_names = HashSet::new();
+
+    loop {
+        let imports = pending_imports.into_iter().chain(glob_imports.iter().cloned()).collect();
+        let (resolved, unresolved) = resolve_imports(crate_id, imports, &context.def_maps);
+
+        // Populate module namespaces according to the imports used
+        let current_def_map = context.def_maps.get_mut(&crate_id).unwrap();
+        let mut scopes_changed = false;
+        for resolved_import in resolved {
+            let name = resolved_import.name;
+            let module = &mut current_def_map.modules[resolved_import.module_scope.0];
+
+            for ns in resolved_import.resolved_namespace.iter_defs() {
+                let result = module.import(name.clone(), ns, resolved_import.is_glob);
+
+                let err = match result {
+                    Ok(changed) => {
+                        scopes_changed |= changed;
+                        continue;
+                    }
+                    Err(ImportConflict::Duplicate(first_def, second_def)) => {
+                        DefCollectorErrorKind::Duplicate {
+                            typ: DuplicateType::Import,
+                            first_def,
+                            second_def,
+                        }
+                    }
+                    Err(ImportConflict::AmbiguousGlob(first_def, second_def)) => {
+                        let key = (resolved_import.module_scope, second_def.0.contents.clone());
+                        if !ambiguous_names.insert(key) {
+                            continue;
+                        }
+                        DefCollectorErrorKind::AmbiguousGlobImport { first_def, second_def }
+                    }
+                };
+                errors.push((err.into(), root_file_id));
+            }
+        }
+
+        if !scopes_changed {
+            let current_def_map = &context.def_maps[&crate_id];
+            errors.extend(vecmap(unresolved, |(error, import)| {
+                let file_id = current_def_map.file_id(import.module_id);
+                let error = DefCollectorErrorKind::PathResolutionError(error);
+                (error.into(), file_id)
+            }));
+            return errors;
+        }
+
+        pending_imports = unresolved
+            .into_iter()
+            .filter_map(|(_, import)| (!import.is_glob).then_some(import))
+            .collect();
+    }
+}
+
 /// Separate the globals Vec into two. The first element in the tuple will be the
 /// literal globals, except for arrays, and the second will be all other globals.
 /// We exclude array literals as they can contain complex types
@@ -370,6 +489,232 @@ fn filter_literal_globals(
     })
 }
 
+/// Expand each `#[derive(...)]` attribute on the structs of this crate into the
+/// trait impls it names and collect them into the module of the struct.
+fn collect_derived_impls(
+    def_collector: &mut DefCollector,
+    context: &mut Context,
+    crate_id: CrateId,
+) -> Vec<(CompilationError, FileId)> {
+    let mut errors = vec![];
+    let mut derived_impls = vec![];
+
+    for unresolved in def_collector.collected_types.values() {
+        let structure = &unresolved.struct_def;
+        for attribute in &structure.attributes {
+            if let SecondaryAttribute::Derive(traits) = attribute {
+                for trait_name in traits {
+                    match derive_trait_impl(structure, trait_name, crate_id) {
+                        Some(trait_impl) => {
+                            derived_impls.push((
+                                unresolved.file_id,
+                                unresolved.module_id,
+                                trait_impl,
+                            ));
+                        }
+                        None => {
+                            let trait_name = trait_name.clone();
+                            let span = structure.name.span();
+                            let error =
+                                DefCollectorErrorKind::UnsupportedDerive { trait_name, span };
+                            errors.push((error.into(), unresolved.file_id));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    for (file_id, module_id, trait_impl) in derived_impls {
+        errors.extend(collect_generated_trait_impls(
+            def_collector,
+            context,
+            vec![trait_impl],
+            file_id,
+            module_id,
+            crate_id,
+        ));
+    }
+    errors
+}
+
+/// Generate the impl of a derivable stdlib trait for the given struct, or None if the
+/// trait cannot be derived. Each generic used as a field type is required to implement
+/// the trait as well.
+///
+/// The methods of the trait are called on each field through the trait's path, so that a
+/// field type without an impl is reported at that field.
+fn derive_trait_impl(
+    structure: &NoirStruct,
+    trait_name: &str,
+    crate_id: CrateId,
+) -> Option<NoirTraitImpl> {
+    let (module_name, derive_method): (&str, fn(&NoirStruct, &Path) -> NoirFunction) =
+        match trait_name {
+            "Eq" => ("cmp", derive_eq),
+            "Default" => ("default", derive_default),
+            _ => return None,
+        };
+
+    let span = structure.name.span();
+    let mut segments = vec![Ident::new(module_name.to_string(), span)];
+    segments.push(Ident::new(trait_name.to_string(), span));
+
+    let trait_path = if crate_id.is_stdlib() {
+        Path { segments, kind: PathKind::Crate, span }
+    } else {
+        segments.insert(0, Ident::new("std".to_string(), span));
+        Path { segments, kind: PathKind::Dep, span }
+    };
+
+    let where_clause = structure
+        .generics
+        .iter()
+        .filter(|generic| {
+            structure.fields.iter().any(|(_, typ)| is_generic_used_as_type(generic, typ))
+        })
+        .map(|generic| UnresolvedTraitConstraint {
+            typ: named_type(Path::from_ident(generic.clone()), vec![], generic.span()),
+            trait_bound: TraitBound {
+                trait_path: trait_path.clone(),
+                trait_id: None,
+                trait_generics: vec![],
+            },
+        })
+        .collect();
+
+    let method = derive_method(structure, &trait_path);
+
+    Some(NoirTraitImpl {
+        impl_generics: structure.generics.clone(),
+        trait_name: trait_path,
+        trait_generics: vec![],
+        object_type: struct_type(structure),
+        where_clause,
+        items: vec![TraitImplItem::Function(method)],
+    })
+}
+
+/// `fn eq(self, other: Self) -> bool { Eq::eq(self.a, other.a) & Eq::eq(self.b, other.b) }`
+fn derive_eq(structure: &NoirStruct, trait_path: &Path) -> NoirFunction {
+    let span = structure.name.span();
+
+    let comparisons = structure.fields.iter().map(|(field, _)| {
+        let field_span = field.span();
+        let lhs = member_access(variable("self", field_span), field);
+        let rhs = member_access(variable("other", field_span), field);
+        call(trait_method(trait_path, "eq", field_span), vec![lhs, rhs])
+    });
+
+    let body = comparisons
+        .reduce(|lhs, rhs| {
+            let span = lhs.span.merge(rhs.span);
+            let operator = Spanned::from(span, BinaryOpKind::And);
+            let infix = InfixExpression { lhs, operator, rhs };
+            Expression::new(ExpressionKind::Infix(Box::new(infix)), span)
+        })
+        .unwrap_or_else(|| Expression::new(ExpressionKind::Literal(Literal::Bool(true)), span));
+
+    // Structs without fields are always equal, so the parameters go unused
+    let (self_name, other_name) =
+        if structure.fields.is_empty() { ("_self", "_other") } else { ("self", "other") };
+
+    let self_type = struct_type(structure);
+    let parameters = [
+        (Ident::new(self_name.to_string(), span), self_type.clone()),
+        (Ident::new(other_name.to_string(), span), self_type),
+    ];
+    let return_type =
+        FunctionReturnType::Ty(UnresolvedType { typ: UnresolvedTypeData::Bool, span: Some(span) });
+
+    derived_method("eq", structure, &parameters, body, return_type)
+}
+
+/// `fn default() -> Self { Foo { a: Default::default(), b: Default::default() } }`
+fn derive_default(structure: &NoirStruct, trait_path: &Path) -> NoirFunction {
+    let span = structure.name.span();
+
+    let fields = vecmap(&structure.fields, |(field, _)| {
+        (field.clone(), call(trait_method(trait_path, "default", field.span()), vec![]))
+    });
+
+    let type_name = Path::from_ident(structure.name.clone());
+    let constructor = ConstructorExpression { type_name, fields };
+    let body = Expression::new(ExpressionKind::Constructor(Box::new(constructor)), span);
+
+    let return_type = FunctionReturnType::Ty(struct_type(structure));
+    derived_method("default", structure, &[], body, return_type)
+}
+
+fn derived_method(
+    name: &str,
+    structure: &NoirStruct,
+    parameters: &[(Ident, UnresolvedType)],
+    body: Expression,
+    return_type: FunctionReturnType,
+) -> NoirFunction {
+    let span = structure.name.span();
+    let name = Ident::new(name.to_string(), span);
+    let body = BlockExpression(vec![Statement { kind: StatementKind::Expression(body), span }]);
+    let def = FunctionDefinition::normal(&name, &vec![], parameters, &body, &[], &return_type);
+    NoirFunction::normal(def)
+}
+
+/// The type of the struct applied to its own generics, e.g. `Foo<T, U>`
+fn struct_type(structure: &NoirStruct) -> UnresolvedType {
+    let span = structure.name.span();
+    let generics = vecmap(&structure.generics, |generic| {
+        named_type(Path::from_ident(generic.clone()), vec![], generic.span())
+    });
+    named_type(Path::from_ident(structure.name.clone()), generics, span)
+}
+
+fn named_type(path: Path, generics: Vec<UnresolvedType>, span: Span) -> UnresolvedType {
+    UnresolvedType { typ: UnresolvedTypeData::Named(path, generics), span: Some(span) }
+}
+
+/// The path to a method of the trait at the given path, e.g. `std::cmp::Eq::eq`
+fn trait_method(trait_path: &Path, method: &str, span: Span) -> Expression {
+    let mut path = trait_path.clone();
+    path.segments.push(Ident::new(method.to_string(), span));
+    path.span = span;
+    Expression::new(ExpressionKind::Variable(path), span)
+}
+
+fn variable(name: &str, span: Span) -> Expression {
+    Expression::new(ExpressionKind::Variable(Path::from_single(name.to_string(), span)), span)
+}
+
+fn member_access(lhs: Expression, rhs: &Ident) -> Expression {
+    let span = lhs.span;
+    let access = MemberAccessExpression { lhs, rhs: rhs.clone() };
+    Expression::new(ExpressionKind::MemberAccess(Box::new(access)), span)
+}
+
+fn call(func: Expression, arguments: Vec<Expression>) -> Expression {
+    let span = func.span;
+    let call = CallExpression { func: Box::new(func), arguments };
+    Expression::new(ExpressionKind::Call(Box::new(call)), span)
+}
+
+/// True if the given generic is used as (part of) the type of a value in the given type,
+/// as opposed to only as the length of an array or string, e.g. `T` in `[T; N]`.
+fn is_generic_used_as_type(generic: &Ident, typ: &UnresolvedType) -> bool {
+    match &typ.typ {
+        UnresolvedTypeData::Named(path, generics) => {
+            (path.segments.len() == 1 && path.segments[0] == *generic)
+                || generics.iter().any(|typ| is_generic_used_as_type(generic, typ))
+        }
+        UnresolvedTypeData::Array(_, element) | UnresolvedTypeData::Parenthesized(element) => {
+            is_generic_used_as_type(generic, element)
+        }
+        UnresolvedTypeData::Tuple(elements) => {
+            elements.iter().any(|typ| is_generic_used_as_type(generic, typ))
+        }
+        _ => false,
+    }
+}
+
 fn type_check_globals(
     interner: &mut NodeInterner,
     global_ids: Vec<(FileId, StmtId)>,
@@ -408,6 +753,7 @@ pub(crate) fn check_methods_signatures(
     impl_methods: &Vec<(FileId, FuncId)>,
     trait_id: TraitId,
     trait_impl_generic_count: usize,
+    associated_types: &[Type],
     errors: &mut Vec<(CompilationError, FileId)>,
 ) {
     let the_trait = resolver.interner.get_trait(trait_id);
@@ -417,6 +763,19 @@ pub(crate) fn check_methods_signatures(
     // Temporarily bind the trait's Self type to self_type so we can type check
     let _ = the_trait.self_type_typevar.borrow_mut().bind_to(self_type.clone(), the_trait.span);
 
+    // Likewise bind each of the trait's associated types and constants to the impl's values
+    let mut associated_type_variables = Vec::new();
+    for (placeholder, value) in the_trait.associated_types().into_iter().zip(associated_types) {
+        if let Type::NamedGeneric(type_variable, _) = placeholder {
+            let id = match &*type_variable.borrow() {
+                TypeBinding::Unbound(id) => *id,
+                TypeBinding::Bound(_) => continue,
+            };
+            let _ = type_variable.borrow_mut().bind_to(value.clone(), the_trait.span);
+            associated_type_variables.push((type_variable, id));
+        }
+    }
+
     for (file_id, func_id) in impl_methods {
         let impl_method = resolver.interner.function_meta(func_id);
         let func_name = resolver.interner.function_name(func_id).to_owned();
@@ -431,8 +790,22 @@ pub(crate) fn check_methods_signatures(
         {
             let impl_function_type = impl_method.typ.instantiate(resolver.interner);
 
-            let impl_method_generic_count =
-                impl_method.typ.generic_count() - trait_impl_generic_count;
+            // The generics declared for the associated items of the method's `where` clause
+            // are implicit, so they are not counted against the trait method's generics.
+            let associated_generic_count = impl_method
+                .trait_constraints
+                .iter()
+                .flat_map(|constraint| &constraint.associated_types)
+                .filter_map(|typ| match typ {
+                    Type::NamedGeneric(_, name) => Some(name.clone()),
+                    _ => None,
+                })
+                .collect::<HashSet<_>>()
+                .len();
+
+            let impl_method_generic_count = impl_method.typ.generic_count()
+                - trait_impl_generic_count
+                - associated_generic_count;
             let trait_method_generic_count = trait_method.generics.len();
 
             if impl_method_generic_count != trait_method_generic_count {
@@ -495,4 +868,8 @@ pub(crate) fn check_methods_signatures(
     }
 
     the_trait.self_type_typevar.borrow_mut().unbind(the_trait.self_type_typevar_id);
+
+    for (type_variable, id) in associated_type_variables {
+        type_variable.borrow_mut().unbind(id);
+    }
 }
@@ -6,11 +6,11 @@ use noirc_errors::Location;
 
 use crate::{
     graph::CrateId,
-    hir::def_collector::dc_crate::{UnresolvedStruct, UnresolvedTrait},
+    hir::def_collector::dc_crate::{UnresolvedEnum, UnresolvedStruct, UnresolvedTrait},
     node_interner::{FunctionModifiers, TraitId, TypeAliasId},
     parser::{SortedModule, SortedSubModule},
-    FunctionDefinition, Ident, LetStatement, NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl,
-    NoirTypeAlias, TraitImplItem, TraitItem, TypeImpl,
+    ExpressionKind, FunctionDefinition, Ident, LetStatement, NoirEnum, NoirFunction, NoirStruct,
+    NoirTrait, NoirTraitImpl, NoirTypeAlias, TraitImplItem, TraitItem, TypeImpl,
 };
 
 use super::{
@@ -57,6 +57,7 @@ pub fn collect_defs(
             module_id: collector.module_id,
             path: import.path,
             alias: import.alias,
+            is_glob: import.is_glob,
         });
     }
 
@@ -66,6 +67,8 @@ pub fn collect_defs(
 
     errors.extend(collector.collect_structs(context, ast.types, crate_id));
 
+    errors.extend(collector.collect_enums(context, ast.enums, crate_id));
+
     errors.extend(collector.collect_type_aliases(context, ast.type_aliases));
 
     errors.extend(collector.collect_functions(context, ast.functions, crate_id));
@@ -77,6 +80,20 @@ pub fn collect_defs(
     errors
 }
 
+/// Collect trait impls generated by the compiler rather than written in the source, such as
+/// those expanded from `#[derive(...)]`, as if they were declared in the given module.
+pub(super) fn collect_generated_trait_impls(
+    def_collector: &mut DefCollector,
+    context: &mut Context,
+    impls: Vec<NoirTraitImpl>,
+    file_id: FileId,
+    module_id: LocalModuleId,
+    crate_id: CrateId,
+) -> Vec<(CompilationError, FileId)> {
+    let mut collector = ModCollector { def_collector, file_id, module_id };
+    collector.collect_trait_impls(context, impls, crate_id)
+}
+
 impl<'a> ModCollector<'a> {
     fn collect_globals(
         &mut self,
@@ -90,6 +107,9 @@ impl<'a> ModCollector<'a> {
             // First create dummy function in the DefInterner
             // So that we can get a StmtId
             let stmt_id = context.def_interner.push_empty_global();
+            if matches!(global.expression.kind, ExpressionKind::Comptime(_)) {
+                context.def_interner.push_comptime_global(stmt_id);
+            }
 
             // Add the statement to the scope so its path can be looked up later
             let result =
@@ -124,7 +144,9 @@ impl<'a> ModCollector<'a> {
                 trait_id: None,
             };
 
-            for method in r#impl.methods {
+            for mut method in r#impl.methods {
+                // Each method is constrained by the `where` clause of its impl as well as its own
+                method.def.where_clause.extend(r#impl.where_clause.iter().cloned());
                 let func_id = context.def_interner.push_empty_fn();
                 context.def_interner.push_function(func_id, &method.def, module_id);
                 unresolved_functions.push_fn(self.module_id, func_id, method);
@@ -155,11 +177,23 @@ impl<'a> ModCollector<'a> {
                 context.def_interner.push_function(*func_id, &noir_function.def, module);
             }
 
+            let mut types = Vec::new();
+            let mut constants = Vec::new();
+            for item in trait_impl.items {
+                match item {
+                    TraitImplItem::Type { name, alias } => types.push((name, alias)),
+                    TraitImplItem::Constant(name, typ, value) => constants.push((name, typ, value)),
+                    TraitImplItem::Function(_) => (),
+                }
+            }
+
             let unresolved_trait_impl = UnresolvedTraitImpl {
                 file_id: self.file_id,
                 module_id: self.module_id,
                 trait_path: trait_name,
                 methods: unresolved_functions,
+                types,
+                constants,
                 object_type: trait_impl.object_type,
                 generics: trait_impl.impl_generics,
                 where_clause: trait_impl.where_clause,
@@ -292,6 +326,64 @@ impl<'a> ModCollector<'a> {
         definition_errors
     }
 
+    /// Collect any enum definitions declared within the ast.
+    /// Returns a vector of errors if any enums were already defined,
+    /// or if an enum has a duplicate variant named in its definition.
+    fn collect_enums(
+        &mut self,
+        context: &mut Context,
+        enums: Vec<NoirEnum>,
+        krate: CrateId,
+    ) -> Vec<(CompilationError, FileId)> {
+        let mut definition_errors = vec![];
+        for enum_definition in enums {
+            let name = enum_definition.name.clone();
+
+            let unresolved = UnresolvedEnum {
+                file_id: self.file_id,
+                module_id: self.module_id,
+                enum_def: enum_definition,
+            };
+
+            // Create the corresponding module for the enum namespace
+            let id = match self.push_child_module(&name, self.file_id, false, false) {
+                Ok(local_id) => context.def_interner.new_enum(&unresolved, krate, local_id),
+                Err(error) => {
+                    definition_errors.push((error.into(), self.file_id));
+                    continue;
+                }
+            };
+
+            // Add the enum to scope so its path can be looked up later
+            let result =
+                self.def_collector.def_map.modules[self.module_id.0].declare_enum(name, id);
+
+            if let Err((first_def, second_def)) = result {
+                let error = DefCollectorErrorKind::Duplicate {
+                    typ: DuplicateType::TypeDefinition,
+                    first_def,
+                    second_def,
+                };
+                definition_errors.push((error.into(), self.file_id));
+            }
+
+            let variants = &unresolved.enum_def.variants;
+            for (i, (variant, _)) in variants.iter().enumerate() {
+                if let Some((first_def, _)) = variants[..i].iter().find(|(v, _)| v == variant) {
+                    let error = DefCollectorErrorKind::Duplicate {
+                        typ: DuplicateType::EnumVariant,
+                        first_def: first_def.clone(),
+                        second_def: variant.clone(),
+                    };
+                    definition_errors.push((error.into(), self.file_id));
+                }
+            }
+
+            self.def_collector.collected_enums.insert(id, unresolved);
+        }
+        definition_errors
+    }
+
     /// Collect any type aliases definitions declared within the ast.
     /// Returns a vector of errors if any type aliases were already defined.
     fn collect_type_aliases(
@@ -387,6 +479,7 @@ impl<'a> ModCollector<'a> {
                             // TODO(Maddiaa): Investigate trait implementations with attributes see: https://github.com/noir-lang/noir/issues/2629
                             attributes: crate::token::Attributes::empty(),
                             is_unconstrained: false,
+                            is_comptime: false,
                             contract_function_type: None,
                             is_internal: None,
                         };
@@ -21,17 +21,20 @@ pub enum DuplicateType {
     TraitAssociatedType,
     TraitAssociatedConst,
     TraitAssociatedFunction,
+    EnumVariant,
 }
 
 #[derive(Error, Debug, Clone)]
 pub enum DefCollectorErrorKind {
     #[error("duplicate {typ} found in namespace")]
     Duplicate { typ: DuplicateType, first_def: Ident, second_def: Ident },
+    #[error("`{}` is imported by multiple glob imports", first_def.0.contents)]
+    AmbiguousGlobImport { first_def: Ident, second_def: Ident },
     #[error("unresolved import")]
     UnresolvedModuleDecl { mod_name: Ident, expected_path: String },
     #[error("path resolution error")]
     PathResolutionError(PathResolutionError),
-    #[error("Non-struct type used in impl")]
+    #[error("Non-struct or enum type used in impl")]
     NonStructTypeInImpl { span: Span },
     #[error("Cannot implement trait on a mutable reference type")]
     MutableReferenceInTraitImpl { span: Span },
@@ -65,6 +68,10 @@ pub enum DefCollectorErrorKind {
     TraitNotFound { trait_path: Path },
     #[error("Missing Trait method implementation")]
     TraitMissingMethod { trait_name: Ident, method_name: Ident, trait_impl_span: Span },
+    #[error("Associated item not in trait")]
+    AssociatedItemNotInTrait { trait_name: Ident, impl_item: Ident },
+    #[error("Missing Trait associated item implementation")]
+    TraitMissingAssociatedItem { trait_name: Ident, item_name: Ident, trait_impl_span: Span },
     #[error("Module is already part of the crate")]
     ModuleAlreadyPartOfCrate { mod_name: Ident, span: Span },
     #[error("Module was originally declared here")]
@@ -73,6 +80,8 @@ pub enum DefCollectorErrorKind {
         "Either the type or the trait must be from the same crate as the trait implementation"
     )]
     TraitImplOrphaned { span: Span },
+    #[error("Trait `{trait_name}` cannot be derived")]
+    UnsupportedDerive { trait_name: String, span: Span },
     #[error("macro error : {0:?}")]
     MacroError(MacroError),
 }
@@ -103,6 +112,7 @@ impl fmt::Display for DuplicateType {
             DuplicateType::Import => write!(f, "import"),
             DuplicateType::TraitAssociatedType => write!(f, "trait associated type"),
             DuplicateType::TraitAssociatedConst => write!(f, "trait associated constant"),
+            DuplicateType::EnumVariant => write!(f, "enum variant"),
             DuplicateType::TraitAssociatedFunction => write!(f, "trait associated function"),
         }
     }
@@ -128,6 +138,20 @@ impl From<DefCollectorErrorKind> for Diagnostic {
                     diag
                 }
             }
+            DefCollectorErrorKind::AmbiguousGlobImport { first_def, second_def } => {
+                let name = &first_def.0.contents;
+                let mut diag = Diagnostic::simple_error(
+                    format!("`{name}` is ambiguous since it is imported by multiple glob imports"),
+                    format!("First glob import of `{name}` found here"),
+                    first_def.span(),
+                );
+                diag.add_secondary(
+                    format!("Second glob import of `{name}` found here"),
+                    second_def.span(),
+                );
+                diag.add_note(format!("Consider importing `{name}` explicitly to disambiguate"));
+                diag
+            }
             DefCollectorErrorKind::UnresolvedModuleDecl { mod_name, expected_path } => {
                 let span = mod_name.0.span();
                 let mod_name = &mod_name.0.contents;
@@ -140,8 +164,8 @@ impl From<DefCollectorErrorKind> for Diagnostic {
             }
             DefCollectorErrorKind::PathResolutionError(error) => error.into(),
             DefCollectorErrorKind::NonStructTypeInImpl { span } => Diagnostic::simple_error(
-                "Non-struct type used in impl".into(),
-                "Only struct types may have implementation methods".into(),
+                "Non-struct or enum type used in impl".into(),
+                "Only struct and enum types may have implementation methods".into(),
                 span,
             ),
             DefCollectorErrorKind::MutableReferenceInTraitImpl { span } => Diagnostic::simple_error(
@@ -223,6 +247,29 @@ impl From<DefCollectorErrorKind> for Diagnostic {
                     trait_impl_span,
                 )
             }
+            DefCollectorErrorKind::AssociatedItemNotInTrait { trait_name, impl_item } => {
+                let trait_name = trait_name.0.contents;
+                let impl_item_span = impl_item.span();
+                let impl_item_name = impl_item.0.contents;
+                let primary_message = format!("`{impl_item_name}` is not an associated type or constant of trait `{trait_name}`, therefore it can't be implemented");
+                Diagnostic::simple_error(primary_message, "".to_owned(), impl_item_span)
+            }
+            DefCollectorErrorKind::TraitMissingAssociatedItem {
+                trait_name,
+                item_name,
+                trait_impl_span,
+            } => {
+                let trait_name = trait_name.0.contents;
+                let item_name = item_name.0.contents;
+                let primary_message = format!(
+                    "Associated item `{item_name}` from trait `{trait_name}` is not implemented"
+                );
+                Diagnostic::simple_error(
+                    primary_message,
+                    format!("Please implement {item_name} here"),
+                    trait_impl_span,
+                )
+            }
             DefCollectorErrorKind::NotATrait { not_a_trait_name } => {
                 let span = not_a_trait_name.span();
                 Diagnostic::simple_error(
@@ -246,6 +293,13 @@ impl From<DefCollectorErrorKind> for Diagnostic {
                 "Either the type or the trait must be from the same crate as the trait implementation".into(),
                 span,
             ),
+            DefCollectorErrorKind::UnsupportedDerive { trait_name, span } => {
+                Diagnostic::simple_error(
+                    format!("Trait `{trait_name}` cannot be derived"),
+                    "Only `Eq` and `Default` can be derived".into(),
+                    span,
+                )
+            }
             DefCollectorErrorKind::MacroError(macro_error) => {
                 Diagnostic::simple_error(macro_error.primary_message, macro_error.secondary_message.unwrap_or_default(), macro_error.span.unwrap_or_default())
             },
@@ -3,18 +3,33 @@ use crate::{
     node_interner::{FuncId, TraitId},
     Ident,
 };
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::{hash_map::Entry, HashMap, HashSet};
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Visibility {
     Public,
 }
 
+/// The error returned when an import conflicts with an item already in scope
+#[derive(Debug)]
+pub enum ImportConflict {
+    /// The name is already defined or explicitly imported. Contains (old_item, new_item)
+    Duplicate(Ident, Ident),
+    /// The name was brought into scope by two glob imports which refer to different items.
+    /// Contains (old_item, new_item)
+    AmbiguousGlob(Ident, Ident),
+}
+
 #[derive(Default, Debug, PartialEq, Eq)]
 pub struct ItemScope {
     types: HashMap<Ident, HashMap<Option<TraitId>, (ModuleDefId, Visibility)>>,
     values: HashMap<Ident, HashMap<Option<TraitId>, (ModuleDefId, Visibility)>>,
 
+    /// Names in `types` and `values` respectively which were brought into scope by a glob
+    /// import. These are shadowed by any definition or explicit import of the same name.
+    glob_types: HashSet<Ident>,
+    glob_values: HashSet<Ident>,
+
     defs: Vec<ModuleDefId>,
 }
 
@@ -62,12 +77,58 @@ impl ItemScope {
             ModuleDefId::ModuleId(_) => add_item(&mut self.types),
             ModuleDefId::FunctionId(_) => add_item(&mut self.values),
             ModuleDefId::TypeId(_) => add_item(&mut self.types),
+            ModuleDefId::EnumId(_) => add_item(&mut self.types),
             ModuleDefId::TypeAliasId(_) => add_item(&mut self.types),
             ModuleDefId::TraitId(_) => add_item(&mut self.types),
             ModuleDefId::GlobalId(_) => add_item(&mut self.values),
         }
     }
 
+    /// Adds an item brought into scope by a `use` statement.
+    ///
+    /// Glob imports never override an existing item of the same name, and an explicit import
+    /// replaces an item previously brought in by a glob import.
+    /// Returns whether the scope was changed.
+    pub fn add_import(
+        &mut self,
+        name: Ident,
+        mod_def: ModuleDefId,
+        is_glob: bool,
+    ) -> Result<bool, ImportConflict> {
+        let (map, globs) = if is_value_definition(mod_def) {
+            (&mut self.values, &mut self.glob_values)
+        } else {
+            (&mut self.types, &mut self.glob_types)
+        };
+
+        let trait_hashmap = map.entry(name.clone()).or_default();
+        let Some((old_def, _)) = trait_hashmap.get(&None).copied() else {
+            trait_hashmap.insert(None, (mod_def, Visibility::Public));
+            if is_glob {
+                globs.insert(name);
+            }
+            return Ok(true);
+        };
+
+        let (old_ident, _) = map.get_key_value(&name).expect("name was just inserted");
+        let old_ident = old_ident.clone();
+        let old_is_glob = globs.contains(&name);
+
+        match (old_is_glob, is_glob) {
+            // An explicit import of a name already brought in by a glob import shadows it
+            (true, false) => {
+                globs.remove(&name);
+                map.get_mut(&name).unwrap().insert(None, (mod_def, Visibility::Public));
+                Ok(true)
+            }
+            (_, true) if old_def == mod_def => Ok(false),
+            (true, true) => Err(ImportConflict::AmbiguousGlob(old_ident, name)),
+            // Glob imports never shadow definitions or explicit imports
+            (false, true) => Ok(false),
+            (false, false) => Err(ImportConflict::Duplicate(old_ident, name)),
+        }
+    }
+
     pub fn find_module_with_name(&self, mod_name: &Ident) -> Option<&ModuleId> {
         let (module_def, _) = self.types.get(mod_name)?.get(&None)?;
         match module_def {
@@ -161,3 +222,14 @@ impl ItemScope {
         self.values.remove(name);
     }
 }
+
+fn is_value_definition(mod_def: ModuleDefId) -> bool {
+    match mod_def {
+        ModuleDefId::FunctionId(_) | ModuleDefId::GlobalId(_) => true,
+        ModuleDefId::ModuleId(_)
+        | ModuleDefId::TypeId(_)
+        | ModuleDefId::EnumId(_)
+        | ModuleDefId::TypeAliasId(_)
+        | ModuleDefId::TraitId(_) => false,
+    }
+}
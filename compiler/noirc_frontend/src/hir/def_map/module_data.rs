@@ -3,11 +3,11 @@ use std::collections::HashMap;
 use noirc_errors::Location;
 
 use crate::{
-    node_interner::{FuncId, StmtId, StructId, TraitId, TypeAliasId},
+    node_interner::{EnumId, FuncId, StmtId, StructId, TraitId, TypeAliasId},
     Ident,
 };
 
-use super::{ItemScope, LocalModuleId, ModuleDefId, ModuleId, PerNs};
+use super::{ImportConflict, ItemScope, LocalModuleId, ModuleDefId, ModuleId, PerNs};
 
 /// Contains the actual contents of a module: its parent (if one exists),
 /// children, and scope with all definitions defined within the scope.
@@ -80,6 +80,10 @@ impl ModuleData {
         self.declare(name, ModuleDefId::TypeId(id), None)
     }
 
+    pub fn declare_enum(&mut self, name: Ident, id: EnumId) -> Result<(), (Ident, Ident)> {
+        self.declare(name, ModuleDefId::EnumId(id), None)
+    }
+
     pub fn declare_type_alias(
         &mut self,
         name: Ident,
@@ -104,14 +108,35 @@ impl ModuleData {
         self.scope.find_func_with_name(name)
     }
 
-    pub fn import(&mut self, name: Ident, id: ModuleDefId) -> Result<(), (Ident, Ident)> {
-        self.scope.add_item_to_namespace(name, id, None)
+    /// Brings an item into this module's scope through a `use` statement.
+    /// Returns whether the scope was changed.
+    pub fn import(
+        &mut self,
+        name: Ident,
+        id: ModuleDefId,
+        is_glob: bool,
+    ) -> Result<bool, ImportConflict> {
+        self.scope.add_import(name, id, is_glob)
     }
 
     pub fn find_name(&self, name: &Ident) -> PerNs {
         self.scope.find_name(name)
     }
 
+    /// Returns each item in this module's scope which isn't associated with a trait,
+    /// including imported items.
+    pub fn scope_items(&self) -> impl Iterator<Item = (&Ident, PerNs)> + '_ {
+        let types = self.scope.types().iter().filter_map(|(name, trait_hashmap)| {
+            let item = trait_hashmap.get(&None)?;
+            Some((name, PerNs { types: Some(*item), values: None }))
+        });
+        let values = self.scope.values().iter().filter_map(|(name, trait_hashmap)| {
+            let item = trait_hashmap.get(&None)?;
+            Some((name, PerNs { types: None, values: Some(*item) }))
+        });
+        types.chain(values)
+    }
+
     pub fn type_definitions(&self) -> impl Iterator<Item = ModuleDefId> + '_ {
         self.definitions.types().values().flat_map(|a| a.values().map(|(id, _)| *id))
     }
@@ -1,3 +1,4 @@
+pub mod comptime;
 pub mod def_collector;
 pub mod def_map;
 pub mod resolution;
@@ -5,8 +6,9 @@ pub mod scope;
 pub mod type_check;
 
 use crate::graph::{CrateGraph, CrateId};
+use crate::hir::comptime::InterpreterLimits;
 use crate::hir_def::function::FuncMeta;
-use crate::node_interner::{FuncId, NodeInterner, StructId};
+use crate::node_interner::{EnumId, FuncId, NodeInterner, StructId};
 use def_map::{Contract, CrateDefMap};
 use fm::FileManager;
 use noirc_errors::Location;
@@ -26,6 +28,9 @@ pub struct Context {
     /// A map of each file that already has been visited from a prior `mod foo;` declaration.
     /// This is used to issue an error if a second `mod foo;` is declared to the same file.
     pub visited_files: BTreeMap<fm::FileId, Location>,
+
+    /// Limits on the evaluation of `comptime` code
+    pub comptime_limits: InterpreterLimits,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -43,6 +48,7 @@ impl Context {
             visited_files: BTreeMap::new(),
             crate_graph,
             file_manager,
+            comptime_limits: InterpreterLimits::default(),
         }
     }
 
@@ -97,7 +103,21 @@ impl Context {
     /// For example, if you project contains a `main.nr` and `foo.nr` and you provide the `main_crate_id` and the
     /// `bar_struct_id` where the `Bar` struct is inside `foo.nr`, this function would return `foo::Bar` as a [String].
     pub fn fully_qualified_struct_path(&self, crate_id: &CrateId, id: StructId) -> String {
-        let module_id = id.module_id();
+        self.fully_qualified_type_path(crate_id, id.module_id())
+    }
+
+    /// Returns a fully-qualified path to the given [EnumId] from the given [CrateId].
+    /// See [Context::fully_qualified_struct_path] for more details.
+    pub fn fully_qualified_enum_path(&self, crate_id: &CrateId, id: EnumId) -> String {
+        self.fully_qualified_type_path(crate_id, id.module_id())
+    }
+
+    /// Types are each given their own module, so the path to a type is the path to its module.
+    fn fully_qualified_type_path(
+        &self,
+        crate_id: &CrateId,
+        module_id: def_map::ModuleId,
+    ) -> String {
         let child_id = module_id.local_id.0;
         let def_map =
             self.def_map(&module_id.krate).expect("The local crate should be analyzed already");
@@ -111,7 +131,7 @@ impl Context {
         } else {
             let crates = self
                 .find_dependencies(crate_id, &module_id.krate)
-                .expect("The Struct was supposed to be defined in a dependency");
+                .expect("The type was supposed to be defined in a dependency");
             crates.join("::") + "::" + &module_path
         }
     }
@@ -0,0 +1,107 @@
+use std::collections::{BTreeMap, HashSet};
+
+use fm::FileId;
+use iter_extended::vecmap;
+
+use crate::{
+    graph::CrateId,
+    hir::{
+        def_collector::dc_crate::{CompilationError, UnresolvedEnum},
+        def_map::ModuleId,
+        Context,
+    },
+    node_interner::{EnumId, StructId},
+    EnumVariant, Generics, Type, TypeBinding,
+};
+
+use super::{errors::ResolverError, path_resolver::StandardPathResolver, resolver::Resolver};
+
+/// Create the mappings from EnumId -> EnumType
+/// so that constructors and match expressions can access the variants of enums
+pub(crate) fn resolve_enums(
+    context: &mut Context,
+    enums: BTreeMap<EnumId, UnresolvedEnum>,
+    crate_id: CrateId,
+) -> Vec<(CompilationError, FileId)> {
+    let mut errors: Vec<(CompilationError, FileId)> = vec![];
+    let enum_files = vecmap(&enums, |(type_id, typ)| (*type_id, typ.file_id));
+
+    // Resolve each variant in each enum.
+    // Each enum should already be present in the NodeInterner after def collection.
+    for (type_id, typ) in enums {
+        let file_id = typ.file_id;
+        let (generics, variants, resolver_errors) = resolve_enum_variants(context, crate_id, typ);
+        errors.extend(vecmap(resolver_errors, |err| (err.into(), file_id)));
+        context.def_interner.update_enum(type_id, |enum_def| {
+            enum_def.set_variants(variants);
+            enum_def.generics = generics;
+        });
+    }
+
+    // Enums may refer to each other, so they can only be checked once all of them are resolved
+    for (type_id, file_id) in enum_files {
+        let enum_type = context.def_interner.get_enum(type_id);
+        let enum_type = enum_type.borrow();
+        let mut visited = Visited::default();
+        let is_recursive = enum_type.variants_as_written().iter().any(|(_, fields)| {
+            fields.iter().any(|field| contains_enum(field, type_id, &mut visited))
+        });
+        if is_recursive {
+            let error = ResolverError::RecursiveEnum { name: enum_type.name.clone() };
+            errors.push((error.into(), file_id));
+        }
+    }
+    errors
+}
+
+/// The structs and enums whose fields have already been searched
+#[derive(Default)]
+struct Visited {
+    structs: HashSet<StructId>,
+    enums: HashSet<EnumId>,
+}
+
+/// Returns true if `typ` stores a value of the enum `target` inline.
+fn contains_enum(typ: &Type, target: EnumId, visited: &mut Visited) -> bool {
+    match typ {
+        Type::Enum(def, args) => {
+            let def = def.borrow();
+            if def.id == target {
+                return true;
+            }
+            visited.enums.insert(def.id)
+                && def.get_variants(args).iter().any(|(_, fields)| {
+                    fields.iter().any(|field| contains_enum(field, target, visited))
+                })
+        }
+        Type::Struct(def, args) => {
+            let def = def.borrow();
+            visited.structs.insert(def.id)
+                && def
+                    .get_fields(args)
+                    .iter()
+                    .any(|(_, field)| contains_enum(field, target, visited))
+        }
+        Type::Array(_, element) | Type::MutableReference(element) => {
+            contains_enum(element, target, visited)
+        }
+        Type::Tuple(fields) => fields.iter().any(|field| contains_enum(field, target, visited)),
+        Type::TypeVariable(binding, _) => match &*binding.borrow() {
+            TypeBinding::Bound(binding) => contains_enum(binding, target, visited),
+            TypeBinding::Unbound(_) => false,
+        },
+        _ => false,
+    }
+}
+
+fn resolve_enum_variants(
+    context: &mut Context,
+    krate: CrateId,
+    unresolved: UnresolvedEnum,
+) -> (Generics, Vec<EnumVariant>, Vec<ResolverError>) {
+    let path_resolver =
+        StandardPathResolver::new(ModuleId { local_id: unresolved.module_id, krate });
+    let file_id = unresolved.file_id;
+    Resolver::new(&mut context.def_interner, &path_resolver, &context.def_maps, file_id)
+        .resolve_enum_variants(unresolved.enum_def)
+}
@@ -2,7 +2,7 @@ pub use noirc_errors::Span;
 use noirc_errors::{CustomDiagnostic as Diagnostic, FileDiagnostic};
 use thiserror::Error;
 
-use crate::{parser::ParserError, Ident, Type};
+use crate::{parser::ParserError, BinaryTypeOperator, Ident, Type};
 
 use super::import::PathResolutionError;
 
@@ -48,6 +48,8 @@ pub enum ResolverError {
     InvalidArrayLengthExpr { span: Span },
     #[error("Integer too large to be evaluated in an array length context")]
     IntegerTooLarge { span: Span },
+    #[error("Array length arithmetic `{lhs} {op} {rhs}` overflows or divides by zero")]
+    InvalidArrayLengthArithmetic { lhs: u64, op: BinaryTypeOperator, rhs: u64, span: Span },
     #[error("No global or generic type parameter found with the given name")]
     NoSuchNumericTypeVariable { path: crate::Path },
     #[error("Closures cannot capture mutable variables")]
@@ -82,6 +84,18 @@ pub enum ResolverError {
     NonCrateFunctionCalled { name: String, span: Span },
     #[error("Only sized types may be used in the entry point to a program")]
     InvalidTypeForEntryPoint { span: Span },
+    #[error("{} is only allowed in unconstrained functions", if *is_break { "break" } else { "continue" })]
+    JumpInConstrainedFn { is_break: bool, span: Span },
+    #[error("{} is only allowed within loops", if *is_break { "break" } else { "continue" })]
+    JumpOutsideLoop { is_break: bool, span: Span },
+    #[error("`while` and `loop` are only allowed in unconstrained functions")]
+    LoopInConstrainedFn { span: Span },
+    #[error("No associated type or constant named {name} found for {object}")]
+    NoSuchAssociatedItem { object: String, name: Ident },
+    #[error("Ambiguous associated item {name} on {object}")]
+    AmbiguousAssociatedItem { object: String, name: Ident },
+    #[error("Enum {name} contains itself")]
+    RecursiveEnum { name: Ident },
 }
 
 impl ResolverError {
@@ -221,7 +235,7 @@ impl From<ResolverError> for Diagnostic {
             ),
             ResolverError::InvalidArrayLengthExpr { span } => Diagnostic::simple_error(
                 "Expression invalid in an array-length context".into(),
-                "Array-length expressions can only have simple integer operations and any variables used must be global constants".into(),
+                "Array-length expressions can only have simple integer operations, any variables used must be global constants or generics, and only `+`, `-` and `*` may be used on generics".into(),
                 span,
             ),
             ResolverError::IntegerTooLarge { span } => Diagnostic::simple_error(
@@ -229,6 +243,14 @@ impl From<ResolverError> for Diagnostic {
                 "Array-lengths may be a maximum size of usize::MAX, including intermediate calculations".into(),
                 span,
             ),
+            ResolverError::InvalidArrayLengthArithmetic { lhs, op, rhs, span } => {
+                let reason = op.failure_reason(rhs);
+                Diagnostic::simple_error(
+                    format!("Array length arithmetic `{lhs} {op} {rhs}` {reason}"),
+                    "Array-lengths must evaluate to an integer between 0 and u64::MAX".into(),
+                    span,
+                )
+            }
             ResolverError::NoSuchNumericTypeVariable { path } => Diagnostic::simple_error(
                 format!("Cannot find a global or generic type parameter named `{path}`"),
                 "Only globals or generic type parameters are allowed to be used as an array type's length".to_string(),
@@ -304,6 +326,54 @@ impl From<ResolverError> for Diagnostic {
             ResolverError::InvalidTypeForEntryPoint { span } => Diagnostic::simple_error(
                 "Only sized types may be used in the entry point to a program".to_string(),
                 "Slices, references, or any type containing them may not be used in main or a contract function".to_string(), span),
+            ResolverError::JumpInConstrainedFn { is_break, span } => {
+                let item = if is_break { "break" } else { "continue" };
+                Diagnostic::simple_error(
+                    format!("{item} is only allowed in unconstrained functions"),
+                    "Constrained code must always have a known number of loop iterations".into(),
+                    span,
+                )
+            }
+            ResolverError::LoopInConstrainedFn { span } => {
+                let mut diagnostic = Diagnostic::simple_error(
+                    "`while` and `loop` are only allowed in unconstrained functions".into(),
+                    "The number of iterations of this loop is only known at runtime".into(),
+                    span,
+                );
+                diagnostic.add_note("Constrained functions are flattened into ACIR, so each of their loops must have a range known at compile-time. Consider moving this loop into an `unconstrained fn`".into());
+                diagnostic
+            }
+            ResolverError::NoSuchAssociatedItem { object, name } => Diagnostic::simple_error(
+                format!("No associated type or constant named `{name}` found for `{object}`"),
+                "Associated items may only be accessed through a generic constrained by their trait, or through `Self` within the trait or its impls".into(),
+                name.span(),
+            ),
+            ResolverError::AmbiguousAssociatedItem { object, name } => {
+                let mut diagnostic = Diagnostic::simple_error(
+                    format!("Ambiguous associated item `{name}` on `{object}`"),
+                    format!("More than one trait constraining `{object}` declares `{name}`"),
+                    name.span(),
+                );
+                diagnostic.add_note(format!("Use `<{object} as Trait>::{name}` to specify which trait's item to use"));
+                diagnostic
+            }
+            ResolverError::JumpOutsideLoop { is_break, span } => {
+                let item = if is_break { "break" } else { "continue" };
+                Diagnostic::simple_error(
+                    format!("{item} is only allowed within loops"),
+                    String::new(),
+                    span,
+                )
+            }
+            ResolverError::RecursiveEnum { name } => {
+                let mut diagnostic = Diagnostic::simple_error(
+                    format!("Enum `{name}` contains itself"),
+                    "Recursive enum".into(),
+                    name.span(),
+                );
+                diagnostic.add_note("Enums are stored inline, so an enum containing itself would have an infinite size".into());
+                diagnostic
+            }
         }
     }
 }
@@ -25,6 +25,7 @@ pub(crate) fn resolve_function_set(
     self_type: Option<Type>,
     trait_impl_id: Option<TraitImplId>,
     impl_generics: Vec<(Rc<String>, Shared<TypeBinding>, Span)>,
+    impl_associated_types: Vec<Type>,
     errors: &mut Vec<(CompilationError, FileId)>,
 ) -> Vec<(FileId, FuncId)> {
     let file_id = unresolved_functions.file_id;
@@ -46,6 +47,11 @@ pub(crate) fn resolve_function_set(
         resolver.set_trait_id(unresolved_functions.trait_id);
         resolver.set_trait_impl_id(trait_impl_id);
 
+        // Within a trait impl, `Self::Output` refers to the impl's own associated items
+        if let (Some(trait_id), Some(_)) = (unresolved_functions.trait_id, trait_impl_id) {
+            resolver.add_associated_items("Self", trait_id, impl_associated_types.clone());
+        }
+
         // Without this, impl methods can accidentally be placed in contracts. See #3254
         if self_type.is_some() {
             resolver.set_in_contract(false);
@@ -78,6 +84,7 @@ pub(crate) fn resolve_free_functions(
                 self_type.clone(),
                 None,
                 vec![], // no impl generics
+                vec![], // no impl associated types
                 errors,
             )
         })
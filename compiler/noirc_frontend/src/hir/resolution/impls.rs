@@ -17,7 +17,7 @@ use crate::{
 };
 
 use super::{
-    errors::ResolverError, functions, get_module_mut, get_struct_type,
+    errors::ResolverError, functions, get_module_mut, get_type_module,
     path_resolver::StandardPathResolver, resolver::Resolver, take_errors,
 };
 
@@ -45,22 +45,19 @@ pub(crate) fn collect_impls(
 
             errors.extend(take_errors(unresolved.file_id, resolver));
 
-            if let Some(struct_type) = get_struct_type(&typ) {
-                let struct_type = struct_type.borrow();
-
+            if let Some((type_module, type_name)) = get_type_module(&typ) {
                 // `impl`s are only allowed on types defined within the current crate
-                if struct_type.id.krate() != crate_id {
+                if type_module.krate != crate_id {
                     let span = *span;
-                    let type_name = struct_type.name.to_string();
                     let error = DefCollectorErrorKind::ForeignImpl { span, type_name };
                     errors.push((error.into(), unresolved.file_id));
                     continue;
                 }
 
-                // Grab the module defined by the struct type. Note that impls are a case
+                // Grab the module defined by the struct or enum type. Note that impls are a case
                 // where the module the methods are added to is not the same as the module
                 // they are resolved in.
-                let module = get_module_mut(def_maps, struct_type.id.module_id());
+                let module = get_module_mut(def_maps, type_module);
 
                 for (_, method_id, method) in &unresolved.functions {
                     // If this method was already declared, remove it from the module so it cannot
@@ -111,6 +108,7 @@ pub(crate) fn resolve_impls(
                 Some(self_type.clone()),
                 None,
                 generics,
+                vec![], // no impl associated types
                 errors,
             );
             if self_type != Type::Error {
@@ -1,4 +1,3 @@
-use iter_extended::partition_results;
 use noirc_errors::{CustomDiagnostic, Span};
 
 use crate::graph::CrateId;
@@ -12,6 +11,8 @@ pub struct ImportDirective {
     pub module_id: LocalModuleId,
     pub path: Path,
     pub alias: Option<Ident>,
+    /// True for `use path::*`, in which case `path` refers to a module rather than an item
+    pub is_glob: bool,
 }
 
 pub type PathResolution = Result<PerNs, PathResolutionError>;
@@ -20,6 +21,7 @@ pub type PathResolution = Result<PerNs, PathResolutionError>;
 pub enum PathResolutionError {
     Unresolved(Ident),
     ExternalContractUsed(Ident),
+    GlobImportNotAModule(Ident),
 }
 
 #[derive(Debug)]
@@ -30,6 +32,9 @@ pub struct ResolvedImport {
     pub resolved_namespace: PerNs,
     // The module which we must add the resolved namespace to
     pub module_scope: LocalModuleId,
+    // True if this item was brought in by a glob import, in which case it is shadowed by
+    // any other item with the same name
+    pub is_glob: bool,
 }
 
 impl From<PathResolutionError> for CustomDiagnostic {
@@ -45,31 +50,86 @@ impl From<PathResolutionError> for CustomDiagnostic {
                 "Contracts may only be referenced from within a contract".to_string(),
                 ident.span(),
             ),
+            PathResolutionError::GlobImportNotAModule(ident) => CustomDiagnostic::simple_error(
+                format!("'{ident}' is not a module"),
+                "Only the items of a module can be imported with `*`".to_string(),
+                ident.span(),
+            ),
         }
     }
 }
 
+/// Resolves each of the given imports, returning the items they bring into scope along with
+/// the imports which failed to resolve. A glob import brings in each item in its module's scope.
 pub fn resolve_imports(
     crate_id: CrateId,
     imports_to_resolve: Vec<ImportDirective>,
     def_maps: &BTreeMap<CrateId, CrateDefMap>,
-) -> (Vec<ResolvedImport>, Vec<(PathResolutionError, LocalModuleId)>) {
+) -> (Vec<ResolvedImport>, Vec<(PathResolutionError, ImportDirective)>) {
     let def_map = &def_maps[&crate_id];
+    let mut resolved = Vec::new();
+    let mut unresolved = Vec::new();
 
-    partition_results(imports_to_resolve, |import_directive| {
+    for import_directive in imports_to_resolve {
         let allow_contracts =
             allow_referencing_contracts(def_maps, crate_id, import_directive.module_id);
 
-        let module_scope = import_directive.module_id;
         let resolved_namespace =
-            resolve_path_to_ns(&import_directive, def_map, def_maps, allow_contracts)
-                .map_err(|error| (error, module_scope))?;
+            resolve_path_to_ns(&import_directive, def_map, def_maps, allow_contracts);
+
+        let result = resolved_namespace.and_then(|resolved_namespace| {
+            if import_directive.is_glob {
+                resolve_glob_import(&import_directive, resolved_namespace, def_maps)
+            } else {
+                let name = resolve_path_name(&import_directive);
+                Ok(vec![ResolvedImport {
+                    name,
+                    resolved_namespace,
+                    module_scope: import_directive.module_id,
+                    is_glob: false,
+                }])
+            }
+        });
+
+        match result {
+            Ok(imports) => resolved.extend(imports),
+            Err(error) => unresolved.push((error, import_directive)),
+        }
+    }
 
-        let name = resolve_path_name(&import_directive);
-        Ok(ResolvedImport { name, resolved_namespace, module_scope })
-    })
+    (resolved, unresolved)
 }
 
+/// Returns an import for each item in the scope of the glob imported module.
+fn resolve_glob_import(
+    import_directive: &ImportDirective,
+    resolved_namespace: PerNs,
+    def_maps: &BTreeMap<CrateId, CrateDefMap>,
+) -> Result<Vec<ResolvedImport>, PathResolutionError> {
+    let glob_module = match resolved_namespace.take_types() {
+        Some(ModuleDefId::ModuleId(module)) => module,
+        _ => {
+            // An empty path always refers to a module so there must be a last segment here
+            let name = import_directive.path.segments.last().unwrap().clone();
+            return Err(PathResolutionError::GlobImportNotAModule(name));
+        }
+    };
+
+    let module_data = glob_module.module(def_maps);
+    let items = module_data.scope_items().map(|(name, resolved_namespace)| {
+        // Point at the glob import rather than the item's definition so that any error
+        // mentioning the name refers to the import which brought it into scope.
+        let name = Ident::new(name.0.contents.clone(), import_directive.path.span);
+        ResolvedImport {
+            name,
+            resolved_namespace,
+            module_scope: import_directive.module_id,
+            is_glob: true,
+        }
+    });
+
+    Ok(items.collect())
+}
 pub(super) fn allow_referencing_contracts(
     def_maps: &BTreeMap<CrateId, CrateDefMap>,
     krate: CrateId,
@@ -152,6 +212,7 @@ fn resolve_name_in_module(
             ModuleDefId::FunctionId(_) => panic!("functions cannot be in the type namespace"),
             // TODO: If impls are ever implemented, types can be used in a path
             ModuleDefId::TypeId(id) => id.module_id(),
+            ModuleDefId::EnumId(id) => id.module_id(),
             ModuleDefId::TypeAliasId(_) => panic!("type aliases cannot be used in type namespace"),
             ModuleDefId::TraitId(id) => id.0,
             ModuleDefId::GlobalId(_) => panic!("globals cannot be in the type namespace"),
@@ -165,6 +226,7 @@ fn resolve_name_in_module(
         if found_ns.is_none() {
             return Err(PathResolutionError::Unresolved(segment.clone()));
         }
+
         // Check if it is a contract and we're calling from a non-contract context
         if current_mod.is_contract && !allow_contracts {
             return Err(PathResolutionError::ExternalContractUsed(segment.clone()));
@@ -207,8 +269,12 @@ fn resolve_external_dep(
         kind: PathKind::Plain,
         span: Span::default(),
     };
-    let dep_directive =
-        ImportDirective { module_id: dep_module.local_id, path, alias: directive.alias.clone() };
+    let dep_directive = ImportDirective {
+        module_id: dep_module.local_id,
+        path,
+        alias: directive.alias.clone(),
+        is_glob: directive.is_glob,
+    };
 
     let dep_def_map = def_maps.get(&dep_module.krate).unwrap();
 
@@ -10,6 +10,7 @@ pub mod import;
 pub mod path_resolver;
 pub mod resolver;
 
+mod enums;
 mod functions;
 mod globals;
 mod impls;
@@ -17,6 +18,7 @@ mod structs;
 mod traits;
 mod type_aliases;
 
+pub(crate) use enums::resolve_enums;
 pub(crate) use functions::resolve_free_functions;
 pub(crate) use globals::resolve_globals;
 pub(crate) use impls::{collect_impls, resolve_impls};
@@ -32,7 +34,7 @@ use crate::{
         def_collector::dc_crate::CompilationError,
         def_map::{CrateDefMap, ModuleData, ModuleId},
     },
-    Shared, StructType, Type,
+    Type,
 };
 use fm::FileId;
 use iter_extended::vecmap;
@@ -50,9 +52,18 @@ fn get_module_mut(
     &mut def_maps.get_mut(&module.krate).unwrap().modules[module.local_id.0]
 }
 
-fn get_struct_type(typ: &Type) -> Option<&Shared<StructType>> {
+/// Returns the module of a user-defined struct or enum type along with the type's name.
+/// Methods in `impl`s on the type are declared within this module.
+fn get_type_module(typ: &Type) -> Option<(ModuleId, String)> {
     match typ {
-        Type::Struct(definition, _) => Some(definition),
+        Type::Struct(definition, _) => {
+            let definition = definition.borrow();
+            Some((definition.id.module_id(), definition.name.to_string()))
+        }
+        Type::Enum(definition, _) => {
+            let definition = definition.borrow();
+            Some((definition.id.module_id(), definition.name.to_string()))
+        }
         _ => None,
     }
 }
@@ -13,9 +13,10 @@
 // XXX: Resolver does not check for unused functions
 use crate::hir_def::expr::{
     HirArrayLiteral, HirBinaryOp, HirBlockExpression, HirCallExpression, HirCapturedVar,
-    HirCastExpression, HirConstructorExpression, HirExpression, HirIdent, HirIfExpression,
-    HirIndexExpression, HirInfixExpression, HirLambda, HirLiteral, HirMemberAccess,
-    HirMethodCallExpression, HirPrefixExpression,
+    HirCastExpression, HirConstructorExpression, HirEnumConstructorExpression, HirExpression,
+    HirIdent, HirIfExpression, HirIndexExpression, HirInfixExpression, HirLambda, HirLiteral,
+    HirMatchExpression, HirMatchPattern, HirMemberAccess, HirMethodCallExpression,
+    HirPrefixExpression,
 };
 
 use crate::hir_def::traits::{Trait, TraitConstraint};
@@ -26,15 +27,17 @@ use std::rc::Rc;
 
 use crate::graph::CrateId;
 use crate::hir::def_map::{LocalModuleId, ModuleDefId, TryFromModuleDefId, MAIN_FUNCTION};
-use crate::hir_def::stmt::{HirAssignStatement, HirForStatement, HirLValue, HirPattern};
+use crate::hir_def::stmt::{
+    HirAssignStatement, HirForStatement, HirLValue, HirPattern, HirWhileStatement,
+};
 use crate::node_interner::{
     DefinitionId, DefinitionKind, ExprId, FuncId, NodeInterner, StmtId, StructId, TraitId,
     TraitImplId, TraitImplKind,
 };
 use crate::{
     hir::{def_map::CrateDefMap, resolution::path_resolver::PathResolver},
-    BlockExpression, Expression, ExpressionKind, FunctionKind, Ident, Literal, NoirFunction,
-    StatementKind,
+    BlockExpression, EnumType, EnumVariant, Expression, ExpressionKind, FunctionKind, Ident,
+    Literal, MatchExpression, MatchPattern, NoirEnum, NoirFunction, Statement, StatementKind,
 };
 use crate::{
     ArrayLiteral, ContractFunctionType, Distinctness, ForRange, FunctionVisibility, Generics,
@@ -111,6 +114,29 @@ pub struct Resolver<'a> {
     /// that are captured. We do this in order to create the hidden environment
     /// parameter for the lambda function.
     lambda_stack: Vec<LambdaContext>,
+
+    /// True if the function currently being resolved is unconstrained.
+    /// `break` and `continue` are only allowed within unconstrained functions.
+    in_unconstrained_fn: bool,
+
+    /// How many loops we're currently within. `break` and `continue` are only valid
+    /// within a loop of the current function, lambda or comptime block.
+    nested_loops: usize,
+
+    /// The associated types and constants which may be referred to through a generic or `Self`,
+    /// e.g. `T::Output` for a `T: Hasher` constraint or `Self::RATE` within a trait or its impls.
+    associated_items: Vec<AssociatedItem>,
+}
+
+/// An associated type or constant of a trait, in scope through the type it is accessed by.
+struct AssociatedItem {
+    /// The name of the generic, or `Self`, this item is accessed through
+    object: String,
+    trait_id: TraitId,
+    name: Ident,
+    typ: Type,
+    /// The declared type of this item if it is a constant, or None if it is a type
+    constant_type: Option<Type>,
 }
 
 /// ResolverMetas are tagged onto each definition to track how many times they are used
@@ -150,6 +176,9 @@ impl<'a> Resolver<'a> {
             current_trait_impl: None,
             file,
             in_contract,
+            in_unconstrained_fn: false,
+            nested_loops: 0,
+            associated_items: Vec::new(),
         }
     }
 
@@ -169,6 +198,23 @@ impl<'a> Resolver<'a> {
         self.self_type.as_ref()
     }
 
+    /// Brings the associated types and constants of the given trait into scope, accessible
+    /// through the given type name. `values` is in the order of `Trait::associated_types`.
+    pub fn add_associated_items(&mut self, object: &str, trait_id: TraitId, values: Vec<Type>) {
+        let names = self.interner.get_trait(trait_id).associated_item_names();
+
+        for ((name, constant_type), typ) in names.into_iter().zip(values) {
+            let object = object.to_owned();
+            self.associated_items.push(AssociatedItem {
+                object,
+                trait_id,
+                name,
+                typ,
+                constant_type,
+            });
+        }
+    }
+
     fn push_err(&mut self, err: ResolverError) {
         self.errors.push(err);
     }
@@ -189,7 +235,9 @@ impl<'a> Resolver<'a> {
         self.resolve_local_globals();
 
         self.add_generics(&func.def.generics);
+        self.add_where_clause_associated_items(&func.def.where_clause);
         self.trait_bounds = func.def.where_clause.clone();
+        self.in_unconstrained_fn = func.def.is_unconstrained;
 
         let (hir_func, func_meta) = self.intern_function(func, func_id);
         let func_scope_tree = self.scopes.end_function();
@@ -373,7 +421,129 @@ impl<'a> Resolver<'a> {
     ) -> Option<TraitConstraint> {
         let typ = self.resolve_type(constraint.typ);
         let trait_id = self.lookup_trait_or_error(constraint.trait_bound.trait_path)?.id;
-        Some(TraitConstraint { typ, trait_id })
+        Some(TraitConstraint::new(typ, trait_id))
+    }
+
+    /// Declares a new generic for each associated type and constant of the traits in the given
+    /// `where` clause so that they may be referred to as `T::Output` within the function.
+    /// These are bound to the items of the impl selected for `T` at each call site.
+    fn add_where_clause_associated_items(&mut self, where_clause: &[UnresolvedTraitConstraint]) {
+        for constraint in where_clause {
+            let Some(trait_id) = constraint.trait_bound.trait_id else { continue };
+            let Some(object) = Self::associated_item_object(&constraint.typ) else { continue };
+
+            let already_added = self
+                .associated_items
+                .iter()
+                .any(|item| item.object == object && item.trait_id == trait_id);
+            if already_added {
+                continue;
+            }
+
+            let span = constraint.typ.span.unwrap_or_default();
+            let names = self.interner.get_trait(trait_id).associated_item_names();
+            let values = vecmap(names, |(name, _)| {
+                let id = self.interner.next_type_variable_id();
+                let typevar = Shared::new(TypeBinding::Unbound(id));
+                let name = Rc::new(format!("{object}::{name}"));
+                self.generics.push((name.clone(), typevar.clone(), span));
+                Type::NamedGeneric(typevar, name)
+            });
+
+            self.add_associated_items(&object, trait_id, values);
+        }
+    }
+
+    /// Returns the name associated items are accessed through for the given type if it is a
+    /// plain name such as a generic `T` or `Self`.
+    fn associated_item_object(typ: &UnresolvedType) -> Option<String> {
+        match &typ.typ {
+            UnresolvedTypeData::Named(path, args)
+                if path.segments.len() == 1 && args.is_empty() =>
+            {
+                Some(path.last_segment().0.contents)
+            }
+            _ => None,
+        }
+    }
+
+    /// Looks up an associated type or constant accessed through a generic or `Self`, returning
+    /// its value along with its declared type if it is a constant. If a trait is given, only the
+    /// items of that trait are considered, as in `<T as Trait>::Output`.
+    fn lookup_associated_item(
+        &mut self,
+        object: &str,
+        trait_id: Option<TraitId>,
+        name: &Ident,
+    ) -> Option<(Type, Option<Type>)> {
+        let mut items = self.associated_items.iter().filter(|item| {
+            item.object == object
+                && item.name.0.contents == name.0.contents
+                && trait_id.map_or(true, |trait_id| item.trait_id == trait_id)
+        });
+
+        let item = items.next()?;
+        let result = (item.typ.clone(), item.constant_type.clone());
+
+        if items.next().is_some() {
+            let object = object.to_owned();
+            self.push_err(ResolverError::AmbiguousAssociatedItem { object, name: name.clone() });
+        }
+        Some(result)
+    }
+
+    /// Looks up a path of the form `T::NAME` or `Self::NAME` referring to an associated item
+    fn lookup_associated_item_by_path(&mut self, path: &Path) -> Option<(Type, Option<Type>)> {
+        if path.kind != PathKind::Plain || path.segments.len() != 2 {
+            return None;
+        }
+        let object = path.segments[0].0.contents.clone();
+        self.lookup_associated_item(&object, None, &path.segments[1])
+    }
+
+    /// Resolves an associated constant used as a value, e.g. `T::RATE`. The constant is given a
+    /// definition like any other numeric generic so its value is known after monomorphization.
+    fn resolve_associated_constant(&mut self, path: &Path) -> Option<HirIdent> {
+        let (value, Some(constant_type)) = self.lookup_associated_item_by_path(path)? else {
+            return None;
+        };
+
+        let type_variable = match value {
+            Type::NamedGeneric(type_variable, _) => type_variable,
+            value => Shared::new(TypeBinding::Bound(value)),
+        };
+
+        let definition = DefinitionKind::GenericType(type_variable);
+        let id = self.interner.push_definition(path.to_string(), false, definition);
+        self.interner.push_definition_type(id, constant_type);
+
+        let location = Location::new(path.span(), self.file);
+        Some(HirIdent { location, id })
+    }
+
+    /// Resolves `<T as Trait>::Name` to the associated type or constant `Name` of `T`.
+    fn resolve_as_trait_path(
+        &mut self,
+        typ: UnresolvedType,
+        trait_path: Path,
+        name: Ident,
+    ) -> Type {
+        let Some(the_trait) = self.lookup_trait_or_error(trait_path) else {
+            return Type::Error;
+        };
+
+        let object = match Self::associated_item_object(&typ) {
+            Some(object) => object,
+            None => typ.to_string(),
+        };
+
+        match self.lookup_associated_item(&object, Some(the_trait.id), &name) {
+            Some((typ, _)) => typ,
+            None => {
+                self.push_err(ResolverError::NoSuchAssociatedItem { object, name });
+                Type::Error
+            }
+        }
     }
 
     /// Translates an UnresolvedType into a Type and appends any
@@ -409,6 +579,9 @@ impl<'a> Resolver<'a> {
             Error => Type::Error,
             Named(path, args) => self.resolve_named_type(path, args, new_variables),
             TraitAsType(path, args) => self.resolve_trait_as_type(path, args, new_variables),
+            AsTraitPath(typ, trait_path, name) => {
+                self.resolve_as_trait_path(*typ, trait_path, name)
+            }
 
             Tuple(fields) => {
                 Type::Tuple(vecmap(fields, |field| self.resolve_type_inner(field, new_variables)))
@@ -500,6 +673,16 @@ impl<'a> Resolver<'a> {
             }
         }
 
+        if let Some(enum_type) = self.lookup_enum(path.clone()) {
+            let expected_generic_count = enum_type.borrow().generics.len();
+
+            self.verify_generics_count(expected_generic_count, &mut args, span, || {
+                enum_type.borrow().to_string()
+            });
+
+            return Type::Enum(enum_type, args);
+        }
+
         match self.lookup_struct_or_error(path) {
             Some(struct_type) => {
                 let expected_generic_count = struct_type.borrow().generics.len();
@@ -555,11 +738,20 @@ impl<'a> Resolver<'a> {
             }
         }
 
+        if let Some((typ, _)) = self.lookup_associated_item_by_path(path) {
+            return Some(typ);
+        }
+
         // If we cannot find a local generic of the same name, try to look up a global
         match self.path_resolver.resolve(self.def_maps, path.clone()) {
-            Ok(ModuleDefId::GlobalId(id)) => {
-                Some(Type::Constant(self.eval_global_as_array_length(id)))
-            }
+            Ok(ModuleDefId::GlobalId(id)) => match self.interner.use_comptime_global_as_length(&id)
+            {
+                Some(value) => {
+                    let name = Rc::new(path.last_segment().0.contents.clone());
+                    Some(Type::NamedGeneric(value, name))
+                }
+                None => Some(Type::Constant(self.eval_global_as_array_length(id))),
+            },
             _ => None,
         }
     }
@@ -584,6 +776,19 @@ impl<'a> Resolver<'a> {
         }
     }
 
+    /// Resolves the value of an associated constant in a trait impl to a type-level integer
+    /// so that it may be used anywhere the trait's constant may be, including array lengths.
+    pub fn resolve_associated_constant_value(&mut self, value: Expression) -> Type {
+        let span = value.span;
+        match UnresolvedTypeExpression::from_expr(value, span) {
+            Ok(value) => self.convert_expression_type(value),
+            Err(error) => {
+                self.push_err(ResolverError::ParserError(Box::new(error)));
+                Type::Error
+            }
+        }
+    }
+
     fn convert_expression_type(&mut self, length: UnresolvedTypeExpression) -> Type {
         match length {
             UnresolvedTypeExpression::Variable(path) => {
@@ -593,14 +798,27 @@ impl<'a> Resolver<'a> {
                 })
             }
             UnresolvedTypeExpression::Constant(int, _) => Type::Constant(int),
-            UnresolvedTypeExpression::BinaryOperation(lhs, op, rhs, _) => {
+            UnresolvedTypeExpression::BinaryOperation(lhs, op, rhs, span) => {
                 let (lhs_span, rhs_span) = (lhs.span(), rhs.span());
                 let lhs = self.convert_expression_type(*lhs);
                 let rhs = self.convert_expression_type(*rhs);
 
                 match (lhs, rhs) {
-                    (Type::Constant(lhs), Type::Constant(rhs)) => {
-                        Type::Constant(op.function()(lhs, rhs))
+                    (Type::Constant(lhs), Type::Constant(rhs)) => match op.function()(lhs, rhs) {
+                        Some(value) => Type::Constant(value),
+                        None => {
+                            self.push_err(ResolverError::InvalidArrayLengthArithmetic {
+                                lhs,
+                                op,
+                                rhs,
+                                span,
+                            });
+                            Type::Constant(0)
+                        }
+                    },
+                    // Arithmetic on generics is only evaluated once they are known
+                    (lhs, rhs) if op.is_allowed_on_generics() => {
+                        Type::InfixExpr(Box::new(lhs), op, Box::new(rhs))
                     }
                     (lhs, _) => {
                         let span =
@@ -614,6 +832,10 @@ impl<'a> Resolver<'a> {
     }
 
     fn get_ident_from_path(&mut self, path: Path) -> (HirIdent, usize) {
+        if let Some(ident) = self.resolve_associated_constant(&path) {
+            return (ident, 0);
+        }
+
         let location = Location::new(path.span(), self.file);
 
         let error = match path.as_ident().map(|ident| self.find_variable(ident)) {
@@ -717,6 +939,22 @@ impl<'a> Resolver<'a> {
         (generics, fields, self.errors)
     }
 
+    pub fn resolve_enum_variants(
+        mut self,
+        unresolved: NoirEnum,
+    ) -> (Generics, Vec<EnumVariant>, Vec<ResolverError>) {
+        let generics = self.add_generics(&unresolved.generics);
+
+        // Check whether the enum definition has globals in the local module and add them to the scope
+        self.resolve_local_globals();
+
+        let variants = vecmap(unresolved.variants, |(ident, fields)| {
+            (ident, vecmap(fields, |typ| self.resolve_type(typ)))
+        });
+
+        (generics, variants, self.errors)
+    }
+
     fn resolve_local_globals(&mut self) {
         for (stmt_id, global_info) in self.interner.get_all_globals() {
             if global_info.local_id == self.path_resolver.local_module_id() {
@@ -733,9 +971,25 @@ impl<'a> Resolver<'a> {
         &mut self,
         where_clause: &Vec<UnresolvedTraitConstraint>,
     ) -> Vec<TraitConstraint> {
-        vecmap(where_clause, |constraint| TraitConstraint {
-            typ: self.resolve_type(constraint.typ.clone()),
-            trait_id: constraint.trait_bound.trait_id.unwrap_or_else(TraitId::dummy_id),
+        vecmap(where_clause, |constraint| {
+            let trait_id = constraint.trait_bound.trait_id.unwrap_or_else(TraitId::dummy_id);
+
+            // Link the constraint to any generics declared for its associated items
+            let associated_types = match Self::associated_item_object(&constraint.typ) {
+                Some(object) => self
+                    .associated_items
+                    .iter()
+                    .filter(|item| item.object == object && item.trait_id == trait_id)
+                    .map(|item| item.typ.clone())
+                    .collect(),
+                None => Vec::new(),
+            };
+
+            TraitConstraint {
+                typ: self.resolve_type(constraint.typ.clone()),
+                trait_id,
+                associated_types,
+            }
         })
     }
 
@@ -943,9 +1197,7 @@ impl<'a> Resolver<'a> {
             | Type::Forall(_, _) => (),
 
             Type::Array(length, element_type) => {
-                if let Type::NamedGeneric(type_variable, name) = length.as_ref() {
-                    found.insert(name.to_string(), type_variable.clone());
-                }
+                Self::find_numeric_generics_in_length(length, found);
                 Self::find_numeric_generics_in_type(element_type, found);
             }
 
@@ -973,21 +1225,45 @@ impl<'a> Resolver<'a> {
                     }
                 }
             }
-            Type::MutableReference(element) => Self::find_numeric_generics_in_type(element, found),
-            Type::String(length) => {
-                if let Type::NamedGeneric(type_variable, name) = length.as_ref() {
-                    found.insert(name.to_string(), type_variable.clone());
+            Type::Enum(enum_type, generics) => {
+                for (i, generic) in generics.iter().enumerate() {
+                    if let Type::NamedGeneric(type_variable, name) = generic {
+                        if enum_type.borrow().generic_is_numeric(i) {
+                            found.insert(name.to_string(), type_variable.clone());
+                        }
+                    } else {
+                        Self::find_numeric_generics_in_type(generic, found);
+                    }
                 }
             }
+            Type::MutableReference(element) => Self::find_numeric_generics_in_type(element, found),
+            Type::String(length) => Self::find_numeric_generics_in_length(length, found),
+            Type::InfixExpr(lhs, _, rhs) => {
+                Self::find_numeric_generics_in_length(lhs, found);
+                Self::find_numeric_generics_in_length(rhs, found);
+            }
             Type::FmtString(length, fields) => {
-                if let Type::NamedGeneric(type_variable, name) = length.as_ref() {
-                    found.insert(name.to_string(), type_variable.clone());
-                }
+                Self::find_numeric_generics_in_length(length, found);
                 Self::find_numeric_generics_in_type(fields, found);
             }
         }
     }
 
+    /// Any generic used in an array or string length, including within arithmetic such as
+    /// `N + 1`, is numeric.
+    fn find_numeric_generics_in_length(
+        length: &Type,
+        found: &mut BTreeMap<String, Shared<TypeBinding>>,
+    ) {
+        match length {
+            Type::NamedGeneric(type_variable, name) => {
+                found.insert(name.to_string(), type_variable.clone());
+            }
+            Type::InfixExpr(..) => Self::find_numeric_generics_in_type(length, found),
+            _ => (),
+        }
+    }
+
     pub fn resolve_global_let(&mut self, let_stmt: crate::LetStatement) -> HirStatement {
         let expression = self.resolve_expression(let_stmt.expression);
         let definition = DefinitionKind::Global(expression);
@@ -999,7 +1275,7 @@ impl<'a> Resolver<'a> {
         })
     }
 
-    pub fn resolve_stmt(&mut self, stmt: StatementKind) -> HirStatement {
+    pub fn resolve_stmt(&mut self, stmt: StatementKind, span: Span) -> HirStatement {
         match stmt {
             StatementKind::Let(let_stmt) => {
                 let expression = self.resolve_expression(let_stmt.expression);
@@ -1041,7 +1317,7 @@ impl<'a> Resolver<'a> {
                                 true,
                                 DefinitionKind::Local(None),
                             );
-                            (decl, this.resolve_expression(block))
+                            (decl, this.resolve_loop_body(block))
                         });
 
                         HirStatement::For(HirForStatement {
@@ -1054,16 +1330,57 @@ impl<'a> Resolver<'a> {
                     range @ ForRange::Array(_) => {
                         let for_stmt =
                             range.into_for(for_loop.identifier, for_loop.block, for_loop.span);
-                        self.resolve_stmt(for_stmt)
+                        self.resolve_stmt(for_stmt, span)
                     }
                 }
             }
+            StatementKind::While(while_loop) => {
+                self.check_runtime_loop(span);
+                let condition = self.resolve_expression(while_loop.condition);
+                let block = self.resolve_loop_body(while_loop.block);
+                HirStatement::While(HirWhileStatement { condition, block })
+            }
+            StatementKind::Loop(block) => {
+                self.check_runtime_loop(span);
+                HirStatement::Loop(self.resolve_loop_body(block))
+            }
+            StatementKind::Break => {
+                self.check_break_continue(true, span);
+                HirStatement::Break
+            }
+            StatementKind::Continue => {
+                self.check_break_continue(false, span);
+                HirStatement::Continue
+            }
             StatementKind::Error => HirStatement::Error,
         }
     }
 
-    pub fn intern_stmt(&mut self, stmt: StatementKind) -> StmtId {
-        let hir_stmt = self.resolve_stmt(stmt);
+    fn resolve_loop_body(&mut self, block: Expression) -> ExprId {
+        self.nested_loops += 1;
+        let block = self.resolve_expression(block);
+        self.nested_loops -= 1;
+        block
+    }
+
+    /// Loops whose number of iterations is only known at runtime can't be flattened into ACIR
+    fn check_runtime_loop(&mut self, span: Span) {
+        if !self.in_unconstrained_fn {
+            self.push_err(ResolverError::LoopInConstrainedFn { span });
+        }
+    }
+
+    fn check_break_continue(&mut self, is_break: bool, span: Span) {
+        if !self.in_unconstrained_fn {
+            self.push_err(ResolverError::JumpInConstrainedFn { is_break, span });
+        }
+        if self.nested_loops == 0 {
+            self.push_err(ResolverError::JumpOutsideLoop { is_break, span });
+        }
+    }
+
+    pub fn intern_stmt(&mut self, stmt: Statement) -> StmtId {
+        let hir_stmt = self.resolve_stmt(stmt.kind, stmt.span);
         self.interner.push_stmt(hir_stmt)
     }
 
@@ -1206,7 +1523,17 @@ impl<'a> Resolver<'a> {
                 Literal::Unit => HirLiteral::Unit,
             }),
             ExpressionKind::Variable(path) => {
-                if let Some((hir_expr, object_type)) = self.resolve_trait_generic_path(&path) {
+                if let Some((r#type, variant_index)) = self.lookup_enum_variant(&path) {
+                    // A variant without any fields, e.g. `Option::None`
+                    let enum_generics = r#type.borrow().instantiate(self.interner);
+                    HirExpression::EnumConstructor(HirEnumConstructorExpression {
+                        r#type,
+                        enum_generics,
+                        variant_index,
+                        arguments: Vec::new(),
+                    })
+                } else if let Some((hir_expr, object_type)) = self.resolve_trait_generic_path(&path)
+                {
                     let expr_id = self.interner.push_expr(hir_expr);
                     self.interner.push_expr_location(expr_id, expr.span, self.file);
                     self.interner
@@ -1277,6 +1604,24 @@ impl<'a> Resolver<'a> {
                 })
             }
             ExpressionKind::Call(call_expr) => {
+                if let ExpressionKind::Variable(path) = &call_expr.func.kind {
+                    if let Some((r#type, variant_index)) = self.lookup_enum_variant(path) {
+                        let enum_generics = r#type.borrow().instantiate(self.interner);
+                        let arguments =
+                            vecmap(call_expr.arguments, |arg| self.resolve_expression(arg));
+                        let constructor = HirEnumConstructorExpression {
+                            r#type,
+                            enum_generics,
+                            variant_index,
+                            arguments,
+                        };
+                        let expr_id =
+                            self.interner.push_expr(HirExpression::EnumConstructor(constructor));
+                        self.interner.push_expr_location(expr_id, expr.span, self.file);
+                        return expr_id;
+                    }
+                }
+
                 // Get the span and name of path for error reporting
                 let func = self.resolve_expression(*call_expr.func);
 
@@ -1305,11 +1650,20 @@ impl<'a> Resolver<'a> {
                 consequence: self.resolve_expression(if_expr.consequence),
                 alternative: if_expr.alternative.map(|e| self.resolve_expression(e)),
             }),
+            ExpressionKind::Match(match_expr) => self.resolve_match(*match_expr),
             ExpressionKind::Index(indexed_expr) => HirExpression::Index(HirIndexExpression {
                 collection: self.resolve_expression(indexed_expr.collection),
                 index: self.resolve_expression(indexed_expr.index),
             }),
-            ExpressionKind::Block(block_expr) => self.resolve_block(block_expr),
+            ExpressionKind::Block(block_expr) => {
+                HirExpression::Block(self.resolve_block(block_expr))
+            }
+            ExpressionKind::Comptime(block_expr) => {
+                let nested_loops = std::mem::take(&mut self.nested_loops);
+                let block = self.resolve_block(block_expr);
+                self.nested_loops = nested_loops;
+                HirExpression::Comptime(block)
+            }
             ExpressionKind::Constructor(constructor) => {
                 let span = constructor.type_name.span();
 
@@ -1359,7 +1713,9 @@ impl<'a> Resolver<'a> {
                 });
 
                 let return_type = this.resolve_inferred_type(lambda.return_type);
+                let nested_loops = std::mem::take(&mut this.nested_loops);
                 let body = this.resolve_expression(lambda.body);
+                this.nested_loops = nested_loops;
 
                 let lambda_context = this.lambda_stack.pop().unwrap();
 
@@ -1380,6 +1736,47 @@ impl<'a> Resolver<'a> {
         expr_id
     }
 
+    fn resolve_match(&mut self, match_expr: MatchExpression) -> HirExpression {
+        let expression = self.resolve_expression(match_expr.expression);
+
+        // Each arm gets its own scope for the variables its pattern binds
+        let rules = vecmap(match_expr.rules, |(pattern, branch)| {
+            self.in_new_scope(|this| {
+                let pattern = this.resolve_match_pattern(pattern);
+                (pattern, this.resolve_expression(branch))
+            })
+        });
+
+        HirExpression::Match(HirMatchExpression { expression, rules })
+    }
+
+    fn resolve_match_pattern(&mut self, pattern: MatchPattern) -> HirMatchPattern {
+        match pattern {
+            MatchPattern::Binding(pattern) => {
+                HirMatchPattern::Binding(self.resolve_pattern(pattern, DefinitionKind::Local(None)))
+            }
+            MatchPattern::Variant(path, fields, span) => {
+                let fields = vecmap(fields, |field| {
+                    self.resolve_pattern(field, DefinitionKind::Local(None))
+                });
+
+                match self.lookup_enum_variant(&path) {
+                    Some((typ, variant_index)) => {
+                        HirMatchPattern::Variant { typ, variant_index, fields, span }
+                    }
+                    None => {
+                        self.push_err(ResolverError::Expected {
+                            span: path.span(),
+                            expected: "enum variant".into(),
+                            got: path.to_string(),
+                        });
+                        HirMatchPattern::Error(span)
+                    }
+                }
+            }
+        }
+    }
+
     fn resolve_pattern(&mut self, pattern: Pattern, definition: DefinitionKind) -> HirPattern {
         self.resolve_pattern_mutable(pattern, None, definition)
     }
@@ -1580,6 +1977,29 @@ impl<'a> Resolver<'a> {
         self.lookup(path).ok().map(|id| self.interner.get_type_alias(id))
     }
 
+    fn lookup_enum(&mut self, path: Path) -> Option<Shared<EnumType>> {
+        self.lookup(path).ok().map(|id| self.interner.get_enum(id))
+    }
+
+    /// Looks up a path of the form `Enum::Variant`, returning the enum
+    /// and the index of the variant if the path refers to one.
+    fn lookup_enum_variant(&mut self, path: &Path) -> Option<(Shared<EnumType>, usize)> {
+        let (variant, enum_path) = path.segments.split_last()?;
+        if enum_path.is_empty() {
+            return None;
+        }
+
+        let enum_path = Path { segments: enum_path.to_vec(), kind: path.kind, span: path.span };
+        let enum_type = match (&self.self_type, enum_path.as_ident()) {
+            (Some(Type::Enum(enum_type, _)), Some(name)) if name == SELF_TYPE_NAME => {
+                enum_type.clone()
+            }
+            _ => self.lookup_enum(enum_path)?,
+        };
+        let variant_index = enum_type.borrow().variant_index(&variant.0.contents)?;
+        Some((enum_type, variant_index))
+    }
+
     // this resolves Self::some_static_method, inside an impl block (where we don't have a concrete self_type)
     fn resolve_trait_static_method_by_self(
         &mut self,
@@ -1640,24 +2060,44 @@ impl<'a> Resolver<'a> {
         None
     }
 
+    // this resolves TraitName::some_method, where the object type is inferred from the call
+    fn resolve_trait_method_by_trait_path(&mut self, path: &Path) -> Option<(HirExpression, Type)> {
+        if path.segments.len() < 2 {
+            return None;
+        }
+
+        let mut trait_path = path.clone();
+        let method = trait_path.segments.pop().unwrap();
+
+        if let Ok(ModuleDefId::TraitId(trait_id)) = self.resolve_path(trait_path) {
+            let the_trait = self.interner.get_trait(trait_id);
+            if let Some(method) = the_trait.find_method(method) {
+                let self_type = self.interner.next_type_variable();
+                return Some((HirExpression::TraitMethodReference(method), self_type));
+            }
+        }
+        None
+    }
+
     fn resolve_trait_generic_path(&mut self, path: &Path) -> Option<(HirExpression, Type)> {
         self.resolve_trait_static_method_by_self(path)
             .or_else(|| self.resolve_trait_method_by_named_generic(path))
+            .or_else(|| self.resolve_trait_method_by_trait_path(path))
     }
 
     fn resolve_path(&mut self, path: Path) -> Result<ModuleDefId, ResolverError> {
         self.path_resolver.resolve(self.def_maps, path).map_err(ResolverError::PathResolutionError)
     }
 
-    fn resolve_block(&mut self, block_expr: BlockExpression) -> HirExpression {
+    fn resolve_block(&mut self, block_expr: BlockExpression) -> HirBlockExpression {
         let statements =
-            self.in_new_scope(|this| vecmap(block_expr.0, |stmt| this.intern_stmt(stmt.kind)));
-        HirExpression::Block(HirBlockExpression(statements))
+            self.in_new_scope(|this| vecmap(block_expr.0, |stmt| this.intern_stmt(stmt)));
+        HirBlockExpression(statements)
     }
 
     pub fn intern_block(&mut self, block: BlockExpression) -> ExprId {
         let hir_block = self.resolve_block(block);
-        self.interner.push_expr(hir_block)
+        self.interner.push_expr(HirExpression::Block(hir_block))
     }
 
     fn eval_global_as_array_length(&mut self, global: StmtId) -> u64 {
@@ -1740,6 +2180,7 @@ impl<'a> Resolver<'a> {
             | UnresolvedTypeData::Function(_, _, _)
             | UnresolvedTypeData::FormatString(_, _)
             | UnresolvedTypeData::TraitAsType(..)
+            | UnresolvedTypeData::AsTraitPath(..)
             | UnresolvedTypeData::Unspecified => {
                 let span = typ.span.expect("Function parameters should always have spans");
                 self.push_err(ResolverError::InvalidTypeForEntryPoint { span });
@@ -1,4 +1,7 @@
-use std::collections::{BTreeMap, HashSet};
+use std::{
+    collections::{BTreeMap, HashSet},
+    rc::Rc,
+};
 
 use fm::FileId;
 use iter_extended::vecmap;
@@ -14,16 +17,17 @@ use crate::{
             errors::{DefCollectorErrorKind, DuplicateType},
         },
         def_map::{CrateDefMap, ModuleDefId, ModuleId},
+        type_check::TypeCheckError,
         Context,
     },
     hir_def::traits::{Trait, TraitConstant, TraitFunction, TraitImpl, TraitType},
     node_interner::{FuncId, NodeInterner, TraitId},
-    Path, Shared, TraitItem, Type, TypeVariableKind,
+    Ident, Path, Shared, TraitItem, Type, TypeBinding, TypeVariableKind,
 };
 
 use super::{
     errors::ResolverError,
-    functions, get_module_mut, get_struct_type,
+    functions, get_module_mut, get_type_module,
     import::PathResolutionError,
     path_resolver::{PathResolver, StandardPathResolver},
     resolver::Resolver,
@@ -44,9 +48,14 @@ pub(crate) fn resolve_traits(
     for (trait_id, unresolved_trait) in traits {
         // Resolve order
         // 1. Trait Types ( Trait constants can have a trait type, therefore types before constants)
-        let _ = resolve_trait_types(context, crate_id, &unresolved_trait);
+        let types = resolve_trait_types(context, &unresolved_trait);
         // 2. Trait Constants ( Trait's methods can use trait types & constants, therefore they should be after)
-        let _ = resolve_trait_constants(context, crate_id, &unresolved_trait);
+        let (constants, errors) = resolve_trait_constants(context, crate_id, &unresolved_trait);
+        res.extend(errors);
+        context.def_interner.update_trait(trait_id, |trait_def| {
+            trait_def.set_types(types);
+            trait_def.set_constants(constants);
+        });
         // 3. Trait Methods
         let (methods, errors) =
             resolve_trait_methods(context, trait_id, crate_id, &unresolved_trait);
@@ -54,25 +63,71 @@ pub(crate) fn resolve_traits(
         context.def_interner.update_trait(trait_id, |trait_def| {
             trait_def.set_methods(methods);
         });
+
+        // Operators on non-primitive types use the operator traits declared in the stdlib
+        if crate_id.is_stdlib() {
+            context.def_interner.try_add_operator_trait(trait_id);
+        }
     }
     res
 }
 
+/// Creates the placeholder standing in for an associated type or constant within the trait's
+/// own method signatures. It is displayed as `Self::name` in any errors.
+fn associated_item_placeholder(interner: &mut NodeInterner, name: &Ident) -> Type {
+    let id = interner.next_type_variable_id();
+    let type_variable = Shared::new(TypeBinding::Unbound(id));
+    Type::NamedGeneric(type_variable, Rc::new(format!("Self::{name}")))
+}
+
 fn resolve_trait_types(
-    _context: &mut Context,
-    _crate_id: CrateId,
-    _unresolved_trait: &UnresolvedTrait,
-) -> (Vec<TraitType>, Vec<(CompilationError, FileId)>) {
-    // TODO
-    (vec![], vec![])
+    context: &mut Context,
+    unresolved_trait: &UnresolvedTrait,
+) -> Vec<TraitType> {
+    let mut types = Vec::new();
+    for item in &unresolved_trait.trait_def.items {
+        if let TraitItem::Type { name } = item {
+            let ty = associated_item_placeholder(&mut context.def_interner, name);
+            types.push(TraitType { name: name.clone(), ty, span: name.span() });
+        }
+    }
+    types
 }
+
 fn resolve_trait_constants(
-    _context: &mut Context,
-    _crate_id: CrateId,
-    _unresolved_trait: &UnresolvedTrait,
+    context: &mut Context,
+    crate_id: CrateId,
+    unresolved_trait: &UnresolvedTrait,
 ) -> (Vec<TraitConstant>, Vec<(CompilationError, FileId)>) {
-    // TODO
-    (vec![], vec![])
+    let interner = &mut context.def_interner;
+    let def_maps = &mut context.def_maps;
+
+    let path_resolver = StandardPathResolver::new(ModuleId {
+        local_id: unresolved_trait.module_id,
+        krate: crate_id,
+    });
+    let file = def_maps[&crate_id].file_id(unresolved_trait.module_id);
+
+    let mut constants = Vec::new();
+    let mut errors = Vec::new();
+    for item in &unresolved_trait.trait_def.items {
+        if let TraitItem::Constant { name, typ, default_value } = item {
+            let mut resolver = Resolver::new(interner, &path_resolver, def_maps, file);
+            let ty = resolver.resolve_type(typ.clone());
+            errors.extend(take_errors(file, resolver));
+
+            let value = associated_item_placeholder(interner, name);
+            let default_value = default_value.clone();
+            constants.push(TraitConstant {
+                name: name.clone(),
+                ty,
+                value,
+                default_value,
+                span: name.span(),
+            });
+        }
+    }
+    (constants, errors)
 }
 
 fn resolve_trait_methods(
@@ -105,10 +160,12 @@ fn resolve_trait_methods(
             let the_trait = interner.get_trait(trait_id);
             let self_type =
                 Type::TypeVariable(the_trait.self_type_typevar.clone(), TypeVariableKind::Normal);
+            let associated_types = the_trait.associated_types();
 
             let mut resolver = Resolver::new(interner, &path_resolver, def_maps, file);
             resolver.add_generics(generics);
             resolver.set_self_type(Some(self_type));
+            resolver.add_associated_items("Self", trait_id, associated_types);
 
             let arguments = vecmap(parameters, |param| resolver.resolve_type(param.1.clone()));
             let resolved_return_type = resolver.resolve_type(return_type.get_type().into_owned());
@@ -256,9 +313,8 @@ fn collect_trait_impl(
         let typ = resolver.resolve_type(unresolved_type);
         errors.extend(take_errors(trait_impl.file_id, resolver));
 
-        if let Some(struct_type) = get_struct_type(&typ) {
-            let struct_type = struct_type.borrow();
-            let module = get_module_mut(def_maps, struct_type.id.module_id());
+        if let Some((type_module, _)) = get_type_module(&typ) {
+            let module = get_module_mut(def_maps, type_module);
 
             for (_, method_id, method) in &trait_impl.methods.functions {
                 // If this method was already declared, remove it from the module so it cannot
@@ -299,9 +355,10 @@ fn check_trait_impl_crate_coherence(
     let path_resolver = StandardPathResolver::new(module);
     let mut resolver = Resolver::new(interner, &path_resolver, def_maps, file);
 
-    let object_crate = match resolver.resolve_type(trait_impl.object_type.clone()) {
-        Type::Struct(struct_type, _) => struct_type.borrow().id.krate(),
-        _ => CrateId::Dummy,
+    let object_crate = match get_type_module(&resolver.resolve_type(trait_impl.object_type.clone()))
+    {
+        Some((type_module, _)) => type_module.krate,
+        None => CrateId::Dummy,
     };
 
     if current_crate != the_trait.crate_id && current_crate != object_crate {
@@ -337,7 +394,7 @@ pub(crate) fn resolve_trait_impls(
     let mut methods = Vec::<(FileId, FuncId)>::new();
 
     for trait_impl in traits {
-        let unresolved_type = trait_impl.object_type;
+        let unresolved_type = &trait_impl.object_type;
         let local_mod_id = trait_impl.module_id;
         let module_id = ModuleId { krate: crate_id, local_id: local_mod_id };
         let path_resolver = StandardPathResolver::new(module_id);
@@ -350,6 +407,25 @@ pub(crate) fn resolve_trait_impls(
         let self_type = resolver.resolve_type(unresolved_type.clone());
         let generics = resolver.get_generics().to_vec();
 
+        let associated_types = match trait_impl.trait_id {
+            Some(trait_id) => {
+                let mut resolver =
+                    Resolver::new(interner, &path_resolver, &context.def_maps, trait_impl.file_id);
+                resolver.set_generics(generics.clone());
+                resolver.set_self_type(Some(self_type.clone()));
+
+                let associated_types = resolve_trait_impl_associated_types(
+                    &mut resolver,
+                    trait_id,
+                    &trait_impl,
+                    errors,
+                );
+                errors.extend(take_errors(trait_impl.file_id, resolver));
+                associated_types
+            }
+            None => Vec::new(),
+        };
+
         let impl_id = interner.next_trait_impl_id();
 
         let mut impl_methods = functions::resolve_function_set(
@@ -360,6 +436,7 @@ pub(crate) fn resolve_trait_impls(
             Some(self_type.clone()),
             Some(impl_id),
             generics.clone(),
+            associated_types.clone(),
             errors,
         );
 
@@ -383,11 +460,14 @@ pub(crate) fn resolve_trait_impls(
         new_resolver.set_self_type(Some(self_type.clone()));
 
         if let Some(trait_id) = maybe_trait_id {
+            new_resolver.add_associated_items("Self", trait_id, associated_types.clone());
+
             check_methods_signatures(
                 &mut new_resolver,
                 &impl_methods,
                 trait_id,
                 trait_impl.generics.len(),
+                &associated_types,
                 errors,
             );
 
@@ -404,6 +484,7 @@ pub(crate) fn resolve_trait_impls(
                 file: trait_impl.file_id,
                 where_clause,
                 methods: vecmap(&impl_methods, |(_, func_id)| *func_id),
+                associated_types,
             });
 
             if let Err((prev_span, prev_file)) = interner.add_trait_implementation(
@@ -431,6 +512,120 @@ pub(crate) fn resolve_trait_impls(
     methods
 }
 
+/// Resolves the associated types and constants of a trait impl in the order given by
+/// `Trait::associated_types`. Constants the impl does not give a value for use the trait's
+/// default value, if there is one.
+fn resolve_trait_impl_associated_types(
+    resolver: &mut Resolver,
+    trait_id: TraitId,
+    trait_impl: &UnresolvedTraitImpl,
+    errors: &mut Vec<(CompilationError, FileId)>,
+) -> Vec<Type> {
+    let the_trait = resolver.interner.get_trait(trait_id).clone();
+    let file = trait_impl.file_id;
+
+    let missing_item_error =
+        |item_name: &Ident| DefCollectorErrorKind::TraitMissingAssociatedItem {
+            trait_name: the_trait.name.clone(),
+            item_name: item_name.clone(),
+            trait_impl_span: trait_impl.object_type.span.expect("type must have a span"),
+        };
+
+    let mut associated_types = Vec::new();
+
+    for trait_type in &the_trait.types {
+        let impl_types: Vec<_> =
+            trait_impl.types.iter().filter(|(name, _)| *name == trait_type.name).collect();
+
+        if impl_types.len() > 1 {
+            let error = DefCollectorErrorKind::Duplicate {
+                typ: DuplicateType::TraitAssociatedType,
+                first_def: impl_types[0].0.clone(),
+                second_def: impl_types[1].0.clone(),
+            };
+            errors.push((error.into(), file));
+        }
+
+        match impl_types.first() {
+            Some((_, typ)) => associated_types.push(resolver.resolve_type(typ.clone())),
+            None => {
+                errors.push((missing_item_error(&trait_type.name).into(), file));
+                associated_types.push(Type::Error);
+            }
+        }
+    }
+
+    for trait_constant in &the_trait.constants {
+        let impl_constants: Vec<_> =
+            trait_impl.constants.iter().filter(|(name, ..)| *name == trait_constant.name).collect();
+
+        if impl_constants.len() > 1 {
+            let error = DefCollectorErrorKind::Duplicate {
+                typ: DuplicateType::TraitAssociatedConst,
+                first_def: impl_constants[0].0.clone(),
+                second_def: impl_constants[1].0.clone(),
+            };
+            errors.push((error.into(), file));
+        }
+
+        let value = match impl_constants.first() {
+            Some((name, typ, value)) => {
+                let typ = resolver.resolve_type(typ.clone());
+                let mut typecheck_errors = Vec::new();
+
+                trait_constant.ty.unify(&typ, &mut typecheck_errors, || {
+                    TypeCheckError::TypeMismatch {
+                        expected_typ: trait_constant.ty.to_string(),
+                        expr_typ: typ.to_string(),
+                        expr_span: name.span(),
+                    }
+                });
+                errors.extend(typecheck_errors.into_iter().map(|error| (error.into(), file)));
+
+                resolver.resolve_associated_constant_value(value.clone())
+            }
+            None => match &trait_constant.default_value {
+                Some(default_value) => {
+                    resolver.resolve_associated_constant_value(default_value.clone())
+                }
+                None => {
+                    errors.push((missing_item_error(&trait_constant.name).into(), file));
+                    Type::Error
+                }
+            },
+        };
+        associated_types.push(value);
+    }
+
+    // Emit errors for any items in the impl which aren't declared by the trait
+    let impl_type_names = trait_impl.types.iter().map(|(name, _)| name);
+    let impl_constant_names = trait_impl.constants.iter().map(|(name, ..)| name);
+
+    for name in impl_type_names {
+        if !the_trait.types.iter().any(|trait_type| trait_type.name == *name) {
+            let trait_name = the_trait.name.clone();
+            let error = DefCollectorErrorKind::AssociatedItemNotInTrait {
+                trait_name,
+                impl_item: name.clone(),
+            };
+            errors.push((error.into(), file));
+        }
+    }
+
+    for name in impl_constant_names {
+        if !the_trait.constants.iter().any(|constant| constant.name == *name) {
+            let trait_name = the_trait.name.clone();
+            let error = DefCollectorErrorKind::AssociatedItemNotInTrait {
+                trait_name,
+                impl_item: name.clone(),
+            };
+            errors.push((error.into(), file));
+        }
+    }
+
+    associated_types
+}
+
 pub(crate) fn take_errors_filter_self_not_resolved(
     file_id: FileId,
     resolver: Resolver<'_>,
@@ -1,4 +1,5 @@
 use acvm::FieldElement;
+use iter_extended::vecmap;
 use noirc_errors::CustomDiagnostic as Diagnostic;
 use noirc_errors::Span;
 use thiserror::Error;
@@ -115,6 +116,10 @@ pub enum TypeCheckError {
     NoMatchingImplFound { constraints: Vec<(Type, String)>, span: Span },
     #[error("Constraint for `{typ}: {trait_name}` is not needed, another matching impl is already in scope")]
     UnneededTraitConstraint { trait_name: String, typ: Type, span: Span },
+    #[error("Match on {typ} is not exhaustive")]
+    NonExhaustiveMatch { typ: Type, missing_patterns: Vec<String>, span: Span },
+    #[error("Unreachable match arm")]
+    UnreachableMatchArm { span: Span },
 }
 
 impl TypeCheckError {
@@ -275,6 +280,17 @@ impl From<TypeCheckError> for Diagnostic {
                 let msg = format!("Constraint for `{typ}: {trait_name}` is not needed, another matching impl is already in scope");
                 Diagnostic::simple_warning(msg, "Unnecessary trait constraint in where clause".into(), span)
             }
+            TypeCheckError::NonExhaustiveMatch { typ, missing_patterns, span } => {
+                let msg = format!("Match on {typ} is not exhaustive");
+                let patterns = vecmap(missing_patterns, |pattern| format!("`{pattern}`"));
+                let secondary = format!("Patterns {} not covered", patterns.join(", "));
+                Diagnostic::simple_error(msg, secondary, span)
+            }
+            TypeCheckError::UnreachableMatchArm { span } => {
+                let msg = "Unreachable match arm".to_string();
+                let secondary = "This pattern is already covered by a previous arm".to_string();
+                Diagnostic::simple_warning(msg, secondary, span)
+            }
         }
     }
 }
@@ -2,16 +2,20 @@ use iter_extended::vecmap;
 use noirc_errors::Span;
 
 use crate::{
-    hir::{resolution::resolver::verify_mutable_reference, type_check::errors::Source},
+    hir::{
+        resolution::{errors::ResolverError, resolver::verify_mutable_reference},
+        type_check::errors::Source,
+    },
     hir_def::{
         expr::{
-            self, HirArrayLiteral, HirBinaryOp, HirExpression, HirLiteral, HirMethodCallExpression,
-            HirMethodReference, HirPrefixExpression,
+            self, HirArrayLiteral, HirBinaryOp, HirBlockExpression, HirExpression, HirLiteral,
+            HirMethodCallExpression, HirMethodReference, HirPrefixExpression,
         },
+        traits::TraitConstraint,
         types::Type,
     },
-    node_interner::{DefinitionKind, ExprId, FuncId, TraitId, TraitMethodId},
-    BinaryOpKind, Signedness, TypeBinding, TypeVariableKind, UnaryOp,
+    node_interner::{DefinitionKind, ExprId, FuncId, TraitId, TraitImplKind, TraitMethodId},
+    BinaryOpKind, Signedness, TypeBinding, TypeBindings, TypeVariableId, TypeVariableKind, UnaryOp,
 };
 
 use super::{errors::TypeCheckError, TypeChecker};
@@ -58,11 +62,7 @@ impl<'interner> TypeChecker<'interner> {
                 // to be checked later when the type of this variable is further constrained.
                 if let Some(definition) = self.interner.try_definition(ident.id) {
                     if let DefinitionKind::Function(function) = definition.kind {
-                        let function = self.interner.function_meta(&function);
-                        for mut constraint in function.trait_constraints.clone() {
-                            constraint.typ = constraint.typ.substitute(&bindings);
-                            self.trait_constraints.push((constraint, *expr_id));
-                        }
+                        self.push_function_trait_constraints(&function, &bindings, *expr_id);
                     }
                 }
 
@@ -136,11 +136,20 @@ impl<'interner> TypeChecker<'interner> {
                 let rhs_span = self.interner.expr_span(&infix_expr.rhs);
                 let span = lhs_span.merge(rhs_span);
 
-                self.infix_operand_type_rules(&lhs_type, &infix_expr.operator, &rhs_type, span)
-                    .unwrap_or_else(|error| {
+                let operator = &infix_expr.operator;
+                match self.infix_operand_type_rules(&lhs_type, operator, &rhs_type, span) {
+                    Ok((typ, use_impl)) => {
+                        if use_impl {
+                            let method = self.interner.get_operator_trait_method(operator.kind);
+                            self.push_operator_trait_constraint(method, lhs_type, *expr_id);
+                        }
+                        typ
+                    }
+                    Err(error) => {
                         self.errors.push(error);
                         Type::Error
-                    })
+                    }
+                }
             }
             HirExpression::Index(index_expr) => self.check_index_expression(expr_id, index_expr),
             HirExpression::Call(call_expr) => {
@@ -224,41 +233,25 @@ impl<'interner> TypeChecker<'interner> {
                 let span = self.interner.expr_span(expr_id);
                 self.check_cast(lhs_type, cast_expr.r#type, span)
             }
-            HirExpression::Block(block_expr) => {
-                let mut block_type = Type::Unit;
-
-                let statements = block_expr.statements();
-                for (i, stmt) in statements.iter().enumerate() {
-                    let expr_type = self.check_statement(stmt);
-
-                    if let crate::hir_def::stmt::HirStatement::Semi(expr) =
-                        self.interner.statement(stmt)
-                    {
-                        let inner_expr_type = self.interner.id_type(expr);
-                        let span = self.interner.expr_span(&expr);
-
-                        self.unify(&inner_expr_type, &Type::Unit, || {
-                            TypeCheckError::UnusedResultError {
-                                expr_type: inner_expr_type.clone(),
-                                expr_span: span,
-                            }
-                        });
-                    }
-
-                    if i + 1 == statements.len() {
-                        block_type = expr_type;
-                    }
-                }
-
-                block_type
-            }
+            HirExpression::Block(block_expr) => self.check_block(block_expr),
+            HirExpression::Comptime(block_expr) => self.check_block(block_expr),
             HirExpression::Prefix(prefix_expr) => {
                 let rhs_type = self.check_expression(&prefix_expr.rhs);
                 let span = self.interner.expr_span(&prefix_expr.rhs);
-                self.type_check_prefix_operand(&prefix_expr.operator, &rhs_type, span)
+                let operator = prefix_expr.operator;
+                let (typ, use_impl) = self.type_check_prefix_operand(&operator, &rhs_type, span);
+                if use_impl {
+                    let method = self.interner.get_prefix_operator_trait_method(operator);
+                    self.push_operator_trait_constraint(method, rhs_type, *expr_id);
+                }
+                typ
             }
             HirExpression::If(if_expr) => self.check_if_expr(&if_expr, expr_id),
+            HirExpression::Match(match_expr) => self.check_match(match_expr, expr_id),
             HirExpression::Constructor(constructor) => self.check_constructor(constructor, expr_id),
+            HirExpression::EnumConstructor(constructor) => {
+                self.check_enum_constructor(constructor, expr_id)
+            }
             HirExpression::MemberAccess(access) => self.check_member_access(access, *expr_id),
             HirExpression::Error => Type::Error,
             HirExpression::Tuple(elements) => {
@@ -297,6 +290,33 @@ impl<'interner> TypeChecker<'interner> {
                     Box::new(Type::Unit),
                 );
 
+                // Replace the trait's `Self` type with the type the method is called on so that
+                // the trait's shared `Self` type variable is never bound by a single call site.
+                // The impl used for this type is then chosen once the function is fully checked.
+                let typ = match self.interner.get_selected_impl_for_ident(*expr_id) {
+                    Some(TraitImplKind::Assumed { object_type })
+                        if !is_type_variable(&object_type, the_trait.self_type_typevar_id) =>
+                    {
+                        let mut self_binding = TypeBindings::new();
+                        let self_type_typevar = the_trait.self_type_typevar.clone();
+                        self_binding.insert(
+                            the_trait.self_type_typevar_id,
+                            (self_type_typevar, object_type.clone()),
+                        );
+
+                        // Each associated type is only known once the impl is selected
+                        let trait_id = the_trait.id;
+                        let associated_types =
+                            self.instantiate_associated_types(trait_id, &mut self_binding);
+
+                        let constraint =
+                            TraitConstraint { typ: object_type, trait_id, associated_types };
+                        self.trait_constraints.push((constraint, *expr_id));
+                        typ.substitute(&self_binding)
+                    }
+                    _ => typ,
+                };
+
                 let (typ, bindings) = typ.instantiate(self.interner);
                 self.interner.store_instantiation_bindings(*expr_id, bindings);
                 typ
@@ -307,15 +327,116 @@ impl<'interner> TypeChecker<'interner> {
         typ
     }
 
+    /// Push each trait constraint from the given function's `where` clause, instantiated
+    /// with the given bindings, so they can be verified once the rest of the caller is checked.
+    fn push_function_trait_constraints(
+        &mut self,
+        func_id: &FuncId,
+        bindings: &TypeBindings,
+        expr_id: ExprId,
+    ) {
+        let function = self.interner.function_meta(func_id);
+        for mut constraint in function.trait_constraints {
+            constraint.typ = constraint.typ.substitute(bindings);
+            constraint.associated_types =
+                vecmap(&constraint.associated_types, |typ| typ.substitute(bindings));
+            self.trait_constraints.push((constraint, expr_id));
+        }
+    }
+
+    /// Operators on non-primitive types are desugared into a call to the operator's trait
+    /// method. The impl used is selected for the operator expression once the operand's
+    /// type is fully known at the end of the function, just as with any other constraint.
+    fn push_operator_trait_constraint(
+        &mut self,
+        method: Option<TraitMethodId>,
+        object_type: Type,
+        expr_id: ExprId,
+    ) {
+        let method = method.expect("Operators only use impls when their trait is known");
+        let constraint = TraitConstraint::new(object_type, method.trait_id);
+        self.trait_constraints.push((constraint, expr_id));
+    }
+
+    /// Bind each associated type and constant of a trait to a fresh type variable in
+    /// the given bindings if it is not already bound there, returning what each is
+    /// bound to in the order of `Trait::associated_types`.
+    fn instantiate_associated_types(
+        &mut self,
+        trait_id: TraitId,
+        bindings: &mut TypeBindings,
+    ) -> Vec<Type> {
+        let placeholders = self.interner.get_trait(trait_id).associated_types();
+        vecmap(placeholders, |placeholder| {
+            if let Type::NamedGeneric(type_variable, _) = &placeholder {
+                if let TypeBinding::Unbound(id) = &*type_variable.borrow() {
+                    if let Some((_, typ)) = bindings.get(id) {
+                        return typ.clone();
+                    }
+                    let fresh = self.interner.next_type_variable();
+                    bindings.insert(*id, (type_variable.clone(), fresh.clone()));
+                    return fresh;
+                }
+            }
+            placeholder
+        })
+    }
+
+    /// Once an impl is selected for a constraint, unify the associated types and constants
+    /// the constraint expects with the values given to them by that impl.
+    pub fn bind_associated_types(
+        &mut self,
+        constraint: &TraitConstraint,
+        impl_kind: TraitImplKind,
+        span: Span,
+    ) {
+        if constraint.associated_types.is_empty() {
+            return;
+        }
+
+        let actual_types = match impl_kind {
+            TraitImplKind::Normal(impl_id) => {
+                self.interner.get_associated_types_for_impl(impl_id, &constraint.typ)
+            }
+            TraitImplKind::Assumed { object_type } => {
+                let current_function =
+                    self.current_function.expect("unexpected trait constraint outside a function");
+                let func_meta = self.interner.function_meta(&current_function);
+
+                let assumed = func_meta.trait_constraints.into_iter().find(|assumed| {
+                    assumed.trait_id == constraint.trait_id
+                        && object_type.try_unify(&assumed.typ).is_ok()
+                });
+
+                match assumed {
+                    Some(assumed) => assumed.associated_types,
+                    None => return,
+                }
+            }
+        };
+
+        for (expected, actual) in constraint.associated_types.iter().zip(actual_types) {
+            self.unify(&actual, expected, || TypeCheckError::TypeMismatch {
+                expected_typ: expected.to_string(),
+                expr_typ: actual.to_string(),
+                expr_span: span,
+            });
+        }
+    }
+
+    /// Select the impl for the given constraint, returning it if one was found.
     pub fn verify_trait_constraint(
         &mut self,
         object_type: &Type,
         trait_id: TraitId,
         function_ident_id: ExprId,
         span: Span,
-    ) {
+    ) -> Option<TraitImplKind> {
         match self.interner.lookup_trait_implementation(object_type, trait_id) {
-            Ok(impl_kind) => self.interner.select_impl_for_ident(function_ident_id, impl_kind),
+            Ok(impl_kind) => {
+                self.interner.select_impl_for_ident(function_ident_id, impl_kind.clone());
+                Some(impl_kind)
+            }
             Err(erroring_constraints) => {
                 // Don't show any errors where try_get_trait returns None.
                 // This can happen if a trait is used that was never declared.
@@ -330,6 +451,7 @@ impl<'interner> TypeChecker<'interner> {
                 if let Some(constraints) = constraints {
                     self.errors.push(TypeCheckError::NoMatchingImplFound { constraints, span });
                 }
+                None
             }
         }
     }
@@ -533,13 +655,13 @@ impl<'interner> TypeChecker<'interner> {
         arguments: Vec<(Type, ExprId, Span)>,
         span: Span,
     ) -> Type {
-        let (fn_typ, param_len) = match method_ref {
+        let (fn_typ, param_len) = match &method_ref {
             HirMethodReference::FuncId(func_id) => {
-                if func_id == FuncId::dummy_id() {
+                if *func_id == FuncId::dummy_id() {
                     return Type::Error;
                 }
 
-                let func_meta = self.interner.function_meta(&func_id);
+                let func_meta = self.interner.function_meta(func_id);
                 let param_len = func_meta.parameters.len();
                 (func_meta.typ, param_len)
             }
@@ -562,11 +684,63 @@ impl<'interner> TypeChecker<'interner> {
 
         let (function_type, instantiation_bindings) = fn_typ.instantiate(self.interner);
 
+        match method_ref {
+            // Methods with a `where` clause need their constraints checked at each call site as well
+            HirMethodReference::FuncId(func_id) => {
+                self.push_function_trait_constraints(
+                    &func_id,
+                    &instantiation_bindings,
+                    *function_ident_id,
+                );
+            }
+            // The associated types in a trait method's signature are bound once its impl is known
+            HirMethodReference::TraitMethodId(method) => {
+                let mut bindings = instantiation_bindings.clone();
+                let associated_types =
+                    self.instantiate_associated_types(method.trait_id, &mut bindings);
+
+                if !associated_types.is_empty() {
+                    let object_type = arguments[0].0.follow_bindings();
+                    let constraint = TraitConstraint {
+                        typ: object_type,
+                        trait_id: method.trait_id,
+                        associated_types,
+                    };
+                    self.trait_constraints.push((constraint, *function_ident_id));
+                }
+            }
+        }
+
         self.interner.store_instantiation_bindings(*function_ident_id, instantiation_bindings);
         self.interner.push_expr_type(function_ident_id, function_type.clone());
         self.bind_function_type(function_type, arguments, span)
     }
 
+    fn check_block(&mut self, block_expr: HirBlockExpression) -> Type {
+        let mut block_type = Type::Unit;
+
+        let statements = block_expr.statements();
+        for (i, stmt) in statements.iter().enumerate() {
+            let expr_type = self.check_statement(stmt);
+
+            if let crate::hir_def::stmt::HirStatement::Semi(expr) = self.interner.statement(stmt) {
+                let inner_expr_type = self.interner.id_type(expr);
+                let span = self.interner.expr_span(&expr);
+
+                self.unify(&inner_expr_type, &Type::Unit, || TypeCheckError::UnusedResultError {
+                    expr_type: inner_expr_type.clone(),
+                    expr_span: span,
+                });
+            }
+
+            if i + 1 == statements.len() {
+                block_type = expr_type;
+            }
+        }
+
+        block_type
+    }
+
     fn check_if_expr(&mut self, if_expr: &expr::HirIfExpression, expr_id: &ExprId) -> Type {
         let cond_type = self.check_expression(&if_expr.condition);
         let then_type = self.check_expression(&if_expr.consequence);
@@ -608,6 +782,168 @@ impl<'interner> TypeChecker<'interner> {
         }
     }
 
+    fn check_match(&mut self, match_expr: expr::HirMatchExpression, expr_id: &ExprId) -> Type {
+        let scrutinee_type = self.check_expression(&match_expr.expression);
+        let result_type = if match_expr.rules.is_empty() {
+            Type::Unit
+        } else {
+            self.interner.next_type_variable()
+        };
+
+        for (pattern, branch) in &match_expr.rules {
+            self.bind_match_pattern(pattern, &scrutinee_type);
+
+            let branch_type = self.check_expression(branch);
+            let expr_span = self.interner.expr_span(branch);
+            self.unify(&branch_type, &result_type, || TypeCheckError::TypeMismatch {
+                expected_typ: result_type.to_string(),
+                expr_typ: branch_type.to_string(),
+                expr_span,
+            });
+        }
+
+        let span = self.interner.expr_span(expr_id);
+        self.check_match_exhaustiveness(&match_expr.rules, scrutinee_type, span);
+        result_type
+    }
+
+    fn bind_match_pattern(&mut self, pattern: &expr::HirMatchPattern, scrutinee_type: &Type) {
+        match pattern {
+            expr::HirMatchPattern::Binding(pattern) => {
+                self.bind_pattern(pattern, scrutinee_type.clone());
+            }
+            expr::HirMatchPattern::Variant { typ, variant_index, fields, span } => {
+                let generics = typ.borrow().instantiate(self.interner);
+                let field_types = typ.borrow().get_variant(*variant_index, &generics);
+                let enum_type = Type::Enum(typ.clone(), generics);
+
+                self.unify(scrutinee_type, &enum_type, || TypeCheckError::TypeMismatch {
+                    expected_typ: scrutinee_type.to_string(),
+                    expr_typ: enum_type.to_string(),
+                    expr_span: *span,
+                });
+
+                if fields.len() != field_types.len() {
+                    self.errors.push(TypeCheckError::ArityMisMatch {
+                        expected: field_types.len() as u16,
+                        found: fields.len() as u16,
+                        span: *span,
+                    });
+                }
+
+                let field_types = field_types.into_iter().chain(std::iter::repeat(Type::Error));
+                for (field, field_type) in fields.iter().zip(field_types) {
+                    self.bind_pattern(field, field_type);
+                }
+            }
+            expr::HirMatchPattern::Error(_) => (),
+        }
+    }
+
+    /// Issues an error if the given match arms do not cover every value of the matched type,
+    /// and a warning for any arm which can never be reached.
+    ///
+    /// Since patterns are not nested, a variant pattern covers its entire variant
+    /// and a binding pattern covers every remaining value.
+    fn check_match_exhaustiveness(
+        &mut self,
+        rules: &[(expr::HirMatchPattern, ExprId)],
+        scrutinee_type: Type,
+        span: Span,
+    ) {
+        let enum_type = match scrutinee_type.follow_bindings() {
+            Type::Enum(enum_type, _) => Some(enum_type),
+            _ => None,
+        };
+
+        let variant_count = enum_type.as_ref().map_or(0, |typ| typ.borrow().num_variants());
+        let mut covered_variants = vec![false; variant_count];
+        let mut has_catch_all = false;
+        let mut has_error = false;
+
+        for (pattern, _) in rules {
+            let is_reachable = !has_catch_all
+                && match pattern {
+                    expr::HirMatchPattern::Binding(_) => {
+                        has_catch_all = true;
+                        true
+                    }
+                    expr::HirMatchPattern::Variant { variant_index, .. } => {
+                        // The variant may be out of range if the pattern's enum did not match
+                        // the scrutinee's type, which has already been reported as an error.
+                        covered_variants
+                            .get_mut(*variant_index)
+                            .map_or(true, |covered| !std::mem::replace(covered, true))
+                    }
+                    expr::HirMatchPattern::Error(_) => {
+                        has_error = true;
+                        true
+                    }
+                };
+
+            if !is_reachable {
+                self.errors.push(TypeCheckError::UnreachableMatchArm { span: pattern.span() });
+            }
+        }
+
+        if has_catch_all || has_error {
+            return;
+        }
+
+        let missing_patterns = match enum_type {
+            Some(enum_type) => {
+                let enum_type = enum_type.borrow();
+                let missing = covered_variants.iter().enumerate().filter(|(_, covered)| !**covered);
+                vecmap(missing, |(index, _)| {
+                    format!("{}::{}", enum_type.name, enum_type.variant_name(index))
+                })
+            }
+            // Only a binding can match a non-enum type so a match without one is missing `_`
+            None if rules.is_empty() => vec!["_".to_string()],
+            None => Vec::new(),
+        };
+
+        if !missing_patterns.is_empty() {
+            let typ = scrutinee_type;
+            self.errors.push(TypeCheckError::NonExhaustiveMatch { typ, missing_patterns, span });
+        }
+    }
+
+    fn check_enum_constructor(
+        &mut self,
+        constructor: expr::HirEnumConstructorExpression,
+        expr_id: &ExprId,
+    ) -> Type {
+        let typ = constructor.r#type;
+        let generics = constructor.enum_generics;
+        let field_types = typ.borrow().get_variant(constructor.variant_index, &generics);
+
+        if field_types.len() != constructor.arguments.len() {
+            self.errors.push(TypeCheckError::ArityMisMatch {
+                expected: field_types.len() as u16,
+                found: constructor.arguments.len() as u16,
+                span: self.interner.expr_span(expr_id),
+            });
+        }
+
+        // Any extra arguments are still checked so that their types are known
+        let field_types = field_types.into_iter().chain(std::iter::repeat(Type::Error));
+        for (arg, param_type) in constructor.arguments.iter().zip(field_types) {
+            let arg_type = self.check_expression(arg);
+
+            let span = self.interner.expr_span(arg);
+            self.unify_with_coercions(&arg_type, &param_type, *arg, || {
+                TypeCheckError::TypeMismatch {
+                    expected_typ: param_type.to_string(),
+                    expr_typ: arg_type.to_string(),
+                    expr_span: span,
+                }
+            });
+        }
+
+        Type::Enum(typ, generics)
+    }
+
     fn check_constructor(
         &mut self,
         constructor: expr::HirConstructorExpression,
@@ -751,13 +1087,13 @@ impl<'interner> TypeChecker<'interner> {
         rhs_type: &Type,
         op: &HirBinaryOp,
         span: Span,
-    ) -> Result<Type, TypeCheckError> {
+    ) -> Result<(Type, bool), TypeCheckError> {
         use crate::BinaryOpKind::{Equal, NotEqual};
         use Type::*;
 
         match (lhs_type, rhs_type) {
             // Avoid reporting errors multiple times
-            (Error, _) | (_, Error) => Ok(Bool),
+            (Error, _) | (_, Error) => Ok((Bool, false)),
 
             // Matches on TypeVariable must be first to follow any type
             // bindings.
@@ -779,7 +1115,7 @@ impl<'interner> TypeChecker<'interner> {
                 }
 
                 if other.try_bind_to_polymorphic_int(int).is_ok() || other == &Type::Error {
-                    Ok(Bool)
+                    Ok((Bool, false))
                 } else {
                     Err(TypeCheckError::TypeMismatchWithSource {
                         expected: lhs_type.clone(),
@@ -804,7 +1140,7 @@ impl<'interner> TypeChecker<'interner> {
                         span,
                     });
                 }
-                Ok(Bool)
+                Ok((Bool, false))
             }
             (Integer(..), FieldElement) | (FieldElement, Integer(..)) => {
                 Err(TypeCheckError::IntegerAndFieldBinaryOperation { span })
@@ -814,14 +1150,14 @@ impl<'interner> TypeChecker<'interner> {
             }
             (FieldElement, FieldElement) => {
                 if op.kind.is_valid_for_field_type() {
-                    Ok(Bool)
+                    Ok((Bool, false))
                 } else {
                     Err(TypeCheckError::FieldComparison { span })
                 }
             }
 
             // <= and friends are technically valid for booleans, just not very useful
-            (Bool, Bool) => Ok(Bool),
+            (Bool, Bool) => Ok((Bool, false)),
 
             // Special-case == and != for arrays
             (Array(x_size, x_type), Array(y_size, y_type))
@@ -841,11 +1177,14 @@ impl<'interner> TypeChecker<'interner> {
                     span: op.location.span,
                 });
 
-                Ok(Bool)
+                Ok((Bool, false))
             }
             (lhs @ NamedGeneric(binding_a, _), rhs @ NamedGeneric(binding_b, _)) => {
                 if binding_a == binding_b {
-                    return Ok(Bool);
+                    // Generics are only compared through their trait if a where clause says they
+                    // implement it. Otherwise they keep the built-in comparison.
+                    let use_impl = self.generic_has_operator_impl(lhs, op.kind);
+                    return Ok((Bool, use_impl));
                 }
                 Err(TypeCheckError::TypeMismatchWithSource {
                     expected: lhs.clone(),
@@ -862,7 +1201,16 @@ impl<'interner> TypeChecker<'interner> {
                     source: Source::StringLen,
                 });
 
-                Ok(Bool)
+                Ok((Bool, false))
+            }
+            (Struct(..) | Enum(..), _) | (_, Struct(..) | Enum(..)) => {
+                let error = TypeCheckError::TypeMismatchWithSource {
+                    expected: lhs_type.clone(),
+                    actual: rhs_type.clone(),
+                    source: Source::Comparison,
+                    span,
+                };
+                self.operator_trait_type_rules(lhs_type, op, rhs_type, span, error)
             }
             (lhs, rhs) => Err(TypeCheckError::TypeMismatchWithSource {
                 expected: lhs.clone(),
@@ -987,6 +1335,21 @@ impl<'interner> TypeChecker<'interner> {
             });
         }
 
+        // Array lengths in the return type may use arithmetic on generics which are only known once
+        // the whole function is checked, e.g. `N - 1` is invalid if `N` is later bound to `0`.
+        let ret = fn_ret.clone();
+        self.push_delayed_type_check(Box::new(move || match ret.find_invalid_infix_expr() {
+            Some((lhs, op, rhs)) => {
+                Err(TypeCheckError::ResolverError(ResolverError::InvalidArrayLengthArithmetic {
+                    lhs,
+                    op,
+                    rhs,
+                    span,
+                }))
+            }
+            None => Ok(()),
+        }));
+
         fn_ret.clone()
     }
 
@@ -1033,7 +1396,7 @@ impl<'interner> TypeChecker<'interner> {
         op: &HirBinaryOp,
         rhs_type: &Type,
         span: Span,
-    ) -> Result<Type, TypeCheckError> {
+    ) -> Result<(Type, bool), TypeCheckError> {
         if op.kind.is_comparator() {
             return self.comparator_operand_type_rules(lhs_type, rhs_type, op, span);
         }
@@ -1041,7 +1404,7 @@ impl<'interner> TypeChecker<'interner> {
         use Type::*;
         match (lhs_type, rhs_type) {
             // An error type on either side will always return an error
-            (Error, _) | (_, Error) => Ok(Error),
+            (Error, _) | (_, Error) => Ok((Error, false)),
 
             // Matches on TypeVariable must be first so that we follow any type
             // bindings.
@@ -1078,7 +1441,7 @@ impl<'interner> TypeChecker<'interner> {
                 }
 
                 if other.try_bind_to_polymorphic_int(int).is_ok() || other == &Type::Error {
-                    Ok(other.clone())
+                    Ok((other.clone(), false))
                 } else {
                     Err(TypeCheckError::TypeMismatchWithSource {
                         expected: lhs_type.clone(),
@@ -1108,7 +1471,7 @@ impl<'interner> TypeChecker<'interner> {
                 {
                     Err(TypeCheckError::InvalidInfixOp { kind: "Signed integer", span })
                 } else {
-                    Ok(Integer(*sign_x, *bit_width_x))
+                    Ok((Integer(*sign_x, *bit_width_x), false))
                 }
             }
             (Integer(..), FieldElement) | (FieldElement, Integer(..)) => {
@@ -1122,7 +1485,12 @@ impl<'interner> TypeChecker<'interner> {
                 Err(TypeCheckError::InvalidInfixOp { kind: "Arrays", span })
             }
             (Struct(..), _) | (_, Struct(..)) => {
-                Err(TypeCheckError::InvalidInfixOp { kind: "Structs", span })
+                let error = TypeCheckError::InvalidInfixOp { kind: "Structs", span };
+                self.operator_trait_type_rules(lhs_type, op, rhs_type, span, error)
+            }
+            (Enum(..), _) | (_, Enum(..)) => {
+                let error = TypeCheckError::InvalidInfixOp { kind: "Enums", span };
+                self.operator_trait_type_rules(lhs_type, op, rhs_type, span, error)
             }
             (Tuple(_), _) | (_, Tuple(_)) => {
                 Err(TypeCheckError::InvalidInfixOp { kind: "Tuples", span })
@@ -1136,10 +1504,16 @@ impl<'interner> TypeChecker<'interner> {
                 if op.is_modulo() {
                     return Err(TypeCheckError::FieldModulo { span });
                 }
-                Ok(FieldElement)
+                Ok((FieldElement, false))
             }
 
-            (Bool, Bool) => Ok(Bool),
+            (Bool, Bool) => Ok((Bool, false)),
+
+            (lhs @ NamedGeneric(binding_a, _), NamedGeneric(binding_b, _))
+                if binding_a == binding_b && self.generic_has_operator_impl(lhs, op.kind) =>
+            {
+                Ok((lhs.clone(), true))
+            }
 
             (lhs, rhs) => Err(TypeCheckError::TypeMismatchWithSource {
                 expected: lhs.clone(),
@@ -1150,12 +1524,77 @@ impl<'interner> TypeChecker<'interner> {
         }
     }
 
+    /// Operators on types without a built-in meaning, such as structs and enums, are
+    /// desugared into a call to the standard library trait for that operator.
+    /// `unsupported` is the error to issue if the trait is not available.
+    fn operator_trait_type_rules(
+        &mut self,
+        lhs_type: &Type,
+        op: &HirBinaryOp,
+        rhs_type: &Type,
+        span: Span,
+        unsupported: TypeCheckError,
+    ) -> Result<(Type, bool), TypeCheckError> {
+        if self.interner.get_operator_trait_method(op.kind).is_none() {
+            return Err(unsupported);
+        }
+
+        let (source, result_type) = if op.kind.is_comparator() {
+            (Source::Comparison, Type::Bool)
+        } else {
+            (Source::BinOp(op.kind), lhs_type.clone())
+        };
+
+        match rhs_type.try_unify(lhs_type) {
+            Ok(()) => Ok((result_type, true)),
+            Err(_) => Err(TypeCheckError::TypeMismatchWithSource {
+                expected: lhs_type.clone(),
+                actual: rhs_type.clone(),
+                source,
+                span,
+            }),
+        }
+    }
+
+    /// True if a `where` clause in scope assumes the given generic implements the
+    /// trait for this operator.
+    fn generic_has_operator_impl(&self, generic: &Type, operator: BinaryOpKind) -> bool {
+        match self.interner.get_operator_trait_method(operator) {
+            Some(method) => {
+                self.interner.lookup_trait_implementation(generic, method.trait_id).is_ok()
+            }
+            None => false,
+        }
+    }
+
     fn type_check_prefix_operand(
         &mut self,
         op: &crate::UnaryOp,
         rhs_type: &Type,
         span: Span,
-    ) -> Type {
+    ) -> (Type, bool) {
+        // Struct, enum and generic operands use the operator's trait if there is one
+        if matches!(op, crate::UnaryOp::Minus | crate::UnaryOp::Not) {
+            let rhs_type = rhs_type.follow_bindings();
+            let use_impl = match &rhs_type {
+                Type::Struct(..) | Type::Enum(..) => {
+                    self.interner.get_prefix_operator_trait_method(*op).is_some()
+                }
+                Type::NamedGeneric(..) => match self.interner.get_prefix_operator_trait_method(*op)
+                {
+                    Some(method) => self
+                        .interner
+                        .lookup_trait_implementation(&rhs_type, method.trait_id)
+                        .is_ok(),
+                    None => false,
+                },
+                _ => false,
+            };
+            if use_impl {
+                return (rhs_type, true);
+            }
+        }
+
         let mut unify = |expected| {
             rhs_type.unify(&expected, &mut self.errors, || TypeCheckError::TypeMismatch {
                 expr_typ: rhs_type.to_string(),
@@ -1176,25 +1615,25 @@ impl<'interner> TypeChecker<'interner> {
                     kind: rhs_type.to_string(),
                     span,
                 });
-                expected
+                (expected, false)
             }
             crate::UnaryOp::Not => {
                 let rhs_type = rhs_type.follow_bindings();
 
                 // `!` can work on booleans or integers
                 if matches!(rhs_type, Type::Integer(..)) {
-                    return rhs_type;
+                    return (rhs_type, false);
                 }
 
-                unify(Type::Bool)
+                (unify(Type::Bool), false)
             }
             crate::UnaryOp::MutableReference => {
-                Type::MutableReference(Box::new(rhs_type.follow_bindings()))
+                (Type::MutableReference(Box::new(rhs_type.follow_bindings())), false)
             }
             crate::UnaryOp::Dereference { implicitly_added: _ } => {
                 let element_type = self.interner.next_type_variable();
                 unify(Type::MutableReference(Box::new(element_type.clone())));
-                element_type
+                (element_type, false)
             }
         }
     }
@@ -1208,3 +1647,14 @@ where
 {
     xs.sort_by(|x, y| key(x).cmp(key(y)));
 }
+
+/// True if the given type is the unbound type variable with the given id
+fn is_type_variable(typ: &Type, id: TypeVariableId) -> bool {
+    match typ {
+        Type::TypeVariable(binding, _) => match &*binding.borrow() {
+            TypeBinding::Unbound(binding_id) => *binding_id == id,
+            TypeBinding::Bound(_) => false,
+        },
+        _ => false,
+    }
+}
@@ -4,7 +4,7 @@ use noirc_errors::{Location, Span};
 use crate::hir_def::expr::{HirExpression, HirIdent, HirLiteral};
 use crate::hir_def::stmt::{
     HirAssignStatement, HirConstrainStatement, HirForStatement, HirLValue, HirLetStatement,
-    HirPattern, HirStatement,
+    HirPattern, HirStatement, HirWhileStatement,
 };
 use crate::hir_def::types::Type;
 use crate::node_interner::{DefinitionId, ExprId, StmtId};
@@ -51,7 +51,11 @@ impl<'interner> TypeChecker<'interner> {
             HirStatement::Constrain(constrain_stmt) => self.check_constrain_stmt(constrain_stmt),
             HirStatement::Assign(assign_stmt) => self.check_assign_stmt(assign_stmt, stmt_id),
             HirStatement::For(for_loop) => self.check_for_loop(for_loop),
-            HirStatement::Error => (),
+            HirStatement::While(while_loop) => self.check_while_loop(while_loop),
+            HirStatement::Loop(block) => {
+                self.check_expression(&block);
+            }
+            HirStatement::Break | HirStatement::Continue | HirStatement::Error => (),
         }
         Type::Unit
     }
@@ -89,6 +93,19 @@ impl<'interner> TypeChecker<'interner> {
         self.check_expression(&for_loop.block);
     }
 
+    fn check_while_loop(&mut self, while_loop: HirWhileStatement) {
+        let condition_type = self.check_expression(&while_loop.condition);
+        let expr_span = self.interner.expr_span(&while_loop.condition);
+
+        self.unify(&condition_type, &Type::Bool, || TypeCheckError::TypeMismatch {
+            expected_typ: Type::Bool.to_string(),
+            expr_typ: condition_type.to_string(),
+            expr_span,
+        });
+
+        self.check_expression(&while_loop.block);
+    }
+
     /// Associate a given HirPattern with the given Type, and remember
     /// this association in the NodeInterner.
     pub(crate) fn bind_pattern(&mut self, pattern: &HirPattern, typ: Type) {
@@ -1,12 +1,12 @@
 use acvm::FieldElement;
 use fm::FileId;
-use noirc_errors::Location;
+use noirc_errors::{Location, Span};
 
 use crate::node_interner::{DefinitionId, ExprId, FuncId, NodeInterner, StmtId, TraitMethodId};
 use crate::{BinaryOp, BinaryOpKind, Ident, Shared, UnaryOp};
 
 use super::stmt::HirPattern;
-use super::types::{StructType, Type};
+use super::types::{EnumType, StructType, Type};
 
 /// A HirExpression is the result of an Expression in the AST undergoing
 /// name resolution. It is almost identical to the Expression AST node, but
@@ -22,11 +22,16 @@ pub enum HirExpression {
     Infix(HirInfixExpression),
     Index(HirIndexExpression),
     Constructor(HirConstructorExpression),
+    EnumConstructor(HirEnumConstructorExpression),
     MemberAccess(HirMemberAccess),
     Call(HirCallExpression),
     MethodCall(HirMethodCallExpression),
     Cast(HirCastExpression),
     If(HirIfExpression),
+    Match(HirMatchExpression),
+    /// A `comptime { ... }` block. These are evaluated and replaced with the
+    /// resulting value after type checking so they never reach monomorphization.
+    Comptime(HirBlockExpression),
     Tuple(Vec<ExprId>),
     Lambda(HirLambda),
     TraitMethodReference(TraitMethodId),
@@ -120,6 +125,35 @@ pub struct HirIfExpression {
     pub alternative: Option<ExprId>,
 }
 
+#[derive(Debug, Clone)]
+pub struct HirMatchExpression {
+    pub expression: ExprId,
+    pub rules: Vec<(HirMatchPattern, ExprId)>,
+}
+
+/// The pattern of a single match arm. Patterns are not nested: a variant
+/// pattern may only bind each of the variant's fields to a regular pattern.
+#[derive(Debug, Clone)]
+pub enum HirMatchPattern {
+    /// A catch-all pattern which binds the whole matched value, e.g. `x` or `_`
+    Binding(HirPattern),
+
+    /// A pattern matching a single enum variant, e.g. `Option::Some(x)`
+    Variant { typ: Shared<EnumType>, variant_index: usize, fields: Vec<HirPattern>, span: Span },
+
+    /// A variant pattern whose path failed to resolve. An error has already been issued.
+    Error(Span),
+}
+
+impl HirMatchPattern {
+    pub fn span(&self) -> Span {
+        match self {
+            HirMatchPattern::Binding(pattern) => pattern.span(),
+            HirMatchPattern::Variant { span, .. } | HirMatchPattern::Error(span) => *span,
+        }
+    }
+}
+
 // `lhs as type` in the source code
 #[derive(Debug, Clone)]
 pub struct HirCastExpression {
@@ -179,6 +213,7 @@ impl HirMethodCallExpression {
             }
         };
         let func = interner.push_expr(expr);
+        interner.push_expr_location(func, location.span, location.file);
         (func, HirExpression::Call(HirCallExpression { func, arguments, location }))
     }
 }
@@ -196,6 +231,15 @@ pub struct HirConstructorExpression {
     pub fields: Vec<(Ident, ExprId)>,
 }
 
+/// Constructs a single variant of an enum, as in `Option::Some(x)`
+#[derive(Debug, Clone)]
+pub struct HirEnumConstructorExpression {
+    pub r#type: Shared<EnumType>,
+    pub enum_generics: Vec<Type>,
+    pub variant_index: usize,
+    pub arguments: Vec<ExprId>,
+}
+
 /// Indexing, as in `array[index]`
 #[derive(Debug, Clone)]
 pub struct HirIndexExpression {
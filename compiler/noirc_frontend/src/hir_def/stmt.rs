@@ -14,6 +14,10 @@ pub enum HirStatement {
     Constrain(HirConstrainStatement),
     Assign(HirAssignStatement),
     For(HirForStatement),
+    While(HirWhileStatement),
+    Loop(ExprId),
+    Break,
+    Continue,
     Expression(ExprId),
     Semi(ExprId),
     Error,
@@ -43,6 +47,12 @@ pub struct HirForStatement {
     pub block: ExprId,
 }
 
+#[derive(Debug, Clone)]
+pub struct HirWhileStatement {
+    pub condition: ExprId,
+    pub block: ExprId,
+}
+
 /// Corresponds to `lvalue = expression;` in the source code
 #[derive(Debug, Clone)]
 pub struct HirAssignStatement {
@@ -3,7 +3,7 @@ use std::rc::Rc;
 use crate::{
     graph::CrateId,
     node_interner::{FuncId, TraitId, TraitMethodId},
-    Generics, Ident, NoirFunction, Type, TypeVariable, TypeVariableId,
+    Expression, Generics, Ident, NoirFunction, Type, TypeVariable, TypeVariableId,
 };
 use fm::FileId;
 use noirc_errors::Span;
@@ -24,12 +24,18 @@ pub struct TraitFunction {
 pub struct TraitConstant {
     pub name: Ident,
     pub ty: Type,
+    /// Stands in for the value of this constant within the trait's method signatures.
+    /// Like `Self`, this is bound to the value given by each impl when its methods are checked.
+    pub value: Type,
+    pub default_value: Option<Expression>,
     pub span: Span,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TraitType {
     pub name: Ident,
+    /// Stands in for this type within the trait's method signatures. Like `Self`,
+    /// this is bound to the type given by each impl when its methods are checked.
     pub ty: Type,
     pub span: Span,
 }
@@ -68,6 +74,10 @@ pub struct TraitImpl {
     pub file: FileId,
     pub methods: Vec<FuncId>, // methods[i] is the implementation of trait.methods[i] for Type typ
 
+    /// associated_types[i] is the value of trait.associated_types()[i] for Type typ.
+    /// Associated constants are stored as type-level integers.
+    pub associated_types: Vec<Type>,
+
     /// The where clause, if present, contains each trait requirement which must
     /// be satisfied for this impl to be selected. E.g. in `impl Eq for [T] where T: Eq`,
     /// `where_clause` would contain the one `T: Eq` constraint. If there is no where clause,
@@ -80,11 +90,15 @@ pub struct TraitConstraint {
     pub typ: Type,
     pub trait_id: TraitId,
     // pub trait_generics: Generics, TODO
+    /// The values of the trait's associated types and constants for `typ`, in the order of
+    /// `Trait::associated_types`. These are bound to the values of the selected impl once the
+    /// constraint is solved. This is empty if they are not referred to by the constraint's user.
+    pub associated_types: Vec<Type>,
 }
 
 impl TraitConstraint {
     pub fn new(typ: Type, trait_id: TraitId) -> Self {
-        Self { typ, trait_id }
+        Self { typ, trait_id, associated_types: Vec::new() }
     }
 }
 
@@ -128,6 +142,33 @@ impl Trait {
         self.methods = methods;
     }
 
+    pub fn set_types(&mut self, types: Vec<TraitType>) {
+        self.types = types;
+    }
+
+    pub fn set_constants(&mut self, constants: Vec<TraitConstant>) {
+        self.constants = constants;
+    }
+
+    /// Returns the placeholders for each associated type of this trait followed by those of
+    /// each associated constant. Impls and trait constraints store the values of these items
+    /// in the same order.
+    pub fn associated_types(&self) -> Vec<Type> {
+        let types = self.types.iter().map(|typ| typ.ty.clone());
+        types.chain(self.constants.iter().map(|constant| constant.value.clone())).collect()
+    }
+
+    /// Returns the name of each associated type and constant of this trait, along with the
+    /// declared type of each constant, in the same order as `associated_types`.
+    pub fn associated_item_names(&self) -> Vec<(Ident, Option<Type>)> {
+        let types = self.types.iter().map(|typ| (typ.name.clone(), None));
+        let constants = self
+            .constants
+            .iter()
+            .map(|constant| (constant.name.clone(), Some(constant.ty.clone())));
+        types.chain(constants).collect()
+    }
+
     pub fn find_method(&self, name: Ident) -> Option<TraitMethodId> {
         for (idx, method) in self.methods.iter().enumerate() {
             if method.name == name {
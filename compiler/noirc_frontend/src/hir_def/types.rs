@@ -12,7 +12,10 @@ use iter_extended::vecmap;
 use noirc_errors::Span;
 use noirc_printable_type::PrintableType;
 
-use crate::{node_interner::StructId, Ident, Signedness};
+use crate::{
+    node_interner::{EnumId, StructId},
+    Ident, Signedness,
+};
 
 use super::{
     expr::{HirCallExpression, HirExpression, HirIdent},
@@ -51,6 +54,11 @@ pub enum Type {
     /// represents the generic arguments (if any) to this struct type.
     Struct(Shared<StructType>, Vec<Type>),
 
+    /// A user-defined enum type. As with structs, the `Shared<EnumType>` is the
+    /// definition shared by every instance of this enum and the `Vec<Type>` holds
+    /// its generic arguments.
+    Enum(Shared<EnumType>, Vec<Type>),
+
     /// A tuple type with the given list of fields in the order they appear in source code.
     Tuple(Vec<Type>),
 
@@ -91,6 +99,10 @@ pub enum Type {
     /// bind to an integer without special checks to bind it to a non-type.
     Constant(u64),
 
+    /// Arithmetic on type-level integers such as `N + M`, used in the length position of
+    /// arrays and strings. Once each operand is known this evaluates to a Type::Constant.
+    InfixExpr(Box<Type>, BinaryTypeOperator, Box<Type>),
+
     /// The type of a slice is an array of size NotConstant.
     /// The size of an array literal is resolved to this if it ever uses operations
     /// involving slices.
@@ -120,6 +132,14 @@ impl Type {
                 let fields = struct_type.get_fields(args);
                 fields.iter().fold(0, |acc, (_, field_type)| acc + field_type.field_count())
             }
+            Type::Enum(ref def, args) => {
+                // One field for the tag followed by the payload of every variant
+                let enum_type = def.borrow();
+                let variants = enum_type.get_variants(args);
+                variants.iter().fold(1, |acc, (_, fields)| {
+                    fields.iter().fold(acc, |acc, field_type| acc + field_type.field_count())
+                })
+            }
             Type::Tuple(fields) => {
                 fields.iter().fold(0, |acc, field_typ| acc + field_typ.field_count())
             }
@@ -138,6 +158,7 @@ impl Type {
             | Type::MutableReference(_)
             | Type::Forall(_, _)
             | Type::Constant(_)
+            | Type::InfixExpr(..)
             | Type::NotConstant
             | Type::Error => unreachable!("This type cannot exist as a parameter to main"),
         }
@@ -271,6 +292,127 @@ impl std::fmt::Display for StructType {
     }
 }
 
+/// Represents an enum type in the type system. Like `StructType`, each instance
+/// of this rust struct will be shared across all Type::Enum variants that represent
+/// the same enum type.
+#[derive(Debug, Eq)]
+pub struct EnumType {
+    /// A unique id representing this enum type. Used to check if two
+    /// enum types are equal.
+    pub id: EnumId,
+
+    pub name: Ident,
+
+    /// Variants are ordered and private, they should only be accessed through
+    /// get_variant() or get_variants() since these will apply any generic
+    /// arguments to the variant fields. The index of a variant in this list is its tag.
+    variants: Vec<EnumVariant>,
+
+    pub generics: Generics,
+    pub span: Span,
+}
+
+/// A single variant of an enum: its name along with the types of its fields.
+pub type EnumVariant = (Ident, Vec<Type>);
+
+impl std::hash::Hash for EnumType {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl PartialEq for EnumType {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl EnumType {
+    pub fn new(
+        id: EnumId,
+        name: Ident,
+        span: Span,
+        variants: Vec<EnumVariant>,
+        generics: Generics,
+    ) -> EnumType {
+        EnumType { id, variants, name, span, generics }
+    }
+
+    /// Like struct fields, an enum's variants are resolved after the enum itself is
+    /// created so that enums may refer to each other. This sets the variants once known.
+    pub fn set_variants(&mut self, variants: Vec<EnumVariant>) {
+        assert!(self.variants.is_empty());
+        self.variants = variants;
+    }
+
+    pub fn num_variants(&self) -> usize {
+        self.variants.len()
+    }
+
+    pub fn variant_name(&self, index: usize) -> &Ident {
+        &self.variants[index].0
+    }
+
+    /// Returns the index of the variant with the given name, if there is one.
+    pub fn variant_index(&self, variant_name: &str) -> Option<usize> {
+        self.variants.iter().position(|(name, _)| name.0.contents == variant_name)
+    }
+
+    /// Returns the field types of the variant at the given index,
+    /// after being applied to the given generic arguments.
+    pub fn get_variant(&self, index: usize, generic_args: &[Type]) -> Vec<Type> {
+        let substitutions = self.substitutions(generic_args);
+        vecmap(&self.variants[index].1, |typ| typ.substitute(&substitutions))
+    }
+
+    /// Returns all the variants of this type, after being applied to the given generic arguments.
+    pub fn get_variants(&self, generic_args: &[Type]) -> Vec<(String, Vec<Type>)> {
+        let substitutions = self.substitutions(generic_args);
+
+        vecmap(&self.variants, |(name, fields)| {
+            let fields = vecmap(fields, |typ| typ.substitute(&substitutions));
+            (name.0.contents.clone(), fields)
+        })
+    }
+
+    /// Returns the variants of this type as they were declared, without applying any generic arguments.
+    pub fn variants_as_written(&self) -> &[EnumVariant] {
+        &self.variants
+    }
+
+    fn substitutions(&self, generic_args: &[Type]) -> TypeBindings {
+        assert_eq!(self.generics.len(), generic_args.len());
+
+        self.generics
+            .iter()
+            .zip(generic_args)
+            .map(|((old_id, old_var), new)| (*old_id, (old_var.clone(), new.clone())))
+            .collect()
+    }
+
+    /// True if the given index is the same index as a generic type of this enum
+    /// which is expected to be a numeric generic.
+    pub fn generic_is_numeric(&self, index_of_generic: usize) -> bool {
+        let target_id = self.generics[index_of_generic].0;
+        self.variants
+            .iter()
+            .flat_map(|(_, fields)| fields)
+            .any(|field| field.contains_numeric_typevar(target_id))
+    }
+
+    /// Instantiate this enum type, returning a Vec of the new generic args (in
+    /// the same order as self.generics)
+    pub fn instantiate(&self, interner: &mut NodeInterner) -> Vec<Type> {
+        vecmap(&self.generics, |_| interner.next_type_variable())
+    }
+}
+
+impl std::fmt::Display for EnumType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
 /// Wrap around an unsolved type
 #[derive(Debug, Clone, Eq)]
 pub struct TypeAliasType {
@@ -505,18 +647,16 @@ impl Type {
     }
 
     fn contains_numeric_typevar(&self, target_id: TypeVariableId) -> bool {
-        // True if the given type is a NamedGeneric with the target_id
-        let named_generic_id_matches_target = |typ: &Type| {
-            if let Type::NamedGeneric(type_variable, _) = typ {
-                match &*type_variable.borrow() {
-                    TypeBinding::Bound(_) => {
-                        unreachable!("Named generics should not be bound until monomorphization")
-                    }
-                    TypeBinding::Unbound(id) => target_id == *id,
+        // True if the given type is a NamedGeneric with the target_id, or arithmetic using one
+        let named_generic_id_matches_target = |typ: &Type| match typ {
+            Type::NamedGeneric(type_variable, _) => match &*type_variable.borrow() {
+                TypeBinding::Bound(_) => {
+                    unreachable!("Named generics should not be bound until monomorphization")
                 }
-            } else {
-                false
-            }
+                TypeBinding::Unbound(id) => target_id == *id,
+            },
+            Type::InfixExpr(..) => typ.contains_numeric_typevar(target_id),
+            _ => false,
         };
 
         match self {
@@ -553,7 +693,17 @@ impl Type {
                     }
                 })
             }
+            Type::Enum(enum_type, generics) => generics.iter().enumerate().any(|(i, generic)| {
+                if named_generic_id_matches_target(generic) {
+                    enum_type.borrow().generic_is_numeric(i)
+                } else {
+                    generic.contains_numeric_typevar(target_id)
+                }
+            }),
             Type::MutableReference(element) => element.contains_numeric_typevar(target_id),
+            Type::InfixExpr(lhs, _, rhs) => {
+                named_generic_id_matches_target(lhs) || named_generic_id_matches_target(rhs)
+            }
             Type::String(length) => named_generic_id_matches_target(length),
             Type::FmtString(length, elements) => {
                 elements.contains_numeric_typevar(target_id)
@@ -593,12 +743,21 @@ impl Type {
                 length.is_valid_for_program_input() && element.is_valid_for_program_input()
             }
             Type::String(length) => length.is_valid_for_program_input(),
+            Type::InfixExpr(lhs, _, rhs) => {
+                lhs.is_valid_for_program_input() && rhs.is_valid_for_program_input()
+            }
             Type::Tuple(elements) => elements.iter().all(|elem| elem.is_valid_for_program_input()),
             Type::Struct(definition, generics) => definition
                 .borrow()
                 .get_fields(generics)
                 .into_iter()
                 .all(|(_, field)| field.is_valid_for_program_input()),
+            Type::Enum(definition, generics) => definition
+                .borrow()
+                .get_variants(generics)
+                .into_iter()
+                .flat_map(|(_, fields)| fields)
+                .all(|field| field.is_valid_for_program_input()),
         }
     }
 
@@ -688,6 +847,14 @@ impl std::fmt::Display for Type {
                     write!(f, "{}<{}>", s.borrow(), args.join(", "))
                 }
             }
+            Type::Enum(e, args) => {
+                let args = vecmap(args, |arg| arg.to_string());
+                if args.is_empty() {
+                    write!(f, "{}", e.borrow())
+                } else {
+                    write!(f, "{}<{}>", e.borrow(), args.join(", "))
+                }
+            }
             Type::TraitAsType(tr) => {
                 write!(f, "impl {}", tr.name)
             }
@@ -708,6 +875,7 @@ impl std::fmt::Display for Type {
                 TypeBinding::Unbound(_) => write!(f, "{name}"),
             },
             Type::Constant(x) => x.fmt(f),
+            Type::InfixExpr(lhs, op, rhs) => write!(f, "({lhs} {op} {rhs})"),
             Type::Forall(typevars, typ) => {
                 let typevars = vecmap(typevars, |(var, _)| var.to_string());
                 write!(f, "forall {}. {}", typevars.join(" "), typ)
@@ -819,10 +987,55 @@ impl Type {
                     },
                 }
             }
+            // A comptime global used as an array length is bound to its value once evaluated
+            Type::NamedGeneric(binding, _) => match &*binding.borrow() {
+                TypeBinding::Bound(typ) => typ.try_bind_to_maybe_constant(var, target_length),
+                TypeBinding::Unbound(_) => Err(UnificationError),
+            },
+            Type::InfixExpr(..) => {
+                self.try_solve_infix_expr(target_length)?;
+                *var.borrow_mut() = TypeBinding::Bound(Type::Constant(target_length));
+                Ok(())
+            }
             _ => Err(UnificationError),
         }
     }
 
+    /// Try to make this arithmetic expression evaluate to the given target. If exactly one
+    /// operand is still unknown it is bound to the value required, e.g. unifying `N + 1`
+    /// with `5` binds `N` to `4`.
+    fn try_solve_infix_expr(&self, target: u64) -> Result<(), UnificationError> {
+        let Type::InfixExpr(lhs, op, rhs) = self else {
+            return Err(UnificationError);
+        };
+
+        let (unknown, value) = match (lhs.evaluate_to_u64(), rhs.evaluate_to_u64()) {
+            (Some(a), Some(b)) if op.function()(a, b) == Some(target) => return Ok(()),
+            (Some(_), Some(_)) | (None, None) => return Err(UnificationError),
+            (None, Some(b)) => {
+                let value = match op {
+                    BinaryTypeOperator::Addition => target.checked_sub(b),
+                    BinaryTypeOperator::Subtraction => target.checked_add(b),
+                    BinaryTypeOperator::Multiplication => checked_exact_div(target, b),
+                    BinaryTypeOperator::Division | BinaryTypeOperator::Modulo => None,
+                };
+                (lhs, value)
+            }
+            (Some(a), None) => {
+                let value = match op {
+                    BinaryTypeOperator::Addition => target.checked_sub(a),
+                    BinaryTypeOperator::Subtraction => a.checked_sub(target),
+                    BinaryTypeOperator::Multiplication => checked_exact_div(target, a),
+                    BinaryTypeOperator::Division | BinaryTypeOperator::Modulo => None,
+                };
+                (rhs, value)
+            }
+        };
+
+        let value = value.ok_or(UnificationError)?;
+        unknown.try_unify(&Type::Constant(value))
+    }
+
     /// Try to bind a PolymorphicInt variable to self, succeeding if self is an integer, field,
     /// other PolymorphicInt type, or type variable.
     pub fn try_bind_to_polymorphic_int(&self, var: &TypeVariable) -> Result<(), UnificationError> {
@@ -914,7 +1127,93 @@ impl Type {
         make_error: impl FnOnce() -> TypeCheckError,
     ) {
         if let Err(UnificationError) = self.try_unify(expected) {
-            errors.push(make_error());
+            errors.push(self.add_length_arithmetic_context(expected, make_error()));
+        }
+    }
+
+    /// Explain why a unification error occurred if either type uses arithmetic in its array
+    /// lengths, since two such lengths may not be provably equal even if they look equivalent.
+    fn add_length_arithmetic_context(
+        &self,
+        expected: &Type,
+        error: TypeCheckError,
+    ) -> TypeCheckError {
+        if self.contains_infix_expr() || expected.contains_infix_expr() {
+            error.add_context(
+                "Array lengths using arithmetic are only equal if they evaluate to the same constant or are written the same way",
+            )
+        } else {
+            error
+        }
+    }
+
+    /// True if this type uses arithmetic on type-level integers anywhere within it.
+    fn contains_infix_expr(&self) -> bool {
+        match self {
+            Type::InfixExpr(..) => true,
+            Type::TypeVariable(binding, _) | Type::NamedGeneric(binding, _) => {
+                match &*binding.borrow() {
+                    TypeBinding::Bound(binding) => binding.contains_infix_expr(),
+                    TypeBinding::Unbound(_) => false,
+                }
+            }
+            Type::Array(length, element) => {
+                length.contains_infix_expr() || element.contains_infix_expr()
+            }
+            Type::String(length) => length.contains_infix_expr(),
+            Type::FmtString(length, elements) => {
+                length.contains_infix_expr() || elements.contains_infix_expr()
+            }
+            Type::Struct(_, args) | Type::Enum(_, args) | Type::Tuple(args) => {
+                args.iter().any(|arg| arg.contains_infix_expr())
+            }
+            Type::Function(args, ret, env) => {
+                args.iter().any(|arg| arg.contains_infix_expr())
+                    || ret.contains_infix_expr()
+                    || env.contains_infix_expr()
+            }
+            Type::MutableReference(element) | Type::Forall(_, element) => {
+                element.contains_infix_expr()
+            }
+            _ => false,
+        }
+    }
+
+    /// Find arithmetic on type-level integers within this type whose operands are both known
+    /// but which overflows, underflows or divides by zero, returning its operands.
+    pub(crate) fn find_invalid_infix_expr(&self) -> Option<(u64, BinaryTypeOperator, u64)> {
+        match self {
+            Type::InfixExpr(lhs, op, rhs) => {
+                if let Some(invalid) =
+                    lhs.find_invalid_infix_expr().or_else(|| rhs.find_invalid_infix_expr())
+                {
+                    return Some(invalid);
+                }
+                let (lhs, rhs) = (lhs.evaluate_to_u64()?, rhs.evaluate_to_u64()?);
+                op.function()(lhs, rhs).is_none().then_some((lhs, *op, rhs))
+            }
+            Type::TypeVariable(binding, _) | Type::NamedGeneric(binding, _) => {
+                match &*binding.borrow() {
+                    TypeBinding::Bound(binding) => binding.find_invalid_infix_expr(),
+                    TypeBinding::Unbound(_) => None,
+                }
+            }
+            Type::Array(length, element) | Type::FmtString(length, element) => {
+                length.find_invalid_infix_expr().or_else(|| element.find_invalid_infix_expr())
+            }
+            Type::String(length) => length.find_invalid_infix_expr(),
+            Type::Struct(_, args) | Type::Enum(_, args) | Type::Tuple(args) => {
+                args.iter().find_map(|arg| arg.find_invalid_infix_expr())
+            }
+            Type::Function(args, ret, env) => args
+                .iter()
+                .find_map(|arg| arg.find_invalid_infix_expr())
+                .or_else(|| ret.find_invalid_infix_expr())
+                .or_else(|| env.find_invalid_infix_expr()),
+            Type::MutableReference(element) | Type::Forall(_, element) => {
+                element.find_invalid_infix_expr()
+            }
+            _ => None,
         }
     }
 
@@ -993,6 +1292,17 @@ impl Type {
                 }
             }
 
+            (Enum(id_a, args_a), Enum(id_b, args_b)) => {
+                if id_a == id_b && args_a.len() == args_b.len() {
+                    for (a, b) in args_a.iter().zip(args_b) {
+                        a.try_unify(b)?;
+                    }
+                    Ok(())
+                } else {
+                    Err(UnificationError)
+                }
+            }
+
             (NamedGeneric(binding, _), other) if !binding.borrow().is_unbound() => {
                 if let TypeBinding::Bound(link) = &*binding.borrow() {
                     link.try_unify(other)
@@ -1036,6 +1346,28 @@ impl Type {
 
             (MutableReference(elem_a), MutableReference(elem_b)) => elem_a.try_unify(elem_b),
 
+            (InfixExpr(lhs_a, op_a, rhs_a), InfixExpr(lhs_b, op_b, rhs_b)) => {
+                match (self.evaluate_to_u64(), other.evaluate_to_u64()) {
+                    (Some(a), Some(b)) if a == b => Ok(()),
+                    (Some(_), Some(_)) => Err(UnificationError),
+                    (Some(a), None) => other.try_solve_infix_expr(a),
+                    (None, Some(b)) => self.try_solve_infix_expr(b),
+                    // Otherwise the expressions are only equal if they have the same shape
+                    (None, None) if op_a == op_b => {
+                        lhs_a.try_unify(lhs_b)?;
+                        rhs_a.try_unify(rhs_b)
+                    }
+                    (None, None) => Err(UnificationError),
+                }
+            }
+
+            (infix @ InfixExpr(..), other) | (other, infix @ InfixExpr(..)) => {
+                match other.evaluate_to_u64() {
+                    Some(target) => infix.try_solve_infix_expr(target),
+                    None => Err(UnificationError),
+                }
+            }
+
             (other_a, other_b) => {
                 if other_a == other_b {
                     Ok(())
@@ -1061,7 +1393,7 @@ impl Type {
     ) {
         if let Err(UnificationError) = self.try_unify(expected) {
             if !self.try_array_to_slice_coercion(expected, expression, interner) {
-                errors.push(make_error());
+                errors.push(self.add_length_arithmetic_context(expected, make_error()));
             }
         }
     }
@@ -1094,8 +1426,8 @@ impl Type {
         false
     }
 
-    /// If this type is a Type::Constant (used in array lengths), or is bound
-    /// to a Type::Constant, return the constant as a u64.
+    /// If this type is a Type::Constant (used in array lengths), is bound
+    /// to a Type::Constant, or is arithmetic on such types, return the constant as a u64.
     pub fn evaluate_to_u64(&self) -> Option<u64> {
         if let Some(binding) = self.get_inner_type_variable() {
             if let TypeBinding::Bound(binding) = &*binding.borrow() {
@@ -1107,6 +1439,9 @@ impl Type {
             Type::TypeVariable(_, TypeVariableKind::Constant(size)) => Some(*size),
             Type::Array(len, _elem) => len.evaluate_to_u64(),
             Type::Constant(x) => Some(*x),
+            Type::InfixExpr(lhs, op, rhs) => {
+                op.function()(lhs.evaluate_to_u64()?, rhs.evaluate_to_u64()?)
+            }
             _ => None,
         }
     }
@@ -1203,11 +1538,15 @@ impl Type {
                 elem.find_all_unbound_type_variables(type_variables);
             }
             Type::String(length) => length.find_all_unbound_type_variables(type_variables),
+            Type::InfixExpr(lhs, _, rhs) => {
+                lhs.find_all_unbound_type_variables(type_variables);
+                rhs.find_all_unbound_type_variables(type_variables);
+            }
             Type::FmtString(length, env) => {
                 length.find_all_unbound_type_variables(type_variables);
                 env.find_all_unbound_type_variables(type_variables);
             }
-            Type::Struct(_, generics) => {
+            Type::Struct(_, generics) | Type::Enum(_, generics) => {
                 for generic in generics {
                     generic.find_all_unbound_type_variables(type_variables);
                 }
@@ -1269,6 +1608,11 @@ impl Type {
                 let size = Box::new(size.substitute(type_bindings));
                 Type::String(size)
             }
+            Type::InfixExpr(lhs, op, rhs) => {
+                let lhs = Box::new(lhs.substitute(type_bindings));
+                let rhs = Box::new(rhs.substitute(type_bindings));
+                Type::InfixExpr(lhs, *op, rhs)
+            }
             Type::FmtString(size, fields) => {
                 let size = Box::new(size.substitute(type_bindings));
                 let fields = Box::new(fields.substitute(type_bindings));
@@ -1283,6 +1627,10 @@ impl Type {
                 let args = vecmap(args, |arg| arg.substitute(type_bindings));
                 Type::Struct(fields.clone(), args)
             }
+            Type::Enum(variants, args) => {
+                let args = vecmap(args, |arg| arg.substitute(type_bindings));
+                Type::Enum(variants.clone(), args)
+            }
             Type::Tuple(fields) => {
                 let fields = vecmap(fields, |field| field.substitute(type_bindings));
                 Type::Tuple(fields)
@@ -1322,13 +1670,16 @@ impl Type {
         match self {
             Type::Array(len, elem) => len.occurs(target_id) || elem.occurs(target_id),
             Type::String(len) => len.occurs(target_id),
+            Type::InfixExpr(lhs, _, rhs) => lhs.occurs(target_id) || rhs.occurs(target_id),
             Type::FmtString(len, fields) => {
                 let len_occurs = len.occurs(target_id);
                 let field_occurs = fields.occurs(target_id);
                 len_occurs || field_occurs
             }
             Type::TraitAsType(_) => todo!(),
-            Type::Struct(_, generic_args) => generic_args.iter().any(|arg| arg.occurs(target_id)),
+            Type::Struct(_, generic_args) | Type::Enum(_, generic_args) => {
+                generic_args.iter().any(|arg| arg.occurs(target_id))
+            }
             Type::Tuple(fields) => fields.iter().any(|field| field.occurs(target_id)),
             Type::NamedGeneric(binding, _) | Type::TypeVariable(binding, _) => {
                 match &*binding.borrow() {
@@ -1369,6 +1720,20 @@ impl Type {
                 Array(Box::new(size.follow_bindings()), Box::new(elem.follow_bindings()))
             }
             String(size) => String(Box::new(size.follow_bindings())),
+            InfixExpr(lhs, op, rhs) => {
+                let lhs = lhs.follow_bindings();
+                let rhs = rhs.follow_bindings();
+
+                // Normalize the expression to a constant once both sides are known. Invalid
+                // arithmetic is left as is so that it can still be reported.
+                match (lhs.evaluate_to_u64(), rhs.evaluate_to_u64()) {
+                    (Some(a), Some(b)) => match op.function()(a, b) {
+                        Some(value) => Constant(value),
+                        None => InfixExpr(Box::new(lhs), *op, Box::new(rhs)),
+                    },
+                    _ => InfixExpr(Box::new(lhs), *op, Box::new(rhs)),
+                }
+            }
             FmtString(size, args) => {
                 let size = Box::new(size.follow_bindings());
                 let args = Box::new(args.follow_bindings());
@@ -1378,6 +1743,10 @@ impl Type {
                 let args = vecmap(args, |arg| arg.follow_bindings());
                 Struct(def.clone(), args)
             }
+            Enum(def, args) => {
+                let args = vecmap(args, |arg| arg.follow_bindings());
+                Enum(def.clone(), args)
+            }
             Tuple(args) => Tuple(vecmap(args, |arg| arg.follow_bindings())),
             TypeVariable(var, _) | NamedGeneric(var, _) => {
                 if let TypeBinding::Bound(typ) = &*var.borrow() {
@@ -1439,17 +1808,49 @@ fn convert_array_expression_to_slice(
     );
 }
 
+/// Divides `a` by `b`, returning None if `b` does not divide `a` exactly.
+fn checked_exact_div(a: u64, b: u64) -> Option<u64> {
+    if b != 0 && a % b == 0 {
+        Some(a / b)
+    } else {
+        None
+    }
+}
+
 impl BinaryTypeOperator {
-    /// Return the actual rust numeric function associated with this operator
-    pub fn function(self) -> fn(u64, u64) -> u64 {
+    /// Return the actual rust numeric function associated with this operator.
+    /// The function returns `None` on overflow, underflow or division by zero.
+    pub fn function(self) -> fn(u64, u64) -> Option<u64> {
         match self {
-            BinaryTypeOperator::Addition => |a, b| a.wrapping_add(b),
-            BinaryTypeOperator::Subtraction => |a, b| a.wrapping_sub(b),
-            BinaryTypeOperator::Multiplication => |a, b| a.wrapping_mul(b),
-            BinaryTypeOperator::Division => |a, b| a.wrapping_div(b),
-            BinaryTypeOperator::Modulo => |a, b| a.wrapping_rem(b), // % b,
+            BinaryTypeOperator::Addition => u64::checked_add,
+            BinaryTypeOperator::Subtraction => u64::checked_sub,
+            BinaryTypeOperator::Multiplication => u64::checked_mul,
+            BinaryTypeOperator::Division => u64::checked_div,
+            BinaryTypeOperator::Modulo => u64::checked_rem,
         }
     }
+
+    /// Describe why applying this operator to some left-hand side and `rhs` failed.
+    pub(crate) fn failure_reason(self, rhs: u64) -> &'static str {
+        match self {
+            BinaryTypeOperator::Division | BinaryTypeOperator::Modulo if rhs == 0 => {
+                "divides by zero"
+            }
+            BinaryTypeOperator::Subtraction => "underflows",
+            _ => "overflows",
+        }
+    }
+
+    /// Only these operators may be used on generics in array lengths, since division
+    /// and modulo could not be evaluated if the divisor turns out to be zero.
+    pub fn is_allowed_on_generics(self) -> bool {
+        matches!(
+            self,
+            BinaryTypeOperator::Addition
+                | BinaryTypeOperator::Subtraction
+                | BinaryTypeOperator::Multiplication
+        )
+    }
 }
 
 impl TypeVariableKind {
@@ -1499,6 +1900,7 @@ impl From<&Type> for PrintableType {
             Type::Error => unreachable!(),
             Type::Unit => unreachable!(),
             Type::Constant(_) => unreachable!(),
+            Type::InfixExpr(..) => unreachable!(),
             Type::Struct(def, ref args) => {
                 let struct_type = def.borrow();
                 let fields = struct_type.get_fields(args);
@@ -1507,6 +1909,13 @@ impl From<&Type> for PrintableType {
             }
             Type::TraitAsType(_) => unreachable!(),
             Type::Tuple(_) => todo!("printing tuple types is not yet implemented"),
+            Type::Enum(def, ref args) => {
+                let enum_type = def.borrow();
+                let variants = enum_type.get_variants(args);
+                let variants =
+                    vecmap(variants, |(name, fields)| (name, vecmap(fields, |typ| typ.into())));
+                PrintableType::Enum { variants, name: enum_type.name.to_string() }
+            }
             Type::TypeVariable(_, _) => unreachable!(),
             Type::NamedGeneric(..) => unreachable!(),
             Type::Forall(..) => unreachable!(),
@@ -59,8 +59,8 @@ pub mod macros_api {
     };
     pub use crate::{
         ForLoopStatement, ForRange, FunctionDefinition, FunctionVisibility, ImportStatement,
-        NoirStruct, Param, PrefixExpression, Signedness, StatementKind, StructType, Type, TypeImpl,
-        UnaryOp,
+        ItemVisibility, NoirStruct, Param, PrefixExpression, Signedness, StatementKind, StructType,
+        Type, TypeImpl, UnaryOp,
     };
 
     /// Methods to process the AST before and after type checking
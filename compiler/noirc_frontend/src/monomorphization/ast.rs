@@ -3,7 +3,9 @@ use iter_extended::vecmap;
 use noirc_errors::Location;
 
 use crate::{
-    hir_def::function::FunctionSignature, BinaryOpKind, Distinctness, Signedness, Visibility,
+    hir_def::function::FunctionSignature,
+    token::{Attributes, FunctionAttribute},
+    BinaryOpKind, Distinctness, Signedness, Visibility,
 };
 
 /// The monomorphized AST is expression-based, all statements are also
@@ -26,6 +28,8 @@ pub enum Expression {
     Index(Index),
     Cast(Cast),
     For(For),
+    While(While),
+    Loop(Box<Expression>),
     If(If),
     Tuple(Vec<Expression>),
     ExtractTupleField(Box<Expression>, usize),
@@ -34,6 +38,8 @@ pub enum Expression {
     Constrain(Box<Expression>, Location, Option<String>),
     Assign(Assign),
     Semi(Box<Expression>),
+    Break,
+    Continue,
 }
 
 /// A definition is either a local (variable), function, or is a built-in
@@ -80,6 +86,12 @@ pub struct For {
     pub end_range_location: Location,
 }
 
+#[derive(Debug, Clone, Hash)]
+pub struct While {
+    pub condition: Box<Expression>,
+    pub block: Box<Expression>,
+}
+
 #[derive(Debug, Clone, Hash)]
 pub enum Literal {
     Array(ArrayLiteral),
@@ -204,6 +216,36 @@ pub struct Function {
 
     pub return_type: Type,
     pub unconstrained: bool,
+    pub inline_type: InlineType,
+}
+
+/// Determines whether a function is inlined into its callers when it is compiled to ACIR.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum InlineType {
+    /// The function is inlined into each of its callers.
+    #[default]
+    Inline,
+    /// The function is kept as a separate ACIR circuit, which its callers call into.
+    /// This is set by the `#[fold]` attribute.
+    Fold,
+}
+
+impl From<&Attributes> for InlineType {
+    fn from(attributes: &Attributes) -> Self {
+        match &attributes.function {
+            Some(FunctionAttribute::Fold) => InlineType::Fold,
+            _ => InlineType::Inline,
+        }
+    }
+}
+
+impl std::fmt::Display for InlineType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InlineType::Inline => write!(f, "inline"),
+            InlineType::Fold => write!(f, "fold"),
+        }
+    }
 }
 
 /// Compared to hir_def::types::Type, this monomorphized Type has:
@@ -0,0 +1,18 @@
+use noirc_errors::Location;
+use thiserror::Error;
+
+use crate::BinaryTypeOperator;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MonomorphizationError {
+    #[error("Array length arithmetic `{lhs} {op} {rhs}` {}", op.failure_reason(*rhs))]
+    InvalidArrayLengthArithmetic { lhs: u64, op: BinaryTypeOperator, rhs: u64, location: Location },
+}
+
+impl MonomorphizationError {
+    pub fn location(&self) -> Location {
+        match self {
+            MonomorphizationError::InvalidArrayLengthArithmetic { location, .. } => *location,
+        }
+    }
+}
@@ -13,7 +13,7 @@ use iter_extended::{btree_map, vecmap};
 use noirc_errors::Location;
 use noirc_printable_type::PrintableType;
 use std::{
-    collections::{BTreeMap, HashMap, VecDeque},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     unreachable,
 };
 
@@ -26,13 +26,15 @@ use crate::{
     },
     node_interner::{self, DefinitionKind, NodeInterner, StmtId, TraitImplKind, TraitMethodId},
     token::FunctionAttribute,
-    ContractFunctionType, FunctionKind, Type, TypeBinding, TypeBindings, TypeVariableKind,
-    Visibility,
+    BinaryOpKind, ContractFunctionType, FunctionKind, Type, TypeBinding, TypeBindings,
+    TypeVariableKind, Visibility,
 };
 
-use self::ast::{Definition, FuncId, Function, LocalId, Program};
+use self::ast::{Definition, FuncId, Function, InlineType, LocalId, Program};
+use self::errors::MonomorphizationError;
 
 pub mod ast;
+pub mod errors;
 pub mod printer;
 
 struct LambdaContext {
@@ -75,7 +77,15 @@ struct Monomorphizer<'interner> {
 
     is_range_loop: bool,
 
+    /// True while monomorphizing the body of an unconstrained function. Lambdas
+    /// defined within it are unconstrained as well.
+    in_unconstrained_function: bool,
+
     return_location: Option<Location>,
+
+    /// The first error found while monomorphizing. Array lengths are only known once the
+    /// generics of a function are instantiated, so some invalid programs are only caught here.
+    error: Option<MonomorphizationError>,
 }
 
 type HirType = crate::Type;
@@ -91,11 +101,14 @@ type HirType = crate::Type;
 /// Note that there is no requirement on the `main` function that can be passed into
 /// this function. Typically, this is the function named "main" in the source project,
 /// but it can also be, for example, an arbitrary test function for running `nargo test`.
-pub fn monomorphize(main: node_interner::FuncId, interner: &NodeInterner) -> Program {
+pub fn monomorphize(
+    main: node_interner::FuncId,
+    interner: &NodeInterner,
+) -> Result<Program, MonomorphizationError> {
     let mut monomorphizer = Monomorphizer::new(interner);
     let function_sig = monomorphizer.compile_main(main);
 
-    while !monomorphizer.queue.is_empty() {
+    while !monomorphizer.queue.is_empty() && monomorphizer.error.is_none() {
         let (next_fn_id, new_id, bindings) = monomorphizer.queue.pop_front().unwrap();
         monomorphizer.locals.clear();
 
@@ -104,15 +117,19 @@ pub fn monomorphize(main: node_interner::FuncId, interner: &NodeInterner) -> Pro
         undo_instantiation_bindings(bindings);
     }
 
+    if let Some(error) = monomorphizer.error {
+        return Err(error);
+    }
+
     let functions = vecmap(monomorphizer.finished_functions, |(_, f)| f);
     let FuncMeta { return_distinctness, return_visibility, .. } = interner.function_meta(&main);
-    Program::new(
+    Ok(Program::new(
         functions,
         function_sig,
         return_distinctness,
         monomorphizer.return_location,
         return_visibility,
-    )
+    ))
 }
 
 impl<'interner> Monomorphizer<'interner> {
@@ -127,7 +144,9 @@ impl<'interner> Monomorphizer<'interner> {
             interner,
             lambda_envs_stack: Vec::new(),
             is_range_loop: false,
+            in_unconstrained_function: false,
             return_location: None,
+            error: None,
         }
     }
 
@@ -150,7 +169,7 @@ impl<'interner> Monomorphizer<'interner> {
     fn lookup_function(
         &mut self,
         id: node_interner::FuncId,
-        expr_id: node_interner::ExprId,
+        bindings: &TypeBindings,
         typ: &HirType,
     ) -> Definition {
         let typ = typ.follow_bindings();
@@ -175,7 +194,7 @@ impl<'interner> Monomorphizer<'interner> {
                         Definition::Builtin(opcode)
                     }
                     FunctionKind::Normal => {
-                        let id = self.queue_function(id, expr_id, typ);
+                        let id = self.queue_function(id, bindings, typ);
                         Definition::Function(id)
                     }
                     FunctionKind::Oracle => {
@@ -235,12 +254,17 @@ impl<'interner> Monomorphizer<'interner> {
             _ => meta.return_type(),
         });
 
-        let parameters = self.parameters(meta.parameters);
-        let body = self.expr(body_expr_id);
         let unconstrained = modifiers.is_unconstrained
             || matches!(modifiers.contract_function_type, Some(ContractFunctionType::Open));
+        self.in_unconstrained_function = unconstrained;
 
-        let function = ast::Function { id, name, parameters, body, return_type, unconstrained };
+        let parameters = self.parameters(meta.parameters);
+        let body = self.expr(body_expr_id);
+
+        let inline_type = InlineType::from(self.interner.function_attributes(&f));
+
+        let function =
+            ast::Function { id, name, parameters, body, return_type, unconstrained, inline_type };
         self.push_function(id, function);
     }
 
@@ -345,6 +369,16 @@ impl<'interner> Monomorphizer<'interner> {
 
             HirExpression::Prefix(prefix) => {
                 let location = self.interner.expr_location(&expr);
+
+                if self.interner.get_selected_impl_for_ident(expr).is_some() {
+                    // This is an operator on a non-primitive type, so call its trait impl instead
+                    let method = self
+                        .interner
+                        .get_prefix_operator_trait_method(prefix.operator)
+                        .expect("Operators with a selected impl should have a trait");
+                    return self.operator_trait_call(expr, method, vec![prefix.rhs], location);
+                }
+
                 ast::Expression::Unary(ast::Unary {
                     operator: prefix.operator,
                     rhs: Box::new(self.expr(prefix.rhs)),
@@ -354,10 +388,15 @@ impl<'interner> Monomorphizer<'interner> {
             }
 
             HirExpression::Infix(infix) => {
-                let lhs = Box::new(self.expr(infix.lhs));
-                let rhs = Box::new(self.expr(infix.rhs));
                 let operator = infix.operator.kind;
                 let location = self.interner.expr_location(&expr);
+
+                if self.interner.get_selected_impl_for_ident(expr).is_some() {
+                    return self.operator_overload(expr, infix, location);
+                }
+
+                let lhs = Box::new(self.expr(infix.lhs));
+                let rhs = Box::new(self.expr(infix.rhs));
                 ast::Expression::Binary(ast::Binary { lhs, rhs, operator, location })
             }
 
@@ -394,12 +433,15 @@ impl<'interner> Monomorphizer<'interner> {
                 ast::Expression::Tuple(fields)
             }
             HirExpression::Constructor(constructor) => self.constructor(constructor, expr),
+            HirExpression::EnumConstructor(constructor) => self.enum_constructor(constructor, expr),
+            HirExpression::Match(match_expr) => self.match_expr(match_expr, expr),
 
             HirExpression::Lambda(lambda) => self.lambda(lambda, expr),
 
             HirExpression::TraitMethodReference(method) => {
                 if let Type::Function(_, _, _) = self.interner.id_type(expr) {
-                    self.resolve_trait_method_reference(expr, method)
+                    let function_type = self.interner.id_type(expr);
+                    self.resolve_trait_method_reference(expr, function_type, method)
                 } else {
                     unreachable!(
                         "Calling a non-function, this should've been caught in typechecking"
@@ -410,6 +452,9 @@ impl<'interner> Monomorphizer<'interner> {
             HirExpression::MethodCall(hir_method_call) => {
                 unreachable!("Encountered HirExpression::MethodCall during monomorphization {hir_method_call:?}")
             }
+            HirExpression::Comptime(_) => {
+                unreachable!("comptime expressions should be evaluated before monomorphization")
+            }
             HirExpression::Error => unreachable!("Encountered Error node during monomorphization"),
         }
     }
@@ -430,11 +475,18 @@ impl<'interner> Monomorphizer<'interner> {
         repeated_element: node_interner::ExprId,
         length: HirType,
     ) -> ast::Expression {
-        let typ = self.convert_type(&self.interner.id_type(array));
-
-        let length = length
-            .evaluate_to_u64()
-            .expect("Length of array is unknown when evaluating numeric generic");
+        let array_type = self.interner.id_type(array);
+        let location = self.interner.expr_location(&array);
+        self.check_array_lengths(&array_type, location);
+        self.check_array_lengths(&length, location);
+        let typ = self.convert_type(&array_type);
+
+        let length = match length.evaluate_to_u64() {
+            Some(length) => length,
+            // The invalid arithmetic in the length has already been reported
+            None if self.error.is_some() => 0,
+            None => panic!("Length of array is unknown when evaluating numeric generic"),
+        };
 
         let contents = vecmap(0..length, |_| self.expr(repeated_element));
         ast::Expression::Literal(ast::Literal::Array(ast::ArrayLiteral { contents, typ }))
@@ -479,6 +531,14 @@ impl<'interner> Monomorphizer<'interner> {
                     block,
                 })
             }
+            HirStatement::While(while_loop) => {
+                let condition = Box::new(self.expr(while_loop.condition));
+                let block = Box::new(self.expr(while_loop.block));
+                ast::Expression::While(ast::While { condition, block })
+            }
+            HirStatement::Loop(block) => ast::Expression::Loop(Box::new(self.expr(block))),
+            HirStatement::Break => ast::Expression::Break,
+            HirStatement::Continue => ast::Expression::Continue,
             HirStatement::Expression(expr) => self.expr(expr),
             HirStatement::Semi(expr) => ast::Expression::Semi(Box::new(self.expr(expr))),
             HirStatement::Error => unreachable!(),
@@ -488,6 +548,8 @@ impl<'interner> Monomorphizer<'interner> {
     fn let_statement(&mut self, let_statement: HirLetStatement) -> ast::Expression {
         let expr = self.expr(let_statement.expression);
         let expected_type = self.interner.id_type(let_statement.expression);
+        let location = self.interner.expr_location(&let_statement.expression);
+        self.check_array_lengths(&expected_type, location);
         self.unpack_pattern(let_statement.pattern, expr, &expected_type)
     }
 
@@ -540,6 +602,171 @@ impl<'interner> Monomorphizer<'interner> {
         ast::Expression::Block(new_exprs)
     }
 
+    /// Enums are represented as a tuple of their tag followed by the fields of every
+    /// variant, each grouped into their own tuple. Only the fields of the variant being
+    /// constructed are set; the fields of every other variant are zeroed.
+    fn enum_constructor(
+        &mut self,
+        constructor: HirEnumConstructorExpression,
+        id: node_interner::ExprId,
+    ) -> ast::Expression {
+        let typ = self.interner.id_type(id);
+        let variants = unwrap_enum_type(&typ);
+        let location = self.interner.expr_location(&id);
+
+        let tag = FieldElement::from(constructor.variant_index as u128);
+        let tag = ast::Expression::Literal(ast::Literal::Integer(tag, ast::Type::Field, location));
+        let mut arguments = Some(constructor.arguments);
+
+        let mut fields = vec![tag];
+        for (i, (_, variant_fields)) in variants.into_iter().enumerate() {
+            let payload = if i == constructor.variant_index {
+                let arguments = arguments.take().unwrap_or_default();
+                vecmap(arguments, |argument| self.expr(argument))
+            } else {
+                vecmap(variant_fields, |field| {
+                    let field = self.convert_type(&field);
+                    self.zeroed_value_of_type(&field, location)
+                })
+            };
+            fields.push(ast::Expression::Tuple(payload));
+        }
+
+        ast::Expression::Tuple(fields)
+    }
+
+    /// Lowers a match into a chain of if expressions comparing the tag of the matched
+    /// value against the tag of each variant pattern. Since matches are exhaustive,
+    /// the last reachable arm is used as the final else branch, which only asserts
+    /// that the tag is one of the variants it covers.
+    fn match_expr(
+        &mut self,
+        match_expr: HirMatchExpression,
+        id: node_interner::ExprId,
+    ) -> ast::Expression {
+        let scrutinee_type = self.interner.id_type(match_expr.expression);
+        let result_type = self.convert_type(&self.interner.id_type(id));
+        let location = self.interner.expr_location(&id);
+
+        let scrutinee_id = self.next_local_id();
+        let scrutinee = ast::Ident {
+            location: None,
+            definition: Definition::Local(scrutinee_id),
+            mutable: false,
+            name: "_match".into(),
+            typ: self.convert_type(&scrutinee_type),
+        };
+
+        let mut block = vec![ast::Expression::Let(ast::Let {
+            id: scrutinee_id,
+            mutable: false,
+            name: "_match".into(),
+            expression: Box::new(self.expr(match_expr.expression)),
+        })];
+
+        // Any arm after a binding pattern is unreachable
+        let mut rules = match_expr.rules;
+        if let Some(index) =
+            rules.iter().position(|(pattern, _)| matches!(pattern, HirMatchPattern::Binding(_)))
+        {
+            rules.truncate(index + 1);
+        }
+
+        let mut arms = vecmap(rules, |(pattern, branch)| {
+            let value = ast::Expression::Ident(scrutinee.clone());
+
+            let (tag, bindings) = match pattern {
+                HirMatchPattern::Binding(pattern) => {
+                    (None, self.unpack_pattern(pattern, value, &scrutinee_type))
+                }
+                HirMatchPattern::Variant { variant_index, fields, .. } => {
+                    let (_, field_types) =
+                        unwrap_enum_type(&scrutinee_type).swap_remove(variant_index);
+                    let payload =
+                        ast::Expression::ExtractTupleField(Box::new(value), variant_index + 1);
+                    let bindings =
+                        self.unpack_tuple_pattern(payload, fields.into_iter().zip(field_types));
+                    (Some(variant_index), bindings)
+                }
+                HirMatchPattern::Error(_) => {
+                    unreachable!("Encountered an invalid match pattern during monomorphization")
+                }
+            };
+
+            (tag, ast::Expression::Block(vec![bindings, self.expr(branch)]))
+        });
+
+        // The tag of an enum is an unconstrained field when it comes from the inputs of the program,
+        // so the final else branch asserts that it holds one of the variants left for the last arm
+        // instead of accepting any other value.
+        let remaining_tags = match (scrutinee_type.follow_bindings(), arms.last()) {
+            (_, Some((Some(tag), _))) => Some(vec![*tag]),
+            (HirType::Enum(def, _), _) => {
+                let checked_tags: HashSet<_> = arms.iter().filter_map(|(tag, _)| *tag).collect();
+                let variant_count = def.borrow().num_variants();
+                Some((0..variant_count).filter(|tag| !checked_tags.contains(tag)).collect())
+            }
+            // Only binding patterns can match values which are not enums
+            _ => None,
+        };
+
+        if let Some((_, mut last_arm)) = arms.pop() {
+            if let Some(remaining_tags) = remaining_tags {
+                let tag_check = remaining_tags
+                    .into_iter()
+                    .map(|tag| Self::enum_tag_equals(&scrutinee, tag, location))
+                    .reduce(|lhs, rhs| {
+                        ast::Expression::Binary(ast::Binary {
+                            lhs: Box::new(lhs),
+                            operator: crate::BinaryOpKind::Or,
+                            rhs: Box::new(rhs),
+                            location,
+                        })
+                    })
+                    .unwrap_or(ast::Expression::Literal(ast::Literal::Bool(false)));
+                let tag_check = ast::Expression::Constrain(
+                    Box::new(tag_check),
+                    location,
+                    Some("Invalid enum tag".to_string()),
+                );
+                last_arm = ast::Expression::Block(vec![tag_check, last_arm]);
+            }
+
+            let chain = arms.into_iter().rev().fold(last_arm, |alternative, (tag, consequence)| {
+                let tag = tag.expect("Only the last arm of a match may be a binding pattern");
+                ast::Expression::If(ast::If {
+                    condition: Box::new(Self::enum_tag_equals(&scrutinee, tag, location)),
+                    consequence: Box::new(consequence),
+                    alternative: Some(Box::new(alternative)),
+                    typ: result_type.clone(),
+                })
+            });
+            block.push(chain);
+        }
+
+        ast::Expression::Block(block)
+    }
+
+    /// Compares the tag of an enum value against the tag of the given variant
+    fn enum_tag_equals(
+        value: &ast::Ident,
+        variant_index: usize,
+        location: Location,
+    ) -> ast::Expression {
+        let value = Box::new(ast::Expression::Ident(value.clone()));
+        let tag = FieldElement::from(variant_index as u128);
+        ast::Expression::Binary(ast::Binary {
+            lhs: Box::new(ast::Expression::ExtractTupleField(value, 0)),
+            operator: crate::BinaryOpKind::Equal,
+            rhs: Box::new(ast::Expression::Literal(ast::Literal::Integer(
+                tag,
+                ast::Type::Field,
+                location,
+            ))),
+            location,
+        })
+    }
+
     fn block(&mut self, statement_ids: Vec<StmtId>) -> ast::Expression {
         ast::Expression::Block(vecmap(statement_ids, |id| self.statement(id)))
     }
@@ -660,7 +887,8 @@ impl<'interner> Monomorphizer<'interner> {
                 let location = Some(ident.location);
                 let name = definition.name.clone();
                 let typ = self.interner.id_type(expr_id);
-                let definition = self.lookup_function(*func_id, expr_id, &typ);
+                let bindings = self.interner.get_instantiation_bindings(expr_id);
+                let definition = self.lookup_function(*func_id, bindings, &typ);
                 let typ = self.convert_type(&typ);
                 let ident = ast::Ident { location, mutable, definition, name, typ: typ.clone() };
                 let ident_expression = ast::Expression::Ident(ident);
@@ -693,11 +921,28 @@ impl<'interner> Monomorphizer<'interner> {
 
                 let value = FieldElement::from(value as u128);
                 let location = self.interner.id_location(expr_id);
-                ast::Expression::Literal(ast::Literal::Integer(value, ast::Type::Field, location))
+                let typ = self.convert_type(&self.interner.id_type(expr_id));
+                ast::Expression::Literal(ast::Literal::Integer(value, typ, location))
             }
         }
     }
 
+    /// Records an error if `typ` contains array length arithmetic which overflows, underflows
+    /// or divides by zero now that the generics of the current function are instantiated.
+    fn check_array_lengths(&mut self, typ: &HirType, location: Location) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Some((lhs, op, rhs)) = typ.find_invalid_infix_expr() {
+            self.error = Some(MonomorphizationError::InvalidArrayLengthArithmetic {
+                lhs,
+                op,
+                rhs,
+                location,
+            });
+        }
+    }
+
     /// Convert a non-tuple/struct type to a monomorphized type
     fn convert_type(&self, typ: &HirType) -> ast::Type {
         match typ {
@@ -774,6 +1019,15 @@ impl<'interner> Monomorphizer<'interner> {
                 ast::Type::Tuple(fields)
             }
 
+            HirType::Enum(def, args) => {
+                let variants = def.borrow().get_variants(args);
+                let variants = variants.into_iter().map(|(_, fields)| {
+                    ast::Type::Tuple(vecmap(fields, |field| self.convert_type(&field)))
+                });
+                let tag = std::iter::once(ast::Type::Field);
+                ast::Type::Tuple(tag.chain(variants).collect())
+            }
+
             HirType::Function(args, ret, env) => {
                 let args = vecmap(args, |x| self.convert_type(x));
                 let ret = Box::new(self.convert_type(ret));
@@ -799,6 +1053,7 @@ impl<'interner> Monomorphizer<'interner> {
 
             HirType::Forall(_, _)
             | HirType::Constant(_)
+            | HirType::InfixExpr(..)
             | HirType::NotConstant
             | HirType::Error => {
                 unreachable!("Unexpected type {} found", typ)
@@ -832,10 +1087,9 @@ impl<'interner> Monomorphizer<'interner> {
     fn resolve_trait_method_reference(
         &mut self,
         expr_id: node_interner::ExprId,
+        function_type: HirType,
         method: TraitMethodId,
     ) -> ast::Expression {
-        let function_type = self.interner.id_type(expr_id);
-
         let trait_impl = self
             .interner
             .get_selected_impl_for_ident(expr_id)
@@ -869,7 +1123,16 @@ impl<'interner> Monomorphizer<'interner> {
             }
         };
 
-        let func_def = self.lookup_function(hir_func_id, expr_id, &function_type);
+        // The bindings of the trait method reference only cover the trait's generics. Any generics
+        // on the impl itself (e.g. the `T` in `impl<T> Foo for Bar<T>`) are found by unifying the
+        // impl method's type with the type it is used at.
+        let (impl_function_type, bindings) =
+            self.interner.function_meta(&hir_func_id).typ.instantiate(self.interner);
+        if impl_function_type.try_unify(&function_type).is_err() {
+            unreachable!("Trait impl method type should unify with the trait method's type");
+        }
+
+        let func_def = self.lookup_function(hir_func_id, &bindings, &function_type);
         let func_id = match func_def {
             Definition::Function(func_id) => func_id,
             _ => unreachable!(),
@@ -886,6 +1149,68 @@ impl<'interner> Monomorphizer<'interner> {
         })
     }
 
+    /// Lower an infix operator on a non-primitive type into a call to its trait impl.
+    /// `!=` negates the result of `Eq::eq`, and the ordering operators compare the tag
+    /// of the `Ordering` returned by `Ord::cmp`.
+    fn operator_overload(
+        &mut self,
+        expr_id: node_interner::ExprId,
+        infix: HirInfixExpression,
+        location: Location,
+    ) -> ast::Expression {
+        let operator = infix.operator.kind;
+        let method = self
+            .interner
+            .get_operator_trait_method(operator)
+            .expect("Operators with a selected impl should have a trait");
+
+        let call = self.operator_trait_call(expr_id, method, vec![infix.lhs, infix.rhs], location);
+
+        // The `Ordering` tag to compare against: Less = 0, Equal = 1, Greater = 2
+        let (ordering_operator, tag) = match operator {
+            BinaryOpKind::NotEqual => {
+                return ast::Expression::Unary(ast::Unary {
+                    operator: crate::UnaryOp::Not,
+                    rhs: Box::new(call),
+                    result_type: ast::Type::Bool,
+                    location,
+                });
+            }
+            BinaryOpKind::Less => (BinaryOpKind::Equal, 0u128),
+            BinaryOpKind::LessEqual => (BinaryOpKind::NotEqual, 2),
+            BinaryOpKind::Greater => (BinaryOpKind::Equal, 2),
+            BinaryOpKind::GreaterEqual => (BinaryOpKind::NotEqual, 0),
+            _ => return call,
+        };
+
+        let lhs = Box::new(ast::Expression::ExtractTupleField(Box::new(call), 0));
+        let tag = ast::Literal::Integer(tag.into(), ast::Type::Field, location);
+        let rhs = Box::new(ast::Expression::Literal(tag));
+        ast::Expression::Binary(ast::Binary { lhs, rhs, operator: ordering_operator, location })
+    }
+
+    /// Call the impl of the given operator trait method that was selected for `expr_id`
+    fn operator_trait_call(
+        &mut self,
+        expr_id: node_interner::ExprId,
+        method: TraitMethodId,
+        arguments: Vec<node_interner::ExprId>,
+        location: Location,
+    ) -> ast::Expression {
+        let argument_types = vecmap(&arguments, |argument| self.interner.id_type(argument));
+        let return_type = self.interner.next_type_variable();
+        let function_type = HirType::Function(
+            argument_types,
+            Box::new(return_type.clone()),
+            Box::new(HirType::Unit),
+        );
+
+        let func = Box::new(self.resolve_trait_method_reference(expr_id, function_type, method));
+        let arguments = vecmap(arguments, |argument| self.expr(argument));
+        let return_type = self.convert_type(&return_type);
+        ast::Expression::Call(ast::Call { func, arguments, return_type, location })
+    }
+
     fn function_call(
         &mut self,
         call: HirCallExpression,
@@ -1090,13 +1415,12 @@ impl<'interner> Monomorphizer<'interner> {
     fn queue_function(
         &mut self,
         id: node_interner::FuncId,
-        expr_id: node_interner::ExprId,
+        bindings: &TypeBindings,
         function_type: HirType,
     ) -> FuncId {
         let new_id = self.next_function_id();
         self.define_global(id, function_type, new_id);
 
-        let bindings = self.interner.get_instantiation_bindings(expr_id);
         let bindings = self.follow_bindings(bindings);
 
         self.queue.push_back((id, new_id, bindings));
@@ -1175,9 +1499,11 @@ impl<'interner> Monomorphizer<'interner> {
         let id = self.next_function_id();
         let return_type = ret_type.clone();
         let name = lambda_name.to_owned();
-        let unconstrained = false;
+        let unconstrained = self.in_unconstrained_function;
 
-        let function = ast::Function { id, name, parameters, body, return_type, unconstrained };
+        let inline_type = InlineType::Inline;
+        let function =
+            ast::Function { id, name, parameters, body, return_type, unconstrained, inline_type };
         self.push_function(id, function);
 
         let typ =
@@ -1290,8 +1616,10 @@ impl<'interner> Monomorphizer<'interner> {
         parameters.push((env_local_id, true, env_name.to_string(), env_typ.clone()));
         parameters.append(&mut converted_parameters);
 
-        let unconstrained = false;
-        let function = ast::Function { id, name, parameters, body, return_type, unconstrained };
+        let unconstrained = self.in_unconstrained_function;
+        let inline_type = InlineType::Inline;
+        let function =
+            ast::Function { id, name, parameters, body, return_type, unconstrained, inline_type };
         self.push_function(id, function);
 
         let lambda_value =
@@ -1408,8 +1736,10 @@ impl<'interner> Monomorphizer<'interner> {
         let return_type = ret_type.clone();
         let name = lambda_name.to_owned();
 
-        let unconstrained = false;
-        let function = ast::Function { id, name, parameters, body, return_type, unconstrained };
+        let unconstrained = self.in_unconstrained_function;
+        let inline_type = InlineType::Inline;
+        let function =
+            ast::Function { id, name, parameters, body, return_type, unconstrained, inline_type };
         self.push_function(id, function);
 
         ast::Expression::Ident(ast::Ident {
@@ -1448,6 +1778,17 @@ fn unwrap_struct_type(typ: &HirType) -> Vec<(String, HirType)> {
     }
 }
 
+fn unwrap_enum_type(typ: &HirType) -> Vec<(String, Vec<HirType>)> {
+    match typ {
+        HirType::Enum(def, args) => def.borrow().get_variants(args),
+        HirType::TypeVariable(binding, TypeVariableKind::Normal) => match &*binding.borrow() {
+            TypeBinding::Bound(binding) => unwrap_enum_type(binding),
+            TypeBinding::Unbound(_) => unreachable!(),
+        },
+        other => unreachable!("unwrap_enum_type: expected enum, found {:?}", other),
+    }
+}
+
 fn perform_instantiation_bindings(bindings: &TypeBindings) {
     for (var, binding) in bindings.values() {
         *var.borrow_mut() = TypeBinding::Bound(binding.clone());
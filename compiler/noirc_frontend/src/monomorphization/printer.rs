@@ -47,6 +47,11 @@ impl AstPrinter {
                 write!(f, " as {})", cast.r#type)
             }
             Expression::For(for_expr) => self.print_for(for_expr, f),
+            Expression::While(while_expr) => self.print_while(while_expr, f),
+            Expression::Loop(block) => {
+                write!(f, "loop {{")?;
+                self.print_loop_body(block, f)
+            }
             Expression::If(if_expr) => self.print_if(if_expr, f),
             Expression::Tuple(tuple) => self.print_tuple(tuple, f),
             Expression::ExtractTupleField(expr, index) => {
@@ -71,6 +76,8 @@ impl AstPrinter {
                 self.print_expr(expr, f)?;
                 write!(f, ";")
             }
+            Expression::Break => write!(f, "break"),
+            Expression::Continue => write!(f, "continue"),
         }
     }
 
@@ -187,9 +194,27 @@ impl AstPrinter {
         write!(f, " .. ")?;
         self.print_expr(&for_expr.end_range, f)?;
         write!(f, " {{")?;
+        self.print_loop_body(&for_expr.block, f)
+    }
 
+    fn print_while(
+        &mut self,
+        while_expr: &super::ast::While,
+        f: &mut Formatter,
+    ) -> Result<(), std::fmt::Error> {
+        write!(f, "while ")?;
+        self.print_expr(&while_expr.condition, f)?;
+        write!(f, " {{")?;
+        self.print_loop_body(&while_expr.block, f)
+    }
+
+    fn print_loop_body(
+        &mut self,
+        block: &Expression,
+        f: &mut Formatter,
+    ) -> Result<(), std::fmt::Error> {
         self.indent_level += 1;
-        self.print_expr_expect_block(&for_expr.block, f)?;
+        self.print_expr_expect_block(block, f)?;
         self.indent_level -= 1;
         self.next_line(f)?;
         write!(f, "}}")
@@ -7,13 +7,15 @@ use noirc_errors::{Location, Span, Spanned};
 
 use crate::ast::Ident;
 use crate::graph::CrateId;
-use crate::hir::def_collector::dc_crate::{UnresolvedStruct, UnresolvedTrait, UnresolvedTypeAlias};
+use crate::hir::def_collector::dc_crate::{
+    UnresolvedEnum, UnresolvedStruct, UnresolvedTrait, UnresolvedTypeAlias,
+};
 use crate::hir::def_map::{LocalModuleId, ModuleId};
 
 use crate::hir_def::stmt::HirLetStatement;
 use crate::hir_def::traits::TraitImpl;
 use crate::hir_def::traits::{Trait, TraitConstraint};
-use crate::hir_def::types::{StructType, Type};
+use crate::hir_def::types::{EnumType, StructType, Type};
 use crate::hir_def::{
     expr::HirExpression,
     function::{FuncMeta, HirFunction},
@@ -21,8 +23,9 @@ use crate::hir_def::{
 };
 use crate::token::{Attributes, SecondaryAttribute};
 use crate::{
-    ContractFunctionType, FunctionDefinition, FunctionVisibility, Generics, Shared, TypeAliasType,
-    TypeBinding, TypeBindings, TypeVariable, TypeVariableId, TypeVariableKind,
+    BinaryOpKind, ContractFunctionType, FunctionDefinition, FunctionVisibility, Generics, Shared,
+    TypeAliasType, TypeBinding, TypeBindings, TypeVariable, TypeVariableId, TypeVariableKind,
+    UnaryOp,
 };
 
 /// An arbitrary number to limit the recursion depth when searching for trait impls.
@@ -74,6 +77,13 @@ pub struct NodeInterner {
     structs: HashMap<StructId, Shared<StructType>>,
 
     struct_attributes: HashMap<StructId, StructAttributes>,
+
+    // Enum map.
+    //
+    // Like structs, each enum definition is shared across every type node referring to it.
+    // Its variants are filled in through the RefCell once they are resolved.
+    enums: HashMap<EnumId, Shared<EnumType>>,
+
     // Type Aliases map.
     //
     // Map type aliases to the actual type.
@@ -121,6 +131,12 @@ pub struct NodeInterner {
 
     globals: HashMap<StmtId, GlobalInfo>, // NOTE: currently only used for checking repeat globals and restricting their scope to a module
 
+    /// The value of a `comptime` global is only known once it has been evaluated, after the rest
+    /// of the crate has been resolved. Until then, each use of such a global as an array length
+    /// refers to the type variable here, which is bound to the value once it is evaluated.
+    /// The flag records whether the global was used as an array length at all.
+    comptime_globals: HashMap<StmtId, (TypeVariable, bool)>,
+
     next_type_variable_id: std::cell::Cell<usize>,
 
     /// A map from a struct type and method name to a function id for the method.
@@ -136,6 +152,13 @@ pub struct NodeInterner {
 
     // For trait implementation functions, this is their self type and trait they belong to
     func_id_to_trait: HashMap<FuncId, (Type, TraitId)>,
+
+    /// The stdlib traits which infix operators on non-primitive types are desugared into.
+    /// Comparison operators share the `Eq` or `Ord` trait of their operator family.
+    operator_traits: HashMap<BinaryOpKind, TraitId>,
+
+    /// The stdlib traits which prefix operators on non-primitive types are desugared into.
+    prefix_operator_traits: HashMap<UnaryOp, TraitId>,
 }
 
 /// A trait implementation is either a normal implementation that is present in the source
@@ -178,6 +201,9 @@ pub struct FunctionModifiers {
 
     pub is_unconstrained: bool,
 
+    /// Whether this function is `comptime` and may only be called at compile-time.
+    pub is_comptime: bool,
+
     /// This function's type in its contract.
     /// If this function is not in a contract, this is always 'Secret'.
     pub contract_function_type: Option<ContractFunctionType>,
@@ -198,6 +224,7 @@ impl FunctionModifiers {
             visibility: FunctionVisibility::Public,
             attributes: Attributes::empty(),
             is_unconstrained: false,
+            is_comptime: false,
             is_internal: None,
             contract_function_type: None,
         }
@@ -276,6 +303,28 @@ impl StructId {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone, PartialOrd, Ord)]
+pub struct EnumId(ModuleId);
+
+impl EnumId {
+    //dummy id for error reporting
+    pub fn dummy_id() -> EnumId {
+        EnumId(ModuleId { krate: CrateId::dummy_id(), local_id: LocalModuleId::dummy_id() })
+    }
+
+    pub fn module_id(self) -> ModuleId {
+        self.0
+    }
+
+    pub fn krate(self) -> CrateId {
+        self.0.krate
+    }
+
+    pub fn local_module_id(self) -> LocalModuleId {
+        self.0.local_id
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Hash, Copy, Clone, PartialOrd, Ord)]
 pub struct TypeAliasId(pub usize);
 
@@ -412,11 +461,14 @@ impl Default for NodeInterner {
             function_modifiers: HashMap::new(),
             function_modules: HashMap::new(),
             func_id_to_trait: HashMap::new(),
+            operator_traits: HashMap::new(),
+            prefix_operator_traits: HashMap::new(),
             id_to_location: HashMap::new(),
             definitions: vec![],
             id_to_type: HashMap::new(),
             structs: HashMap::new(),
             struct_attributes: HashMap::new(),
+            enums: HashMap::new(),
             type_aliases: Vec::new(),
             traits: HashMap::new(),
             trait_implementations: Vec::new(),
@@ -426,6 +478,7 @@ impl Default for NodeInterner {
             field_indices: HashMap::new(),
             next_type_variable_id: std::cell::Cell::new(0),
             globals: HashMap::new(),
+            comptime_globals: HashMap::new(),
             struct_methods: HashMap::new(),
             primitive_methods: HashMap::new(),
         };
@@ -539,6 +592,26 @@ impl NodeInterner {
         struct_id
     }
 
+    pub fn new_enum(
+        &mut self,
+        typ: &UnresolvedEnum,
+        krate: CrateId,
+        local_id: LocalModuleId,
+    ) -> EnumId {
+        let enum_id = EnumId(ModuleId { krate, local_id });
+        let name = typ.enum_def.name.clone();
+
+        // Variants will be filled in later, as with struct fields
+        let generics = vecmap(&typ.enum_def.generics, |_| {
+            let id = TypeVariableId(0);
+            (id, Shared::new(TypeBinding::Unbound(id)))
+        });
+
+        let new_enum = EnumType::new(enum_id, name, typ.enum_def.span, Vec::new(), generics);
+        self.enums.insert(enum_id, Shared::new(new_enum));
+        enum_id
+    }
+
     pub fn push_type_alias(&mut self, typ: &UnresolvedTypeAlias) -> TypeAliasId {
         let type_id = TypeAliasId(self.type_aliases.len());
 
@@ -561,6 +634,11 @@ impl NodeInterner {
         f(&mut value);
     }
 
+    pub fn update_enum(&mut self, type_id: EnumId, f: impl FnOnce(&mut EnumType)) {
+        let mut value = self.enums.get_mut(&type_id).unwrap().borrow_mut();
+        f(&mut value);
+    }
+
     pub fn update_trait(&mut self, trait_id: TraitId, f: impl FnOnce(&mut Trait)) {
         let value = self.traits.get_mut(&trait_id).unwrap();
         f(value);
@@ -604,6 +682,26 @@ impl NodeInterner {
         self.globals.insert(stmt_id, GlobalInfo { ident, local_id });
     }
 
+    /// Mark the given global as a `comptime` global, whose value is evaluated after name resolution
+    pub fn push_comptime_global(&mut self, stmt_id: StmtId) {
+        let id = self.next_type_variable_id();
+        self.comptime_globals.insert(stmt_id, (Shared::new(TypeBinding::Unbound(id)), false));
+    }
+
+    /// Returns the type variable standing in for the value of the given global when it is
+    /// used as an array length, if it is a `comptime` global.
+    pub fn use_comptime_global_as_length(&mut self, stmt_id: &StmtId) -> Option<TypeVariable> {
+        let (value, used_as_length) = self.comptime_globals.get_mut(stmt_id)?;
+        *used_as_length = true;
+        Some(value.clone())
+    }
+
+    /// Returns the type variable standing in for the value of the given `comptime` global
+    /// along with whether it was used as an array length.
+    pub fn get_comptime_global(&self, stmt_id: &StmtId) -> Option<(TypeVariable, bool)> {
+        self.comptime_globals.get(stmt_id).cloned()
+    }
+
     /// Intern an empty global stmt. Used for collecting globals
     pub fn push_empty_global(&mut self) -> StmtId {
         self.push_stmt(HirStatement::Error)
@@ -698,6 +796,7 @@ impl NodeInterner {
             visibility: function.visibility,
             attributes: function.attributes.clone(),
             is_unconstrained: function.is_unconstrained,
+            is_comptime: function.is_comptime,
             contract_function_type: Some(if function.is_open { Open } else { Secret }),
             is_internal: Some(function.is_internal),
         };
@@ -857,6 +956,10 @@ impl NodeInterner {
         self.structs[&id].clone()
     }
 
+    pub fn get_enum(&self, id: EnumId) -> Shared<EnumType> {
+        self.enums[&id].clone()
+    }
+
     pub fn get_trait(&self, id: TraitId) -> Trait {
         self.traits[&id].clone()
     }
@@ -865,6 +968,51 @@ impl NodeInterner {
         self.traits.get(&id).cloned()
     }
 
+    /// Registers the given stdlib trait as the trait used by its operators if it is one of
+    /// the operator traits, e.g. `std::ops::Add` for `+` or `std::cmp::Ord` for `<` and `>=`.
+    pub fn try_add_operator_trait(&mut self, trait_id: TraitId) {
+        let the_trait = self.get_trait(trait_id);
+        let operators: &[BinaryOpKind] = match the_trait.name.0.contents.as_str() {
+            "Add" => &[BinaryOpKind::Add],
+            "Sub" => &[BinaryOpKind::Subtract],
+            "Mul" => &[BinaryOpKind::Multiply],
+            "Div" => &[BinaryOpKind::Divide],
+            "Eq" => &[BinaryOpKind::Equal, BinaryOpKind::NotEqual],
+            "Ord" => &[
+                BinaryOpKind::Less,
+                BinaryOpKind::LessEqual,
+                BinaryOpKind::Greater,
+                BinaryOpKind::GreaterEqual,
+            ],
+            "Neg" => {
+                self.prefix_operator_traits.insert(UnaryOp::Minus, trait_id);
+                return;
+            }
+            "Not" => {
+                self.prefix_operator_traits.insert(UnaryOp::Not, trait_id);
+                return;
+            }
+            _ => return,
+        };
+
+        for operator in operators {
+            self.operator_traits.insert(*operator, trait_id);
+        }
+    }
+
+    /// Returns the trait method an infix operator on a non-primitive type is desugared into.
+    /// Each operator trait has exactly one method.
+    pub fn get_operator_trait_method(&self, operator: BinaryOpKind) -> Option<TraitMethodId> {
+        let trait_id = *self.operator_traits.get(&operator)?;
+        Some(TraitMethodId { trait_id, method_index: 0 })
+    }
+
+    /// Returns the trait method a prefix operator on a non-primitive type is desugared into.
+    pub fn get_prefix_operator_trait_method(&self, operator: UnaryOp) -> Option<TraitMethodId> {
+        let trait_id = *self.prefix_operator_traits.get(&operator)?;
+        Some(TraitMethodId { trait_id, method_index: 0 })
+    }
+
     pub fn get_type_alias(&self, id: TypeAliasId) -> &TypeAliasType {
         &self.type_aliases[id.0]
     }
@@ -989,6 +1137,23 @@ impl NodeInterner {
         self.trait_implementations[id.0].clone()
     }
 
+    /// Returns the values the given impl assigns to each of its trait's associated types and
+    /// constants, with any generics of the impl instantiated to match the given object type.
+    pub fn get_associated_types_for_impl(
+        &self,
+        impl_id: TraitImplId,
+        object_type: &Type,
+    ) -> Vec<Type> {
+        let trait_impl = self.get_trait_implementation(impl_id);
+        let trait_impl = trait_impl.borrow();
+
+        let (impl_type, bindings) = trait_impl.typ.instantiate_type_variables(self);
+
+        // This impl was already selected for the object type, so this always succeeds
+        let _ = object_type.try_unify(&impl_type);
+        vecmap(&trait_impl.associated_types, |typ| typ.substitute(&bindings))
+    }
+
     /// Given a `ObjectType: TraitId` pair, try to find an existing impl that satisfies the
     /// constraint. If an impl cannot be found, this will return a vector of each constraint
     /// in the path to get to the failing constraint. Usually this is just the single failing
@@ -1299,7 +1464,8 @@ impl Methods {
     }
 }
 
-/// These are the primitive type variants that we support adding methods to
+/// These are the primitive type variants that we support adding methods to.
+/// Enums are also included here since, unlike structs, they have no dedicated method map.
 #[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
 enum TypeMethodKey {
     /// Fields and integers share methods for ease of use. These methods may still
@@ -1313,6 +1479,7 @@ enum TypeMethodKey {
     Tuple,
     Function,
     Generic,
+    Enum(EnumId),
 }
 
 fn get_type_method_key(typ: &Type) -> Option<TypeMethodKey> {
@@ -1330,12 +1497,14 @@ fn get_type_method_key(typ: &Type) -> Option<TypeMethodKey> {
         Type::Tuple(_) => Some(Tuple),
         Type::Function(_, _, _) => Some(Function),
         Type::NamedGeneric(_, _) => Some(Generic),
+        Type::Enum(enum_type, _) => Some(Enum(enum_type.borrow().id)),
         Type::MutableReference(element) => get_type_method_key(element),
 
         // We do not support adding methods to these types
         Type::TypeVariable(_, _)
         | Type::Forall(_, _)
         | Type::Constant(_)
+        | Type::InfixExpr(..)
         | Type::Error
         | Type::NotConstant
         | Type::Struct(_, _)
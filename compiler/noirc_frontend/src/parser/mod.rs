@@ -12,10 +12,10 @@ mod labels;
 mod parser;
 
 use crate::token::{Keyword, Token};
-use crate::{ast::ImportStatement, Expression, NoirStruct};
+use crate::{ast::ImportStatement, Expression, NoirEnum, NoirStruct};
 use crate::{
-    Ident, LetStatement, NoirFunction, NoirTrait, NoirTraitImpl, NoirTypeAlias, Recoverable,
-    StatementKind, TypeImpl, UseTree,
+    Ident, ItemVisibility, LetStatement, NoirFunction, NoirTrait, NoirTraitImpl, NoirTypeAlias,
+    Recoverable, StatementKind, TypeImpl, UseTree,
 };
 
 use chumsky::prelude::*;
@@ -29,8 +29,9 @@ pub use parser::parse_program;
 pub(crate) enum TopLevelStatement {
     Function(NoirFunction),
     Module(Ident),
-    Import(UseTree),
+    Import(UseTree, ItemVisibility),
     Struct(NoirStruct),
+    Enum(NoirEnum),
     Trait(NoirTrait),
     TraitImpl(NoirTraitImpl),
     Impl(TypeImpl),
@@ -213,6 +214,7 @@ pub struct SortedModule {
     pub imports: Vec<ImportStatement>,
     pub functions: Vec<NoirFunction>,
     pub types: Vec<NoirStruct>,
+    pub enums: Vec<NoirEnum>,
     pub traits: Vec<NoirTrait>,
     pub trait_impls: Vec<NoirTraitImpl>,
     pub impls: Vec<TypeImpl>,
@@ -244,6 +246,10 @@ impl std::fmt::Display for SortedModule {
             write!(f, "{type_}")?;
         }
 
+        for enum_ in &self.enums {
+            write!(f, "{enum_}")?;
+        }
+
         for function in &self.functions {
             write!(f, "{function}")?;
         }
@@ -276,9 +282,10 @@ impl ParsedModule {
 
         for item in self.items {
             match item.kind {
-                ItemKind::Import(import) => module.push_import(import),
+                ItemKind::Import(import, visibility) => module.push_import(import, visibility),
                 ItemKind::Function(func) => module.push_function(func),
                 ItemKind::Struct(typ) => module.push_type(typ),
+                ItemKind::Enum(typ) => module.push_enum(typ),
                 ItemKind::Trait(noir_trait) => module.push_trait(noir_trait),
                 ItemKind::TraitImpl(trait_impl) => module.push_trait_impl(trait_impl),
                 ItemKind::Impl(r#impl) => module.push_impl(r#impl),
@@ -301,9 +308,10 @@ pub struct Item {
 
 #[derive(Clone, Debug)]
 pub enum ItemKind {
-    Import(UseTree),
+    Import(UseTree, ItemVisibility),
     Function(NoirFunction),
     Struct(NoirStruct),
+    Enum(NoirEnum),
     Trait(NoirTrait),
     TraitImpl(NoirTraitImpl),
     Impl(TypeImpl),
@@ -359,6 +367,10 @@ impl SortedModule {
         self.types.push(typ);
     }
 
+    fn push_enum(&mut self, typ: NoirEnum) {
+        self.enums.push(typ);
+    }
+
     fn push_trait(&mut self, noir_trait: NoirTrait) {
         self.traits.push(noir_trait);
     }
@@ -375,8 +387,8 @@ impl SortedModule {
         self.type_aliases.push(type_alias);
     }
 
-    fn push_import(&mut self, import_stmt: UseTree) {
-        self.imports.extend(import_stmt.desugar(None));
+    fn push_import(&mut self, import_stmt: UseTree, visibility: ItemVisibility) {
+        self.imports.extend(import_stmt.desugar(None, visibility));
     }
 
     fn push_module_decl(&mut self, mod_name: Ident) {
@@ -474,10 +486,16 @@ impl std::fmt::Display for TopLevelStatement {
         match self {
             TopLevelStatement::Function(fun) => fun.fmt(f),
             TopLevelStatement::Module(m) => write!(f, "mod {m}"),
-            TopLevelStatement::Import(tree) => write!(f, "use {tree}"),
+            TopLevelStatement::Import(tree, visibility) => {
+                if *visibility == ItemVisibility::Public {
+                    write!(f, "pub ")?;
+                }
+                write!(f, "use {tree}")
+            }
             TopLevelStatement::Trait(t) => t.fmt(f),
             TopLevelStatement::TraitImpl(i) => i.fmt(f),
             TopLevelStatement::Struct(s) => s.fmt(f),
+            TopLevelStatement::Enum(e) => e.fmt(f),
             TopLevelStatement::Impl(i) => i.fmt(f),
             TopLevelStatement::TypeAlias(t) => t.fmt(f),
             TopLevelStatement::SubModule(s) => s.fmt(f),
@@ -39,10 +39,11 @@ use crate::token::{Attribute, Attributes, Keyword, SecondaryAttribute, Token, To
 use crate::{
     BinaryOp, BinaryOpKind, BlockExpression, ConstrainKind, ConstrainStatement, Distinctness,
     ForLoopStatement, ForRange, FunctionDefinition, FunctionReturnType, FunctionVisibility, Ident,
-    IfExpression, InfixExpression, LValue, Lambda, Literal, NoirFunction, NoirStruct, NoirTrait,
-    NoirTraitImpl, NoirTypeAlias, Param, Path, PathKind, Pattern, Recoverable, Statement,
-    TraitBound, TraitImplItem, TraitItem, TypeImpl, UnaryOp, UnresolvedTraitConstraint,
-    UnresolvedTypeExpression, UseTree, UseTreeKind, Visibility,
+    IfExpression, InfixExpression, ItemVisibility, LValue, Lambda, Literal, MatchExpression,
+    MatchPattern, NoirEnum, NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl, NoirTypeAlias,
+    Param, Path, PathKind, Pattern, Recoverable, Statement, TraitBound, TraitImplItem, TraitItem,
+    TypeImpl, UnaryOp, UnresolvedTraitConstraint, UnresolvedTypeExpression, UseTree, UseTreeKind,
+    Visibility, WhileStatement,
 };
 
 use chumsky::prelude::*;
@@ -82,8 +83,11 @@ fn module() -> impl NoirParser<ParsedModule> {
                 match statement {
                     TopLevelStatement::Function(f) => push_item(ItemKind::Function(f)),
                     TopLevelStatement::Module(m) => push_item(ItemKind::ModuleDecl(m)),
-                    TopLevelStatement::Import(i) => push_item(ItemKind::Import(i)),
+                    TopLevelStatement::Import(i, visibility) => {
+                        push_item(ItemKind::Import(i, visibility));
+                    }
                     TopLevelStatement::Struct(s) => push_item(ItemKind::Struct(s)),
+                    TopLevelStatement::Enum(e) => push_item(ItemKind::Enum(e)),
                     TopLevelStatement::Trait(t) => push_item(ItemKind::Trait(t)),
                     TopLevelStatement::TraitImpl(t) => push_item(ItemKind::TraitImpl(t)),
                     TopLevelStatement::Impl(i) => push_item(ItemKind::Impl(i)),
@@ -99,6 +103,7 @@ fn module() -> impl NoirParser<ParsedModule> {
 
 /// top_level_statement: function_definition
 ///                    | struct_definition
+///                    | enum_definition
 ///                    | trait_definition
 ///                    | implementation
 ///                    | submodule
@@ -111,6 +116,7 @@ fn top_level_statement(
     choice((
         function_definition(false).map(TopLevelStatement::Function),
         struct_definition(),
+        enum_definition(),
         trait_definition(),
         trait_implementation(),
         implementation(),
@@ -120,11 +126,13 @@ fn top_level_statement(
         module_declaration().then_ignore(force(just(Token::Semicolon))),
         use_statement().then_ignore(force(just(Token::Semicolon))),
         global_declaration().then_ignore(force(just(Token::Semicolon))),
+        comptime_global_declaration().then_ignore(force(just(Token::Semicolon))),
     ))
     .recover_via(top_level_statement_recovery())
 }
 
 /// global_declaration: 'global' ident global_type_annotation '=' literal
+///                   | 'comptime' 'global' ident global_type_annotation '=' expression
 fn global_declaration() -> impl NoirParser<TopLevelStatement> {
     let p = ignore_then_commit(
         keyword(Keyword::Global).labelled(ParsingRuleLabel::Global),
@@ -136,6 +144,25 @@ fn global_declaration() -> impl NoirParser<TopLevelStatement> {
     p.map(LetStatement::new_let).map(TopLevelStatement::Global)
 }
 
+/// A `comptime global` may be initialized by any expression. The expression is wrapped
+/// in a `comptime` block so that it is evaluated during elaboration.
+fn comptime_global_declaration() -> impl NoirParser<TopLevelStatement> {
+    let p = ignore_then_commit(
+        keyword(Keyword::CompTime).then(keyword(Keyword::Global)),
+        ident().map(Pattern::Identifier),
+    );
+    let p = then_commit(p, optional_type_annotation());
+    let p = then_commit_ignore(p, just(Token::Assign));
+    let p = then_commit(
+        p,
+        expression().map_with_span(|expr, span| {
+            let statement = Statement { kind: StatementKind::Expression(expr), span };
+            Expression::new(ExpressionKind::Comptime(BlockExpression(vec![statement])), span)
+        }),
+    );
+    p.map(LetStatement::new_let).map(TopLevelStatement::Global)
+}
+
 /// submodule: 'mod' ident '{' module '}'
 fn submodule(module_parser: impl NoirParser<ParsedModule>) -> impl NoirParser<TopLevelStatement> {
     keyword(Keyword::Mod)
@@ -183,6 +210,7 @@ fn function_definition(allow_self: bool) -> impl NoirParser<NoirFunction> {
                 name,
                 attributes,
                 is_unconstrained: modifiers.0,
+                is_comptime: modifiers.5,
                 is_open: modifiers.2,
                 is_internal: modifiers.3,
                 visibility: if modifiers.1 {
@@ -204,23 +232,25 @@ fn function_definition(allow_self: bool) -> impl NoirParser<NoirFunction> {
         })
 }
 
-/// function_modifiers: 'unconstrained'? 'pub(crate)'? 'pub'? 'open'? 'internal'?
+/// function_modifiers: 'unconstrained'? 'comptime'? 'pub(crate)'? 'pub'? 'open'? 'internal'?
 ///
-/// returns (is_unconstrained, is_pub_crate, is_open, is_internal, is_pub) for whether each keyword was present
-fn function_modifiers() -> impl NoirParser<(bool, bool, bool, bool, bool)> {
+/// returns (is_unconstrained, is_pub_crate, is_open, is_internal, is_pub, is_comptime) for whether each keyword was present
+fn function_modifiers() -> impl NoirParser<(bool, bool, bool, bool, bool, bool)> {
     keyword(Keyword::Unconstrained)
         .or_not()
+        .then(keyword(Keyword::CompTime).or_not())
         .then(is_pub_crate())
         .then(keyword(Keyword::Pub).or_not())
         .then(keyword(Keyword::Open).or_not())
         .then(keyword(Keyword::Internal).or_not())
-        .map(|((((unconstrained, pub_crate), public), open), internal)| {
+        .map(|(((((unconstrained, comptime), pub_crate), public), open), internal)| {
             (
                 unconstrained.is_some(),
                 pub_crate,
                 open.is_some(),
                 internal.is_some(),
                 public.is_some(),
+                comptime.is_some(),
             )
         })
 }
@@ -274,6 +304,44 @@ fn struct_definition() -> impl NoirParser<TopLevelStatement> {
         })
 }
 
+/// enum_definition: attributes? 'enum' ident generics '{' enum_variants '}'
+fn enum_definition() -> impl NoirParser<TopLevelStatement> {
+    use self::Keyword::Enum;
+    use Token::*;
+
+    let variants = enum_variants().delimited_by(just(LeftBrace), just(RightBrace)).recover_with(
+        nested_delimiters(
+            LeftBrace,
+            RightBrace,
+            [(LeftParen, RightParen), (LeftBracket, RightBracket)],
+            |_| vec![],
+        ),
+    );
+
+    attributes()
+        .or_not()
+        .then_ignore(keyword(Enum))
+        .then(ident())
+        .then(generics())
+        .then(variants)
+        .validate(|(((raw_attributes, name), generics), variants), span, emit| {
+            let attributes = validate_struct_attributes(raw_attributes, span, emit);
+            TopLevelStatement::Enum(NoirEnum { name, attributes, generics, variants, span })
+        })
+}
+
+/// enum_variants: (ident ('(' type (',' type)* ')')?),*
+fn enum_variants() -> impl NoirParser<Vec<(Ident, Vec<UnresolvedType>)>> {
+    let fields = parse_type()
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+        .delimited_by(just(Token::LeftParen), just(Token::RightParen))
+        .or_not()
+        .map(Option::unwrap_or_default);
+
+    ident().then(fields).separated_by(just(Token::Comma)).allow_trailing()
+}
+
 fn type_alias_definition() -> impl NoirParser<TopLevelStatement> {
     use self::Keyword::Type;
 
@@ -431,7 +499,8 @@ fn optional_default_value() -> impl NoirParser<Option<Expression>> {
 }
 
 fn trait_constant_declaration() -> impl NoirParser<TraitItem> {
-    keyword(Keyword::Let)
+    keyword(Keyword::Const)
+        .or(keyword(Keyword::Let))
         .ignore_then(ident())
         .then_ignore(just(Token::Colon))
         .then(parse_type())
@@ -557,11 +626,18 @@ fn implementation() -> impl NoirParser<TopLevelStatement> {
     keyword(Keyword::Impl)
         .ignore_then(generics())
         .then(parse_type().map_with_span(|typ, span| (typ, span)))
+        .then(where_clause())
         .then_ignore(just(Token::LeftBrace))
         .then(function_definition(true).repeated())
         .then_ignore(just(Token::RightBrace))
-        .map(|((generics, (object_type, type_span)), methods)| {
-            TopLevelStatement::Impl(TypeImpl { generics, object_type, type_span, methods })
+        .map(|(((generics, (object_type, type_span)), where_clause), methods)| {
+            TopLevelStatement::Impl(TypeImpl {
+                generics,
+                object_type,
+                type_span,
+                where_clause,
+                methods,
+            })
         })
 }
 
@@ -607,7 +683,17 @@ fn trait_implementation_body() -> impl NoirParser<Vec<TraitImplItem>> {
         .then_ignore(just(Token::Semicolon))
         .map(|(name, alias)| TraitImplItem::Type { name, alias });
 
-    function.or(alias).repeated()
+    let constant = keyword(Keyword::Const)
+        .or(keyword(Keyword::Let))
+        .ignore_then(ident())
+        .then_ignore(just(Token::Colon))
+        .then(parse_type())
+        .then_ignore(just(Token::Assign))
+        .then(expression())
+        .then_ignore(just(Token::Semicolon))
+        .map(|((name, typ), value)| TraitImplItem::Constant(name, typ, value));
+
+    function.or(alias).or(constant).repeated()
 }
 
 fn where_clause() -> impl NoirParser<Vec<UnresolvedTraitConstraint>> {
@@ -702,8 +788,17 @@ fn module_declaration() -> impl NoirParser<TopLevelStatement> {
     keyword(Keyword::Mod).ignore_then(ident()).map(TopLevelStatement::Module)
 }
 
+/// use_statement: 'pub'? 'use' use_tree
 fn use_statement() -> impl NoirParser<TopLevelStatement> {
-    keyword(Keyword::Use).ignore_then(use_tree()).map(TopLevelStatement::Import)
+    let visibility = keyword(Keyword::Pub).or_not().map(|pub_keyword| match pub_keyword {
+        Some(_) => ItemVisibility::Public,
+        None => ItemVisibility::Private,
+    });
+
+    visibility
+        .then_ignore(keyword(Keyword::Use))
+        .then(use_tree())
+        .map(|(visibility, tree)| TopLevelStatement::Import(tree, visibility))
 }
 
 fn keyword(keyword: Keyword) -> impl NoirParser<Token> {
@@ -756,8 +851,14 @@ fn use_tree() -> impl NoirParser<UseTree> {
             UseTree { prefix, kind: UseTreeKind::Path(ident, alias) }
         });
 
+        let prefix = path().or(empty_path()).then_ignore(just(Token::DoubleColon));
+
+        let glob = prefix
+            .clone()
+            .then_ignore(just(Token::Star))
+            .map(|prefix| UseTree { prefix, kind: UseTreeKind::Glob });
+
         let list = {
-            let prefix = path().or(empty_path()).then_ignore(just(Token::DoubleColon));
             let tree = use_tree
                 .separated_by(just(Token::Comma))
                 .allow_trailing()
@@ -767,7 +868,7 @@ fn use_tree() -> impl NoirParser<UseTree> {
             prefix.then(tree).map(|(prefix, kind)| UseTree { prefix, kind })
         };
 
-        choice((list, simple))
+        choice((glob, list, simple))
     })
 }
 
@@ -790,7 +891,11 @@ where
             assertion_eq(expr_parser.clone()),
             declaration(expr_parser.clone()),
             assignment(expr_parser.clone()),
-            for_loop(expr_no_constructors, statement),
+            for_loop(expr_no_constructors.clone(), statement.clone()),
+            while_loop(expr_no_constructors, statement.clone()),
+            loop_statement(statement),
+            break_statement(),
+            continue_statement(),
             return_statement(expr_parser.clone()),
             expr_parser.map(StatementKind::Expression),
         ))
@@ -1014,6 +1119,7 @@ fn parse_type_inner(
         format_string_type(recursive_type_parser.clone()),
         named_type(recursive_type_parser.clone()),
         named_trait(recursive_type_parser.clone()),
+        as_trait_path_type(recursive_type_parser.clone()),
         array_type(recursive_type_parser.clone()),
         parenthesized_type(recursive_type_parser.clone()),
         tuple_type(recursive_type_parser.clone()),
@@ -1128,6 +1234,21 @@ fn named_trait(type_parser: impl NoirParser<UnresolvedType>) -> impl NoirParser<
     )
 }
 
+/// Parses an associated type accessed through a trait: `<T as Trait>::Output`
+fn as_trait_path_type(
+    type_parser: impl NoirParser<UnresolvedType>,
+) -> impl NoirParser<UnresolvedType> {
+    type_parser
+        .then_ignore(keyword(Keyword::As))
+        .then(path())
+        .delimited_by(just(Token::Less), just(Token::Greater))
+        .then_ignore(just(Token::DoubleColon))
+        .then(ident())
+        .map_with_span(|((typ, trait_path), name), span| {
+            UnresolvedTypeData::AsTraitPath(Box::new(typ), trait_path, name).with_span(span)
+        })
+}
+
 fn generic_type_args(
     type_parser: impl NoirParser<UnresolvedType>,
 ) -> impl NoirParser<Vec<UnresolvedType>> {
@@ -1253,6 +1374,14 @@ where
     .labelled(ParsingRuleLabel::Expression)
 }
 
+fn break_statement() -> impl NoirParser<StatementKind> {
+    keyword(Keyword::Break).to(StatementKind::Break)
+}
+
+fn continue_statement() -> impl NoirParser<StatementKind> {
+    keyword(Keyword::Continue).to(StatementKind::Continue)
+}
+
 fn return_statement<'a, P>(expr_parser: P) -> impl NoirParser<StatementKind> + 'a
 where
     P: ExprParser + 'a,
@@ -1468,6 +1597,63 @@ where
     })
 }
 
+/// match_expr: 'match' expression_no_constructors '{' (match_pattern '=>' expression ','?)* '}'
+///
+/// The comma after each arm is optional so that arms with block bodies may be written
+/// without one, as in Rust.
+fn match_expr<'a, P, P2>(
+    expr_parser: P,
+    expr_no_constructors: P2,
+) -> impl NoirParser<ExpressionKind> + 'a
+where
+    P: ExprParser + 'a,
+    P2: ExprParser + 'a,
+{
+    let rule = match_pattern()
+        .then_ignore(just(Token::FatArrow))
+        .then(expr_parser)
+        .then_ignore(just(Token::Comma).or_not());
+
+    keyword(Keyword::Match)
+        .ignore_then(expr_no_constructors)
+        .then(rule.repeated().delimited_by(just(Token::LeftBrace), just(Token::RightBrace)))
+        .map(|(expression, rules)| {
+            ExpressionKind::Match(Box::new(MatchExpression { expression, rules }))
+        })
+}
+
+/// comptime_expr: 'comptime' block
+fn comptime_expr<'a, S>(statement: S) -> impl NoirParser<ExpressionKind> + 'a
+where
+    S: NoirParser<StatementKind> + 'a,
+{
+    keyword(Keyword::CompTime).ignore_then(block(statement)).map(ExpressionKind::Comptime)
+}
+
+/// match_pattern: path '(' pattern (',' pattern)* ')'
+///              | path
+///              | pattern
+///
+/// A path with a single segment and no argument list is parsed as an ordinary
+/// binding pattern rather than as an enum variant.
+fn match_pattern() -> impl NoirParser<MatchPattern> {
+    let fields = pattern()
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+        .delimited_by(just(Token::LeftParen), just(Token::RightParen));
+
+    let variant = path().then(fields.or_not()).try_map(|(path, fields), span| {
+        if path.segments.len() == 1 && fields.is_none() {
+            let found = Token::Ident(path.segments[0].0.contents.clone());
+            Err(ParserError::expected_label(ParsingRuleLabel::Pattern, found, span))
+        } else {
+            Ok(MatchPattern::Variant(path, fields.unwrap_or_default(), span))
+        }
+    });
+
+    variant.or(pattern().map(MatchPattern::Binding))
+}
+
 fn lambda<'a>(
     expr_parser: impl NoirParser<Expression> + 'a,
 ) -> impl NoirParser<ExpressionKind> + 'a {
@@ -1495,6 +1681,29 @@ where
         })
 }
 
+fn while_loop<'a, P, S>(
+    expr_no_constructors: P,
+    statement: S,
+) -> impl NoirParser<StatementKind> + 'a
+where
+    P: ExprParser + 'a,
+    S: NoirParser<StatementKind> + 'a,
+{
+    keyword(Keyword::While)
+        .ignore_then(expr_no_constructors)
+        .then(block_expr(statement))
+        .map_with_span(|(condition, block), span| {
+            StatementKind::While(WhileStatement { condition, block, span })
+        })
+}
+
+fn loop_statement<'a, S>(statement: S) -> impl NoirParser<StatementKind> + 'a
+where
+    S: NoirParser<StatementKind> + 'a,
+{
+    keyword(Keyword::Loop).ignore_then(block_expr(statement)).map(StatementKind::Loop)
+}
+
 /// The 'range' of a for loop. Either an actual range `start .. end` or an array expression.
 fn for_range<P>(expr_no_constructors: P) -> impl NoirParser<ForRange>
 where
@@ -1594,7 +1803,8 @@ where
     S: NoirParser<StatementKind> + 'a,
 {
     choice((
-        if_expr(expr_no_constructors, statement.clone()),
+        if_expr(expr_no_constructors.clone(), statement.clone()),
+        match_expr(expr_parser.clone(), expr_no_constructors),
         array_expr(expr_parser.clone()),
         if allow_constructors {
             constructor(expr_parser.clone()).boxed()
@@ -1602,6 +1812,7 @@ where
             nothing().boxed()
         },
         lambda(expr_parser.clone()),
+        comptime_expr(statement.clone()),
         block(statement).map(ExpressionKind::Block),
         variable(),
         literal(),
@@ -2126,11 +2337,29 @@ mod test {
         parse_all_failing(fresh_statement(), vec!["pub x = y", "pub x : pub Field = y"]);
     }
 
+    #[test]
+    fn parse_while_and_loop() {
+        let cases = vec![
+            "while x < 10 { x += 1; }",
+            "while foo.bar() { }",
+            "loop { if done { break; } }",
+            "loop { continue; }",
+        ];
+        parse_all(fresh_statement(), cases);
+
+        let failing = vec!["while { }", "while x < 10", "loop x"];
+        parse_all_failing(fresh_statement(), failing);
+    }
+
     #[test]
     fn parse_for_loop() {
         parse_all(
             for_loop(expression_no_constructors(expression()), fresh_statement()),
-            vec!["for i in x+y..z {}", "for i in 0..100 { foo; bar }"],
+            vec![
+                "for i in x+y..z {}",
+                "for i in 0..100 { foo; bar }",
+                "for i in 0..100 { if i == 3 { break; } else { continue; } }",
+            ],
         );
 
         parse_all_failing(
@@ -2209,6 +2438,8 @@ mod test {
                 "trait TraitWithAssociatedType { type Element; fn item(self, index: Field) -> Self::Element; }",
                 "trait TraitWithAssociatedConstant { let Size: Field; }",
                 "trait TraitWithAssociatedConstantWithDefaultValue { let Size: Field = 10; }",
+                "trait TraitWithConstKeyword { const RATE: u32; const SIZE: u32 = 4; }",
+                "trait TraitUsingAssociatedItems { type Output; fn hash(self) -> <Self as TraitUsingAssociatedItems>::Output; }",
                 "trait GenericTrait<T> { fn elem(&mut self, index: Field) -> T; }",
                 "trait GenericTraitWithConstraints<T> where T: SomeTrait { fn elem(self, index: Field) -> T; }",
                 "trait TraitWithMultipleGenericParams<A, B, C> where A: SomeTrait, B: AnotherTrait<C> { let Size: Field; fn zero() -> Self; }",
@@ -2221,6 +2452,50 @@ mod test {
         );
     }
 
+    #[test]
+    fn parse_impl() {
+        parse_all(
+            implementation(),
+            vec![
+                "impl Foo {}",
+                "impl<T> Foo<T> { fn foo(self) -> T { self.x } }",
+                "impl<T> Foo<T> where T: SomeTrait { fn foo(self) -> Field { self.x.bar() } }",
+                "impl<A, B> Foo<A, B> where A: SomeTrait, B: AnotherTrait<A> + Eq {}",
+            ],
+        );
+
+        parse_all_failing(implementation(), vec!["impl Foo where T {}", "impl<T> Foo<T> where {"]);
+    }
+
+    #[test]
+    fn parse_trait_impl_with_associated_items() {
+        parse_all(
+            trait_implementation(),
+            vec![
+                "impl Hasher for Poseidon { type Output = Field; }",
+                "impl Hasher for Poseidon { const RATE: u32 = 3; fn hash(self) -> Field { 0 } }",
+                "impl<T> Hasher for Foo<T> { type Output = [T; 2]; let RATE: u32 = 2; }",
+            ],
+        );
+
+        parse_all_failing(
+            trait_implementation(),
+            vec![
+                "impl Hasher for Poseidon { type Output; }",
+                "impl Hasher for Poseidon { const RATE: u32; }",
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_as_trait_path_type() {
+        parse_all(
+            parse_type(),
+            vec!["<T as Hasher>::Output", "[<T as Hasher>::Output; 3]", "<Self as std::Foo>::Bar"],
+        );
+        parse_all_failing(parse_type(), vec!["<T as Hasher>", "<T>::Output"]);
+    }
+
     #[test]
     fn parse_parenthesized_expression() {
         parse_all(
@@ -2364,6 +2639,11 @@ mod test {
                 "use foo::{bar as bar2, hello}",
                 "use foo::{bar as bar2, hello::{foo}, nested::{foo, bar}}",
                 "use dep::{std::println, bar::baz}",
+                "use foo::*",
+                "use dep::*",
+                "use foo::{bar::*, baz}",
+                "pub use foo::bar",
+                "pub use foo::{bar as bar2, baz::*}",
             ],
         );
 
@@ -2376,6 +2656,9 @@ mod test {
                 "use foo bar::baz",
                 "use foo bar::{baz}",
                 "use foo::{,}",
+                "use *",
+                "use foo::* as bar",
+                "pub(crate) use foo::bar",
             ],
         );
     }
@@ -2400,6 +2683,52 @@ mod test {
         parse_all_failing(struct_definition(), failing);
     }
 
+    #[test]
+    fn parse_enums() {
+        let cases = vec![
+            "enum Foo { }",
+            "enum Foo { A, B }",
+            "enum Foo { A(Field), B(u8, [Field; 2]), C, }",
+            "enum Option<T> { Some(T), None }",
+            "#[attribute] enum Foo { A }",
+        ];
+        parse_all(enum_definition(), cases);
+
+        let failing = vec!["enum {  }", "enum Foo { A: Field }", "#[oracle(some)] enum Foo { A }"];
+        parse_all_failing(enum_definition(), failing);
+    }
+
+    #[test]
+    fn parse_match() {
+        let cases = vec![
+            "match x { }",
+            "match x { y => y }",
+            "match x { Foo::A => 1, Foo::B(a, b) => a + b, _ => 0 }",
+            "match x { Foo::A(mut a, (b, c)) => { a } Foo::B => { 2 } }",
+            "match foo.bar() { Option::Some(x) => x, Option::None => 0, }",
+        ];
+        parse_all(expression(), cases);
+
+        let failing = vec!["match { }", "match x { Foo::A }", "match x { Foo::A => }"];
+        parse_all_failing(expression(), failing);
+    }
+
+    #[test]
+    fn parse_comptime() {
+        let cases = vec!["comptime { }", "comptime { let x = 1; x + 2 }", "1 + comptime { 2 }"];
+        parse_all(expression(), cases);
+        parse_all_failing(expression(), vec!["comptime", "comptime 1"]);
+
+        let cases = vec![
+            "comptime global FOO = 1;",
+            "comptime global FOO: [u8; 2] = foo();",
+            "comptime fn foo() -> u32 { 1 }",
+            "unconstrained comptime fn foo() {}",
+        ];
+        parse_all(module(), cases);
+        parse_all_failing(module(), vec!["comptime global FOO;", "comptime 1;"]);
+    }
+
     #[test]
     fn parse_type_aliases() {
         let cases = vec!["type foo = u8", "type bar = String", "type baz<T> = Vec<T>"];
@@ -11,6 +11,7 @@ mod test {
 
     use noirc_errors::Location;
 
+    use crate::hir::comptime::{InterpreterError, InterpreterLimits};
     use crate::hir::def_collector::dc_crate::CompilationError;
     use crate::hir::def_collector::errors::{DefCollectorErrorKind, DuplicateType};
     use crate::hir::def_map::ModuleData;
@@ -25,8 +26,10 @@ mod test {
     use crate::hir::def_collector::dc_crate::DefCollector;
     use crate::hir_def::expr::HirExpression;
     use crate::hir_def::stmt::HirStatement;
+    use crate::monomorphization::errors::MonomorphizationError;
     use crate::monomorphization::monomorphize;
     use crate::parser::ParserErrorReason;
+    use crate::BinaryTypeOperator;
     use crate::ParsedModule;
     use crate::{
         hir::def_map::{CrateDefMap, LocalModuleId},
@@ -57,12 +60,20 @@ mod test {
 
     pub(crate) fn get_program(
         src: &str,
+    ) -> (ParsedModule, Context, Vec<(CompilationError, FileId)>) {
+        get_program_with_comptime_limits(src, InterpreterLimits::default())
+    }
+
+    pub(crate) fn get_program_with_comptime_limits(
+        src: &str,
+        comptime_limits: InterpreterLimits,
     ) -> (ParsedModule, Context, Vec<(CompilationError, FileId)>) {
         let root = std::path::Path::new("/");
         let fm = FileManager::new(root, Box::new(|path| std::fs::read_to_string(path)));
         //let fm = FileManager::new(root,  Box::new(get_non_stdlib_asset));
         let graph = CrateGraph::default();
         let mut context = Context::new(fm, graph);
+        context.comptime_limits = comptime_limits;
         let root_file_id = FileId::dummy();
         let root_crate_id = context.crate_graph.add_crate_root(root_file_id);
         let (program, parser_errors) = parse_program(src);
@@ -717,6 +728,92 @@ mod test {
         }
     }
 
+    #[test]
+    fn check_trait_constraints_at_call_site() {
+        let src = "
+        trait Hash {
+            fn hash(self) -> Field;
+        }
+
+        struct Foo {
+            x: Field,
+        }
+
+        struct Bar {
+            x: Field,
+        }
+
+        struct Wrapper<T> {
+            inner: T,
+        }
+
+        impl Hash for Foo {
+            fn hash(self) -> Field {
+                self.x
+            }
+        }
+
+        impl<T> Wrapper<T> where T: Hash {
+            fn hash_inner(self) -> Field {
+                self.inner.hash()
+            }
+        }
+
+        fn hash_all<T>(xs: [T; 2]) -> Field where T: Hash {
+            xs[0].hash() + Hash::hash(xs[1])
+        }
+
+        fn main() {
+            let foo = Foo { x: 1 };
+            let bar = Bar { x: 2 };
+            let _ = hash_all([foo, foo]) + Hash::hash(foo) + Wrapper { inner: foo }.hash_inner();
+            let _ = hash_all([bar, bar]);
+            let _ = Hash::hash(bar);
+            let _ = Wrapper { inner: bar }.hash_inner();
+        }
+        ";
+        let errors = get_program_errors(src);
+        assert!(!has_parser_error(&errors));
+        assert!(errors.len() == 3, "Expected 3 errors, got: {:?}", errors);
+        for (err, _file_id) in errors {
+            match &err {
+                CompilationError::TypeError(TypeCheckError::NoMatchingImplFound {
+                    constraints,
+                    ..
+                }) => {
+                    assert_eq!(constraints.len(), 1);
+                    assert_eq!(constraints[0].0.to_string(), "Bar");
+                    assert_eq!(constraints[0].1, "Hash");
+                }
+                _ => {
+                    panic!("No other errors are expected! Found = {:?}", err);
+                }
+            };
+        }
+    }
+
+    #[test]
+    fn unsupported_derive() {
+        let src = "
+        #[derive(Hash)]
+        struct Foo {
+            x: Field,
+        }
+
+        fn main() {}
+        ";
+        let errors = get_program_errors(src);
+        assert!(!has_parser_error(&errors));
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        match &errors[0].0 {
+            CompilationError::DefinitionError(DefCollectorErrorKind::UnsupportedDerive {
+                trait_name,
+                ..
+            }) => assert_eq!(trait_name, "Hash"),
+            err => panic!("No other errors are expected! Found = {:?}", err),
+        }
+    }
+
     fn get_program_captures(src: &str) -> Vec<Vec<String>> {
         let (program, context, _errors) = get_program(src);
         let interner = context.def_interner;
@@ -748,6 +845,9 @@ mod test {
                 HirStatement::Constrain(constr_stmt) => constr_stmt.0,
                 HirStatement::Semi(semi_expr) => semi_expr,
                 HirStatement::For(for_loop) => for_loop.block,
+                HirStatement::While(while_loop) => while_loop.block,
+                HirStatement::Loop(block) => block,
+                HirStatement::Break | HirStatement::Continue => continue,
                 HirStatement::Error => panic!("Invalid HirStatement!"),
             };
             let expr = interner.expression(&expr_id);
@@ -1096,10 +1196,406 @@ mod test {
         }
     }
 
+    #[test]
+    fn resolve_enums_and_match() {
+        let src = r#"
+            enum Shape {
+                Circle(Field),
+                Rectangle(Field, Field),
+                Empty,
+            }
+
+            impl Shape {
+                fn area(self) -> Field {
+                    match self {
+                        Shape::Circle(radius) => 3 * radius * radius,
+                        Shape::Rectangle(width, height) => width * height,
+                        Shape::Empty => 0,
+                    }
+                }
+
+                fn empty() -> Self {
+                    Self::Empty
+                }
+            }
+
+            fn main(x: Field) {
+                let shapes = [Shape::Circle(x), Shape::Rectangle(x, 2), Shape::empty()];
+                for i in 0..3 {
+                    assert(shapes[i].area() != 1);
+                }
+            }
+        "#;
+
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn check_non_exhaustive_match() {
+        let src = r#"
+            enum Light {
+                Red,
+                Yellow,
+                Green,
+            }
+
+            fn main() {
+                let light = Light::Red;
+                let _ = match light {
+                    Light::Yellow => 1,
+                };
+            }
+        "#;
+
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+
+        match &errors[0].0 {
+            CompilationError::TypeError(TypeCheckError::NonExhaustiveMatch {
+                missing_patterns,
+                ..
+            }) => {
+                assert_eq!(missing_patterns, &["Light::Red", "Light::Green"]);
+            }
+            _ => panic!("Expected a non-exhaustive match error, got: {:?}", errors[0].0),
+        }
+    }
+
+    #[test]
+    fn check_unreachable_match_arms() {
+        let src = r#"
+            enum Light {
+                Red,
+                Green,
+            }
+
+            fn main() {
+                let light = Light::Red;
+                let _ = match light {
+                    Light::Red => 1,
+                    Light::Red => 2,
+                    _ => 3,
+                    Light::Green => 4,
+                };
+            }
+        "#;
+
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
+
+        for (error, _file_id) in errors {
+            assert!(
+                matches!(
+                    error,
+                    CompilationError::TypeError(TypeCheckError::UnreachableMatchArm { .. })
+                ),
+                "Expected an unreachable match arm warning, got: {:?}",
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn check_enum_variant_argument_count() {
+        let src = r#"
+            enum Message {
+                Move(Field, Field),
+                Quit,
+            }
+
+            fn main() {
+                let _ = Message::Move(1);
+                let _ = match Message::Quit {
+                    Message::Move(x) => x,
+                    Message::Quit => 0,
+                };
+            }
+        "#;
+
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
+
+        for (error, _file_id) in errors {
+            assert!(
+                matches!(
+                    error,
+                    CompilationError::TypeError(TypeCheckError::ArityMisMatch {
+                        expected: 2,
+                        found: 1,
+                        ..
+                    })
+                ),
+                "Expected an arity mismatch error, got: {:?}",
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn check_recursive_enums() {
+        let src = r#"
+            enum List {
+                Cons(Field, Wrapper),
+                Nil,
+            }
+
+            struct Wrapper {
+                inner: [List; 1],
+            }
+
+            enum Tree<T> {
+                Node(T, (Tree<T>, Tree<T>)),
+                Leaf,
+            }
+
+            enum NotRecursive {
+                Value(Option<Field>),
+            }
+
+            enum Option<T> {
+                Some(T),
+                None,
+            }
+
+            fn main() {}
+        "#;
+
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
+
+        let mut names: Vec<_> = errors
+            .into_iter()
+            .map(|(error, _file_id)| match error {
+                CompilationError::ResolverError(ResolverError::RecursiveEnum { name }) => {
+                    name.0.contents
+                }
+                _ => panic!("Expected a recursive enum error, got: {:?}", error),
+            })
+            .collect();
+        names.sort();
+        assert_eq!(names, ["List", "Tree"]);
+    }
+
+    #[test]
+    fn break_and_continue_in_constrained_fn() {
+        let src = r#"
+            fn main() {
+                for i in 0 .. 10 {
+                    if i == 2 {
+                        continue;
+                    }
+                    if i == 5 {
+                        break;
+                    }
+                }
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 2, "Expected 2 errors, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::ResolverError(ResolverError::JumpInConstrainedFn {
+                is_break: false,
+                ..
+            })
+        ));
+        assert!(matches!(
+            errors[1].0,
+            CompilationError::ResolverError(ResolverError::JumpInConstrainedFn {
+                is_break: true,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn break_and_continue_outside_loop() {
+        let src = r#"
+            unconstrained fn main() {
+                continue;
+                for _ in 0 .. 10 {
+                    let f = || { break; };
+                    f();
+                }
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 2, "Expected 2 errors, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::ResolverError(ResolverError::JumpOutsideLoop { is_break: false, .. })
+        ));
+        assert!(matches!(
+            errors[1].0,
+            CompilationError::ResolverError(ResolverError::JumpOutsideLoop { is_break: true, .. })
+        ));
+    }
+
+    #[test]
+    fn while_and_loop_in_constrained_fn() {
+        let src = r#"
+            fn main(x: u32) {
+                let mut i = 0;
+                while i < x {
+                    i += 1;
+                }
+                loop {}
+            }
+
+            unconstrained fn allowed(x: u32) {
+                let mut i = 0;
+                while i < x {
+                    i += 1;
+                }
+                loop {
+                    break;
+                }
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 2, "Expected 2 errors, got: {:?}", errors);
+        for (error, _) in errors {
+            assert!(matches!(
+                error,
+                CompilationError::ResolverError(ResolverError::LoopInConstrainedFn { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn while_condition_must_be_bool() {
+        let src = r#"
+            unconstrained fn main(x: u32) {
+                while x {}
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::TypeError(TypeCheckError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn comptime_code_cannot_reference_runtime_variables() {
+        let src = r#"
+            fn main(x: Field) -> pub Field {
+                comptime { x + 1 }
+            }
+        "#;
+
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        match &errors[0].0 {
+            CompilationError::InterpreterError(InterpreterError::NonComptimeVarReferenced {
+                name,
+                ..
+            }) => assert_eq!(name, "x"),
+            error => panic!("Expected a non-comptime variable error, got: {:?}", error),
+        }
+    }
+
+    #[test]
+    fn comptime_errors_are_reported() {
+        let src = r#"
+            comptime global LENGTH: u32 = 3;
+
+            comptime fn checked_length() -> u32 {
+                assert(LENGTH > 3, "too short");
+                LENGTH
+            }
+
+            fn main() {
+                let _ = checked_length();
+                let _ = comptime { let x: u8 = 255; x + 1 };
+                let _ = checked_length;
+            }
+        "#;
+
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 3, "Expected 3 errors, got: {:?}", errors);
+        assert!(matches!(
+            &errors[0].0,
+            CompilationError::InterpreterError(InterpreterError::FailingConstraint {
+                message: Some(message),
+                ..
+            }) if message == "too short"
+        ));
+        assert!(matches!(
+            &errors[1].0,
+            CompilationError::InterpreterError(InterpreterError::IntegerOutOfRange { .. })
+        ));
+        assert!(matches!(
+            &errors[2].0,
+            CompilationError::InterpreterError(
+                InterpreterError::ComptimeFunctionUsedAsValue { .. }
+            )
+        ));
+    }
+
+    #[test]
+    fn comptime_evaluation_is_limited() {
+        let src = r#"
+            comptime fn forever(x: Field) -> Field {
+                forever(x + 1)
+            }
+
+            fn main() {
+                let _ = forever(0);
+                let _ = comptime {
+                    let mut sum: u32 = 0;
+                    for i in 0..1000 {
+                        sum = sum + i;
+                    }
+                    sum
+                };
+            }
+        "#;
+
+        let limits = InterpreterLimits { max_call_depth: 16, max_steps: 1000 };
+        let (_program, _context, errors) = get_program_with_comptime_limits(src, limits);
+        let errors = remove_experimental_feature_warnings(errors);
+        assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
+        assert!(matches!(
+            &errors[0].0,
+            CompilationError::InterpreterError(InterpreterError::CallDepthExceeded {
+                limit: 16,
+                ..
+            })
+        ));
+        assert!(matches!(
+            &errors[1].0,
+            CompilationError::InterpreterError(InterpreterError::StepLimitExceeded {
+                limit: 1000,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn lambdas_take_the_runtime_of_their_enclosing_function() {
+        let src = r#"
+            unconstrained fn main(x: Field) -> pub Field {
+                let add_one = |y: Field| y + 1;
+                let add_x = |y: Field| y + x;
+                add_one(x) + add_x(x)
+            }
+        "#;
+
+        let (_program, context, errors) = get_program(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+        let main_func_id = context.def_interner.find_function("main").unwrap();
+        let program = monomorphize(main_func_id, &context.def_interner).unwrap();
+        assert_eq!(program.functions.len(), 3);
+        assert!(program.functions.iter().all(|function| function.unconstrained));
+    }
+
     fn check_rewrite(src: &str, expected: &str) {
         let (_program, context, _errors) = get_program(src);
         let main_func_id = context.def_interner.find_function("main").unwrap();
-        let program = monomorphize(main_func_id, &context.def_interner);
+        let program = monomorphize(main_func_id, &context.def_interner).unwrap();
         assert!(format!("{}", program) == expected);
     }
 
@@ -1133,4 +1629,405 @@ fn lambda$f1(mut env$l1: (Field)) -> Field {
 "#;
         check_rewrite(src, expected_rewrite);
     }
+
+    #[test]
+    fn comptime_blocks_are_replaced_with_their_value() {
+        let src = r#"
+        comptime fn square(x: u32) -> u32 {
+            x * x
+        }
+
+        fn main() -> pub u32 {
+            comptime { square(3) + 1 }
+        }
+        "#;
+
+        let expected_rewrite = r#"fn main$f0() -> u32 {
+    10
+}
+"#;
+        check_rewrite(src, expected_rewrite);
+    }
+
+    #[test]
+    fn glob_import_and_pub_use() {
+        let src = r#"
+            mod shapes {
+                mod circle {
+                    pub fn area(r: Field) -> Field {
+                        3 * r * r
+                    }
+                }
+
+                struct Square { side: Field }
+            }
+
+            mod prelude {
+                pub use crate::shapes::circle::area;
+                pub use crate::shapes::Square;
+            }
+
+            use prelude::*;
+            use crate::prelude::area as circle_area;
+
+            fn main(x: Field) {
+                let square = Square { side: x };
+                assert(area(square.side) == circle_area(x));
+                assert(prelude::area(x) == area(x));
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn plain_import_is_visible_outside_its_module() {
+        let src = r#"
+            mod foo {
+                use crate::bar::baz;
+
+                pub fn call_baz() {
+                    baz();
+                }
+            }
+
+            mod bar {
+                pub fn baz() {}
+            }
+
+            fn main() {
+                foo::call_baz();
+                // Imports without `pub` are re-exported as well, as they always have been
+                foo::baz();
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn glob_imports_of_the_same_name_are_ambiguous() {
+        let src = r#"
+            mod a {
+                pub fn foo() {}
+                pub fn only_in_a() {}
+            }
+
+            mod b {
+                pub fn foo() {}
+            }
+
+            mod c {
+                use crate::a::*;
+                use crate::b::*;
+                use crate::b::foo;
+
+                pub fn call_foo() {
+                    foo();
+                    only_in_a();
+                }
+            }
+
+            use a::*;
+            use b::*;
+
+            fn main() {
+                c::call_foo();
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            &errors[0].0,
+            CompilationError::DefinitionError(DefCollectorErrorKind::AmbiguousGlobImport { .. })
+        ));
+    }
+
+    #[test]
+    fn glob_import_of_a_non_module() {
+        let src = r#"
+            mod foo {
+                struct Bar {}
+            }
+
+            use foo::Bar::*;
+
+            fn main() {}
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            &errors[0].0,
+            CompilationError::DefinitionError(DefCollectorErrorKind::PathResolutionError(
+                PathResolutionError::GlobImportNotAModule(_)
+            ))
+        ));
+    }
+
+    const HASHER_TRAIT: &str = r#"
+            trait Hasher {
+                type Output;
+                const RATE: u32;
+
+                fn hash(self) -> Self::Output;
+            }
+
+            struct Poseidon {}
+    "#;
+
+    #[test]
+    fn trait_associated_items_in_generic_code() {
+        let src = format!(
+            "{HASHER_TRAIT}{}",
+            r#"
+            impl Hasher for Poseidon {
+                type Output = Field;
+                const RATE: u32 = 3;
+
+                fn hash(self) -> Field { 0 }
+            }
+
+            fn absorb<T>(hasher: T, inputs: [Field; T::RATE]) -> <T as Hasher>::Output where T: Hasher {
+                let _ = inputs[T::RATE - 1];
+                hasher.hash()
+            }
+
+            fn main() {
+                let _: Field = absorb(Poseidon {}, [1, 2, 3]);
+            }
+        "#
+        );
+        let errors = get_program_errors(&src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn trait_associated_constant_mismatch_at_call_site() {
+        let src = format!(
+            "{HASHER_TRAIT}{}",
+            r#"
+            impl Hasher for Poseidon {
+                type Output = Field;
+                const RATE: u32 = 3;
+
+                fn hash(self) -> Field { 0 }
+            }
+
+            fn absorb<T>(_hasher: T, _inputs: [Field; T::RATE]) where T: Hasher {}
+
+            fn main() {
+                absorb(Poseidon {}, [1, 2]);
+            }
+        "#
+        );
+        let errors = get_program_errors(&src);
+        assert_eq!(errors.len(), 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            &errors[0].0,
+            CompilationError::TypeError(TypeCheckError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn trait_impl_missing_associated_item() {
+        let src = format!(
+            "{HASHER_TRAIT}{}",
+            r#"
+            impl Hasher for Poseidon {
+                type Output = Field;
+
+                fn hash(self) -> Field { 0 }
+            }
+
+            fn main() {}
+        "#
+        );
+        let errors = get_program_errors(&src);
+        assert_eq!(errors.len(), 1, "Expected 1 error, got: {:?}", errors);
+        match &errors[0].0 {
+            CompilationError::DefinitionError(
+                DefCollectorErrorKind::TraitMissingAssociatedItem { item_name, .. },
+            ) => assert_eq!(item_name, "RATE"),
+            error => panic!("Expected a missing associated item error, got: {:?}", error),
+        }
+    }
+
+    #[test]
+    fn trait_impl_associated_item_not_in_trait() {
+        let src = format!(
+            "{HASHER_TRAIT}{}",
+            r#"
+            impl Hasher for Poseidon {
+                type Output = Field;
+                type Digest = Field;
+                const RATE: u32 = 3;
+
+                fn hash(self) -> Field { 0 }
+            }
+
+            fn main() {}
+        "#
+        );
+        let errors = get_program_errors(&src);
+        assert_eq!(errors.len(), 1, "Expected 1 error, got: {:?}", errors);
+        match &errors[0].0 {
+            CompilationError::DefinitionError(
+                DefCollectorErrorKind::AssociatedItemNotInTrait { impl_item, .. },
+            ) => assert_eq!(impl_item, "Digest"),
+            error => panic!("Expected an associated item not in trait error, got: {:?}", error),
+        }
+    }
+
+    #[test]
+    fn arithmetic_on_numeric_generics_in_array_lengths() {
+        let src = r#"
+            fn concat<T, N, M>(a: [T; N], b: [T; M]) -> [T; N + M] {
+                let mut result = [a[0]; N + M];
+                for i in 0..N {
+                    result[i] = a[i];
+                }
+                for i in 0..M {
+                    result[N + i] = b[i];
+                }
+                result
+            }
+
+            fn double<N>(a: [Field; N]) -> [Field; N + N] {
+                concat(a, a)
+            }
+
+            fn main() {
+                let c: [Field; 5] = concat([1, 2], [3, 4, 5]);
+                assert(c[4] == 5);
+                let d: [Field; 4] = double([1, 2]);
+                assert(d[3] == 2);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn array_length_arithmetic_not_proven_equal() {
+        let src = r#"
+            fn swap<N, M>(a: [Field; N + M]) -> [Field; M + N] {
+                a
+            }
+
+            fn concat<N, M>(_a: [Field; N], _b: [Field; M]) -> [Field; N + M] {
+                [0; N + M]
+            }
+
+            fn main() {
+                let _: [Field; 4] = concat([1, 2], [3, 4, 5]);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 2, "Expected 2 errors, got: {:?}", errors);
+        for (error, _) in errors {
+            assert!(
+                matches!(error, CompilationError::TypeError(TypeCheckError::Context { .. })),
+                "Expected a type error explaining the array lengths, got: {:?}",
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn division_on_numeric_generics_in_array_lengths() {
+        let src = r#"
+            fn half<N>(_a: [Field; N / 2]) {}
+
+            fn main() {}
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            &errors[0].0,
+            CompilationError::ResolverError(ResolverError::InvalidArrayLengthExpr { .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_constant_arithmetic_in_array_lengths() {
+        let src = r#"
+            fn main() {
+                let _: [Field; 2 - 3] = [];
+                let _: [Field; 4 / 0] = [];
+                let _: [Field; 18446744073709551615 * 2] = [];
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 3, "Expected 3 errors, got: {:?}", errors);
+
+        let expected = [
+            (2, BinaryTypeOperator::Subtraction, 3),
+            (4, BinaryTypeOperator::Division, 0),
+            (u64::MAX, BinaryTypeOperator::Multiplication, 2),
+        ];
+        for ((error, _), expected) in errors.into_iter().zip(expected) {
+            match error {
+                CompilationError::ResolverError(ResolverError::InvalidArrayLengthArithmetic {
+                    lhs,
+                    op,
+                    rhs,
+                    ..
+                }) => assert_eq!((lhs, op, rhs), expected),
+                error => {
+                    panic!("Expected an invalid array length arithmetic error, got: {:?}", error)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_generic_arithmetic_in_array_lengths() {
+        let src = r#"
+            fn pop<N>(a: [Field; N]) -> [Field; N - 1] {
+                let mut result = [0; N - 1];
+                for i in 0..N - 1 {
+                    result[i] = a[i];
+                }
+                result
+            }
+
+            fn main() {
+                let _ = pop([1, 2]);
+                let _ = pop([]);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 1, "Expected 1 error, got: {:?}", errors);
+        match &errors[0].0 {
+            CompilationError::TypeError(TypeCheckError::ResolverError(
+                ResolverError::InvalidArrayLengthArithmetic { lhs: 0, op, rhs: 1, .. },
+            )) => assert_eq!(*op, BinaryTypeOperator::Subtraction),
+            error => panic!("Expected an invalid array length arithmetic error, got: {:?}", error),
+        }
+    }
+
+    #[test]
+    fn invalid_generic_arithmetic_in_function_bodies() {
+        let src = r#"
+            fn zeroes_for_all_but_last<N>(_a: [Field; N]) {
+                let _zeroes = [0; N - 1];
+            }
+
+            fn main() {
+                let empty: [Field; 0] = [];
+                zeroes_for_all_but_last(empty);
+            }
+        "#;
+        let (_program, context, errors) = get_program(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+        let main_func_id = context.def_interner.find_function("main").unwrap();
+        match monomorphize(main_func_id, &context.def_interner) {
+            Err(MonomorphizationError::InvalidArrayLengthArithmetic {
+                lhs: 0, op, rhs: 1, ..
+            }) => assert_eq!(op, BinaryTypeOperator::Subtraction),
+            result => {
+                panic!("Expected an invalid array length arithmetic error, got: {:?}", result)
+            }
+        }
+    }
 }
@@ -29,6 +29,10 @@ pub enum PrintableType {
     String {
         length: u64,
     },
+    Enum {
+        name: String,
+        variants: Vec<(String, Vec<PrintableType>)>,
+    },
 }
 
 impl PrintableType {
@@ -44,6 +48,9 @@ impl PrintableType {
                 fields.iter().fold(0, |acc, (_, field_type)| acc + field_type.field_count())
             }
             Self::String { length } => *length as u32,
+            Self::Enum { variants, .. } => variants.iter().fold(1, |acc, (_, fields)| {
+                acc + fields.iter().map(PrintableType::field_count).sum::<u32>()
+            }),
         }
     }
 }
@@ -230,6 +237,32 @@ fn to_string(value: &PrintableValue, typ: &PrintableType) -> Option<String> {
             output.push_str(" }");
         }
 
+        (PrintableValue::Struct(map), PrintableType::Enum { name, variants }) => {
+            // An enum value is decoded into a map with a single entry for the active variant
+            let (variant_name, value) = map.iter().next()?;
+            let (_, field_types) = variants.iter().find(|(name, _)| name == variant_name)?;
+            output.push_str(&format!("{name}::{variant_name}"));
+
+            if !field_types.is_empty() {
+                let field_values = match value {
+                    PrintableValue::Vec(field_values) => field_values,
+                    _ => return None,
+                };
+                output.push('(');
+                let mut fields = field_values.iter().zip(field_types).peekable();
+                while let Some((value, field_type)) = fields.next() {
+                    output.push_str(&format!(
+                        "{}",
+                        PrintableValueDisplay::Plain(value.clone(), field_type.clone())
+                    ));
+                    if fields.peek().is_some() {
+                        output.push_str(", ");
+                    }
+                }
+                output.push(')');
+            }
+        }
+
         _ => return None,
     };
 
@@ -330,6 +363,20 @@ fn decode_value(
 
             PrintableValue::Struct(struct_map)
         }
+        PrintableType::Enum { variants, .. } => {
+            let tag = field_iterator.next().unwrap().to_u128() as usize;
+
+            // Every variant's fields are encoded, but only those of the active variant are kept
+            let mut enum_map = BTreeMap::new();
+            for (index, (variant_name, field_types)) in variants.iter().enumerate() {
+                let field_values = vecmap(field_types, |typ| decode_value(field_iterator, typ));
+                if index == tag {
+                    enum_map.insert(variant_name.to_owned(), PrintableValue::Vec(field_values));
+                }
+            }
+
+            PrintableValue::Struct(enum_map)
+        }
     }
 }
 
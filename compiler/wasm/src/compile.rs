@@ -174,7 +174,7 @@ pub fn compile(
             })?
             .0;
 
-        let optimized_contract =
+        let (optimized_contract, _) =
             nargo::ops::optimize_contract(compiled_contract, np_language, &is_opcode_supported)
                 .expect("Contract optimization failed");
 
@@ -191,7 +191,7 @@ pub fn compile(
             })?
             .0;
 
-        let optimized_program =
+        let (optimized_program, _) =
             nargo::ops::optimize_program(compiled_program, np_language, &is_opcode_supported)
                 .expect("Program optimization failed");
 
@@ -244,7 +244,7 @@ fn preprocess_program(program: CompiledProgram) -> CompileResult {
         backend: String::from(BACKEND_IDENTIFIER),
         abi: program.abi,
         noir_version: NOIR_ARTIFACT_VERSION_STRING.to_string(),
-        bytecode: program.circuit,
+        bytecode: program.program,
     };
 
     CompileResult::Program { program: preprocessed_program, debug: debug_artifact }
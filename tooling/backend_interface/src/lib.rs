@@ -148,6 +148,7 @@ impl BackendOpcodeSupport {
             Opcode::Brillig(_) => self.opcodes.contains("brillig"),
             Opcode::MemoryInit { .. } => self.opcodes.contains("memory_init"),
             Opcode::MemoryOp { .. } => self.opcodes.contains("memory_op"),
+            Opcode::Call { .. } => self.opcodes.contains("call"),
             Opcode::BlackBoxFuncCall(func) => {
                 self.black_box_functions.contains(func.get_black_box_func().name())
             }
@@ -157,6 +157,9 @@ impl<'a, B: BlackBoxFunctionSolver> DebugContext<'a, B> {
             ACVMStatus::RequiresForeignCall(_) => {
                 unreachable!("Unexpected pending foreign call resolution");
             }
+            ACVMStatus::RequiresAcirCall(call_info) => DebugCommandResult::Error(
+                NargoError::ExecutionError(ExecutionError::UnknownAcirFunction(call_info.id)),
+            ),
         }
     }
 
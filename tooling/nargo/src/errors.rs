@@ -67,11 +67,13 @@ impl NargoError {
 
         match execution_error {
             ExecutionError::AssertionFailed(message, _) => Some(message),
+            ExecutionError::UnknownAcirFunction(_) => None,
             ExecutionError::SolvingError(error) => match error {
                 OpcodeResolutionError::IndexOutOfBounds { .. }
                 | OpcodeResolutionError::UnsupportedBlackBoxFunc(_)
                 | OpcodeResolutionError::OpcodeNotSolvable(_)
-                | OpcodeResolutionError::UnsatisfiedConstrain { .. } => None,
+                | OpcodeResolutionError::UnsatisfiedConstrain { .. }
+                | OpcodeResolutionError::AcirCallOutputsMismatch { .. } => None,
                 OpcodeResolutionError::BrilligFunctionFailed { message, .. } => Some(message),
                 OpcodeResolutionError::BlackBoxFunctionFailed(_, reason) => Some(reason),
             },
@@ -84,6 +86,9 @@ pub enum ExecutionError {
     #[error("Failed assertion: '{}'", .0)]
     AssertionFailed(String, Vec<OpcodeLocation>),
 
+    #[error("Program does not contain a function with id {0}")]
+    UnknownAcirFunction(u32),
+
     #[error(transparent)]
     SolvingError(#[from] OpcodeResolutionError),
 }
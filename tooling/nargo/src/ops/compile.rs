@@ -77,7 +77,7 @@ pub fn compile_program(
     let mut debug_artifact_path = program_artifact_path.clone();
     debug_artifact_path.set_file_name(format!("debug_{}.json", package.name));
 
-    let (program, warnings) =
+    let (program, mut warnings) =
         match noirc_driver::compile_main(&mut context, crate_id, compile_options, None, true) {
             Ok(program_and_warnings) => program_and_warnings,
             Err(errors) => {
@@ -98,9 +98,10 @@ pub fn compile_program(
     };
 
     // Apply backend specific optimizations.
-    let optimized_program =
+    let (optimized_program, optimization_warnings) =
         crate::ops::optimize_program(program, np_language, &is_opcode_supported_pedersen_hash)
             .expect("Backend does not support an opcode that is in the IR");
+    warnings.extend(optimization_warnings);
 
     (context.file_manager, Ok((optimized_program, warnings)))
 }
@@ -113,7 +114,7 @@ fn compile_contract(
 ) -> (FileManager, CompilationResult<CompiledContract>) {
     let (mut context, crate_id) =
         prepare_package(package, Box::new(|path| std::fs::read_to_string(path)));
-    let (contract, warnings) =
+    let (contract, mut warnings) =
         match noirc_driver::compile_contract(&mut context, crate_id, compile_options) {
             Ok(contracts_and_warnings) => contracts_and_warnings,
             Err(errors) => {
@@ -121,9 +122,10 @@ fn compile_contract(
             }
         };
 
-    let optimized_contract =
+    let (optimized_contract, optimization_warnings) =
         crate::ops::optimize_contract(contract, np_language, &is_opcode_supported)
             .expect("Backend does not support an opcode that is in the IR");
+    warnings.extend(optimization_warnings);
 
     (context.file_manager, Ok((optimized_contract, warnings)))
 }
@@ -1,6 +1,7 @@
-use acvm::pwg::{ACVMStatus, ErrorLocation, OpcodeResolutionError, ACVM};
+use acvm::acir::circuit::{Circuit, OpcodeLocation, Program};
+use acvm::pwg::{ACVMStatus, ErrorLocation, OpcodeNotSolvable, OpcodeResolutionError, ACVM};
 use acvm::BlackBoxFunctionSolver;
-use acvm::{acir::circuit::Circuit, acir::native_types::WitnessMap};
+use acvm::{acir::native_types::WitnessMap, FieldElement};
 
 use crate::errors::ExecutionError;
 use crate::NargoError;
@@ -12,6 +13,34 @@ pub fn execute_circuit<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
     initial_witness: WitnessMap,
     blackbox_solver: &B,
     foreign_call_executor: &mut F,
+) -> Result<WitnessMap, NargoError> {
+    execute_function(circuit, &[], initial_witness, blackbox_solver, foreign_call_executor)
+}
+
+/// Executes the `main` circuit of `program`, executing any circuits which it calls into along the way.
+///
+/// Returns the solved witness map of `main`.
+pub fn execute_program<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
+    program: &Program,
+    initial_witness: WitnessMap,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut F,
+) -> Result<WitnessMap, NargoError> {
+    execute_function(
+        program.main(),
+        &program.functions,
+        initial_witness,
+        blackbox_solver,
+        foreign_call_executor,
+    )
+}
+
+fn execute_function<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
+    circuit: &Circuit,
+    functions: &[Circuit],
+    initial_witness: WitnessMap,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut F,
 ) -> Result<WitnessMap, NargoError> {
     let mut acvm = ACVM::new(blackbox_solver, &circuit.opcodes, initial_witness);
 
@@ -51,9 +80,79 @@ pub fn execute_circuit<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
                 let foreign_call_result = foreign_call_executor.execute(&foreign_call)?;
                 acvm.resolve_pending_foreign_call(foreign_call_result);
             }
+            ACVMStatus::RequiresAcirCall(call_info) => {
+                let callee = functions
+                    .get(call_info.id as usize)
+                    .ok_or(ExecutionError::UnknownAcirFunction(call_info.id))?;
+
+                let callee_witness = execute_function(
+                    callee,
+                    functions,
+                    call_info.initial_witness(callee),
+                    blackbox_solver,
+                    foreign_call_executor,
+                )
+                .map_err(|error| {
+                    // Debug information only exists for the caller, so any failure within the
+                    // callee is reported at the location of the call opcode.
+                    relocate_error(error, OpcodeLocation::Acir(acvm.instruction_pointer()))
+                })?;
+
+                let call_results: Vec<FieldElement> = callee
+                    .return_values
+                    .0
+                    .iter()
+                    .map(|return_witness| {
+                        callee_witness.get(return_witness).copied().ok_or(
+                            OpcodeResolutionError::OpcodeNotSolvable(
+                                OpcodeNotSolvable::MissingAssignment(return_witness.0),
+                            ),
+                        )
+                    })
+                    .collect::<Result<_, _>>()
+                    .map_err(ExecutionError::SolvingError)?;
+                acvm.resolve_pending_acir_call(call_results);
+            }
         }
     }
 
     let solved_witness = acvm.finalize();
     Ok(solved_witness)
 }
+
+/// Replaces the opcode locations of an error raised while executing a nested ACIR call
+/// with the location of the call within its caller.
+fn relocate_error(error: NargoError, call_location: OpcodeLocation) -> NargoError {
+    let execution_error = match error {
+        NargoError::ExecutionError(execution_error) => execution_error,
+        _ => return error,
+    };
+
+    NargoError::ExecutionError(match execution_error {
+        ExecutionError::AssertionFailed(message, _) => {
+            ExecutionError::AssertionFailed(message, vec![call_location])
+        }
+        ExecutionError::SolvingError(OpcodeResolutionError::UnsatisfiedConstrain { .. }) => {
+            ExecutionError::SolvingError(OpcodeResolutionError::UnsatisfiedConstrain {
+                opcode_location: ErrorLocation::Resolved(call_location),
+            })
+        }
+        ExecutionError::SolvingError(OpcodeResolutionError::IndexOutOfBounds {
+            index,
+            array_size,
+            ..
+        }) => ExecutionError::SolvingError(OpcodeResolutionError::IndexOutOfBounds {
+            opcode_location: ErrorLocation::Resolved(call_location),
+            index,
+            array_size,
+        }),
+        ExecutionError::SolvingError(OpcodeResolutionError::BrilligFunctionFailed {
+            message,
+            ..
+        }) => ExecutionError::SolvingError(OpcodeResolutionError::BrilligFunctionFailed {
+            message,
+            call_stack: vec![call_location],
+        }),
+        execution_error => execution_error,
+    })
+}
@@ -1,5 +1,5 @@
 pub use self::compile::{compile_program, compile_workspace};
-pub use self::execute::execute_circuit;
+pub use self::execute::{execute_circuit, execute_program};
 pub use self::foreign_calls::{DefaultForeignCallExecutor, ForeignCallExecutor};
 pub use self::optimize::{optimize_contract, optimize_program};
 pub use self::test::{run_test, TestStatus};
@@ -1,34 +1,73 @@
-use acvm::{acir::circuit::Opcode, Language};
+use acvm::{acir::circuit::Opcode, compiler::CompileWarning, Language};
 use iter_extended::try_vecmap;
 use noirc_driver::{CompiledContract, CompiledProgram};
+use noirc_errors::{debug_info::DebugInfo, CustomDiagnostic, FileDiagnostic};
 
 use crate::NargoError;
 
+/// Applies backend specific optimizations to each circuit of the program, returning the
+/// optimized program along with warnings about any issues found in its circuits.
 pub fn optimize_program(
     mut program: CompiledProgram,
     np_language: Language,
     is_opcode_supported: &impl Fn(&Opcode) -> bool,
-) -> Result<CompiledProgram, NargoError> {
-    let (optimized_circuit, location_map) =
-        acvm::compiler::compile(program.circuit, np_language, is_opcode_supported)?;
-
-    program.circuit = optimized_circuit;
-    program.debug.update_acir(location_map);
-    Ok(program)
+) -> Result<(CompiledProgram, Vec<FileDiagnostic>), NargoError> {
+    let mut warnings = Vec::new();
+    let functions = std::mem::take(&mut program.program.functions);
+    for (index, circuit) in functions.into_iter().enumerate() {
+        let (optimized_circuit, location_map, circuit_warnings) =
+            acvm::compiler::compile(circuit, np_language, is_opcode_supported)?;
+        // Debug information is only tracked for `main`.
+        if index == 0 {
+            program.debug.update_acir(location_map);
+            warnings.extend(locate_warnings(circuit_warnings, &program.debug));
+        }
+        program.program.functions.push(optimized_circuit);
+    }
+    Ok((program, warnings))
 }
 
+/// Applies backend specific optimizations to each function of the contract, returning the
+/// optimized contract along with warnings about any issues found in its functions.
 pub fn optimize_contract(
     contract: CompiledContract,
     np_language: Language,
     is_opcode_supported: &impl Fn(&Opcode) -> bool,
-) -> Result<CompiledContract, NargoError> {
+) -> Result<(CompiledContract, Vec<FileDiagnostic>), NargoError> {
+    let mut warnings = Vec::new();
     let functions = try_vecmap(contract.functions, |mut func| {
-        let (optimized_bytecode, location_map) =
+        let (optimized_bytecode, location_map, function_warnings) =
             acvm::compiler::compile(func.bytecode, np_language, is_opcode_supported)?;
         func.bytecode = optimized_bytecode;
         func.debug.update_acir(location_map);
+        warnings.extend(locate_warnings(function_warnings, &func.debug));
         Ok::<_, NargoError>(func)
     })?;
 
-    Ok(CompiledContract { functions, ..contract })
+    Ok((CompiledContract { functions, ..contract }, warnings))
+}
+
+/// Points each warning found while compiling a circuit at the source code which the opcode it
+/// refers to was generated from. Warnings about opcodes without a known location are dropped.
+fn locate_warnings(warnings: Vec<CompileWarning>, debug: &DebugInfo) -> Vec<FileDiagnostic> {
+    warnings
+        .into_iter()
+        .filter_map(|warning| {
+            let (location, primary_message, secondary_message) = match warning {
+                CompileWarning::UnusedBrilligOutputs(location) => (
+                    location,
+                    "Results of an unconstrained function call are never used",
+                    "this call does not affect the circuit and only slows down witness generation",
+                ),
+            };
+            let call_stack = debug.opcode_location(&location)?;
+            let source_location = *call_stack.last()?;
+            let diagnostic = CustomDiagnostic::simple_warning(
+                primary_message.to_string(),
+                secondary_message.to_string(),
+                source_location.span,
+            );
+            Some(diagnostic.in_file(source_location.file).with_call_stack(call_stack))
+        })
+        .collect()
 }
@@ -2,13 +2,15 @@ use crate::backends::Backend;
 use crate::errors::CliError;
 
 use clap::Args;
-use iter_extended::btree_map;
+use iter_extended::{btree_map, vecmap};
 use nargo::{errors::CompileError, package::Package, prepare_package};
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_abi::{AbiParameter, AbiType, MAIN_RETURN_NAME};
 use noirc_driver::{
-    check_crate, compute_function_abi, CompileOptions, NOIR_ARTIFACT_VERSION_STRING,
+    check_crate, compile_no_check, compute_function_abi, CompileOptions,
+    NOIR_ARTIFACT_VERSION_STRING,
 };
+use noirc_errors::FileDiagnostic;
 use noirc_frontend::{
     graph::{CrateId, CrateName},
     hir::Context,
@@ -28,6 +30,11 @@ pub(crate) struct CheckCommand {
     #[clap(long, conflicts_with = "package")]
     workspace: bool,
 
+    /// Also build the constraint system of each binary package to find values returned from
+    /// unconstrained functions which are never properly constrained
+    #[clap(long)]
+    check_underconstrained: bool,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
@@ -48,13 +55,17 @@ pub(crate) fn run(
     )?;
 
     for package in &workspace {
-        check_package(package, &args.compile_options)?;
+        check_package(package, &args.compile_options, args.check_underconstrained)?;
         println!("[{}] Constraint system successfully built!", package.name);
     }
     Ok(())
 }
 
-fn check_package(package: &Package, compile_options: &CompileOptions) -> Result<(), CompileError> {
+fn check_package(
+    package: &Package,
+    compile_options: &CompileOptions,
+    check_underconstrained: bool,
+) -> Result<(), CompileError> {
     let (mut context, crate_id) =
         prepare_package(package, Box::new(|path| std::fs::read_to_string(path)));
     check_crate_and_report_errors(
@@ -64,6 +75,11 @@ fn check_package(package: &Package, compile_options: &CompileOptions) -> Result<
         compile_options.silence_warnings,
     )?;
 
+    // This requires compiling the whole program, so is only done when asked for
+    if check_underconstrained && package.is_binary() {
+        check_for_underconstrained_values(&context, &crate_id, compile_options)?;
+    }
+
     if package.is_library() || package.is_contract() {
         // Libraries do not have ABIs while contracts have many, so we cannot generate a `Prover.toml` file.
         Ok(())
@@ -93,6 +109,29 @@ fn check_package(package: &Package, compile_options: &CompileOptions) -> Result<
     }
 }
 
+/// Builds the constraint system of the package's `main` function and reports any warnings about
+/// values returned from unconstrained functions which are not properly constrained.
+fn check_for_underconstrained_values(
+    context: &Context,
+    crate_id: &CrateId,
+    compile_options: &CompileOptions,
+) -> Result<(), CompileError> {
+    let Some(main) = context.get_main_function(crate_id) else {
+        // A missing `main` function is reported when computing the ABI.
+        return Ok(());
+    };
+
+    let result = compile_no_check(context, compile_options, main, None, false)
+        .map(|program| ((), vecmap(program.warnings, FileDiagnostic::from)))
+        .map_err(|error| vec![FileDiagnostic::from(error)]);
+    super::compile_cmd::report_errors(
+        result,
+        &context.file_manager,
+        compile_options.deny_warnings,
+        compile_options.silence_warnings,
+    )
+}
+
 /// Generates the contents of a toml file with fields for each of the passed parameters.
 fn create_input_toml_template(
     parameters: Vec<AbiParameter>,
@@ -79,7 +79,11 @@ fn smart_contract_for_package(
     let program =
         compile_bin_package(workspace, package, compile_options, np_language, opcode_support)?;
 
-    let mut smart_contract_string = backend.eth_contract(&program.circuit)?;
+    if program.program.functions.len() > 1 {
+        return Err(CliError::UnsupportedFoldFunctions("Generating verifiers for"));
+    }
+
+    let mut smart_contract_string = backend.eth_contract(program.program.main())?;
 
     if backend.name() == ACVM_BACKEND_BARRETENBERG {
         smart_contract_string =
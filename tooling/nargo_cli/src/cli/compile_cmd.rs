@@ -187,7 +187,7 @@ fn compile_program(
     ) {
         Some(CompiledProgram {
             hash: preprocessed_program.hash,
-            circuit: preprocessed_program.bytecode,
+            program: preprocessed_program.bytecode,
             abi: preprocessed_program.abi,
             noir_version: preprocessed_program.noir_version,
             debug: debug_artifact.debug_symbols.remove(0),
@@ -200,7 +200,7 @@ fn compile_program(
 
     let force_recompile =
         cached_program.as_ref().map_or(false, |p| p.noir_version != NOIR_ARTIFACT_VERSION_STRING);
-    let (program, warnings) = match noirc_driver::compile_main(
+    let (program, mut warnings) = match noirc_driver::compile_main(
         &mut context,
         crate_id,
         compile_options,
@@ -223,9 +223,10 @@ fn compile_program(
     };
 
     // Apply backend specific optimizations.
-    let optimized_program =
+    let (optimized_program, optimization_warnings) =
         nargo::ops::optimize_program(program, np_language, &is_opcode_supported_pedersen_hash)
             .expect("Backend does not support an opcode that is in the IR");
+    warnings.extend(optimization_warnings);
 
     save_program(optimized_program.clone(), package, &workspace.target_directory_path());
 
@@ -240,7 +241,7 @@ fn compile_contract(
 ) -> (FileManager, CompilationResult<CompiledContract>) {
     let (mut context, crate_id) =
         prepare_package(package, Box::new(|path| std::fs::read_to_string(path)));
-    let (contract, warnings) =
+    let (contract, mut warnings) =
         match noirc_driver::compile_contract(&mut context, crate_id, compile_options) {
             Ok(contracts_and_warnings) => contracts_and_warnings,
             Err(errors) => {
@@ -248,9 +249,10 @@ fn compile_contract(
             }
         };
 
-    let optimized_contract =
+    let (optimized_contract, optimization_warnings) =
         nargo::ops::optimize_contract(contract, np_language, &is_opcode_supported)
             .expect("Backend does not support an opcode that is in the IR");
+    warnings.extend(optimization_warnings);
 
     (context.file_manager, Ok((optimized_contract, warnings)))
 }
@@ -261,7 +263,7 @@ fn save_program(program: CompiledProgram, package: &Package, circuit_dir: &Path)
         backend: String::from(BACKEND_IDENTIFIER),
         abi: program.abi,
         noir_version: program.noir_version,
-        bytecode: program.circuit,
+        bytecode: program.program,
     };
 
     save_program_to_file(&preprocessed_program, &package.name, circuit_dir);
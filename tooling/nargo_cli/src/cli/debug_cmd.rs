@@ -131,6 +131,10 @@ pub(crate) fn debug_program(
     #[allow(deprecated)]
     let blackbox_solver = barretenberg_blackbox_solver::BarretenbergSolver::new();
 
+    if compiled_program.program.functions.len() > 1 {
+        return Err(CliError::UnsupportedFoldFunctions("Debugging"));
+    }
+
     let initial_witness = compiled_program.abi.encode(inputs_map, None)?;
 
     let debug_artifact = DebugArtifact {
@@ -141,7 +145,7 @@ pub(crate) fn debug_program(
 
     noir_debugger::debug_circuit(
         &blackbox_solver,
-        &compiled_program.circuit,
+        compiled_program.program.main(),
         debug_artifact,
         initial_witness,
     )
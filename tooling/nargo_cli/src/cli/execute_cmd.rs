@@ -106,8 +106,8 @@ pub(crate) fn execute_program(
 
     let initial_witness = compiled_program.abi.encode(inputs_map, None)?;
 
-    let solved_witness_err = nargo::ops::execute_circuit(
-        &compiled_program.circuit,
+    let solved_witness_err = nargo::ops::execute_program(
+        &compiled_program.program,
         initial_witness,
         &blackbox_solver,
         &mut DefaultForeignCallExecutor::new(true),
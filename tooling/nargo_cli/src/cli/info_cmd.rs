@@ -257,8 +257,19 @@ fn count_opcodes_and_gates_in_program(
     Ok(ProgramInfo {
         name: package.name.to_string(),
         language,
-        acir_opcodes: compiled_program.circuit.opcodes.len(),
-        circuit_size: backend.get_exact_circuit_size(&compiled_program.circuit)?,
+        acir_opcodes: compiled_program
+            .program
+            .functions
+            .iter()
+            .map(|circuit| circuit.opcodes.len())
+            .sum(),
+        // Every circuit of the program has to be proven, not only `main`
+        circuit_size: compiled_program
+            .program
+            .functions
+            .iter()
+            .map(|circuit| backend.get_exact_circuit_size(circuit))
+            .sum::<Result<_, _>>()?,
     })
 }
 
@@ -90,6 +90,11 @@ pub(crate) fn prove_package(
     verifier_name: &str,
     check_proof: bool,
 ) -> Result<(), CliError> {
+    // A proof of `main` alone says nothing about the circuits it calls.
+    if compiled_program.program.functions.len() > 1 {
+        return Err(CliError::UnsupportedFoldFunctions("Proving"));
+    }
+
     // Parse the initial witness values from Prover.toml
     let (inputs_map, _) =
         read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &compiled_program.abi)?;
@@ -109,12 +114,12 @@ pub(crate) fn prove_package(
         Format::Toml,
     )?;
 
-    let proof = backend.prove(&compiled_program.circuit, solved_witness, false)?;
+    let proof = backend.prove(compiled_program.program.main(), solved_witness, false)?;
 
     if check_proof {
         let public_inputs = public_abi.encode(&public_inputs, return_value)?;
         let valid_proof =
-            backend.verify(&proof, public_inputs, &compiled_program.circuit, false)?;
+            backend.verify(&proof, public_inputs, compiled_program.program.main(), false)?;
 
         if !valid_proof {
             return Err(CliError::InvalidProof("".into()));
@@ -71,6 +71,10 @@ fn verify_package(
     compiled_program: CompiledProgram,
     verifier_name: &str,
 ) -> Result<(), CliError> {
+    if compiled_program.program.functions.len() > 1 {
+        return Err(CliError::UnsupportedFoldFunctions("Verifying"));
+    }
+
     // Load public inputs (if any) from `verifier_name`.
     let public_abi = compiled_program.abi.public_abi();
     let (public_inputs_map, return_value) =
@@ -83,7 +87,8 @@ fn verify_package(
 
     let proof = load_hex_data(&proof_path)?;
 
-    let valid_proof = backend.verify(&proof, public_inputs, &compiled_program.circuit, false)?;
+    let valid_proof =
+        backend.verify(&proof, public_inputs, compiled_program.program.main(), false)?;
 
     if valid_proof {
         Ok(())
@@ -42,6 +42,10 @@ pub(crate) enum CliError {
     #[error("Invalid package name {0}. Did you mean to use `--name`?")]
     InvalidPackageName(String),
 
+    /// The backend can only handle programs made of a single circuit
+    #[error("{0} programs with `#[fold]` functions is not yet supported")]
+    UnsupportedFoldFunctions(&'static str),
+
     /// ABI encoding/decoding error
     #[error(transparent)]
     AbiError(#[from] AbiError),
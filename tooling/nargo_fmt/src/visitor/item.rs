@@ -191,8 +191,9 @@ impl super::FmtVisitor<'_> {
                     self.close_block((self.last_position..span.end() - 1).into());
                     self.last_position = span.end();
                 }
-                ItemKind::Import(_)
+                ItemKind::Import(..)
                 | ItemKind::Struct(_)
+                | ItemKind::Enum(_)
                 | ItemKind::Trait(_)
                 | ItemKind::TraitImpl(_)
                 | ItemKind::Impl(_)
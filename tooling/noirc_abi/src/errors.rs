@@ -1,5 +1,5 @@
 use crate::{input_parser::InputValue, AbiParameter, AbiType};
-use acvm::acir::native_types::Witness;
+use acvm::{acir::native_types::Witness, FieldElement};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -52,4 +52,6 @@ pub enum AbiError {
     ReturnTypeMismatch { return_type: AbiType, value: InputValue },
     #[error("No return value is expected but received {0:?}")]
     UnexpectedReturnValue(InputValue),
+    #[error("Enum {path} has {num_variants} variants but found a value with tag {tag}")]
+    InvalidEnumTag { path: String, tag: FieldElement, num_variants: usize },
 }
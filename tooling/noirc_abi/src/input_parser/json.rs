@@ -114,6 +114,19 @@ impl JsonTypes {
                 JsonTypes::Array(fields)
             }
 
+            (InputValue::Struct(map), AbiType::Enum { variants, .. }) => {
+                let map_with_json_types = try_btree_map(map, |(variant_name, value)| {
+                    let (_, fields) = variants
+                        .iter()
+                        .find(|(name, _)| name == variant_name)
+                        .ok_or_else(|| InputParserError::AbiTypeMismatch(abi_type.clone()))?;
+                    let fields = AbiType::Tuple { fields: fields.clone() };
+                    JsonTypes::try_from_input_value(value, &fields)
+                        .map(|json_value| (variant_name.to_owned(), json_value))
+                })?;
+                JsonTypes::Table(map_with_json_types)
+            }
+
             _ => return Err(InputParserError::AbiTypeMismatch(abi_type.clone())),
         };
         Ok(json_value)
@@ -186,6 +199,22 @@ impl InputValue {
                 InputValue::Vec(tuple_fields)
             }
 
+            // An enum is given as a table with a single entry from the variant name to its fields
+            (JsonTypes::Table(table), AbiType::Enum { variants, .. }) => {
+                let native_table = try_btree_map(table, |(variant_name, value)| {
+                    let (_, fields) = variants
+                        .iter()
+                        .find(|(name, _)| *name == variant_name)
+                        .ok_or_else(|| InputParserError::AbiTypeMismatch(param_type.clone()))?;
+                    let fields = AbiType::Tuple { fields: fields.clone() };
+                    let variant_id = format!("{arg_name}.{variant_name}");
+                    InputValue::try_from_json(value, &fields, &variant_id)
+                        .map(|input_value| (variant_name, input_value))
+                })?;
+
+                InputValue::Struct(native_table)
+            }
+
             (_, _) => return Err(InputParserError::AbiTypeMismatch(param_type.clone())),
         };
 
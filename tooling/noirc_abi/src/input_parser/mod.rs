@@ -75,6 +75,25 @@ impl InputValue {
                     .all(|(input_value, abi_param)| input_value.matches_abi(abi_param))
             }
 
+            (InputValue::Struct(map), AbiType::Enum { variants, .. }) => {
+                // An enum value is a single variant name mapped to the values of its fields
+                if map.len() != 1 {
+                    return false;
+                }
+
+                let (variant_name, variant_value) = map.iter().next().unwrap();
+                match (variants.iter().find(|(name, _)| name == variant_name), variant_value) {
+                    (Some((_, fields)), InputValue::Vec(values)) => {
+                        values.len() == fields.len()
+                            && values
+                                .iter()
+                                .zip(fields)
+                                .all(|(input_value, abi_param)| input_value.matches_abi(abi_param))
+                    }
+                    _ => false,
+                }
+            }
+
             // All other InputValue-AbiType combinations are fundamentally incompatible.
             _ => false,
         }
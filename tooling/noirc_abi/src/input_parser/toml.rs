@@ -112,6 +112,19 @@ impl TomlTypes {
                 TomlTypes::Array(fields)
             }
 
+            (InputValue::Struct(map), AbiType::Enum { variants, .. }) => {
+                let map_with_toml_types = try_btree_map(map, |(variant_name, value)| {
+                    let (_, fields) = variants
+                        .iter()
+                        .find(|(name, _)| name == variant_name)
+                        .ok_or_else(|| InputParserError::AbiTypeMismatch(abi_type.clone()))?;
+                    let fields = AbiType::Tuple { fields: fields.clone() };
+                    TomlTypes::try_from_input_value(value, &fields)
+                        .map(|toml_value| (variant_name.to_owned(), toml_value))
+                })?;
+                TomlTypes::Table(map_with_toml_types)
+            }
+
             _ => return Err(InputParserError::AbiTypeMismatch(abi_type.clone())),
         };
         Ok(toml_value)
@@ -173,6 +186,22 @@ impl InputValue {
                 InputValue::Vec(tuple_fields)
             }
 
+            // An enum is given as a table with a single entry from the variant name to its fields
+            (TomlTypes::Table(table), AbiType::Enum { variants, .. }) => {
+                let native_table = try_btree_map(table, |(variant_name, value)| {
+                    let (_, fields) = variants
+                        .iter()
+                        .find(|(name, _)| *name == variant_name)
+                        .ok_or_else(|| InputParserError::AbiTypeMismatch(param_type.clone()))?;
+                    let fields = AbiType::Tuple { fields: fields.clone() };
+                    let variant_id = format!("{arg_name}.{variant_name}");
+                    InputValue::try_from_toml(value, &fields, &variant_id)
+                        .map(|input_value| (variant_name, input_value))
+                })?;
+
+                InputValue::Struct(native_table)
+            }
+
             (_, _) => return Err(InputParserError::AbiTypeMismatch(param_type.clone())),
         };
 
@@ -68,6 +68,14 @@ pub enum AbiType {
     String {
         length: u64,
     },
+    Enum {
+        path: String,
+        #[serde(
+            serialize_with = "serialization::serialize_enum_variants",
+            deserialize_with = "serialization::deserialize_enum_variants"
+        )]
+        variants: Vec<(String, Vec<AbiType>)>,
+    },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -161,6 +169,7 @@ impl AbiType {
             Type::Error => unreachable!(),
             Type::Unit => unreachable!(),
             Type::Constant(_) => unreachable!(),
+            Type::InfixExpr(..) => unreachable!(),
             Type::TraitAsType(_) => unreachable!(),
             Type::Struct(def, ref args) => {
                 let struct_type = def.borrow();
@@ -171,6 +180,15 @@ impl AbiType {
                     context.fully_qualified_struct_path(context.root_crate_id(), struct_type.id);
                 Self::Struct { fields, path }
             }
+            Type::Enum(def, ref args) => {
+                let enum_type = def.borrow();
+                let variants = vecmap(enum_type.get_variants(args), |(name, fields)| {
+                    (name, vecmap(fields, |typ| Self::from_type(context, &typ)))
+                });
+                // For the ABI, we always want to resolve the enum paths from the root crate
+                let path = context.fully_qualified_enum_path(context.root_crate_id(), enum_type.id);
+                Self::Enum { variants, path }
+            }
             Type::Tuple(fields) => {
                 let fields = vecmap(fields, |typ| Self::from_type(context, typ));
                 Self::Tuple { fields }
@@ -196,6 +214,11 @@ impl AbiType {
                 fields.iter().fold(0, |acc, field_typ| acc + field_typ.field_count())
             }
             AbiType::String { length } => *length as u32,
+            // Enums are encoded as their tag followed by the fields of every variant
+            AbiType::Enum { variants, .. } => variants
+                .iter()
+                .flat_map(|(_, fields)| fields)
+                .fold(1, |acc, field_typ| acc + field_typ.field_count()),
         }
     }
 }
@@ -394,6 +417,31 @@ impl Abi {
                     encoded_value.extend(Self::encode_value(value, typ)?);
                 }
             }
+            (InputValue::Struct(object), AbiType::Enum { variants, .. }) => {
+                // Enum values are represented as a single entry mapping the variant name to its fields
+                let (variant_name, variant_value) = object.into_iter().next().unwrap();
+                let tag = variants.iter().position(|(name, _)| *name == variant_name).unwrap();
+                encoded_value.push(FieldElement::from(tag as u128));
+
+                let mut variant_values = match variant_value {
+                    InputValue::Vec(values) => values,
+                    _ => unreachable!("value should have already been checked to match abi type"),
+                };
+
+                for (i, (_, fields)) in variants.iter().enumerate() {
+                    if i == tag {
+                        for (value, typ) in
+                            std::mem::take(&mut variant_values).into_iter().zip(fields)
+                        {
+                            encoded_value.extend(Self::encode_value(value, typ)?);
+                        }
+                    } else {
+                        // The fields of every other variant are zeroed
+                        let field_count: u32 = fields.iter().map(AbiType::field_count).sum();
+                        encoded_value.extend(vec![FieldElement::zero(); field_count as usize]);
+                    }
+                }
+            }
             _ => unreachable!("value should have already been checked to match abi type"),
         }
         Ok(encoded_value)
@@ -494,6 +542,32 @@ fn decode_value(
 
             InputValue::Vec(tuple_elements)
         }
+        AbiType::Enum { path, variants } => {
+            let tag_field = field_iterator.next().unwrap();
+            let tag = tag_field
+                .try_to_u64()
+                .map(|tag| tag as usize)
+                .filter(|tag| *tag < variants.len())
+                .ok_or_else(|| AbiError::InvalidEnumTag {
+                    path: path.clone(),
+                    tag: tag_field,
+                    num_variants: variants.len(),
+                })?;
+
+            let mut variant = None;
+            for (i, (variant_name, fields)) in variants.iter().enumerate() {
+                let mut field_values = Vec::with_capacity(fields.len());
+                for field_typ in fields {
+                    field_values.push(decode_value(field_iterator, field_typ)?);
+                }
+
+                if i == tag {
+                    variant = Some((variant_name.to_owned(), InputValue::Vec(field_values)));
+                }
+            }
+
+            InputValue::Struct(BTreeMap::from_iter(variant))
+        }
     };
 
     Ok(value)
@@ -552,11 +626,14 @@ fn range_to_vec(ranges: &[Range<Witness>]) -> Vec<Witness> {
 mod test {
     use std::collections::BTreeMap;
 
-    use acvm::{acir::native_types::Witness, FieldElement};
+    use acvm::{
+        acir::native_types::{Witness, WitnessMap},
+        FieldElement,
+    };
 
     use crate::{
-        input_parser::InputValue, Abi, AbiParameter, AbiReturnType, AbiType, AbiVisibility,
-        InputMap,
+        errors::AbiError, input_parser::InputValue, Abi, AbiParameter, AbiReturnType, AbiType,
+        AbiVisibility, InputMap,
     };
 
     #[test]
@@ -608,4 +685,29 @@ mod test {
         // We also decode the return value (we can do this immediately as we know it shares a witness with an input).
         assert_eq!(return_value.unwrap(), reconstructed_inputs["thing2"]);
     }
+
+    #[test]
+    fn rejects_out_of_range_enum_tag() {
+        let abi = Abi {
+            parameters: vec![AbiParameter {
+                name: "light".to_string(),
+                typ: AbiType::Enum {
+                    path: "Light".to_string(),
+                    variants: vec![("Red".to_string(), vec![]), ("Green".to_string(), vec![])],
+                },
+                visibility: AbiVisibility::Private,
+            }],
+            param_witnesses: BTreeMap::from([(
+                "light".to_string(),
+                vec![(Witness(1)..Witness(2))],
+            )]),
+            return_type: None,
+            return_witnesses: vec![],
+        };
+
+        let witness_map =
+            WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::from(2_u128))]));
+        let error = abi.decode(&witness_map).unwrap_err();
+        assert!(matches!(error, AbiError::InvalidEnumTag { num_variants: 2, .. }));
+    }
 }